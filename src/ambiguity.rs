@@ -0,0 +1,278 @@
+//! Readings of ambiguous bandwidth strings
+//!
+//! Users often write units loosely: `10mb/s` may mean megabits, megabytes or
+//! even mebibytes per second. Rather than guessing, [`interpretations`] lists
+//! every plausible reading so that interactive tools can ask the user.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::ambiguity::{interpretations, Interpretation};
+//!
+//! assert_eq!(
+//!     interpretations("10mb/s"),
+//!     [
+//!         (Interpretation::DecimalBits, Bandwidth::from_mbps(10)),
+//!         (Interpretation::DecimalBytes, Bandwidth::from_mbps(80)),
+//!         (Interpretation::BinaryBits, Bandwidth::from_bps(10_485_760)),
+//!         (Interpretation::BinaryBytes, Bandwidth::from_bps(83_886_080)),
+//!     ],
+//! );
+//! assert_eq!(
+//!     interpretations("10MiB/s"),
+//!     [(Interpretation::BinaryBytes, Bandwidth::from_bps(83_886_080))],
+//! );
+//! ```
+
+use alloc::{string::ToString, vec::Vec};
+use core::fmt;
+
+use bandwidth::Bandwidth;
+
+use crate::{
+    consts::{BITS_PER_BYTE, KIBI, KILO},
+    saturating_value, Error, Parser,
+};
+
+/// A way to read the units of a bandwidth
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interpretation {
+    /// Prefixes are powers of 1000 and the unit is the bit, e.g. `Mb/s` as megabit per second
+    DecimalBits,
+    /// Prefixes are powers of 1000 and the unit is the Byte, e.g. `Mb/s` as megabyte per second
+    DecimalBytes,
+    /// Prefixes are powers of 1024 and the unit is the bit, e.g. `Mb/s` as mebibit per second
+    BinaryBits,
+    /// Prefixes are powers of 1024 and the unit is the Byte, e.g. `Mb/s` as mebibyte per second
+    BinaryBytes,
+}
+
+impl Interpretation {
+    const ALL: [Interpretation; 4] = [
+        Interpretation::DecimalBits,
+        Interpretation::DecimalBytes,
+        Interpretation::BinaryBits,
+        Interpretation::BinaryBytes,
+    ];
+
+    fn is_binary(self) -> bool {
+        matches!(
+            self,
+            Interpretation::BinaryBits | Interpretation::BinaryBytes
+        )
+    }
+
+    fn is_bytes(self) -> bool {
+        matches!(
+            self,
+            Interpretation::DecimalBytes | Interpretation::BinaryBytes
+        )
+    }
+}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Interpretation::DecimalBits => f.write_str("decimal prefixes, bits per second"),
+            Interpretation::DecimalBytes => f.write_str("decimal prefixes, Bytes per second"),
+            Interpretation::BinaryBits => f.write_str("binary prefixes, bits per second"),
+            Interpretation::BinaryBytes => f.write_str("binary prefixes, Bytes per second"),
+        }
+    }
+}
+
+/// What a unit says about itself
+struct Unit {
+    /// 0 for no prefix, 1 for kilo or kibi, 2 for mega or mebi...
+    power: u32,
+    /// Whether the prefix is explicitly binary, like `Mi`
+    binary: bool,
+    /// `None` when a lone `b` may be a bit or a Byte
+    bytes: Option<bool>,
+}
+
+impl Unit {
+    fn parse(unit: &str) -> Option<Unit> {
+        let unit = unit
+            .strip_suffix("ps")
+            .or_else(|| unit.strip_suffix("/s"))?;
+        let (prefix, bytes) = [
+            ("bits", Some(false)),
+            ("bit", Some(false)),
+            ("Bit", Some(false)),
+            ("bytes", Some(true)),
+            ("byte", Some(true)),
+            ("Byte", Some(true)),
+            ("b", None),
+            ("B", Some(true)),
+            ("o", Some(true)),
+        ]
+        .into_iter()
+        .find_map(|(base, bytes)| unit.strip_suffix(base).map(|prefix| (prefix, bytes)))?;
+        let (prefix, binary) = match prefix.strip_suffix('i') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+        let power = match prefix {
+            "" if !binary => 0,
+            "k" | "K" => 1,
+            "m" | "M" => 2,
+            "g" | "G" => 3,
+            "t" | "T" => 4,
+            _ => return None,
+        };
+        Some(Unit {
+            power,
+            binary,
+            bytes,
+        })
+    }
+
+    /// Bits per second of one unit with this interpretation
+    fn multiplier(&self, interpretation: Interpretation) -> Option<u128> {
+        if (self.binary && !interpretation.is_binary())
+            || self
+                .bytes
+                .is_some_and(|bytes| bytes != interpretation.is_bytes())
+        {
+            return None;
+        }
+        let prefix = if interpretation.is_binary() {
+            u128::from(KIBI).pow(self.power)
+        } else {
+            u128::from(KILO).pow(self.power)
+        };
+        Some(if interpretation.is_bytes() {
+            prefix * u128::from(BITS_PER_BYTE)
+        } else {
+            prefix
+        })
+    }
+}
+
+/// Lists every plausible reading of a bandwidth, with the value it gives
+///
+/// A lowercase `b` may stand for bits or Bytes, while `B` (and `o` for octet)
+/// is only read as Bytes and `bit` only as bits. The decimal prefixes `k`, `M`,
+/// `G` and `T` may also be misused for the binary ones, unlike the explicit
+/// `ki`, `Mi`, `Gi` and `Ti`. The units are all read the same way, binary
+/// readings are only listed when a prefix is present, and fractions less than
+/// 1bps are ignored.
+///
+/// The readings are ordered from the most to the least common: decimal bits,
+/// decimal Bytes, binary bits and binary Bytes. The result is empty when the
+/// string can not be read at all.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::ambiguity::{interpretations, Interpretation};
+///
+/// assert_eq!(
+///     interpretations("1.5KB/s"),
+///     [
+///         (Interpretation::DecimalBytes, Bandwidth::from_bps(12_000)),
+///         (Interpretation::BinaryBytes, Bandwidth::from_bps(12_288)),
+///     ],
+/// );
+/// assert_eq!(
+///     interpretations("300 bit/s"),
+///     [(Interpretation::DecimalBits, Bandwidth::from_bps(300))],
+/// );
+/// assert_eq!(interpretations("fast"), []);
+/// ```
+pub fn interpretations(s: &str) -> Vec<(Interpretation, Bandwidth)> {
+    let mut prefixed = false;
+    let mut readings = Vec::new();
+    for interpretation in Interpretation::ALL {
+        let parsed = Parser::new(s).parse_with(
+            |parser, n, fraction, fraction_cnt, start, end| -> Result<(), Error> {
+                let unknown = || Error::UnknownUnit {
+                    start,
+                    end,
+                    unit: s[start..end].to_string(),
+                    value: saturating_value(n),
+                };
+                let unit = Unit::parse(&s[start..end]).ok_or_else(unknown)?;
+                prefixed |= unit.power > 0;
+                let multiplier = unit.multiplier(interpretation).ok_or_else(unknown)?;
+                let bps = n
+                    .saturating_mul(multiplier)
+                    .saturating_add(fraction as u128 * multiplier / 10u128.pow(fraction_cnt));
+                Ok(parser.add_bps(bps)?)
+            },
+        );
+        if let Ok(val) = parsed {
+            readings.push((interpretation, val));
+        }
+    }
+    if !prefixed {
+        // Without prefix, the binary readings are the same as the decimal ones
+        readings.retain(|(interpretation, _)| !interpretation.is_binary());
+    }
+    readings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unambiguous() {
+        assert_eq!(
+            interpretations("10Mbit/s"),
+            [
+                (Interpretation::DecimalBits, Bandwidth::from_mbps(10)),
+                (Interpretation::BinaryBits, Bandwidth::from_bps(10_485_760)),
+            ]
+        );
+        assert_eq!(
+            interpretations("2GiBps"),
+            [(
+                Interpretation::BinaryBytes,
+                Bandwidth::from_bps(17_179_869_184)
+            )]
+        );
+        assert_eq!(
+            interpretations("3 Kiops"),
+            [(Interpretation::BinaryBytes, Bandwidth::from_bps(24_576))]
+        );
+    }
+
+    #[test]
+    fn test_combo() {
+        assert_eq!(
+            interpretations("1kb/s 8b/s"),
+            [
+                (Interpretation::DecimalBits, Bandwidth::from_bps(1_008)),
+                (Interpretation::DecimalBytes, Bandwidth::from_bps(8_064)),
+                (Interpretation::BinaryBits, Bandwidth::from_bps(1_032)),
+                (Interpretation::BinaryBytes, Bandwidth::from_bps(8_256)),
+            ]
+        );
+        // The units are read consistently
+        assert_eq!(interpretations("1MiB/s 1Mbit/s"), []);
+        assert_eq!(
+            interpretations("1B/s 1kib/s"),
+            [(Interpretation::BinaryBytes, Bandwidth::from_bps(8_200))]
+        );
+        assert_eq!(
+            interpretations("8b/s"),
+            [
+                (Interpretation::DecimalBits, Bandwidth::from_bps(8)),
+                (Interpretation::DecimalBytes, Bandwidth::from_bps(64)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(interpretations(""), []);
+        assert_eq!(interpretations("10"), []);
+        assert_eq!(interpretations("10 Mib"), []);
+        assert_eq!(interpretations("10iB/s"), []);
+        assert_eq!(interpretations("3000000000000000TB/s"), []);
+    }
+}
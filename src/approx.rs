@@ -0,0 +1,176 @@
+//! Approximate phrasing of bandwidth for end users
+//!
+//! Exact values like `9.87342Mbps` feel robotic in status messages, this module
+//! rounds them into friendly phrases instead:
+//!
+//! * `"just under 10Mbps"` or `"just over 1Gbps"` when the value is close to a
+//!   power of ten
+//! * `"about 12Mbps"` when the value had to be rounded
+//! * `"150Mbps"` when the value is already round
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::approx::{format_approx, format_approx_with, ApproxOptions};
+//!
+//! assert_eq!(format_approx(Bandwidth::from_kbps(9_870)), "just under 10Mbps");
+//! assert_eq!(format_approx(Bandwidth::from_kbps(12_345)), "about 12Mbps");
+//! assert_eq!(format_approx(Bandwidth::from_mbps(1_020)), "just over 1Gbps");
+//!
+//! let options = ApproxOptions::new().margin(0.01).significant_digits(3);
+//! assert_eq!(format_approx_with(Bandwidth::from_kbps(9_876), &options), "about 9.88Mbps");
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, bandwidth_from_bps, DecimalBandwidth};
+
+/// Thresholds used by [`format_approx_with`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ApproxOptions {
+    margin: f64,
+    significant_digits: u32,
+}
+
+impl Default for ApproxOptions {
+    fn default() -> Self {
+        ApproxOptions {
+            margin: 0.05,
+            significant_digits: 2,
+        }
+    }
+}
+
+impl ApproxOptions {
+    /// Create the default options: a margin of 5% and 2 significant digits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the relative distance to a power of ten under which the value is
+    /// said to be "just under" or "just over" it
+    ///
+    /// A margin of `0.0` disables this phrasing.
+    pub fn margin(mut self, margin: f64) -> Self {
+        self.margin = margin.max(0.0);
+        self
+    }
+
+    /// Set the number of significant digits kept when the value is rounded
+    ///
+    /// The value is clamped to at least 1.
+    pub fn significant_digits(mut self, digits: u32) -> Self {
+        self.significant_digits = digits.max(1);
+        self
+    }
+}
+
+/// Format a bandwidth as a friendly approximate phrase with the default options
+///
+/// See [`format_approx_with`] for the details.
+pub fn format_approx(val: Bandwidth) -> String {
+    format_approx_with(val, &ApproxOptions::default())
+}
+
+/// Format a bandwidth as a friendly approximate phrase
+///
+/// * If the value is within the margin below or above a power of ten
+///   (e.g. `1Mbps`, `10Mbps`, `100Mbps`), the result is `"just under X"` or
+///   `"just over X"`.
+/// * Otherwise the value is rounded to the configured number of significant
+///   digits, giving `"about X"`, or simply `"X"` when no rounding was needed.
+pub fn format_approx_with(val: Bandwidth, options: &ApproxOptions) -> String {
+    let bps = bandwidth_as_bps(val);
+    if bps == 0 {
+        return DecimalBandwidth(val).to_string();
+    }
+    // The power of ten at most `bps`, and the next one
+    let exponent = bps.to_string().len() as u32 - 1;
+    let below = 10u128.pow(exponent);
+    let above = below * 10;
+    if bps != below {
+        if (above - bps) as f64 / above as f64 <= options.margin {
+            return phrase("just under ", above);
+        }
+        if (bps - below) as f64 / below as f64 <= options.margin {
+            return phrase("just over ", below);
+        }
+    }
+    let scale = match (exponent + 1).checked_sub(options.significant_digits) {
+        Some(dropped) if dropped > 0 => 10u128.pow(dropped),
+        _ => return DecimalBandwidth(val).to_string(),
+    };
+    // Rounded to the nearest, with ties away from zero
+    let rounded = (bps + scale / 2) / scale * scale;
+    if rounded == bps {
+        DecimalBandwidth(val).to_string()
+    } else {
+        phrase("about ", rounded)
+    }
+}
+
+fn phrase(prefix: &str, bps: u128) -> String {
+    match bandwidth_from_bps(bps) {
+        Ok(val) => format!("{}{}", prefix, DecimalBandwidth(val)),
+        Err(_) => format!("{}{}", prefix, DecimalBandwidth(Bandwidth::MAX)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_approx() {
+        assert_eq!(format_approx(Bandwidth::new(0, 0)), "0bps");
+        assert_eq!(format_approx(Bandwidth::new(0, 7)), "7bps");
+        assert_eq!(format_approx(Bandwidth::from_mbps(10)), "10Mbps");
+        assert_eq!(format_approx(Bandwidth::from_mbps(150)), "150Mbps");
+        assert_eq!(
+            format_approx(Bandwidth::from_kbps(9_980)),
+            "just under 10Mbps"
+        );
+        assert_eq!(
+            format_approx(Bandwidth::from_kbps(10_300)),
+            "just over 10Mbps"
+        );
+        assert_eq!(format_approx(Bandwidth::from_mbps(990)), "just under 1Gbps");
+        assert_eq!(format_approx(Bandwidth::from_kbps(12_345)), "about 12Mbps");
+        assert_eq!(format_approx(Bandwidth::from_kbps(1_789)), "about 1.8Mbps");
+        assert_eq!(format_approx(Bandwidth::new(9420, 0)), "about 9.4Tbps");
+        // Rounded exactly near the largest bandwidth
+        assert_eq!(format_approx(Bandwidth::MAX), "about 18000000000Ebps");
+        assert_eq!(
+            format_approx_with(Bandwidth::MAX, &ApproxOptions::new().significant_digits(12)),
+            "about 18446744073.7Ebps"
+        );
+    }
+
+    #[test]
+    fn test_format_approx_with() {
+        let options = ApproxOptions::new().margin(0.0);
+        assert_eq!(
+            format_approx_with(Bandwidth::from_kbps(9_980), &options),
+            "about 10Mbps"
+        );
+        let options = ApproxOptions::new().margin(0.2).significant_digits(1);
+        assert_eq!(
+            format_approx_with(Bandwidth::from_kbps(8_500), &options),
+            "just under 10Mbps"
+        );
+        assert_eq!(
+            format_approx_with(Bandwidth::from_kbps(3_456), &options),
+            "about 3Mbps"
+        );
+        let options = ApproxOptions::new().significant_digits(4);
+        assert_eq!(
+            format_approx_with(Bandwidth::from_kbps(12_345), &options),
+            "about 12.35Mbps"
+        );
+    }
+}
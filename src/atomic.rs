@@ -0,0 +1,169 @@
+//! Bandwidths shared between threads without locks
+//!
+//! An [`AtomicBandwidth`] holds the rate of a throttle or a token bucket,
+//! which a configuration reload thread changes while the workers read it.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::{atomic::Ordering, Arc};
+//! use std::thread;
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::atomic::AtomicBandwidth;
+//!
+//! let limit = Arc::new(AtomicBandwidth::new(Bandwidth::from_mbps(10)));
+//! let reload = {
+//!     let limit = Arc::clone(&limit);
+//!     thread::spawn(move || limit.store(Bandwidth::from_mbps(25), Ordering::Relaxed))
+//! };
+//! reload.join().unwrap();
+//! assert_eq!(limit.load(Ordering::Relaxed), Bandwidth::from_mbps(25));
+//! assert_eq!(limit.to_string(), "25Mbps");
+//! ```
+
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, format_bandwidth};
+
+/// A bandwidth which can be shared between threads, stored as an
+/// [`AtomicU64`] of bits per second
+///
+/// Bandwidths above `u64::MAX` bits per second, i.e. about 18.4Ebps, are
+/// clamped to this value when stored.
+#[derive(Default)]
+pub struct AtomicBandwidth {
+    bps: AtomicU64,
+}
+
+/// The bits per second stored for `val`, clamped to `u64::MAX`
+fn to_bps(val: Bandwidth) -> u64 {
+    u64::try_from(bandwidth_as_bps(val)).unwrap_or(u64::MAX)
+}
+
+impl AtomicBandwidth {
+    /// Create an atomic bandwidth, `val` being clamped to `u64::MAX` bits per
+    /// second
+    pub fn new(val: Bandwidth) -> Self {
+        AtomicBandwidth {
+            bps: AtomicU64::new(to_bps(val)),
+        }
+    }
+
+    /// Loads the bandwidth, see [`AtomicU64::load`]
+    pub fn load(&self, order: Ordering) -> Bandwidth {
+        Bandwidth::from_bps(self.bps.load(order))
+    }
+
+    /// Stores a bandwidth, clamped to `u64::MAX` bits per second, see
+    /// [`AtomicU64::store`]
+    pub fn store(&self, val: Bandwidth, order: Ordering) {
+        self.bps.store(to_bps(val), order);
+    }
+
+    /// Stores a bandwidth and returns the previous one, see
+    /// [`AtomicU64::swap`]
+    pub fn swap(&self, val: Bandwidth, order: Ordering) -> Bandwidth {
+        Bandwidth::from_bps(self.bps.swap(to_bps(val), order))
+    }
+
+    /// Updates the bandwidth with `f` until no other thread changed it in the
+    /// meantime, see [`AtomicU64::fetch_update`]
+    ///
+    /// Returns the previous bandwidth, or an error with the current one if
+    /// `f` returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::atomic::Ordering;
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::atomic::AtomicBandwidth;
+    ///
+    /// let limit = AtomicBandwidth::new(Bandwidth::from_mbps(10));
+    /// let cap = Bandwidth::from_mbps(5);
+    /// // Lowers the limit to the cap, unless it is already lower
+    /// let lower = |val| (val > cap).then(|| cap);
+    /// let previous = limit.fetch_update(Ordering::SeqCst, Ordering::SeqCst, lower);
+    /// assert_eq!(previous, Ok(Bandwidth::from_mbps(10)));
+    /// assert_eq!(limit.load(Ordering::SeqCst), cap);
+    /// let previous = limit.fetch_update(Ordering::SeqCst, Ordering::SeqCst, lower);
+    /// assert_eq!(previous, Err(cap));
+    /// ```
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<Bandwidth, Bandwidth>
+    where
+        F: FnMut(Bandwidth) -> Option<Bandwidth>,
+    {
+        self.bps
+            .fetch_update(set_order, fetch_order, |bps| {
+                f(Bandwidth::from_bps(bps)).map(to_bps)
+            })
+            .map(Bandwidth::from_bps)
+            .map_err(Bandwidth::from_bps)
+    }
+
+    /// Consumes the atomic bandwidth and returns its value
+    pub fn into_inner(self) -> Bandwidth {
+        Bandwidth::from_bps(self.bps.into_inner())
+    }
+}
+
+impl From<Bandwidth> for AtomicBandwidth {
+    fn from(val: Bandwidth) -> Self {
+        AtomicBandwidth::new(val)
+    }
+}
+
+impl fmt::Debug for AtomicBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
+
+/// Writes the current bandwidth like [`format_bandwidth`]
+impl fmt::Display for AtomicBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&format_bandwidth(self.load(Ordering::Relaxed)), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic() {
+        let val = AtomicBandwidth::default();
+        assert_eq!(val.load(Ordering::Relaxed), Bandwidth::new(0, 0));
+        assert_eq!(
+            val.swap(Bandwidth::from_kbps(3), Ordering::Relaxed),
+            Bandwidth::new(0, 0)
+        );
+        assert_eq!(val.to_string(), "3kbps");
+        assert_eq!(
+            format!("{:?}", val),
+            format!("{:?}", Bandwidth::from_kbps(3))
+        );
+        assert_eq!(
+            val.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |_| None),
+            Err(Bandwidth::from_kbps(3))
+        );
+        assert_eq!(
+            val.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |_| Some(
+                Bandwidth::MAX
+            )),
+            Ok(Bandwidth::from_kbps(3))
+        );
+        // Clamped to u64::MAX bits per second
+        assert_eq!(val.into_inner(), Bandwidth::from_bps(u64::MAX));
+        let val = AtomicBandwidth::from(Bandwidth::MAX);
+        assert_eq!(val.load(Ordering::Relaxed), Bandwidth::from_bps(u64::MAX));
+    }
+}
@@ -0,0 +1,258 @@
+//! 95th percentile billing
+//!
+//! Transit and colocation links are usually billed with the 95/5 method: the
+//! measured rates are averaged over buckets of five minutes, the busiest 5% of
+//! the buckets are discarded and the highest remaining average is billed.
+//!
+//! # Bucketing
+//!
+//! Buckets are [`BUCKET`] long and start at the earliest sample: a sample
+//! taken `t` after it falls in the bucket `t / 5min`. The average of a bucket
+//! is the mean of its samples, rounded to the nearest bps. Buckets without any
+//! sample, e.g. during a collector outage, are skipped rather than counted as
+//! idle.
+//!
+//! With `n` buckets, the `n / 20` (rounded down) busiest buckets are
+//! discarded, which is the nearest-rank 95th percentile.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::billing::percentile_95;
+//!
+//! let start = Instant::now();
+//! // A day of 5 minute samples: 50Mbps, with a burst at 200Mbps for an hour
+//! let samples: Vec<_> = (0..288)
+//!     .map(|i| {
+//!         let mbps = if (100..112).contains(&i) { 200 } else { 50 };
+//!         (start + Duration::from_secs(300 * i), Bandwidth::from_mbps(mbps))
+//!     })
+//!     .collect();
+//! let report = percentile_95(&samples).unwrap();
+//! assert_eq!(report.rate, Bandwidth::from_mbps(50));
+//! assert_eq!(
+//!     report.to_string(),
+//!     "95th percentile: 50Mbps over 288 buckets (14 discarded, peak 200Mbps)"
+//! );
+//! ```
+
+use core::fmt;
+use std::time::{Duration, Instant};
+
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, bandwidth_from_bps, format_bandwidth};
+
+/// The length of a billing bucket, five minutes
+pub const BUCKET: Duration = Duration::from_secs(5 * 60);
+
+/// The outcome of a 95th percentile computation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BillingReport {
+    /// The billed rate, i.e. the 95th percentile of the bucket averages
+    pub rate: Bandwidth,
+    /// The largest bucket average
+    pub peak: Bandwidth,
+    /// The number of buckets with at least one sample
+    pub buckets: usize,
+    /// The number of busiest buckets which are not billed
+    pub discarded: usize,
+}
+
+impl fmt::Display for BillingReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "95th percentile: {} over {} buckets ({} discarded, peak {})",
+            format_bandwidth(self.rate),
+            self.buckets,
+            self.discarded,
+            format_bandwidth(self.peak)
+        )
+    }
+}
+
+/// Computes the 95th percentile of samples, see the
+/// [module documentation](self) for the bucketing
+///
+/// The samples can be in any order. Returns `None` without samples.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::billing::percentile_95;
+///
+/// let start = Instant::now();
+/// let samples = [
+///     (start, Bandwidth::from_mbps(10)),
+///     // Same bucket as the first sample
+///     (start + Duration::from_secs(60), Bandwidth::from_mbps(30)),
+///     (start + Duration::from_secs(300), Bandwidth::from_mbps(5)),
+/// ];
+/// let report = percentile_95(&samples).unwrap();
+/// assert_eq!(report.buckets, 2);
+/// assert_eq!(report.rate, Bandwidth::from_mbps(20));
+/// assert_eq!(percentile_95(&[]), None);
+/// ```
+pub fn percentile_95(samples: &[(Instant, Bandwidth)]) -> Option<BillingReport> {
+    let mut samples = samples.to_vec();
+    samples.sort_by_key(|(at, _)| *at);
+    let mut percentile = StreamingPercentile95::new();
+    for (at, bw) in samples {
+        percentile.record(at, bw);
+    }
+    percentile.report()
+}
+
+/// Computes the 95th percentile of samples as they are measured
+///
+/// Only the average of each bucket is kept, i.e. 8640 averages for a 30 day
+/// billing period. See the [module documentation](self) for the bucketing.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::billing::StreamingPercentile95;
+///
+/// let start = Instant::now();
+/// let mut percentile = StreamingPercentile95::new();
+/// for minute in 0..60 {
+///     let at = start + Duration::from_secs(60 * minute);
+///     percentile.record(at, Bandwidth::from_mbps(minute));
+/// }
+/// // The averages of the 12 buckets are 2Mbps, 7Mbps, ... and 57Mbps
+/// let report = percentile.report().unwrap();
+/// assert_eq!(report.rate, Bandwidth::from_mbps(57));
+/// assert_eq!(report.discarded, 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StreamingPercentile95 {
+    start: Option<Instant>,
+    /// Index, sum in bps and number of samples of the current bucket
+    current: Option<(u64, u128, u128)>,
+    /// Averages in bps of the previous buckets
+    averages: Vec<u128>,
+}
+
+impl StreamingPercentile95 {
+    /// Create a computation without samples
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a bandwidth measured at `at`
+    ///
+    /// Samples are expected in chronological order: a sample older than the
+    /// current bucket is counted in the current bucket.
+    pub fn record(&mut self, at: Instant, bw: Bandwidth) {
+        let start = *self.start.get_or_insert(at);
+        let index = (at.saturating_duration_since(start).as_secs() / BUCKET.as_secs())
+            .max(self.current.map_or(0, |(index, _, _)| index));
+        match &mut self.current {
+            Some((current, sum, count)) if *current == index => {
+                *sum += bandwidth_as_bps(bw);
+                *count += 1;
+            }
+            current => {
+                if let Some((_, sum, count)) = current.take() {
+                    self.averages.push(average(sum, count));
+                }
+                *current = Some((index, bandwidth_as_bps(bw), 1));
+            }
+        }
+    }
+
+    /// The 95th percentile of the samples recorded so far, including the
+    /// current bucket, or `None` without samples
+    pub fn report(&self) -> Option<BillingReport> {
+        let mut averages = self.averages.clone();
+        let (_, sum, count) = self.current?;
+        averages.push(average(sum, count));
+        averages.sort_unstable();
+        let discarded = averages.len() / 20;
+        let rate = averages[averages.len() - 1 - discarded];
+        let peak = averages[averages.len() - 1];
+        Some(BillingReport {
+            // Averages of bandwidths are bandwidths
+            rate: bandwidth_from_bps(rate).unwrap_or(Bandwidth::MAX),
+            peak: bandwidth_from_bps(peak).unwrap_or(Bandwidth::MAX),
+            buckets: averages.len(),
+            discarded,
+        })
+    }
+}
+
+/// Mean of `count` samples summing to `sum` bps, rounded to the nearest
+fn average(sum: u128, count: u128) -> u128 {
+    (sum + count / 2) / count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_95() {
+        let start = Instant::now();
+        // One sample per bucket, from 1Mbps to 100Mbps, shuffled
+        let samples: Vec<_> = (0..100u64)
+            .map(|i| {
+                let bucket = (i * 37) % 100;
+                (
+                    start + BUCKET * bucket as u32,
+                    Bandwidth::from_mbps(bucket + 1),
+                )
+            })
+            .collect();
+        assert_eq!(
+            percentile_95(&samples),
+            Some(BillingReport {
+                rate: Bandwidth::from_mbps(95),
+                peak: Bandwidth::from_mbps(100),
+                buckets: 100,
+                discarded: 5,
+            })
+        );
+
+        // Fewer than 20 buckets are billed at their peak
+        let report = percentile_95(&samples[..19]).unwrap();
+        assert_eq!(report.discarded, 0);
+        assert_eq!(report.rate, report.peak);
+    }
+
+    #[test]
+    fn test_bucketing() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut percentile = StreamingPercentile95::new();
+        assert_eq!(percentile.report(), None);
+        percentile.record(at(10), Bandwidth::from_bps(1));
+        percentile.record(at(309), Bandwidth::from_bps(2));
+        // The first sample starts the buckets
+        assert_eq!(percentile.report().unwrap().buckets, 1);
+        assert_eq!(percentile.report().unwrap().rate, Bandwidth::from_bps(2));
+        percentile.record(at(310), Bandwidth::from_bps(10));
+        // Late samples are counted in the current bucket
+        percentile.record(at(0), Bandwidth::from_bps(20));
+        // Empty buckets are skipped
+        percentile.record(at(3_000), Bandwidth::from_bps(3));
+        let report = percentile.report().unwrap();
+        assert_eq!(report.buckets, 3);
+        assert_eq!(report.peak, Bandwidth::from_bps(15));
+        assert_eq!(report.rate, Bandwidth::from_bps(15));
+
+        percentile.record(at(3_000), Bandwidth::MAX);
+        percentile.record(at(3_001), Bandwidth::MAX);
+        let max = bandwidth_as_bps(Bandwidth::MAX);
+        assert_eq!(
+            percentile.report().unwrap().peak,
+            bandwidth_from_bps((3 + 2 * max + 1) / 3).unwrap()
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Bit rates with binary prefixes, e.g. `Kibit/s` or `Mibit/s`
+//!
+//! Some network gear reports rates in powers of two of bits rather than of
+//! Bytes. These units are available as the [`BinaryBits`] system of units, and
+//! with the [`parse_binary_bits_bandwidth`] and
+//! [`format_binary_bits_bandwidth`] shortcuts.
+//!
+//! Examples:
+//!
+//! * `1Kibit/s` is equal to `1.024kbps`
+//! * `1Mibit/s` is equal to `1.048576Mbps`
+//! * `1Mibit/s` is equal to `128kiB/s`
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::binary_bits::{format_binary_bits_bandwidth, parse_binary_bits_bandwidth};
+//!
+//! let val = parse_binary_bits_bandwidth("1.5Mibit/s").unwrap();
+//! assert_eq!(val, Bandwidth::from_bps(1_572_864));
+//! assert_eq!(format_binary_bits_bandwidth(val), "1.5Mibit/s");
+//! ```
+
+use alloc::string::{String, ToString};
+use bandwidth::Bandwidth;
+
+use crate::{
+    system::{self, UnitSystem},
+    Error,
+};
+
+/// The binary prefix system of bits per second, e.g. `1.5Mibit/s`
+///
+/// Each unit can be spelled `Mibit/s`, `Mibps` or `Mib/s`, with an upper or
+/// lower case first letter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BinaryBits;
+
+impl UnitSystem for BinaryBits {
+    fn parse_unit(&self, unit: &str) -> Option<u128> {
+        let power = match unit {
+            "bit/s" | "bps" | "b/s" => 0,
+            "Kibit/s" | "kibit/s" | "Kibps" | "kibps" | "Kib/s" | "kib/s" => 1,
+            "Mibit/s" | "mibit/s" | "Mibps" | "mibps" | "Mib/s" | "mib/s" => 2,
+            "Gibit/s" | "gibit/s" | "Gibps" | "gibps" | "Gib/s" | "gib/s" => 3,
+            "Tibit/s" | "tibit/s" | "Tibps" | "tibps" | "Tib/s" | "tib/s" => 4,
+            _ => return None,
+        };
+        Some(1 << (10 * power))
+    }
+
+    fn units(&self) -> &[(&'static str, u128)] {
+        &[
+            ("bit/s", 1),
+            ("Kibit/s", 1 << 10),
+            ("Mibit/s", 1 << 20),
+            ("Gibit/s", 1 << 30),
+            ("Tibit/s", 1 << 40),
+        ]
+    }
+
+    /// The fraction is rounded to the nearest bit, with ties away from zero,
+    /// so that formatted values are read back exactly
+    fn span_bps(
+        &self,
+        n: u128,
+        fraction: u64,
+        fraction_cnt: u32,
+        multiplier: u128,
+    ) -> Option<u128> {
+        let scale = 10u128.pow(fraction_cnt);
+        let fraction = (u128::from(fraction).checked_mul(multiplier)? + scale / 2) / scale;
+        n.checked_mul(multiplier)?.checked_add(fraction)
+    }
+
+    /// As many decimals as the unit has digits in bits, e.g. 4 for `Kibit/s`,
+    /// so that the last one is worth less than 1 bit and the value is read
+    /// back to the nearest bit exactly
+    fn decimals(&self, index: usize) -> usize {
+        self.units()[index].1.to_string().len()
+    }
+}
+
+/// Parse bandwidth object `1Gibit/s 12Mibit/s` or `1.5Mibit/s`
+///
+/// The bandwidth object is a concatenation of rate spans like for
+/// [`parse_bandwidth`](crate::parse_bandwidth), in the units of
+/// [`BinaryBits`]. The fractional part less than 1bps is rounded to the
+/// nearest, with ties away from zero.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::binary_bits::parse_binary_bits_bandwidth;
+///
+/// assert_eq!(parse_binary_bits_bandwidth("1Gibit/s 12Mibps"), Ok(Bandwidth::from_bps(1_086_324_736)));
+/// assert_eq!(parse_binary_bits_bandwidth("1.5 Kib/s"), Ok(Bandwidth::from_bps(1_536)));
+/// ```
+pub fn parse_binary_bits_bandwidth(s: &str) -> Result<Bandwidth, Error> {
+    system::parse(s, &BinaryBits)
+}
+
+/// Formats bandwidth in the largest binary unit of bits in which it is at
+/// least 1, e.g. `1.5Mibit/s`
+///
+/// The value is rounded to the nearest with ties to even, to at most 4
+/// decimals for `Kibit/s` and up to 13 decimals for `Tibit/s`, which are
+/// enough for [`parse_binary_bits_bandwidth`] to read back the same
+/// bandwidth.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::binary_bits::format_binary_bits_bandwidth;
+///
+/// assert_eq!(format_binary_bits_bandwidth(Bandwidth::from_bps(1_572_864)), "1.5Mibit/s");
+/// assert_eq!(format_binary_bits_bandwidth(Bandwidth::from_mbps(1)), "976.5625Kibit/s");
+/// ```
+pub fn format_binary_bits_bandwidth(val: Bandwidth) -> String {
+    system::format(val, &BinaryBits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        for (s, bps) in [
+            ("0bit/s", 0),
+            ("1023bps", 1_023),
+            ("1Kibit/s", 1_024),
+            ("1kibps", 1_024),
+            ("0.5Kib/s", 512),
+            ("0.0005Kibit/s", 1),
+            ("0.0004Kibit/s", 0),
+            ("3 Mibit/s 1bit/s", 3 * 1_048_576 + 1),
+            ("2gib/s", 2 << 30),
+            ("1Tibit/s", 1 << 40),
+        ] {
+            assert_eq!(parse_binary_bits_bandwidth(s), Ok(Bandwidth::from_bps(bps)));
+        }
+        assert_eq!(
+            parse_binary_bits_bandwidth("1MiB/s")
+                .unwrap_err()
+                .to_string(),
+            "unknown bandwidth unit \"MiB/s\", \
+             supported units: bit/s, Kibit/s, Mibit/s, Gibit/s, Tibit/s"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for bps in [0, 1, 1_023, 1_024, 1_000_000, 123_456_789, u64::MAX] {
+            let formatted = format_binary_bits_bandwidth(Bandwidth::from_bps(bps));
+            let parsed = parse_binary_bits_bandwidth(&formatted).unwrap();
+            assert_eq!(format_binary_bits_bandwidth(parsed), formatted);
+        }
+        assert_eq!(
+            format_binary_bits_bandwidth(Bandwidth::from_bps(1_023)),
+            "1023bit/s"
+        );
+        assert_eq!(
+            format_binary_bits_bandwidth(Bandwidth::from_bps(3 << 40)),
+            "3Tibit/s"
+        );
+        assert_eq!(
+            format_binary_bits_bandwidth(Bandwidth::new(12, 223_000_000)),
+            "11.3835558295Gibit/s"
+        );
+    }
+
+    #[test]
+    fn test_round_trip_random() {
+        // xorshift64*, which must not start from 0
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut next = move || {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        };
+        for _ in 0..10_000 {
+            // From a few bits per second to the whole range of `u64`
+            let bps = next() >> (next() % 64);
+            let val = Bandwidth::from_bps(bps);
+            let formatted = format_binary_bits_bandwidth(val);
+            assert_eq!(
+                parse_binary_bits_bandwidth(&formatted),
+                Ok(val),
+                "{}",
+                formatted
+            );
+        }
+    }
+}
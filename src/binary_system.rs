@@ -12,6 +12,7 @@
 //! * `1B/s` is equal to `8bps`
 //! * `1kiB/s` is equal to `8.192kbps`
 //! * `1MiBps` is equal to `8.388_608kbps`
+//! * `1MiB/min` is equal to `139.810_133kbps`
 //!
 //! # Example
 //!
@@ -22,159 +23,238 @@
 //! let val = Bandwidth::new(0, 32 * 1024 * 1024);
 //! assert_eq!(format_binary_bandwidth(val).to_string(), "4MiB/s");
 //! ```
+//!
+//! # Mixed units
+//!
+//! When the system of a user-supplied string is not known in advance,
+//! [`parse_any_bandwidth`] reads each rate span with the units of its own
+//! system, e.g. `1.5Gbps 200MiB/s`.
+//!
+//! # Per-interval units
+//!
+//! Backup and replication tools usually quote sizes per hour or per minute
+//! rather than per second. Such units, e.g. `4GiB/h` or `300MiB/min`, are
+//! parsed by [`parse_binary_bandwidth`] and written with
+//! [`FormattedBinaryBandwidth::per`].
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::binary_system::{format_binary_bandwidth, parse_binary_bandwidth_borrowed};
+//! use human_bandwidth::Interval;
+//!
+//! let val = parse_binary_bandwidth_borrowed("4GiB/h").unwrap();
+//! assert_eq!(val, Bandwidth::from_bps(9_544_372));
+//! # #[cfg(not(feature = "display-integer"))]
+//! assert_eq!(format!("{:.1}", format_binary_bandwidth(val).per(Interval::Hour)), "4.0GiB/h");
+//! ```
 
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::fmt;
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
 
 use bandwidth::Bandwidth;
 
 #[cfg(feature = "serde")]
 pub mod serde;
 
-use crate::{item, Error, OverflowOp, Parser};
+#[cfg(feature = "std")]
+use crate::os_str;
+use crate::{
+    consts::BITS_PER_BYTE, item, saturating_value, spelled::UnitBuf, split_interval, Interval,
+    ParseError, Parser,
+};
+#[cfg(feature = "alloc")]
+use crate::{
+    range::{parse_range_with, BandwidthRange, RangeError},
+    si_interval_unit_name, si_unit, utf8_str, Error, Unit,
+};
 
 /// A wrapper type that allows you to [Display](core::fmt::Display) a [`Bandwidth`] in binary prefix system
-#[derive(Debug, Clone)]
-pub struct FormattedBinaryBandwidth(Bandwidth);
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct FormattedBinaryBandwidth(Bandwidth, Interval);
+
+trait OverflowOp: Sized {
+    fn mul(self, other: Self) -> Result<Self, ParseError<'static>>;
+    fn add(self, other: Self) -> Result<Self, ParseError<'static>>;
+}
 
 impl OverflowOp for u128 {
-    fn mul(self, other: Self) -> Result<Self, Error> {
-        self.checked_mul(other).ok_or(Error::NumberOverflow)
+    fn mul(self, other: Self) -> Result<Self, ParseError<'static>> {
+        self.checked_mul(other).ok_or(ParseError::NumberOverflow)
     }
-    fn add(self, other: Self) -> Result<Self, Error> {
-        self.checked_add(other).ok_or(Error::NumberOverflow)
+    fn add(self, other: Self) -> Result<Self, ParseError<'static>> {
+        self.checked_add(other).ok_or(ParseError::NumberOverflow)
     }
 }
 
 /// Convert the fractionnal part of a binary prefix value to the right amount of Bytes per second
 ///
 /// The rounding is to the nearest with ties away from 0
-fn parse_binary_fraction(fraction: u64, fraction_cnt: u32, unit: u32) -> Result<u64, Error> {
+fn parse_binary_fraction(
+    fraction: u64,
+    fraction_cnt: u32,
+    unit: u32,
+) -> Result<u64, ParseError<'static>> {
     let rounding = 10_u128.pow(fraction_cnt) >> 1;
     let fraction = (fraction as u128)
         .checked_shl(10 * unit)
-        .ok_or(Error::NumberOverflow)?;
+        .ok_or(ParseError::NumberOverflow)?;
     Ok(((fraction + rounding) / 10u128.pow(fraction_cnt)) as u64)
 }
 
-impl Parser<'_> {
-    fn parse_binary_unit(
+/// Power of 1024 of a binary unit spelling, e.g. `2` for `MiB/s`
+pub(crate) fn binary_unit(unit: &str) -> Option<u32> {
+    match unit {
+        "Bps" | "Byte/s" | "B/s" | "ops" | "o/s" => Some(0),
+        "kiBps" | "KiBps" | "kiByte/s" | "KiByte/s" | "kiB/s" | "KiB/s" | "kiops" | "Kiops"
+        | "kio/s" | "Kio/s" => Some(1),
+        "MiBps" | "miBps" | "MiByte/s" | "miByte/s" | "MiB/s" | "miB/s" | "Miops" | "miops"
+        | "Mio/s" | "mio/s" => Some(2),
+        "GiBps" | "giBps" | "GiByte/s" | "giByte/s" | "GiB/s" | "giB/s" | "Giops" | "giops"
+        | "Gio/s" | "gio/s" => Some(3),
+        "TiBps" | "tiBps" | "TiByte/s" | "tiByte/s" | "TiB/s" | "tiB/s" | "Tiops" | "tiops"
+        | "Tio/s" | "tio/s" => Some(4),
+        "PiBps" | "piBps" | "PiByte/s" | "piByte/s" | "PiB/s" | "piB/s" | "Piops" | "piops"
+        | "Pio/s" | "pio/s" => Some(5),
+        "EiBps" | "eiBps" | "EiByte/s" | "eiByte/s" | "EiB/s" | "eiB/s" | "Eiops" | "eiops"
+        | "Eio/s" | "eio/s" => Some(6),
+        _ => None,
+    }
+}
+
+/// Power of 1024 and interval of a binary unit spelling, e.g. `(3, Hour)`
+/// for `GiB/h`
+pub(crate) fn binary_interval_unit(unit: &str) -> Option<(u32, Interval)> {
+    if let Some(power) = binary_unit(unit) {
+        return Some((power, Interval::Second));
+    }
+    let (base, interval) = split_interval(unit)?;
+    Some((binary_unit(&UnitBuf::concat(&[base, "/s"])?)?, interval))
+}
+
+/// Canonical spelling of a binary unit from its power of 1024 and interval
+pub(crate) const fn binary_unit_name(power: u32, interval: Interval) -> &'static str {
+    const NAMES: [[&str; 5]; 7] = [
+        ["B/s", "B/min", "B/h", "B/d", "B/month"],
+        ["kiB/s", "kiB/min", "kiB/h", "kiB/d", "kiB/month"],
+        ["MiB/s", "MiB/min", "MiB/h", "MiB/d", "MiB/month"],
+        ["GiB/s", "GiB/min", "GiB/h", "GiB/d", "GiB/month"],
+        ["TiB/s", "TiB/min", "TiB/h", "TiB/d", "TiB/month"],
+        ["PiB/s", "PiB/min", "PiB/h", "PiB/d", "PiB/month"],
+        ["EiB/s", "EiB/min", "EiB/h", "EiB/d", "EiB/month"],
+    ];
+    let power = if power > 6 { 6 } else { power };
+    NAMES[power as usize][interval as usize]
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn parse_binary_unit(
         &mut self,
-        n: u64,
+        n: u128,
         fraction: u64,
         fraction_cnt: u32,
         start: usize,
         end: usize,
-    ) -> Result<(), Error> {
-        let unit = match &self.src[start..end] {
-            "Bps" | "Byte/s" | "B/s" | "ops" | "o/s" => 0,
-            "kiBps" | "KiBps" | "kiByte/s" | "KiByte/s" | "kiB/s" | "KiB/s" | "kiops" | "Kiops"
-            | "kio/s" | "Kio/s" => 1,
-            "MiBps" | "miBps" | "MiByte/s" | "miByte/s" | "MiB/s" | "miB/s" | "Miops" | "miops"
-            | "Mio/s" | "mio/s" => 2,
-            "GiBps" | "giBps" | "GiByte/s" | "giByte/s" | "GiB/s" | "giB/s" | "Giops" | "giops"
-            | "Gio/s" | "gio/s" => 3,
-            "TiBps" | "tiBps" | "TiByte/s" | "tiByte/s" | "TiB/s" | "tiB/s" | "Tiops" | "tiops"
-            | "Tio/s" | "tio/s" => 4,
-            _ => {
-                return Err(Error::UnknownBinaryUnit {
+    ) -> Result<(), ParseError<'a>> {
+        let src = self.src;
+        let unit = self.options.resolve_alias(&src[start..end]);
+        let spelled = self.options.spelled_unit(unit);
+        let unit = spelled.as_deref().unwrap_or(unit);
+        let known = binary_interval_unit(unit).filter(|(_, interval)| {
+            self.options.accepts_prefix_case(unit)
+                && (*interval == Interval::Second
+                    || self.options.get_enabled_units().is_per_interval())
+        });
+        let (unit, interval) = match known {
+            Some(unit) => unit,
+            None => {
+                return Err(ParseError::UnknownBinaryUnit {
                     start,
                     end,
-                    unit: self.src[start..end].to_string(),
-                    value: n,
+                    unit: &src[start..end],
+                    value: saturating_value(n),
                 });
             }
         };
-        let bps = (n as u128)
-            .checked_shl(unit * 10)
-            .ok_or(Error::NumberOverflow)? // Converting the unit to Byte per second
+        let bps = n
+            .checked_mul(1 << (unit * 10))
+            .ok_or(ParseError::NumberOverflow)? // Converting the unit to Byte per second
             .add(parse_binary_fraction(fraction, fraction_cnt, unit)? as u128)? // Adding the fractional part
-            .mul(8)?; // Converting to bit per second
-        let (gbps, bps) = ((bps / 1_000_000_000), (bps % 1_000_000_000) as u32);
-        let gbps = if gbps > u64::MAX as u128 {
-            return Err(Error::NumberOverflow);
-        } else {
-            gbps as u64
-        };
-        let new_bandwidth = Bandwidth::new(gbps, bps);
-        self.current += new_bandwidth;
-        Ok(())
+            .mul(BITS_PER_BYTE as u128)?; // Converting to bit per interval
+
+        // Converting to bit per second, rounded to the nearest
+        let seconds = self.options.interval_seconds(interval) as u128;
+        self.add_bps((bps + seconds / 2) / seconds)
     }
 
-    fn parse_binary(mut self) -> Result<Bandwidth, Error> {
-        let mut n = self.parse_first_char()?.ok_or(Error::Empty)?;
-        let mut decimal = false;
-        let mut fraction: u64 = 0;
-        let mut fraction_cnt: u32 = 0;
-        'outer: loop {
-            let mut off = self.off();
-            while let Some(c) = self.iter.next() {
-                match c {
-                    '0'..='9' => {
-                        if decimal {
-                            if fraction_cnt >= super::FRACTION_PART_LIMIT {
-                                continue;
-                            }
-                            fraction = fraction
-                                .checked_mul(10)
-                                .and_then(|x| x.checked_add(c as u64 - '0' as u64))
-                                .ok_or(Error::NumberOverflow)?;
-                            fraction_cnt += 1;
-                        } else {
-                            n = n
-                                .checked_mul(10)
-                                .and_then(|x| x.checked_add(c as u64 - '0' as u64))
-                                .ok_or(Error::NumberOverflow)?;
-                        }
-                    }
-                    c if c.is_whitespace() => {}
-                    '_' => {}
-                    '.' => {
-                        if decimal {
-                            return Err(Error::InvalidCharacter(off));
-                        }
-                        decimal = true;
-                    }
-                    'a'..='z' | 'A'..='Z' | '/' => {
-                        break;
-                    }
-                    _ => {
-                        return Err(Error::InvalidCharacter(off));
-                    }
-                }
-                off = self.off();
-            }
-            let start = off;
-            let mut off = self.off();
-            while let Some(c) = self.iter.next() {
-                match c {
-                    '0'..='9' => {
-                        self.parse_binary_unit(n, fraction, fraction_cnt, start, off)?;
-                        n = c as u64 - '0' as u64;
-                        fraction = 0;
-                        decimal = false;
-                        fraction_cnt = 0;
-                        continue 'outer;
-                    }
-                    c if c.is_whitespace() => break,
-                    'a'..='z' | 'A'..='Z' | '/' => {}
-                    _ => {
-                        return Err(Error::InvalidCharacter(off));
-                    }
-                }
-                off = self.off();
-            }
-            self.parse_binary_unit(n, fraction, fraction_cnt, start, off)?;
-            n = match self.parse_first_char()? {
-                Some(n) => n,
-                None => return Ok(self.current),
-            };
-            fraction = 0;
-            decimal = false;
-            fraction_cnt = 0;
+    pub(crate) fn parse_binary(mut self) -> Result<Bandwidth, ParseError<'a>> {
+        self.parse_with(Self::parse_binary_unit)
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Dispatches each span to the SI or the binary units, the spellings of
+    /// the two systems being distinct
+    fn parse_any_unit(
+        &mut self,
+        n: u128,
+        fraction: u64,
+        fraction_cnt: u32,
+        start: usize,
+        end: usize,
+    ) -> Result<(), Error> {
+        let unit = self.options.resolve_alias(&self.src[start..end]);
+        let spelled = self.options.spelled_unit(unit);
+        let unit = spelled.as_deref().unwrap_or(unit);
+        let per_second = match split_interval(unit) {
+            Some((base, _)) => [base, "/s"].concat(),
+            None => unit.to_string(),
+        };
+        if si_unit(&per_second).is_some() {
+            Ok(self.parse_unit(n, fraction, fraction_cnt, start, end)?)
+        } else if binary_interval_unit(unit).is_some() {
+            Ok(self.parse_binary_unit(n, fraction, fraction_cnt, start, end)?)
+        } else {
+            const INTERVALS: [Interval; 5] = [
+                Interval::Second,
+                Interval::Minute,
+                Interval::Hour,
+                Interval::Day,
+                Interval::Month,
+            ];
+            let supported: Vec<_> = INTERVALS
+                .iter()
+                .flat_map(|&interval| {
+                    let si = Unit::ALL
+                        .iter()
+                        .map(move |unit| si_interval_unit_name(unit.exponent(), interval));
+                    let binary = BinaryUnit::ALL
+                        .iter()
+                        .map(move |unit| binary_unit_name(*unit as u32, interval));
+                    si.chain(binary)
+                })
+                .collect();
+            Err(Error::UnknownSystemUnit {
+                start,
+                end,
+                unit: self.src[start..end].to_string(),
+                value: saturating_value(n),
+                supported: supported.join(", "),
+            })
         }
     }
+
+    #[cfg(feature = "alloc")]
+    fn parse_any(mut self) -> Result<Bandwidth, Error> {
+        self.parse_with(Self::parse_any_unit)
+    }
 }
 
+#[cfg(feature = "alloc")]
 /// Parse bandwidth object `1GiBps 12MiBps 5Bps` or `1.012000005GiBps`
 ///
 /// Unlike [`parse_bandwidth`](super::parse_bandwidth), this method expect bandwidth to
@@ -188,9 +268,19 @@ impl Parser<'_> {
 /// * `MiBps`, `MiByte/s`, `MiB/s`, `Miops`, 'Mio/s` -- mebiByte per second
 /// * `GiBps`, `GiByte/s`, `GiB/s`, `Giops`, 'Gio/s` -- gibiByte per second
 /// * `TiBps`, `TiByte/s`, `TiB/s`, `Tiops`, 'Tio/s` -- tebiByte per second
+/// * `PiBps`, `PiByte/s`, `PiB/s`, `Piops`, 'Pio/s` -- pebiByte per second
+/// * `EiBps`, `EiByte/s`, `EiB/s`, `Eiops`, 'Eio/s` -- exbiByte per second
+///
+/// The `/s` of these units can be replaced by `/min`, `/h`, `/d` (or `/day`)
+/// or `/month` for amounts per minute, hour, day or month, e.g. `300MiB/min`
+/// or `4GiB/h`.
 ///
-/// While the number can be integer or decimal, the fractional part less than 1Bps will always be
-/// rounded to the closest (ties away from zero).
+/// The units can also be spelled out, in any case and in the singular or the
+/// plural, like `4 mebibytes/s` or `300 Mebibytes per minute`.
+///
+/// While the number can be integer, decimal or in scientific notation like `1.5e3`, the fractional
+/// part less than 1Byte will always be rounded to the closest (ties away from zero), as is the
+/// resulting number of bits per second for per-interval units.
 ///
 /// # Examples
 ///
@@ -205,11 +295,109 @@ impl Parser<'_> {
 /// // The fractional part less than 1Bps will always be ignored
 /// assert_eq!(parse_binary_bandwidth("150.02456kiBps"),
 ///            Ok(Bandwidth::new(0, (150.02456 * 1024_f64).round() as u32 * 8)));
+/// assert_eq!(parse_binary_bandwidth("300MiB/min"), Ok(Bandwidth::from_bps(41_943_040)));
+/// assert_eq!(parse_binary_bandwidth("4 mebibytes/s"), Ok(Bandwidth::from_bps(33_554_432)));
 /// ```
 pub fn parse_binary_bandwidth(s: &str) -> Result<Bandwidth, Error> {
+    Parser::new(s)
+        .parse_binary()
+        .map_err(ParseError::into_owned)
+}
+
+/// Parse bandwidth object like [`parse_binary_bandwidth`], without allocating
+/// on errors
+///
+/// The unit of [`ParseError::UnknownBinaryUnit`] borrows from `s`, see
+/// [`parse_bandwidth_borrowed`](crate::parse_bandwidth_borrowed).
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{binary_system::parse_binary_bandwidth_borrowed, ParseError};
+///
+/// assert_eq!(parse_binary_bandwidth_borrowed("1kiB/s"), Ok(Bandwidth::from_bps(8_192)));
+/// assert_eq!(
+///     parse_binary_bandwidth_borrowed("1 MiBs"),
+///     Err(ParseError::UnknownBinaryUnit { start: 2, end: 6, unit: "MiBs", value: 1 })
+/// );
+/// ```
+pub fn parse_binary_bandwidth_borrowed(s: &str) -> Result<Bandwidth, ParseError<'_>> {
     Parser::new(s).parse_binary()
 }
 
+#[cfg(feature = "alloc")]
+/// Parse bandwidth object like [`parse_binary_bandwidth`] from bytes, without
+/// copying them
+///
+/// See [`parse_bandwidth_bytes`](crate::parse_bandwidth_bytes).
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{binary_system::parse_binary_bandwidth_bytes, Error};
+///
+/// assert_eq!(parse_binary_bandwidth_bytes(b"1kiB/s"), Ok(Bandwidth::from_bps(8_192)));
+/// assert_eq!(parse_binary_bandwidth_bytes(b"1kiB\xff"), Err(Error::InvalidCharacter(4)));
+/// ```
+pub fn parse_binary_bandwidth_bytes(s: &[u8]) -> Result<Bandwidth, Error> {
+    parse_binary_bandwidth(utf8_str(s)?)
+}
+
+/// Parse bandwidth object like [`parse_binary_bandwidth`] from an OS string,
+/// e.g. a command line argument
+///
+/// See [`parse_bandwidth_os`](crate::parse_bandwidth_os).
+#[cfg(feature = "std")]
+pub fn parse_binary_bandwidth_os(s: &OsStr) -> Result<Bandwidth, Error> {
+    parse_binary_bandwidth(os_str(s)?)
+}
+
+#[cfg(feature = "alloc")]
+/// Parse a range of bandwidths like
+/// [`parse_bandwidth_range`](crate::range::parse_bandwidth_range), with the
+/// units of [`parse_binary_bandwidth`]
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{binary_system::parse_binary_bandwidth_range, range::BandwidthRange};
+///
+/// let range = BandwidthRange::new(Bandwidth::from_bps(8 << 20), Bandwidth::from_bps(16 << 20));
+/// assert_eq!(parse_binary_bandwidth_range("1MiB/s-2MiB/s"), Ok(range));
+/// assert_eq!(parse_binary_bandwidth_range("1–2MiB/s"), Ok(range));
+/// ```
+pub fn parse_binary_bandwidth_range(s: &str) -> Result<BandwidthRange, RangeError> {
+    parse_range_with(s, parse_binary_bandwidth)
+}
+
+#[cfg(feature = "alloc")]
+/// Parse bandwidth object mixing SI and binary units, e.g. `1.5Gbps 200MiB/s`
+///
+/// Each rate span is read with the units of
+/// [`parse_bandwidth`](super::parse_bandwidth) or of
+/// [`parse_binary_bandwidth`] depending on its own unit, so that
+/// user-supplied strings can be read without knowing their system in advance.
+/// An unknown unit is reported as an [`Error::UnknownSystemUnit`] listing the
+/// units of both systems, per second then per minute, hour, day and month.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::binary_system::parse_any_bandwidth;
+///
+/// assert_eq!(parse_any_bandwidth("1.5Gbps"), Ok(Bandwidth::from_mbps(1_500)));
+/// assert_eq!(parse_any_bandwidth("1MiB/s"), Ok(Bandwidth::from_bps(8_388_608)));
+/// assert_eq!(
+///     parse_any_bandwidth("1.5Gbps 200MiB/s"),
+///     Ok(Bandwidth::from_bps(1_500_000_000 + 200 * 8_388_608)),
+/// );
+/// ```
+pub fn parse_any_bandwidth(s: &str) -> Result<Bandwidth, Error> {
+    Parser::new(s).parse_any()
+}
+
 /// Formats bandwidth into a human-readable string using the binary prefix system
 ///
 /// Note: this format is NOT guaranteed to have same value when using
@@ -246,65 +434,156 @@ pub fn parse_binary_bandwidth(s: &str) -> Result<Bandwidth, Error> {
 /// # }
 /// ```
 pub fn format_binary_bandwidth(val: Bandwidth) -> FormattedBinaryBandwidth {
-    FormattedBinaryBandwidth(val)
+    FormattedBinaryBandwidth(val, Interval::Second)
 }
 
-#[derive(Copy, Clone)]
+/// A binary unit of Bytes per second, see [`largest_binary_unit`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(usize)]
-enum LargestBinaryUnit {
+pub enum BinaryUnit {
+    /// Bytes per second
     Bps = 0,
+    /// Kibibytes per second
     KiBps = 1,
+    /// Mebibytes per second
     MiBps = 2,
+    /// Gibibytes per second
     GiBps = 3,
+    /// Tebibytes per second
     TiBps = 4,
+    /// Pebibytes per second
+    PiBps = 5,
+    /// Exbibytes per second
+    EiBps = 6,
 }
 
-impl fmt::Display for LargestBinaryUnit {
+impl BinaryUnit {
+    /// Every unit, from the smallest to the largest
+    ///
+    /// The names of these units are the ones listed by the errors of unknown
+    /// units.
+    pub const ALL: [BinaryUnit; 7] = [
+        BinaryUnit::Bps,
+        BinaryUnit::KiBps,
+        BinaryUnit::MiBps,
+        BinaryUnit::GiBps,
+        BinaryUnit::TiBps,
+        BinaryUnit::PiBps,
+        BinaryUnit::EiBps,
+    ];
+
+    /// The largest unit in which `bytes` is at least 1, or [`BinaryUnit::Bps`]
+    fn of(bytes: u128) -> Self {
+        BinaryUnit::ALL
+            .into_iter()
+            .rev()
+            .find(|unit| bytes >= u128::from(unit.bytes_per_second()))
+            .unwrap_or(BinaryUnit::Bps)
+    }
+
+    /// The canonical name of the unit, e.g. `"MiB/s"`, as written by
+    /// [`format_binary_bandwidth`]
+    pub const fn name(self) -> &'static str {
+        binary_unit_name(self as u32, Interval::Second)
+    }
+
+    /// The number of Bytes per second in one of this unit, e.g. `1_048_576`
+    /// for [`BinaryUnit::MiBps`]
+    pub const fn bytes_per_second(self) -> u64 {
+        1 << (10 * self as u32)
+    }
+}
+
+impl fmt::Display for BinaryUnit {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            LargestBinaryUnit::Bps => f.write_str("B/s"),
-            LargestBinaryUnit::KiBps => f.write_str("kiB/s"),
-            LargestBinaryUnit::MiBps => f.write_str("MiB/s"),
-            LargestBinaryUnit::GiBps => f.write_str("GiB/s"),
-            LargestBinaryUnit::TiBps => f.write_str("TiB/s"),
-        }
+        f.write_str(self.name())
     }
 }
 
+/// Returns the unit [`format_binary_bandwidth`] writes a bandwidth in, i.e.
+/// the largest one in which it is at least 1, and the value in this unit
+///
+/// Like the formatting, the unit is chosen from the amount of Bytes per
+/// second rounded to the nearest.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::binary_system::{largest_binary_unit, BinaryUnit};
+///
+/// let val = Bandwidth::from_bps(12_582_912);
+/// assert_eq!(largest_binary_unit(val), (BinaryUnit::MiBps, 1.5));
+/// assert_eq!(largest_binary_unit(Bandwidth::from_bps(4)), (BinaryUnit::Bps, 0.5));
+/// assert_eq!(BinaryUnit::MiBps.to_string(), "MiB/s");
+/// ```
+pub fn largest_binary_unit(val: Bandwidth) -> (BinaryUnit, f64) {
+    let unit = BinaryUnit::of(FormattedBinaryBandwidth(val, Interval::Second).total_bytes());
+    let bytes = crate::bandwidth_as_bps(val) as f64 / BITS_PER_BYTE as f64;
+    (unit, bytes / unit.bytes_per_second() as f64)
+}
+
 impl FormattedBinaryBandwidth {
+    /// Writes the bandwidth as an amount per `interval` instead of per
+    /// second, e.g. `4GiB/h`
+    ///
+    /// The amount is rounded to the nearest Byte per interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{binary_system::format_binary_bandwidth, Interval};
+    ///
+    /// let val = Bandwidth::from_bps(41_943_040);
+    /// let per_minute = format_binary_bandwidth(val).per(Interval::Minute);
+    /// # #[cfg(not(feature = "display-integer"))]
+    /// assert_eq!(per_minute.to_string(), "300MiB/min");
+    /// assert_eq!(per_minute.interval(), Interval::Minute);
+    /// ```
+    pub fn per(self, interval: Interval) -> Self {
+        FormattedBinaryBandwidth(self.0, interval)
+    }
+
+    /// The interval the bandwidth is written per, [`Interval::Second`] by
+    /// default
+    pub fn interval(&self) -> Interval {
+        self.1
+    }
+
+    /// Amount of Bytes per interval, rounded to the nearest
+    ///
+    /// It is rounded down at the top of the range, where rounding up would
+    /// write a bandwidth above [`Bandwidth::MAX`] which could not be parsed
+    /// back.
+    fn total_bytes(&self) -> u128 {
+        let seconds = self.1.seconds() as u128;
+        let bits = crate::bandwidth_as_bps(self.0) * seconds;
+        let bytes = (bits + BITS_PER_BYTE as u128 / 2) / BITS_PER_BYTE as u128;
+        if bytes * BITS_PER_BYTE as u128 > crate::bandwidth_as_bps(Bandwidth::MAX) * seconds {
+            bits / BITS_PER_BYTE as u128
+        } else {
+            bytes
+        }
+    }
+
     /// Enabling the `display-integer` feature will display integer values only
     ///
     /// This method is preserved for backward compatibility and custom formatting.
     pub fn fmt_integer(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let gbps = self.0.as_gbps();
-        let bps = self.0.subgbps_bps();
-
-        if gbps == 0 && bps == 0 {
-            f.write_str("0B/s")?;
-            return Ok(());
+        if self.0.as_gbps() == 0 && self.0.subgbps_bps() == 0 {
+            return write!(f, "0{}", binary_unit_name(0, self.1));
         }
 
-        let total: u64 = gbps * 1_000_000_000 + bps as u64;
-        let total = (total + 4) / 8;
-
-        let tibps = (total / (1024 * 1024 * 1024 * 1024)) as u32;
-        let total = total % (1024 * 1024 * 1024 * 1024);
-
-        let gibps = (total / (1024 * 1024 * 1024)) as u32;
-        let total = total % (1024 * 1024 * 1024);
-
-        let mibps = (total / (1024 * 1024)) as u32;
-        let total = total % (1024 * 1024);
-
-        let kibps = (total / 1024) as u32;
-        let bps = (total % 1024) as u32;
-
+        let mut total = self.total_bytes();
         let started = &mut false;
-        item(f, started, "TiB/s", tibps)?;
-        item(f, started, "GiB/s", gibps)?;
-        item(f, started, "MiB/s", mibps)?;
-        item(f, started, "kiB/s", kibps)?;
-        item(f, started, "B/s", bps)?;
+        for power in (0..=BinaryUnit::EiBps as u32).rev() {
+            let scale = 1u128 << (10 * power);
+            // At most 2^34 EiB per day
+            let value = (total / scale) as u64;
+            total %= scale;
+            item(f, started, binary_unit_name(power, self.1), value)?;
+        }
         Ok(())
     }
 
@@ -312,55 +591,19 @@ impl FormattedBinaryBandwidth {
     ///
     /// This method is preserved for custom formatting.
     pub fn fmt_decimal(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let gbps = self.0.as_gbps();
-        let bps = self.0.subgbps_bps();
-
-        if gbps == 0 && bps == 0 {
-            f.write_str("0B/s")?;
-            return Ok(());
+        if self.0.as_gbps() == 0 && self.0.subgbps_bps() == 0 {
+            return write!(f, "0{}", binary_unit_name(0, self.1));
         }
 
-        let total: u64 = gbps * 1_000_000_000 + bps as u64;
-        let total = (total + 4) / 8;
-
-        let tibps = (total / (1024 * 1024 * 1024 * 1024)) as u32;
-        let total = total % (1024 * 1024 * 1024 * 1024);
-
-        let gibps = (total / (1024 * 1024 * 1024)) as u32;
-        let total = total % (1024 * 1024 * 1024);
-
-        let mibps = (total / (1024 * 1024)) as u32;
-        let total = total % (1024 * 1024);
+        let total = self.total_bytes();
+        let index = BinaryUnit::of(total) as usize;
 
-        let kibps = (total / 1024) as u32;
-        let bps = (total % 1024) as u32;
+        let mut value = total >> (index * 10);
+        let reminder = total % (1 << (index * 10));
 
-        let largest_unit = if tibps > 0 {
-            LargestBinaryUnit::TiBps
-        } else if gibps > 0 {
-            LargestBinaryUnit::GiBps
-        } else if mibps > 0 {
-            LargestBinaryUnit::MiBps
-        } else if kibps > 0 {
-            LargestBinaryUnit::KiBps
-        } else {
-            LargestBinaryUnit::Bps
-        };
-
-        let values = [bps, kibps, mibps, gibps, tibps];
-        let index = largest_unit as usize;
-
-        let mut value = values[index];
-
-        let mut reminder = 0;
-        let mut i = index;
-        while i > 0 {
-            reminder *= 1024;
-            reminder += values[i - 1] as u64;
-            i -= 1;
-        }
         let mut zeros = index * 3;
-        let reminder = (reminder as u128) * 1000_u128.pow(index as u32);
+        // The reminder is below 2^60, so it fits in an u128 once scaled
+        let reminder = reminder * 1000_u128.pow(index as u32);
         let rounding = if index == 0 { 0 } else { 1 << (index * 10 - 1) };
         let loss = reminder % (1 << (index * 10));
         let mut reminder = (reminder + rounding) >> (index * 10);
@@ -377,7 +620,7 @@ impl FormattedBinaryBandwidth {
                     0 => {
                         // rounding_direction does not change
                     }
-                    1..5 => {
+                    1..=4 => {
                         // we are smaller
                         rounding_direction = -1;
                     }
@@ -400,7 +643,7 @@ impl FormattedBinaryBandwidth {
                             rounding_direction = -1;
                         }
                     }
-                    6..10 => {
+                    6..=9 => {
                         // we are bigger
                         reminder += 1;
                         rounding_direction = 1;
@@ -410,7 +653,7 @@ impl FormattedBinaryBandwidth {
                 zeros -= 1;
             }
             if precision == 0 && reminder > 0 {
-                value += reminder as u32;
+                value += reminder;
                 reminder = 0;
             }
         } else if reminder != 0 {
@@ -425,7 +668,7 @@ impl FormattedBinaryBandwidth {
         if zeros != 0 || reminder != 0 {
             write!(f, ".{reminder:0zeros$}", zeros = zeros)?;
         }
-        write!(f, "{}", largest_unit)
+        f.write_str(binary_unit_name(index as u32, self.1))
     }
 }
 
@@ -453,23 +696,228 @@ impl core::ops::DerefMut for FormattedBinaryBandwidth {
     }
 }
 
-#[cfg(test)]
+/// Shows the raw amount of bits per second alongside its rounded rendering in
+/// the binary prefix system, e.g.
+/// `FormattedBinaryBandwidth(12_582_912bps ≈ 1.5MiB/s)`
+impl fmt::Debug for FormattedBinaryBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FormattedBinaryBandwidth({}bps ≈ ",
+            crate::Grouped(crate::bandwidth_as_bps(self.0))
+        )?;
+        self.fmt_decimal(f)?;
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Parses a bandwidth with [`parse_binary_bandwidth`], so that the wrapper
+/// can both read and write the binary prefix format
+///
+/// Per-interval units are accepted, but the bandwidth is written per second
+/// until [`per`](FormattedBinaryBandwidth::per) is called.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::binary_system::FormattedBinaryBandwidth;
+///
+/// let formatted: FormattedBinaryBandwidth = "1MiB/s 512kiB/s".parse().unwrap();
+/// assert_eq!(*formatted, Bandwidth::from_bps(1_536 * 1_024 * 8));
+/// # #[cfg(not(feature = "display-integer"))]
+/// assert_eq!(formatted.to_string(), "1.5MiB/s");
+/// ```
+impl core::str::FromStr for FormattedBinaryBandwidth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        parse_binary_bandwidth(s).map(format_binary_bandwidth)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<FormattedBinaryBandwidth> for String {
+    fn from(val: FormattedBinaryBandwidth) -> String {
+        val.to_string()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for FormattedBinaryBandwidth {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Error> {
+        s.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<String> for FormattedBinaryBandwidth {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Error> {
+        s.parse()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
+    use crate::consts::{GIBI, GIGA, KIBI, MEBI, TEBI};
+    use crate::ParseOptions;
     use bandwidth::Bandwidth;
 
     fn new_bandwidth(tebi: u16, gibi: u16, mibi: u16, kibi: u16, bytes: u16) -> Bandwidth {
-        const KI_B: u64 = 1024 * 8;
-        const MI_B: u64 = 1024 * KI_B;
-        const GI_B: u64 = 1024 * MI_B;
-        const TI_B: u64 = 1024 * GI_B;
-
-        let res: u64 = bytes as u64 * 8
-            + kibi as u64 * KI_B
-            + mibi as u64 * MI_B
-            + gibi as u64 * GI_B
-            + tebi as u64 * TI_B;
-        Bandwidth::new(res / 1_000_000_000, (res % 1_000_000_000) as u32)
+        let res: u64 = (bytes as u64
+            + kibi as u64 * KIBI
+            + mibi as u64 * MEBI
+            + gibi as u64 * GIBI
+            + tebi as u64 * TEBI)
+            * BITS_PER_BYTE;
+        Bandwidth::new(res / GIGA, (res % GIGA) as u32)
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(
+            parse_binary_bandwidth_bytes(b"1MiB/s 1B/s"),
+            parse_binary_bandwidth("1MiB/s 1B/s")
+        );
+        assert_eq!(
+            parse_binary_bandwidth_bytes(b"\xe9kiB/s"),
+            Err(Error::InvalidCharacter(0))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_os() {
+        assert_eq!(
+            parse_binary_bandwidth_os(OsStr::new("2GiB/h")),
+            parse_binary_bandwidth("2GiB/h")
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            assert_eq!(
+                parse_binary_bandwidth_os(OsStr::from_bytes(b"1 \x80MiB/s")),
+                Err(Error::InvalidCharacter(2))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_borrowed() {
+        for s in ["", "1", "1MiBs", "1MiB/s x", "99999999999999999999EiB/s"] {
+            assert_eq!(
+                parse_binary_bandwidth_borrowed(s).map_err(Error::from),
+                parse_binary_bandwidth(s)
+            );
+            let err = parse_binary_bandwidth_borrowed(s).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                parse_binary_bandwidth(s).unwrap_err().to_string()
+            );
+        }
+        let src = String::from("1GiB/s 10MiBs");
+        match parse_binary_bandwidth_borrowed(&src) {
+            Err(err @ ParseError::UnknownBinaryUnit { unit, .. }) => {
+                assert!(std::ptr::eq(unit, &src[9..]));
+                assert_eq!(err.suggestion(), Some("MiB/s"));
+            }
+            res => panic!("unexpected {:?}", res),
+        }
+        assert_eq!(
+            ParseOptions::strict().parse_binary_borrowed("1 kib/s"),
+            Err(ParseError::UnknownBinaryUnit {
+                start: 2,
+                end: 7,
+                unit: "kib/s",
+                value: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let range = |min, max| {
+            Ok(BandwidthRange::new(
+                Bandwidth::from_bps(min),
+                Bandwidth::from_bps(max),
+            ))
+        };
+        assert_eq!(
+            parse_binary_bandwidth_range("512kiB/s - 2MiB/s"),
+            range(4 << 20, 16 << 20)
+        );
+        assert_eq!(
+            parse_binary_bandwidth_range("0.5–2 MiB/s"),
+            range(4 << 20, 16 << 20)
+        );
+        assert_eq!(
+            parse_binary_bandwidth_range("1–1GiB/s"),
+            range(8 << 30, 8 << 30)
+        );
+        assert_eq!(
+            parse_binary_bandwidth_range("2–1kiB/s"),
+            Err(RangeError::Reversed)
+        );
+        assert_eq!(
+            parse_binary_bandwidth_range("1Mbps-2Mbps"),
+            Err(RangeError::InvalidBandwidth(Error::UnknownBinaryUnit {
+                start: 1,
+                end: 5,
+                unit: "Mbps".to_string(),
+                value: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_unit_aliases() {
+        const ALIASES: &[(&str, &str)] = &[("MB/s", "MiB/s"), ("megs", "Mbps")];
+        let options = ParseOptions::new().unit_aliases(ALIASES);
+        assert_eq!(
+            options.parse_binary("1MB/s"),
+            Ok(Bandwidth::from_bps(8 << 20))
+        );
+        assert_eq!(
+            options.parse_binary("1megs"),
+            Err(Error::UnknownBinaryUnit {
+                start: 1,
+                end: 5,
+                unit: "megs".to_string(),
+                value: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_case_sensitive_prefixes() {
+        // The units per interval are checked too
+        let options = ParseOptions::strict().enabled_units(crate::EnabledUnits::ALL);
+        assert_eq!(
+            options.parse_binary("1KiB/s 1kiBps 1Bps"),
+            Ok(Bandwidth::from_bps(2 * 8 * 1_024 + 8))
+        );
+        assert_eq!(
+            options.parse_binary("1GiB/h 1MiB/min"),
+            parse_binary_bandwidth("1GiB/h 1MiB/min")
+        );
+        for unit in ["miB/s", "giBps", "tiByte/s", "piops", "eio/s", "miB/min"] {
+            assert!(parse_binary_bandwidth(&format!("1{}", unit)).is_ok());
+            assert_eq!(
+                options.parse_binary(&format!("1{}", unit)),
+                Err(Error::UnknownBinaryUnit {
+                    start: 1,
+                    end: 1 + unit.len(),
+                    unit: unit.to_string(),
+                    value: 1,
+                })
+            );
+        }
     }
 
     #[test]
@@ -834,22 +1282,26 @@ mod tests {
 
     #[test]
     fn test_overflow() {
-        // The overflow arrives du to the limits of u64 to read the number, not during the conversion to bandwidth
+        // The overflow arrives once the value cannot be held by a bandwidth
         assert_eq!(
-            parse_binary_bandwidth("100_000_000_000_000_000_000Bps"),
+            parse_binary_bandwidth("10_000_000_000_000_000_000_000_000_000Bps"),
             Err(Error::NumberOverflow)
         );
-        assert!(parse_binary_bandwidth("10_000_000_000_000_000_000Bps").is_ok());
+        assert!(parse_binary_bandwidth("1_000_000_000_000_000_000_000_000_000Bps").is_ok());
+        assert_eq!(
+            parse_binary_bandwidth("100_000_000_000_000_000_000Bps"),
+            Ok(Bandwidth::new(800_000_000_000, 0))
+        );
         assert_eq!(
-            parse_binary_bandwidth("100_000_000_000_000_000_000kiBps"),
+            parse_binary_bandwidth("10_000_000_000_000_000_000_000_000kiBps"),
             Err(Error::NumberOverflow)
         );
-        assert!(parse_binary_bandwidth("10_000_000_000_000_000_000kiBps").is_ok());
+        assert!(parse_binary_bandwidth("1_000_000_000_000_000_000_000_000kiBps").is_ok());
         assert_eq!(
-            parse_binary_bandwidth("100_000_000_000_000_000_000MiBps"),
+            parse_binary_bandwidth("10_000_000_000_000_000_000_000MiBps"),
             Err(Error::NumberOverflow)
         );
-        assert!(parse_binary_bandwidth("10_000_000_000_000_000_000MiBps").is_ok());
+        assert!(parse_binary_bandwidth("1_000_000_000_000_000_000_000MiBps").is_ok());
 
         // For GiBps and TiBps, the overflow arrive for smaller number du to the multiplication by 8 (for B/s to bps)
         assert_eq!(
@@ -862,6 +1314,13 @@ mod tests {
             Err(Error::NumberOverflow)
         );
         assert!(parse_binary_bandwidth("1_000_000_000_000_000TiBps").is_ok());
+
+        // The total of several spans is checked once, at the end
+        assert!(parse_binary_bandwidth(&"1_000_000_000_000_000TiBps ".repeat(2)).is_ok());
+        assert_eq!(
+            parse_binary_bandwidth(&"1_000_000_000_000_000TiBps ".repeat(3)),
+            Err(Error::NumberOverflow)
+        );
     }
 
     #[test]
@@ -879,10 +1338,174 @@ mod tests {
         assert_eq!(
             parse_binary_bandwidth("10 byte/s").unwrap_err().to_string(),
             "unknown binary bandwidth unit \"byte/s\", \
-                    supported units: B/s, kiB/s, MiB/s, GiB/s, TiB/s"
+                    supported units: B/s, kiB/s, MiB/s, GiB/s, TiB/s, PiB/s, EiB/s"
+        );
+        let err = parse_binary_bandwidth("10Mibs").unwrap_err();
+        assert_eq!(err.suggestion(), Some("MiB/s"));
+        assert_eq!(
+            err.to_string(),
+            "unknown binary bandwidth unit \"Mibs\", did you mean \"MiB/s\"?"
         );
     }
 
+    #[test]
+    fn test_exponent_notation() {
+        assert_eq!(
+            parse_binary_bandwidth("1.5e3kiB/s"),
+            Ok(Bandwidth::from_bps(8 * 1_500 * 1_024))
+        );
+        assert_eq!(
+            parse_binary_bandwidth("2E-1 MiB/s 1e1B/s"),
+            parse_binary_bandwidth("0.2MiB/s 10B/s")
+        );
+        assert_eq!(
+            parse_binary_bandwidth("2EiB/s"),
+            parse_binary_bandwidth("2048PiB/s")
+        );
+        assert_eq!(
+            parse_any_bandwidth("1e3kbps 1e0B/s"),
+            Ok(Bandwidth::from_bps(1_000_008))
+        );
+    }
+
+    #[test]
+    fn test_large_units() {
+        const PEBI: u128 = 1 << 50;
+        const EXBI: u128 = 1 << 60;
+        for (s, bps) in [
+            ("1PiB/s", 8 * PEBI),
+            ("3PiBps", 24 * PEBI),
+            ("1.5EiB/s", 12 * EXBI),
+            ("1_999_999_999EiB/s", 8 * 1_999_999_999 * EXBI),
+            // Rounded to the nearest bit per second
+            ("1EiB/d", (8 * EXBI + 43_200) / 86_400),
+        ] {
+            let parsed = parse_binary_bandwidth(s).map(crate::bandwidth_as_bps);
+            assert_eq!(parsed, Ok(bps), "{}", s);
+        }
+        // Just above the maximum bandwidth
+        assert_eq!(
+            parse_binary_bandwidth("2_000_000_000EiB/s"),
+            Err(Error::NumberOverflow)
+        );
+        let val = Bandwidth::from_bps(1 << 53);
+        assert_eq!(largest_binary_unit(val), (BinaryUnit::PiBps, 1.0));
+        #[cfg(not(feature = "display-integer"))]
+        {
+            assert_eq!(format_binary_bandwidth(val).to_string(), "1PiB/s");
+            // Rounded down, 2000000000EiB/s being above the maximum
+            assert_eq!(
+                format_binary_bandwidth(Bandwidth::MAX).to_string(),
+                "1999999999.999999999999999999EiB/s"
+            );
+        }
+        // Written bandwidths are parsed back, rounded to the nearest Byte per
+        // interval
+        let max = crate::bandwidth_as_bps(Bandwidth::MAX);
+        for bps in [max, max - 1, max - 4, max - 7, max - 8, max - 1_000] {
+            let val = Bandwidth::new((bps / 1_000_000_000) as u64, (bps % 1_000_000_000) as u32);
+            let formatted = format_binary_bandwidth(val).to_string();
+            let parsed = parse_binary_bandwidth(&formatted).map(crate::bandwidth_as_bps);
+            assert!(
+                matches!(parsed, Ok(parsed) if parsed.abs_diff(bps) < 8),
+                "{} {:?}",
+                formatted,
+                parsed
+            );
+            for interval in [Interval::Minute, Interval::Hour, Interval::Month] {
+                let formatted = format_binary_bandwidth(val).per(interval).to_string();
+                assert!(parse_binary_bandwidth(&formatted).is_ok(), "{}", formatted);
+            }
+        }
+        #[cfg(feature = "display-integer")]
+        assert_eq!(
+            format_binary_bandwidth(Bandwidth::from_bps(8 << 60 | 8 << 50)).to_string(),
+            "1EiB/s 1PiB/s"
+        );
+    }
+
+    #[test]
+    fn test_spelled_units() {
+        assert_eq!(
+            parse_binary_bandwidth("4 mebibytes/s"),
+            Ok(Bandwidth::from_bps(4 * 8 * 1024 * 1024))
+        );
+        assert_eq!(
+            parse_binary_bandwidth("1 Kibibyte per second 2 bytes/s"),
+            Ok(Bandwidth::from_bps(8 * 1024 + 16))
+        );
+        assert_eq!(
+            parse_binary_bandwidth("300 MEBIBYTES PER MINUTE"),
+            Ok(Bandwidth::from_bps(41_943_040))
+        );
+        assert_eq!(
+            parse_binary_bandwidth("4 megabytes/s"),
+            Err(Error::UnknownBinaryUnit {
+                start: 2,
+                end: 13,
+                unit: "megabytes/s".to_string(),
+                value: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_any() {
+        assert_eq!(
+            parse_any_bandwidth("1Gbps 1GiB/s"),
+            Ok(Bandwidth::from_bps(1_000_000_000 + 8 * GIBI))
+        );
+        // The per-interval and fractional rules of each system apply
+        assert_eq!(
+            parse_any_bandwidth("1.5kbps 1kiB/min"),
+            Ok(Bandwidth::from_bps(1_500 + 137))
+        );
+        assert_eq!(
+            parse_any_bandwidth("500Gbit/h 2Tbit/d 1MiB/h"),
+            Ok(Bandwidth::from_bps(138_888_888 + 23_148_148 + 2_330))
+        );
+        assert_eq!(
+            parse_any_bandwidth("2TiBps 10Tbps"),
+            Ok(Bandwidth::from_bps(2 * 8 * TEBI + 10_000_000_000_000))
+        );
+        assert_eq!(
+            parse_any_bandwidth("1 gigabit per second 1 gibibyte/s"),
+            Ok(Bandwidth::from_bps(1_000_000_000 + 8 * GIBI))
+        );
+        assert_eq!(
+            parse_any_bandwidth(" "),
+            Err(Error::OnlyWhitespace { len: 1 })
+        );
+        let error = parse_any_bandwidth("10Mbps 1 MB/s").unwrap_err();
+        let supported = match error {
+            Error::UnknownSystemUnit {
+                start: 9,
+                end: 13,
+                unit,
+                value: 1,
+                supported,
+            } if unit == "MB/s" => supported,
+            error => panic!("unexpected error {:?}", error),
+        };
+        // The units per second, then per minute, hour, day and month
+        assert!(supported.starts_with(
+            "bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps, \
+             B/s, kiB/s, MiB/s, GiB/s, TiB/s, PiB/s, EiB/s, \
+             bit/min, kbit/min,"
+        ));
+        assert!(supported.contains(", Gbit/h, "));
+        assert!(supported.ends_with(", PiB/month, EiB/month"));
+        assert_eq!(supported.split(", ").count(), 70);
+        assert!(parse_any_bandwidth("12")
+            .unwrap_err()
+            .to_string()
+            .starts_with(
+                "bandwidth unit needed, supported units: \
+                 bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps, B/s, kiB/s, MiB/s, GiB/s, TiB/s, PiB/s, \
+                 EiB/s, bit/min,"
+            ));
+    }
+
     #[test]
     fn test_formatted_bandwidth_integer() {
         struct TestInteger(FormattedBinaryBandwidth);
@@ -1083,4 +1706,110 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_per_interval() {
+        let bps = |bps| Ok(Bandwidth::from_bps(bps));
+        assert_eq!(parse_binary_bandwidth("4GiB/h"), bps(9_544_372));
+        assert_eq!(parse_binary_bandwidth("300MiB/min"), bps(41_943_040));
+        assert_eq!(parse_binary_bandwidth("1GiB/d"), bps(99_421));
+        assert_eq!(parse_binary_bandwidth("1.5kiB/h"), bps(3));
+        assert_eq!(parse_binary_bandwidth("1B/min"), bps(0));
+        assert_eq!(parse_binary_bandwidth("2MiByte/h 1Mio/min"), bps(144_470));
+        assert_eq!(parse_binary_bandwidth("1TiB/month"), bps(3_393_554));
+        let options = ParseOptions::new().month_days(31);
+        assert_eq!(options.parse_binary("1TiB/month"), bps(3_284_085));
+        assert_eq!(
+            options.parse_binary("1TiB/d"),
+            parse_binary_bandwidth("1TiB/d")
+        );
+        for s in ["1GiB/week", "1MiB/s/h", "1MiBps/h", "1Mbps/h"] {
+            assert!(
+                matches!(
+                    parse_binary_bandwidth(s),
+                    Err(Error::UnknownBinaryUnit { .. })
+                ),
+                "{}",
+                s
+            );
+        }
+
+        let val = Bandwidth::from_bps(41_943_040);
+        let formatted = |interval| format_binary_bandwidth(val).per(interval).to_string();
+        assert_eq!(formatted(Interval::Second), "5MiB/s");
+        assert_eq!(formatted(Interval::Minute), "300MiB/min");
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(formatted(Interval::Day), "421.875GiB/d");
+        #[cfg(feature = "display-integer")]
+        assert_eq!(formatted(Interval::Day), "421GiB/d 896MiB/d");
+        assert_eq!(
+            format_binary_bandwidth(Bandwidth::new(0, 0))
+                .per(Interval::Hour)
+                .to_string(),
+            "0B/h"
+        );
+        #[cfg(not(feature = "display-integer"))]
+        for s in ["1GiB/h", "12MiB/min", "3TiB/d", "2TiB/month"] {
+            let val = parse_binary_bandwidth(s).unwrap();
+            let interval = if s.ends_with("/h") {
+                Interval::Hour
+            } else if s.ends_with("/min") {
+                Interval::Minute
+            } else if s.ends_with("/month") {
+                Interval::Month
+            } else {
+                Interval::Day
+            };
+            let formatted = format!("{:.0}", format_binary_bandwidth(val).per(interval));
+            assert_eq!(formatted, s);
+        }
+    }
+
+    #[test]
+    fn test_formatted_bandwidth_from_str() {
+        let formatted: FormattedBinaryBandwidth = "3MiB/s 5B/s".parse().unwrap();
+        assert_eq!(*formatted, Bandwidth::from_bps((3 * 1_024 * 1_024 + 5) * 8));
+        assert_eq!(formatted.interval(), Interval::Second);
+        assert_eq!(formatted, format_binary_bandwidth(*formatted));
+        assert_ne!(formatted, formatted.clone().per(Interval::Minute));
+        assert!(matches!(
+            "1Mbps".parse::<FormattedBinaryBandwidth>(),
+            Err(Error::UnknownBinaryUnit { .. })
+        ));
+        for bytes in [0, 1, 1_025, 3 << 40] {
+            let val = Bandwidth::from_bps(bytes * 8);
+            let s = String::from(format_binary_bandwidth(val));
+            assert_eq!(s.parse::<FormattedBinaryBandwidth>().map(|f| *f), Ok(val));
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        let val = Bandwidth::from_bps(12_582_912);
+        assert_eq!(
+            format!("{:?}", format_binary_bandwidth(val)),
+            "FormattedBinaryBandwidth(12_582_912bps ≈ 1.5MiB/s)"
+        );
+        assert_eq!(
+            format!("{:?}", format_binary_bandwidth(val).per(Interval::Minute)),
+            "FormattedBinaryBandwidth(12_582_912bps ≈ 90MiB/min)"
+        );
+        assert_eq!(
+            format!("{:?}", format_binary_bandwidth(Bandwidth::from_bps(1))),
+            "FormattedBinaryBandwidth(1bps ≈ 0B/s)"
+        );
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            FormattedBinaryBandwidth::try_from("1kiB/s").map(|f| *f),
+            Ok(Bandwidth::from_bps(8_192))
+        );
+        assert_eq!(
+            FormattedBinaryBandwidth::try_from(String::from("2B/s")).map(|f| *f),
+            Ok(Bandwidth::from_bps(16))
+        );
+        assert!(FormattedBinaryBandwidth::try_from("1kbps").is_err());
+    }
 }
@@ -26,10 +26,11 @@
 //! }
 //! ```
 
+use alloc::string::ToString;
 use bandwidth::Bandwidth;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
 use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
-use std::fmt;
-use std::ops::{Deref, DerefMut};
 
 /// Deserializes a `Bandwidth` in human-readable format.
 ///
@@ -96,30 +97,60 @@ impl<T> From<T> for Serde<T> {
     }
 }
 
+/// The [`Visitor`](de::Visitor) used to deserialize a `Bandwidth` from a string
+///
+/// It parses the string with [`parse_binary_bandwidth`](super::parse_binary_bandwidth)
+/// and can be reused by manual `Deserialize` implementations, e.g. for enums.
+///
+/// # Example
+///
+/// ```
+/// use serde::{de::Visitor, Deserialize, Deserializer};
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::binary_system::serde::BinaryBandwidthVisitor;
+///
+/// enum Limit {
+///     Unlimited,
+///     Rate(Bandwidth),
+/// }
+///
+/// impl<'de> Deserialize<'de> for Limit {
+///     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Limit, D::Error> {
+///         let s = String::deserialize(d)?;
+///         if s == "unlimited" {
+///             return Ok(Limit::Unlimited);
+///         }
+///         BinaryBandwidthVisitor.visit_str(&s).map(Limit::Rate)
+///     }
+/// }
+///
+/// let limit: Limit = serde_json::from_str(r#""10MiB/s""#).unwrap();
+/// assert!(matches!(limit, Limit::Rate(_)));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BinaryBandwidthVisitor;
+
+impl de::Visitor<'_> for BinaryBandwidthVisitor {
+    type Value = Bandwidth;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a bandwidth")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
+    where
+        E: de::Error,
+    {
+        super::parse_binary_bandwidth(v).map_err(|e| crate::serde::invalid_bandwidth(v, e))
+    }
+}
+
 impl<'de> Deserialize<'de> for Serde<Bandwidth> {
     fn deserialize<D>(d: D) -> Result<Serde<Bandwidth>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct V;
-
-        impl de::Visitor<'_> for V {
-            type Value = Bandwidth;
-
-            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-                fmt.write_str("a bandwidth")
-            }
-
-            fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
-            where
-                E: de::Error,
-            {
-                super::parse_binary_bandwidth(v)
-                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
-            }
-        }
-
-        d.deserialize_str(V).map(Serde)
+        d.deserialize_str(BinaryBandwidthVisitor).map(Serde)
     }
 }
 
@@ -224,4 +255,21 @@ mod tests {
         let foo = serde_json::from_str::<Foo>(json).unwrap();
         assert_eq!(foo.bandwidth, None);
     }
+
+    #[test]
+    fn errors() {
+        #[derive(Debug, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            #[allow(dead_code)]
+            bandwidth: Bandwidth,
+        }
+
+        let json = r#"{"bandwidth": "1Mbps"}"#;
+        assert_eq!(
+            serde_json::from_str::<Foo>(json).unwrap_err().to_string(),
+            "invalid bandwidth \"1Mbps\" at bytes 1..5: unknown binary bandwidth unit \"Mbps\", \
+            supported units: B/s, kiB/s, MiB/s, GiB/s, TiB/s, PiB/s, EiB/s at line 1 column 21"
+        );
+    }
 }
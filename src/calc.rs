@@ -0,0 +1,286 @@
+//! Arithmetic on bandwidths for simulators and schedulers
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::calc::{per_tick, Remainder};
+//!
+//! // 1kbps is 125B/s, so 0.125B every millisecond
+//! let (bytes, remainder) = per_tick(Bandwidth::from_kbps(1), Duration::from_millis(1));
+//! assert_eq!(bytes, 0);
+//!
+//! let mut carry = Remainder::default();
+//! let sent: u64 = (0..1_000).map(|_| bytes + carry.accumulate(remainder)).sum();
+//! assert_eq!(sent, 125);
+//! ```
+
+use core::{ops::RangeInclusive, time::Duration};
+
+use bandwidth::Bandwidth;
+
+use crate::{
+    bandwidth_as_bps,
+    consts::{BITS_PER_BYTE, GIGA},
+    size::DataSize,
+};
+
+/// Bits in a Byte times nanoseconds in a second: the remainders are counted
+/// in this fraction of a Byte
+const DENOMINATOR: u128 = BITS_PER_BYTE as u128 * GIGA as u128;
+
+/// The fraction of a Byte which does not fit in a tick, see [`per_tick`]
+///
+/// Adding the remainder of every tick to an accumulator with
+/// [`accumulate`](Remainder::accumulate) gives back the missing Bytes once
+/// they add up to whole ones, so the rate does not drift over time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Remainder {
+    /// In `1 / DENOMINATOR` of a Byte, always less than `DENOMINATOR`
+    numerator: u128,
+}
+
+impl Remainder {
+    /// Whether there is no fraction of a Byte left
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The fraction of a Byte, between 0 inclusive and 1 exclusive
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / DENOMINATOR as f64
+    }
+
+    /// The exact fraction of a Byte, as a numerator and a denominator
+    ///
+    /// The denominator is the same for every remainder, 8 000 000 000 i.e.
+    /// the bits in a Byte times the nanoseconds in a second.
+    pub fn as_ratio(&self) -> (u128, u128) {
+        (self.numerator, DENOMINATOR)
+    }
+
+    /// Adds `other` to this accumulator, returning the whole Byte it carries
+    /// over, if any
+    pub fn accumulate(&mut self, other: Remainder) -> u64 {
+        self.numerator += other.numerator;
+        if self.numerator >= DENOMINATOR {
+            self.numerator -= DENOMINATOR;
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// The fraction of a Byte left over by [`refill`], to be carried over to the
+/// next refill with [`Remainder::accumulate`]
+pub type SubByteRemainder = Remainder;
+
+/// Returns how many whole Bytes a bandwidth emits every `tick`, and the
+/// fraction of a Byte left over
+///
+/// Discrete-event simulators emitting `bytes` each tick and accumulating the
+/// remainder (see [`Remainder`]) follow the bandwidth exactly over time. The
+/// number of Bytes saturates at `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::calc::per_tick;
+///
+/// let (bytes, remainder) = per_tick(Bandwidth::from_mbps(100), Duration::from_millis(10));
+/// assert_eq!(bytes, 125_000);
+/// assert!(remainder.is_zero());
+///
+/// let (bytes, remainder) = per_tick(Bandwidth::from_bps(100), Duration::from_millis(100));
+/// assert_eq!(bytes, 1);
+/// assert_eq!(remainder.as_f64(), 0.25);
+/// ```
+pub fn per_tick(bw: Bandwidth, tick: Duration) -> (u64, Remainder) {
+    let bps = bandwidth_as_bps(bw);
+    let nanos = tick.as_nanos();
+    // Split the bandwidth to keep the products within an u128
+    let (quotient, remainder) = (bps / DENOMINATOR, bps % DENOMINATOR);
+    let partial = remainder * nanos;
+    let bytes = quotient
+        .checked_mul(nanos)
+        .and_then(|bytes| bytes.checked_add(partial / DENOMINATOR))
+        .and_then(|bytes| u64::try_from(bytes).ok())
+        .unwrap_or(u64::MAX);
+    let remainder = Remainder {
+        numerator: partial % DENOMINATOR,
+    };
+    (bytes, remainder)
+}
+
+/// Returns how many whole Bytes a token bucket at `bw` gains over `elapsed`,
+/// and the fraction of a Byte left over
+///
+/// This is the core of a token bucket refill: limiters woken up at irregular
+/// times add the Bytes to the bucket and accumulate the remainders, so that
+/// no fraction of a Byte is lost to rounding even at low rates. The
+/// remainders of different durations are exact fractions of the same
+/// denominator (see [`Remainder::as_ratio`]), which sum without drift. The
+/// number of Bytes saturates at `u64::MAX`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::calc::{refill, SubByteRemainder};
+///
+/// // 100bps is 12.5B/s
+/// let limit = Bandwidth::from_bps(100);
+/// let mut tokens = 0;
+/// let mut carry = SubByteRemainder::default();
+/// for millis in [130, 270, 600] {
+///     let (bytes, remainder) = refill(limit, Duration::from_millis(millis));
+///     tokens += bytes + carry.accumulate(remainder);
+/// }
+/// assert_eq!(tokens, 12);
+/// assert_eq!(carry.as_ratio(), (4_000_000_000, 8_000_000_000));
+/// ```
+pub fn refill(bw: Bandwidth, elapsed: Duration) -> (u64, SubByteRemainder) {
+    per_tick(bw, elapsed)
+}
+
+/// Suggests an IO chunk size for transfers at `bw`, such that about one
+/// chunk is transferred every `target_interval`
+///
+/// The size is the amount of data transferred in `target_interval`, rounded
+/// down to a power of two so that chunks are at least as frequent as asked,
+/// then clamped within `bounds`.
+///
+/// # Panics
+///
+/// Panics if the start of `bounds` is greater than its end.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{calc::chunk_size_for, parse_bandwidth_borrowed, size::DataSize};
+///
+/// let bounds = DataSize::from_kib(4)..=DataSize::from_mib(16);
+/// let limit = parse_bandwidth_borrowed("100Mbps").unwrap();
+/// // 100Mbps is 1.25MB every 100ms
+/// let chunk = chunk_size_for(limit, Duration::from_millis(100), bounds.clone());
+/// assert_eq!(chunk, DataSize::from_mib(1));
+/// // Slow links still use chunks of at least 4kiB
+/// let chunk = chunk_size_for(Bandwidth::from_kbps(56), Duration::from_millis(100), bounds);
+/// assert_eq!(chunk.to_string(), "4kiB");
+/// ```
+pub fn chunk_size_for(
+    bw: Bandwidth,
+    target_interval: Duration,
+    bounds: RangeInclusive<DataSize>,
+) -> DataSize {
+    let (bytes, _) = per_tick(bw, target_interval);
+    let chunk = match bytes {
+        0 => 0,
+        bytes => 1 << (u64::BITS - 1 - bytes.leading_zeros()),
+    };
+    let (min, max) = bounds.into_inner();
+    DataSize::from_bytes(chunk).clamp(min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_tick() {
+        let (bytes, remainder) = per_tick(Bandwidth::from_gbps(1), Duration::from_secs(1));
+        assert_eq!(bytes, 125_000_000);
+        assert!(remainder.is_zero());
+
+        let (bytes, remainder) = per_tick(Bandwidth::from_bps(1), Duration::from_secs(1));
+        assert_eq!(bytes, 0);
+        assert_eq!(remainder.as_f64(), 0.125);
+
+        let (bytes, remainder) = per_tick(Bandwidth::new(0, 0), Duration::from_secs(3));
+        assert_eq!(bytes, 0);
+        assert!(remainder.is_zero());
+    }
+
+    #[test]
+    fn test_no_drift() {
+        let tick = Duration::from_micros(333);
+        let (bytes, remainder) = per_tick(Bandwidth::from_bps(1_234_567), tick);
+        let mut carry = Remainder::default();
+        let sent: u64 = (0..3_000_000)
+            .map(|_| bytes + carry.accumulate(remainder))
+            .sum();
+        // 999s at 1234567bps, i.e. 154320.875B/s
+        assert_eq!(sent, 154_166_554);
+        assert_eq!(carry.as_f64(), 0.125);
+    }
+
+    #[test]
+    fn test_refill() {
+        // 1bps is 1/8B/s, refilled at irregular intervals for 8s
+        let limit = Bandwidth::from_bps(1);
+        let mut carry = SubByteRemainder::default();
+        let mut tokens = 0;
+        for nanos in [1, 999_999_999, 3_333_333_333, 2_666_666_667, 1_000_000_000] {
+            let (bytes, remainder) = refill(limit, Duration::from_nanos(nanos));
+            assert_eq!(bytes, 0);
+            tokens += carry.accumulate(remainder);
+        }
+        assert_eq!(tokens, 1);
+        assert!(carry.is_zero());
+
+        let (bytes, remainder) =
+            refill(Bandwidth::from_bps(8_000_000_001), Duration::from_nanos(3));
+        assert_eq!(bytes, 3);
+        assert_eq!(remainder.as_ratio(), (3, 8_000_000_000));
+    }
+
+    #[test]
+    fn test_chunk_size_for() {
+        let bounds = DataSize::from_bytes(1)..=DataSize::from_bytes(u64::MAX);
+        let chunk_size = |bps, millis| {
+            let interval = Duration::from_millis(millis);
+            chunk_size_for(Bandwidth::from_bps(bps), interval, bounds.clone()).bytes()
+        };
+        assert_eq!(chunk_size(8_000, 1_000), 512);
+        assert_eq!(chunk_size(8_192, 1_000), 1_024);
+        assert_eq!(chunk_size(8_192, 999), 512);
+        assert_eq!(chunk_size(0, 1_000), 1);
+        assert_eq!(chunk_size(8, 999), 1);
+        assert_eq!(
+            chunk_size_for(Bandwidth::MAX, Duration::MAX, bounds),
+            DataSize::from_bytes(1 << 63)
+        );
+
+        let bounds = DataSize::from_kib(64)..=DataSize::from_kib(64);
+        assert_eq!(
+            chunk_size_for(Bandwidth::from_gbps(10), Duration::from_secs(1), bounds),
+            DataSize::from_kib(64)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunk_size_for_invalid_bounds() {
+        let bounds = DataSize::from_kib(2)..=DataSize::from_kib(1);
+        chunk_size_for(Bandwidth::from_mbps(1), Duration::from_secs(1), bounds);
+    }
+
+    #[test]
+    fn test_saturation() {
+        let (bytes, _) = per_tick(Bandwidth::MAX, Duration::MAX);
+        assert_eq!(bytes, u64::MAX);
+        let (bytes, _) = per_tick(Bandwidth::MAX, Duration::from_secs(1));
+        assert_eq!(bytes, u64::MAX);
+        let (bytes, _) = per_tick(Bandwidth::from_bps(8), Duration::from_secs(u64::MAX));
+        assert_eq!(bytes, u64::MAX);
+        let (bytes, _) = per_tick(Bandwidth::from_bps(16), Duration::from_secs(u64::MAX));
+        assert_eq!(bytes, u64::MAX);
+    }
+}
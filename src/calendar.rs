@@ -0,0 +1,141 @@
+//! Calendar context for the rates per month
+//!
+//! A rate per month, e.g. a quota of `1TiB/month`, is converted to bits per
+//! second with a fixed month of 30 days by default, like
+//! [`Interval::Month`](crate::Interval::Month). With
+//! [`ParseOptions::month_of`], the rate is spread over the actual length of
+//! the month of a given date, which [`CalendarDate`] provides for the dates of
+//! `chrono` and `time` with the features of the same names.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::{calendar::days_in_month, ParseOptions};
+//!
+//! assert_eq!(days_in_month(2024, 2), 29);
+//! let february = ParseOptions::new().month_days(days_in_month(2024, 2));
+//! assert_eq!(february.get_month_days(), 29);
+//! # #[cfg(feature = "binary-system")]
+//! assert_eq!(february.parse_binary_borrowed("1TiB/month"), Ok(Bandwidth::from_bps(3_510_574)));
+//! ```
+//!
+//! [`ParseOptions::month_of`]: crate::ParseOptions::month_of
+
+/// A date, whose month gives the length of the rates per month
+///
+/// It is implemented for the dates of `chrono` with the `chrono` feature, and
+/// for the ones of `time` with the `time` feature.
+pub trait CalendarDate {
+    /// Number of days in the month of the date, from 28 to 31
+    fn days_in_month(&self) -> u8;
+}
+
+/// Number of days in a month of the proleptic Gregorian calendar, `month`
+/// being from 1 to 12
+///
+/// # Panics
+///
+/// Panics if `month` is not from 1 to 12.
+pub const fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        1..=12 => 31,
+        _ => panic!("a month is from 1 to 12"),
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone};
+
+    use super::{days_in_month, CalendarDate};
+
+    impl CalendarDate for NaiveDate {
+        fn days_in_month(&self) -> u8 {
+            days_in_month(self.year(), self.month() as u8)
+        }
+    }
+
+    impl CalendarDate for NaiveDateTime {
+        fn days_in_month(&self) -> u8 {
+            days_in_month(self.year(), self.month() as u8)
+        }
+    }
+
+    /// The month of the date in the time zone of `Tz`
+    impl<Tz: TimeZone> CalendarDate for DateTime<Tz> {
+        fn days_in_month(&self) -> u8 {
+            days_in_month(self.year(), self.month() as u8)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+    use super::{days_in_month, CalendarDate};
+
+    impl CalendarDate for Date {
+        fn days_in_month(&self) -> u8 {
+            days_in_month(self.year(), self.month() as u8)
+        }
+    }
+
+    impl CalendarDate for PrimitiveDateTime {
+        fn days_in_month(&self) -> u8 {
+            days_in_month(self.year(), self.month() as u8)
+        }
+    }
+
+    /// The month of the date at its offset
+    impl CalendarDate for OffsetDateTime {
+        fn days_in_month(&self) -> u8 {
+            days_in_month(self.year(), self.month() as u8)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_in_month() {
+        let days: Vec<_> = (1..=12).map(|month| days_in_month(2023, month)).collect();
+        assert_eq!(days, [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]);
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(-4, 2), 29);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let date = NaiveDate::from_ymd_opt(2024, 2, 10).unwrap();
+        assert_eq!(date.days_in_month(), 29);
+        assert_eq!(date.and_hms_opt(0, 0, 0).unwrap().days_in_month(), 29);
+        let time = Utc.with_ymd_and_hms(2023, 4, 30, 23, 59, 59).unwrap();
+        assert_eq!(time.days_in_month(), 30);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time() {
+        use time::{Date, Month, Time};
+
+        let date = Date::from_calendar_date(2023, Month::February, 10).unwrap();
+        assert_eq!(date.days_in_month(), 28);
+        assert_eq!(date.with_time(Time::MIDNIGHT).days_in_month(), 28);
+        assert_eq!(
+            date.with_time(Time::MIDNIGHT).assume_utc().days_in_month(),
+            28
+        );
+    }
+}
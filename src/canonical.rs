@@ -0,0 +1,196 @@
+//! Canonical form of bandwidths for configuration files
+//!
+//! Configuration management systems diff and commit generated files, so the
+//! same value must always be written the same way. [`CanonicalProfile`]
+//! defines that single spelling, and [`normalize`] rewrites any string
+//! accepted by [`crate::parse_bandwidth`] into it.
+//!
+//! # Stability
+//!
+//! The canonical form is part of the public API and will not change across
+//! crate versions:
+//!
+//! * the value is written in the largest of `bit/s`, `kbit/s`, `Mbit/s`,
+//!   `Gbit/s` and `Tbit/s` in which it is at least 1 (or `bit/s` for zero)
+//! * the number is written exactly, with `.` as decimal separator, without
+//!   leading or trailing zeros, nor digit grouping
+//! * a single space separates the number from its unit, and there is no other
+//!   whitespace
+//!
+//! # Example
+//!
+//! ```
+//! use human_bandwidth::canonical::normalize;
+//!
+//! assert_eq!(normalize("1500kbps").unwrap(), "1.5 Mbit/s");
+//! assert_eq!(normalize("  1Gbps 500mb/s ").unwrap(), "1.5 Gbit/s");
+//! assert_eq!(normalize("0bps").unwrap(), "0 bit/s");
+//! ```
+//!
+//! # Stable identifiers
+//!
+//! [`stable_id`] hashes the exact number of bits per second of a bandwidth
+//! into a `u64` which is the same on every architecture and in every crate
+//! version, unlike [`Hash`](core::hash::Hash) whose output depends on the
+//! hasher. Distributed systems can use it to key caches or deduplicate rate
+//! configurations across processes.
+
+use alloc::string::String;
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, parse_bandwidth, Error, FormatOptions};
+
+/// The canonical form of bandwidths, see the [module documentation](self)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CanonicalProfile;
+
+impl CanonicalProfile {
+    /// The formatting options writing the canonical form
+    ///
+    /// Like those of [`Profile::V1`](crate::Profile::V1), they are frozen: the
+    /// output never changes as long as they are not customized.
+    pub const fn format_options(&self) -> FormatOptions {
+        FormatOptions::canonical()
+    }
+
+    /// Format a bandwidth in canonical form
+    pub fn format(&self, val: Bandwidth) -> String {
+        self.format_options().format(val)
+    }
+
+    /// Parse a bandwidth in any form accepted by [`parse_bandwidth`], which
+    /// includes the canonical form
+    pub fn parse(&self, s: &str) -> Result<Bandwidth, Error> {
+        parse_bandwidth(s)
+    }
+
+    /// Rewrite a bandwidth in canonical form
+    pub fn normalize(&self, s: &str) -> Result<String, Error> {
+        self.parse(s).map(|val| self.format(val))
+    }
+
+    /// Whether a string is already in canonical form
+    pub fn is_canonical(&self, s: &str) -> bool {
+        self.normalize(s).is_ok_and(|normalized| normalized == s)
+    }
+}
+
+/// Rewrite a bandwidth in canonical form, see [`CanonicalProfile`]
+///
+/// # Examples
+///
+/// ```
+/// use human_bandwidth::{canonical::normalize, Error};
+///
+/// assert_eq!(normalize("10 Mbit/s").unwrap(), "10 Mbit/s");
+/// assert_eq!(normalize("10Mbps").unwrap(), "10 Mbit/s");
+/// assert_eq!(normalize("0.000001Tbps").unwrap(), "1 Mbit/s");
+/// assert_eq!(normalize(""), Err(Error::Empty));
+/// ```
+pub fn normalize(s: &str) -> Result<String, Error> {
+    CanonicalProfile.normalize(s)
+}
+
+/// A stable identifier of a bandwidth, see the
+/// [module documentation](self#stable-identifiers)
+///
+/// It is the 64-bit FNV-1a hash of the number of bits per second as 16
+/// little-endian Bytes, so that equal bandwidths have equal identifiers
+/// whatever the way they were written. This definition is part of the public
+/// API and will not change.
+///
+/// # Example
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{canonical::stable_id, parse_bandwidth};
+///
+/// let val = parse_bandwidth("1000kbps").unwrap();
+/// assert_eq!(stable_id(val), 0x7e9b_297f_8d25_9248);
+/// assert_eq!(stable_id(val), stable_id(Bandwidth::from_mbps(1)));
+/// ```
+pub fn stable_id(bw: Bandwidth) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bandwidth_as_bps(bw)
+        .to_le_bytes()
+        .iter()
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let profile = CanonicalProfile;
+        assert_eq!(profile.format(Bandwidth::new(0, 7)), "7 bit/s");
+        assert_eq!(profile.format(Bandwidth::new(0, 150_024)), "150.024 kbit/s");
+        assert_eq!(profile.format(Bandwidth::new(9_420, 0)), "9.42 Tbit/s");
+        assert_eq!(
+            profile.format(Bandwidth::new(1_000_000, 1)),
+            "1000.000000000001 Tbit/s"
+        );
+        // The larger units are never used
+        assert_eq!(
+            profile.format(Bandwidth::MAX),
+            "18446744073709551.615999999999 Tbit/s"
+        );
+    }
+
+    #[test]
+    fn test_frozen() {
+        let options = CanonicalProfile.format_options();
+        assert!(options.is_frozen());
+        assert_eq!(options.format(Bandwidth::new(0, 1_536_000)), "1.536 Mbit/s");
+        // Customized options are not frozen anymore
+        let options = options.precision(1);
+        assert!(!options.is_frozen());
+        assert_eq!(options.format(Bandwidth::new(0, 1_536_000)), "1.5 Mbit/s");
+    }
+
+    #[test]
+    fn test_normalize() {
+        for s in ["1.5Mbps", "1500 kb/s", "1 Mbps 500 kbps", "1.500000mbit/s"] {
+            assert_eq!(normalize(s).unwrap(), "1.5 Mbit/s");
+        }
+        assert!(matches!(
+            normalize("1.5 MB/s"),
+            Err(Error::UnknownUnit { .. })
+        ));
+    }
+
+    #[test]
+    fn test_is_canonical() {
+        let profile = CanonicalProfile;
+        assert!(profile.is_canonical("1.5 Mbit/s"));
+        assert!(profile.is_canonical("0 bit/s"));
+        assert!(!profile.is_canonical("1.5Mbit/s"));
+        assert!(!profile.is_canonical("1.50 Mbit/s"));
+        assert!(!profile.is_canonical("1500 kbit/s"));
+        assert!(!profile.is_canonical("fast"));
+    }
+
+    #[test]
+    fn test_stable_id() {
+        // Frozen values, which must never change
+        assert_eq!(stable_id(Bandwidth::new(0, 0)), 0x8820_1fb9_60ff_6465);
+        assert_eq!(
+            stable_id(Bandwidth::from_kbps(1_000)),
+            0x7e9b_297f_8d25_9248
+        );
+        assert_eq!(stable_id(Bandwidth::MAX), 0x92b7_4930_aa62_59da);
+        assert_ne!(
+            stable_id(Bandwidth::from_bps(1)),
+            stable_id(Bandwidth::from_bps(1 << 8))
+        );
+        assert_ne!(
+            stable_id(Bandwidth::new(1, 0)),
+            stable_id(Bandwidth::new(0, 1))
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Bandwidths accepted in several syntaxes, tried in order
+//!
+//! An application reading rates from several sources may accept the units of
+//! [`parse_bandwidth`], binary units and carrier rates like `2xE1`. A
+//! [`ParserChain`] lists the accepted syntaxes and returns the first
+//! successful parse, or a [`ChainError`] with the reason each syntax
+//! rejected the string.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::chain::ParserChain;
+//!
+//! let chain = ParserChain::new().si().tc().k8s();
+//! assert_eq!(chain.parse("10Mbps"), Ok(Bandwidth::from_mbps(10)));
+//! assert_eq!(chain.parse("2xE1"), Ok(Bandwidth::from_kbps(4_096)));
+//! assert_eq!(chain.parse("1500k"), Ok(Bandwidth::from_kbps(1_500)));
+//! let err = chain.parse("10 Mpbs").unwrap_err();
+//! assert_eq!(err.failures().len(), 3);
+//! assert_eq!(
+//!     err.to_string(),
+//!     "invalid bandwidth in every syntax: \
+//!      SI: unknown bandwidth unit \"Mpbs\", did you mean \"Mbps\"?; \
+//!      telecom carrier: unknown bandwidth unit \"10 Mpbs\", supported units: \
+//!      DS0, T1, T2, T3, E0, E1, E2, E3, E4, OC-n, STS-n, STM-n; \
+//!      Kubernetes quantity: invalid character at 2",
+//! );
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+use bandwidth::Bandwidth;
+
+use crate::{
+    compat::{k8s::parse_quantity, telecom::parse_carrier_rate},
+    parse_bandwidth, Error, ParseOptions,
+};
+
+/// A parser of one syntax of a [`ParserChain`]
+#[derive(Debug, Clone, Copy)]
+enum Stage {
+    Function(fn(&str) -> Result<Bandwidth, Error>),
+    Options(ParseOptions),
+}
+
+/// An ordered list of accepted syntaxes, see the
+/// [module documentation](self)
+#[derive(Debug, Clone, Default)]
+pub struct ParserChain {
+    stages: Vec<(&'static str, Stage)>,
+}
+
+impl ParserChain {
+    /// Create a chain accepting no syntax
+    pub fn new() -> Self {
+        ParserChain { stages: Vec::new() }
+    }
+
+    /// Accept the SI units of [`parse_bandwidth`], in a stage named `SI`
+    pub fn si(self) -> Self {
+        self.with("SI", parse_bandwidth)
+    }
+
+    /// Accept the syntax of [`ParseOptions::parse`] with the given options, in
+    /// a stage named `SI`
+    pub fn si_with(mut self, options: ParseOptions) -> Self {
+        self.stages.push(("SI", Stage::Options(options)));
+        self
+    }
+
+    /// Accept the binary units of
+    /// [`parse_binary_bandwidth`](crate::binary_system::parse_binary_bandwidth),
+    /// in a stage named `binary`
+    #[cfg(feature = "binary-system")]
+    pub fn binary(self) -> Self {
+        self.with("binary", crate::binary_system::parse_binary_bandwidth)
+    }
+
+    /// Accept the telecom carrier rates of [`parse_carrier_rate`] like `2xE1`,
+    /// in a stage named `telecom carrier`
+    pub fn tc(self) -> Self {
+        self.with("telecom carrier", parse_carrier_rate)
+    }
+
+    /// Accept the Kubernetes quantities of [`parse_quantity`] like `500M`, in
+    /// a stage named `Kubernetes quantity`
+    pub fn k8s(self) -> Self {
+        self.with("Kubernetes quantity", parse_quantity)
+    }
+
+    /// Accept the syntax of `parse`, in a stage named `name`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{chain::ParserChain, Error};
+    ///
+    /// fn unlimited(s: &str) -> Result<Bandwidth, Error> {
+    ///     match s.trim() {
+    ///         "unlimited" => Ok(Bandwidth::MAX),
+    ///         _ => Err(Error::Empty),
+    ///     }
+    /// }
+    ///
+    /// let chain = ParserChain::new().with("unlimited", unlimited).si();
+    /// assert_eq!(chain.parse("unlimited"), Ok(Bandwidth::MAX));
+    /// assert_eq!(chain.parse("1Gbps"), Ok(Bandwidth::from_gbps(1)));
+    /// ```
+    pub fn with(mut self, name: &'static str, parse: fn(&str) -> Result<Bandwidth, Error>) -> Self {
+        self.stages.push((name, Stage::Function(parse)));
+        self
+    }
+
+    /// The names of the stages, in the order they are tried
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.stages.iter().map(|(name, _)| *name)
+    }
+
+    /// Parses `s` with each stage in turn, returning the first bandwidth
+    ///
+    /// When no stage accepts `s`, the error of each of them is returned in
+    /// order. A chain without any stage rejects every string.
+    pub fn parse(&self, s: &str) -> Result<Bandwidth, ChainError> {
+        let mut failures = Vec::with_capacity(self.stages.len());
+        for (name, stage) in &self.stages {
+            let parsed = match stage {
+                Stage::Function(parse) => parse(s),
+                Stage::Options(options) => options.parse(s),
+            };
+            match parsed {
+                Ok(val) => return Ok(val),
+                Err(error) => failures.push((*name, error)),
+            }
+        }
+        Err(ChainError { failures })
+    }
+}
+
+/// Error of a [`ParserChain`] which no stage accepts
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainError {
+    failures: Vec<(&'static str, Error)>,
+}
+
+impl ChainError {
+    /// The name and the error of each stage, in the order they were tried
+    pub fn failures(&self) -> &[(&'static str, Error)] {
+        &self.failures
+    }
+
+    /// Consumes the error, returning the name and the error of each stage
+    pub fn into_failures(self) -> Vec<(&'static str, Error)> {
+        self.failures
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for ChainError {}
+
+/// Writes the error of each stage after its name, separated by semicolons
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.failures.is_empty() {
+            return f.write_str("no syntax to parse the bandwidth");
+        }
+        f.write_str("invalid bandwidth in every syntax: ")?;
+        for (i, (name, error)) in self.failures.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}: {}", name, error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let chain = ParserChain::new().si().tc();
+        assert_eq!(chain.names().collect::<Vec<_>>(), ["SI", "telecom carrier"]);
+        assert_eq!(
+            chain.parse("1Gbps 500Mbps"),
+            Ok(Bandwidth::from_mbps(1_500))
+        );
+        assert_eq!(chain.parse("T1"), Ok(Bandwidth::from_kbps(1_544)));
+        // The first stage which succeeds wins
+        let chain = ParserChain::new().k8s().si();
+        assert_eq!(chain.parse("1M"), Ok(Bandwidth::from_mbps(1)));
+        assert_eq!(chain.parse("1Mbps"), Ok(Bandwidth::from_mbps(1)));
+
+        let strict = ParserChain::new().si_with(ParseOptions::strict());
+        assert!(strict.parse("1Mbps 1Mbps").is_err());
+        assert_eq!(
+            ParserChain::new()
+                .si_with(ParseOptions::new().quadrant_units(true))
+                .parse("1MB/s"),
+            Ok(Bandwidth::from_mbps(8))
+        );
+    }
+
+    #[cfg(feature = "binary-system")]
+    #[test]
+    fn test_binary() {
+        let chain = ParserChain::new().si().binary().tc();
+        assert_eq!(chain.parse("1MiB/s"), Ok(Bandwidth::from_bps(8 << 20)));
+        let err = chain.parse("").unwrap_err();
+        assert_eq!(
+            err.failures(),
+            [
+                ("SI", Error::Empty),
+                ("binary", Error::Empty),
+                ("telecom carrier", Error::Empty)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        let chain = ParserChain::new().si().tc();
+        let err = chain.parse("3 bananas").unwrap_err();
+        let names: Vec<_> = err.failures().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["SI", "telecom carrier"]);
+        assert_eq!(
+            err.failures()[0].1,
+            crate::parse_bandwidth("3 bananas").unwrap_err()
+        );
+        assert_eq!(
+            err.clone().into_failures()[1].1,
+            parse_carrier_rate("3 bananas").unwrap_err()
+        );
+        assert!(err.to_string().starts_with(
+            "invalid bandwidth in every syntax: SI: unknown bandwidth unit \"bananas\""
+        ));
+
+        let err = ParserChain::new().parse("1Mbps").unwrap_err();
+        assert!(err.failures().is_empty());
+        assert_eq!(err.to_string(), "no syntax to parse the bandwidth");
+        assert_eq!(ParserChain::default().names().count(), 0);
+    }
+}
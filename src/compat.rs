@@ -0,0 +1,7 @@
+//! Bandwidths written in the notations of other ecosystems
+//!
+//! Each submodule reads and writes the rates of one ecosystem, which do not
+//! follow the units of [`parse_bandwidth`](crate::parse_bandwidth).
+
+pub mod k8s;
+pub mod telecom;
@@ -0,0 +1,355 @@
+//! Kubernetes quantities of bandwidth annotations
+//!
+//! Pods limit their traffic with annotations like
+//! `kubernetes.io/ingress-bandwidth: 500M`, whose value is a Kubernetes
+//! quantity: a number with an optional suffix, counting bits per second.
+//! This module converts these quantities to and from [`Bandwidth`].
+//!
+//! The suffixes are:
+//!
+//! * the decimal ones `k`, `M`, `G`, `T`, `P` and `E`, powers of 1000, and
+//!   `m`, `u` and `n` for thousandths, millionths and billionths
+//! * the binary ones `Ki`, `Mi`, `Gi`, `Ti`, `Pi` and `Ei`, powers of 1024
+//! * decimal exponents, e.g. `1e6` or `1E-3`
+//!
+//! Suffixes are case-sensitive: `m` is a thousandth and `M` a million, `K`
+//! is no suffix. There are no byte suffixes either, `2Gi` being 2 binary
+//! gigabits per second and not gibibytes. Like Kubernetes, a quantity which
+//! is not a whole number of bits per second is rounded up.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::compat::k8s::{format_quantity, parse_quantity, Suffixes};
+//!
+//! assert_eq!(parse_quantity("500M"), Ok(Bandwidth::from_mbps(500)));
+//! assert_eq!(parse_quantity("2Gi"), Ok(Bandwidth::from_bps(2 << 30)));
+//!
+//! let limit = Bandwidth::from_mbps(500);
+//! assert_eq!(format_quantity(limit, Suffixes::Decimal), "500M");
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, bandwidth_from_bps, Error, FRACTION_PART_LIMIT};
+
+/// Listed in [`Error::UnknownSystemUnit`] errors
+const SUPPORTED: &str = "n, u, m, k, M, G, T, P, E, Ki, Mi, Gi, Ti, Pi, Ei, e<exponent>";
+
+/// The suffixes of a written quantity, see [`format_quantity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Suffixes {
+    /// `k`, `M`, `G`, `T`, `P` and `E`, e.g. `500M`
+    #[default]
+    Decimal,
+    /// `Ki`, `Mi`, `Gi`, `Ti`, `Pi` and `Ei`, e.g. `2Gi`
+    Binary,
+}
+
+impl Suffixes {
+    /// Suffixes with their multiplier, from the largest to the smallest
+    fn multipliers(self) -> [(&'static str, u128); 6] {
+        match self {
+            Suffixes::Decimal => [
+                ("E", 1_000_000_000_000_000_000),
+                ("P", 1_000_000_000_000_000),
+                ("T", 1_000_000_000_000),
+                ("G", 1_000_000_000),
+                ("M", 1_000_000),
+                ("k", 1_000),
+            ],
+            Suffixes::Binary => [
+                ("Ei", 1 << 60),
+                ("Pi", 1 << 50),
+                ("Ti", 1 << 40),
+                ("Gi", 1 << 30),
+                ("Mi", 1 << 20),
+                ("Ki", 1 << 10),
+            ],
+        }
+    }
+}
+
+/// The power of 1024 and the power of 10 of a suffix
+fn suffix_scale(suffix: &str) -> Option<(u32, i32)> {
+    let scale = match suffix {
+        "" => (0, 0),
+        "n" => (0, -9),
+        "u" => (0, -6),
+        "m" => (0, -3),
+        "k" => (0, 3),
+        "M" => (0, 6),
+        "G" => (0, 9),
+        "T" => (0, 12),
+        "P" => (0, 15),
+        "E" => (0, 18),
+        "Ki" => (1, 0),
+        "Mi" => (2, 0),
+        "Gi" => (3, 0),
+        "Ti" => (4, 0),
+        "Pi" => (5, 0),
+        "Ei" => (6, 0),
+        _ => {
+            let exponent = suffix.strip_prefix(['e', 'E'])?;
+            let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            (0, exponent.parse().ok()?)
+        }
+    };
+    Some(scale)
+}
+
+/// Parses a Kubernetes quantity like `500M`, `2Gi` or `1e6` as bits per
+/// second
+///
+/// The number may have a `+` sign and a fractional part, e.g. `1.5G` or
+/// `.5M`, and is directly followed by its suffix. See the
+/// [module documentation](self) for the suffixes.
+///
+/// # Errors
+///
+/// Negative quantities and whitespace are rejected with
+/// [`Error::InvalidCharacter`], unknown suffixes with
+/// [`Error::UnknownSystemUnit`], and fractional parts of more than 18 digits
+/// with [`Error::TooManyDecimals`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{compat::k8s::parse_quantity, Error};
+///
+/// assert_eq!(parse_quantity("1.5G"), Ok(Bandwidth::from_mbps(1_500)));
+/// assert_eq!(parse_quantity("100Ki"), Ok(Bandwidth::from_bps(102_400)));
+/// assert_eq!(parse_quantity("1e6"), Ok(Bandwidth::from_mbps(1)));
+/// assert_eq!(parse_quantity("1500m"), Ok(Bandwidth::from_bps(2)));
+/// assert!(matches!(
+///     parse_quantity("10MB"),
+///     Err(Error::UnknownSystemUnit { start: 2, end: 4, .. })
+/// ));
+/// ```
+pub fn parse_quantity(s: &str) -> Result<Bandwidth, Error> {
+    if s.is_empty() {
+        return Err(Error::Empty);
+    } else if s.trim().is_empty() {
+        return Err(Error::OnlyWhitespace { len: s.len() });
+    }
+    let bytes = s.as_bytes();
+    let start = usize::from(bytes[0] == b'+');
+    let mut pos = start;
+    let mut number: u128 = 0;
+    let digit = |number: u128, b: u8| {
+        number
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(u128::from(b - b'0')))
+            .ok_or(Error::NumberOverflow)
+    };
+    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+        number = digit(number, bytes[pos])?;
+        pos += 1;
+    }
+    let integer = number;
+    let mut fraction_cnt = 0;
+    if bytes.get(pos) == Some(&b'.') {
+        pos += 1;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            if fraction_cnt == FRACTION_PART_LIMIT {
+                return Err(Error::TooManyDecimals(pos));
+            }
+            number = digit(number, bytes[pos])?;
+            fraction_cnt += 1;
+            pos += 1;
+        }
+    }
+    if pos == start || (pos == start + 1 && fraction_cnt == 0 && bytes[start] == b'.') {
+        return Err(match s[start..].chars().next() {
+            Some(c) if !c.is_ascii_alphanumeric() && c != '.' => Error::InvalidCharacter(start),
+            _ => Error::NumberExpected(start),
+        });
+    }
+    let suffix = &s[pos..];
+    if let Some(at) = suffix.find(|c: char| !c.is_ascii_alphanumeric() && c != '+' && c != '-') {
+        return Err(Error::InvalidCharacter(pos + at));
+    }
+    let (binary, decimal) = suffix_scale(suffix).ok_or_else(|| Error::UnknownSystemUnit {
+        start: pos,
+        end: s.len(),
+        unit: suffix.to_string(),
+        value: u64::try_from(integer).unwrap_or(u64::MAX),
+        supported: SUPPORTED.to_string(),
+    })?;
+    let bps = number
+        .checked_mul(1 << (10 * binary))
+        .ok_or(Error::NumberOverflow)?;
+    let exponent = decimal - fraction_cnt as i32;
+    let bps = if exponent >= 0 {
+        10u128
+            .checked_pow(exponent as u32)
+            .and_then(|scale| bps.checked_mul(scale))
+            .ok_or(Error::NumberOverflow)?
+    } else {
+        match 10u128.checked_pow(exponent.unsigned_abs()) {
+            // Rounded up, like Kubernetes reads quantities as integers
+            Some(scale) => bps / scale + u128::from(bps % scale != 0),
+            None => u128::from(bps != 0),
+        }
+    };
+    bandwidth_from_bps(bps)
+}
+
+/// Formats a bandwidth as a Kubernetes quantity, e.g. for a
+/// `kubernetes.io/egress-bandwidth` annotation
+///
+/// The bandwidth is written with the largest of the `suffixes` it is a
+/// multiple of, and as a plain number of bits per second otherwise, so that
+/// [`parse_quantity`] reads it back exactly.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::compat::k8s::{format_quantity, Suffixes};
+///
+/// assert_eq!(format_quantity(Bandwidth::from_gbps(10), Suffixes::Decimal), "10G");
+/// assert_eq!(format_quantity(Bandwidth::from_kbps(1_500), Suffixes::Decimal), "1500k");
+/// assert_eq!(format_quantity(Bandwidth::from_bps(2 << 30), Suffixes::Binary), "2Gi");
+/// assert_eq!(format_quantity(Bandwidth::from_mbps(1), Suffixes::Binary), "1000000");
+/// ```
+pub fn format_quantity(bw: Bandwidth, suffixes: Suffixes) -> String {
+    let bps = bandwidth_as_bps(bw);
+    if bps != 0 {
+        for (suffix, multiplier) in suffixes.multipliers() {
+            if bps % multiplier == 0 {
+                return format!("{}{}", bps / multiplier, suffix);
+            }
+        }
+    }
+    bps.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let bps = |bps| Ok(Bandwidth::from_bps(bps));
+        assert_eq!(parse_quantity("0"), bps(0));
+        assert_eq!(parse_quantity("10"), bps(10));
+        assert_eq!(parse_quantity("+10k"), bps(10_000));
+        assert_eq!(parse_quantity("500M"), bps(500_000_000));
+        assert_eq!(parse_quantity("1.5G"), bps(1_500_000_000));
+        assert_eq!(parse_quantity(".5M"), bps(500_000));
+        assert_eq!(parse_quantity("5."), bps(5));
+        assert_eq!(parse_quantity("3T"), Ok(Bandwidth::from_gbps(3_000)));
+        assert_eq!(
+            parse_quantity("2E"),
+            Ok(Bandwidth::from_gbps(2_000_000_000))
+        );
+        assert_eq!(parse_quantity("1Ki"), bps(1_024));
+        assert_eq!(parse_quantity("1.5Mi"), bps(1_572_864));
+        assert_eq!(parse_quantity("1Ei"), bps(1 << 60));
+        assert_eq!(parse_quantity("1e3"), bps(1_000));
+        assert_eq!(parse_quantity("1E+3"), bps(1_000));
+        assert_eq!(parse_quantity("25e-1"), bps(3));
+        assert_eq!(parse_quantity("1e-40"), bps(1));
+        assert_eq!(parse_quantity("0e-40"), bps(0));
+        assert_eq!(parse_quantity("1500m"), bps(2));
+        assert_eq!(parse_quantity("2000m"), bps(2));
+        assert_eq!(parse_quantity("1u"), bps(1));
+        assert_eq!(parse_quantity("0.5n"), bps(1));
+        assert_eq!(parse_quantity("0.000000000000000001"), bps(1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_quantity(""), Err(Error::Empty));
+        assert_eq!(parse_quantity(" "), Err(Error::OnlyWhitespace { len: 1 }));
+        assert_eq!(parse_quantity("-1M"), Err(Error::InvalidCharacter(0)));
+        assert_eq!(parse_quantity("10 M"), Err(Error::InvalidCharacter(2)));
+        assert_eq!(parse_quantity(" 10M"), Err(Error::InvalidCharacter(0)));
+        assert_eq!(parse_quantity("M"), Err(Error::NumberExpected(0)));
+        assert_eq!(parse_quantity("."), Err(Error::NumberExpected(0)));
+        assert_eq!(parse_quantity("+"), Err(Error::NumberExpected(1)));
+        assert_eq!(parse_quantity("1.2.3"), Err(Error::InvalidCharacter(3)));
+        assert_eq!(parse_quantity("1e1.5"), Err(Error::InvalidCharacter(3)));
+        assert_eq!(
+            parse_quantity("10K"),
+            Err(Error::UnknownSystemUnit {
+                start: 2,
+                end: 3,
+                unit: "K".to_string(),
+                value: 10,
+                supported: SUPPORTED.to_string(),
+            })
+        );
+        for s in ["1KB", "1Mbps", "1mi", "1e", "1e+", "1e99999999999"] {
+            assert!(
+                matches!(parse_quantity(s), Err(Error::UnknownSystemUnit { .. })),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            parse_quantity("0.1234567890123456789"),
+            Err(Error::TooManyDecimals(20))
+        );
+        assert_eq!(parse_quantity("1e40"), Err(Error::NumberOverflow));
+        assert_eq!(parse_quantity("20000000000E"), Err(Error::NumberOverflow));
+        assert_eq!(
+            parse_quantity("999999999999999999999999999999999999999"),
+            Err(Error::NumberOverflow)
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let decimal = |bps| format_quantity(Bandwidth::from_bps(bps), Suffixes::Decimal);
+        let binary = |bps| format_quantity(Bandwidth::from_bps(bps), Suffixes::Binary);
+        assert_eq!(decimal(0), "0");
+        assert_eq!(decimal(999), "999");
+        assert_eq!(decimal(1_000), "1k");
+        assert_eq!(decimal(1_500_000), "1500k");
+        assert_eq!(decimal(2_000_000_000), "2G");
+        assert_eq!(binary(0), "0");
+        assert_eq!(binary(1_024), "1Ki");
+        assert_eq!(binary(1_536), "1536");
+        assert_eq!(binary(3 << 20), "3Mi");
+        assert_eq!(binary(1_000), "1000");
+        assert_eq!(
+            format_quantity(Bandwidth::MAX, Suffixes::Decimal),
+            "18446744073709551615999999999"
+        );
+        assert_eq!(Suffixes::default(), Suffixes::Decimal);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for s in [
+            "1",
+            "500M",
+            "2G",
+            "10k",
+            "3Gi",
+            "1536",
+            "7Ei",
+            "18446744073",
+        ] {
+            let bw = parse_quantity(s).unwrap();
+            for suffixes in [Suffixes::Decimal, Suffixes::Binary] {
+                assert_eq!(parse_quantity(&format_quantity(bw, suffixes)), Ok(bw));
+            }
+        }
+        assert_eq!(
+            parse_quantity(&format_quantity(Bandwidth::MAX, Suffixes::Binary)),
+            Ok(Bandwidth::MAX)
+        );
+    }
+}
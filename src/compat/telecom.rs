@@ -0,0 +1,322 @@
+//! Carrier rates of telephone networks
+//!
+//! Legacy network inventories describe links by their carrier signal rather
+//! than by a bandwidth, e.g. `T1`, `2xE1`, `OC-12` or `STM-4`. This module
+//! converts these rates to and from [`Bandwidth`].
+//!
+//! The supported signals are:
+//!
+//! * the North American digital hierarchy: `DS0` (64kbps), `T1` or `DS1`
+//!   (1.544Mbps), `T2` or `DS2` (6.312Mbps) and `T3` or `DS3` (44.736Mbps)
+//! * the European digital hierarchy: `E0` (64kbps), `E1` (2.048Mbps), `E2`
+//!   (8.448Mbps), `E3` (34.368Mbps) and `E4` (139.264Mbps)
+//! * the SONET optical carriers `OC-n` or `STS-n` (n × 51.84Mbps)
+//! * the SDH transport modules `STM-n` (n × 155.52Mbps), `STM-0` being
+//!   51.84Mbps
+//!
+//! Signal names are case-insensitive and the dash of optical levels is
+//! optional.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::compat::telecom::{format_carrier_rate, parse_carrier_rate, Hierarchy};
+//!
+//! assert_eq!(parse_carrier_rate("2xE1"), Ok(Bandwidth::from_kbps(4_096)));
+//! assert_eq!(parse_carrier_rate("OC-12"), parse_carrier_rate("STM-4"));
+//!
+//! let link = Bandwidth::from_kbps(622_080);
+//! assert_eq!(format_carrier_rate(link, Hierarchy::NorthAmerican).as_deref(), Some("OC-12"));
+//! assert_eq!(format_carrier_rate(link, Hierarchy::European).as_deref(), Some("STM-4"));
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, bandwidth_from_bps, Error};
+
+/// Rate of an `OC-1`, `STS-1` or `STM-0` signal in bps
+const OC1: u128 = 51_840_000;
+/// Rate of an `STM-1` signal in bps
+const STM1: u128 = 155_520_000;
+
+/// Electrical signals, with their rate in bps
+const SIGNALS: &[(&str, u128)] = &[
+    ("DS0", 64_000),
+    ("T1", 1_544_000),
+    ("DS1", 1_544_000),
+    ("T2", 6_312_000),
+    ("DS2", 6_312_000),
+    ("T3", 44_736_000),
+    ("DS3", 44_736_000),
+    ("E0", 64_000),
+    ("E1", 2_048_000),
+    ("E2", 8_448_000),
+    ("E3", 34_368_000),
+    ("E4", 139_264_000),
+];
+
+/// Listed in [`Error::UnknownSystemUnit`] errors
+const SUPPORTED: &str = "DS0, T1, T2, T3, E0, E1, E2, E3, E4, OC-n, STS-n, STM-n";
+
+/// The family of signals in which a rate is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Hierarchy {
+    /// `DS0`, `T1`, `T2` and `T3` signals, and `OC-n` optical carriers
+    #[default]
+    NorthAmerican,
+    /// `E0` to `E4` signals, and `STM-n` transport modules
+    European,
+}
+
+impl Hierarchy {
+    /// Electrical signals of the hierarchy, from the fastest to the slowest
+    fn signals(self) -> &'static [(&'static str, u128)] {
+        match self {
+            Hierarchy::NorthAmerican => &[
+                ("T3", 44_736_000),
+                ("T2", 6_312_000),
+                ("T1", 1_544_000),
+                ("DS0", 64_000),
+            ],
+            Hierarchy::European => &[
+                ("E4", 139_264_000),
+                ("E3", 34_368_000),
+                ("E2", 8_448_000),
+                ("E1", 2_048_000),
+                ("E0", 64_000),
+            ],
+        }
+    }
+}
+
+/// Rate in bps of a signal name, already uppercase
+fn signal_bps(name: &str) -> Option<u128> {
+    if let Some(&(_, bps)) = SIGNALS.iter().find(|(signal, _)| *signal == name) {
+        return Some(bps);
+    }
+    let (level, base) = if let Some(level) = name.strip_prefix("OC") {
+        (level, OC1)
+    } else if let Some(level) = name.strip_prefix("STS") {
+        (level, OC1)
+    } else if let Some(level) = name.strip_prefix("STM") {
+        (level, STM1)
+    } else {
+        return None;
+    };
+    let level = level.strip_prefix('-').unwrap_or(level);
+    if level.is_empty() || !level.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    match level.parse::<u128>().ok()? {
+        0 if base == STM1 => Some(OC1),
+        0 => None,
+        n => n.checked_mul(base),
+    }
+}
+
+/// Parses one `[count x] signal` term starting at byte `offset`
+fn parse_term(term: &str, offset: usize) -> Result<u128, Error> {
+    let start = offset + term.len() - term.trim_start().len();
+    let term = term.trim();
+    let separator = term.find(['x', 'X', '\u{d7}']);
+    let (count, name, name_start) = match separator {
+        Some(i) if i > 0 && term[..i].trim_end().bytes().all(|b| b.is_ascii_digit()) => {
+            let count = term[..i]
+                .trim_end()
+                .parse::<u64>()
+                .map_err(|_| Error::NumberOverflow)?;
+            let rest = &term[i + term[i..].chars().next().map_or(1, char::len_utf8)..];
+            let name = rest.trim_start();
+            (count, name, start + term.len() - name.len())
+        }
+        _ => (1, term, start),
+    };
+    let bps = signal_bps(&name.to_ascii_uppercase()).ok_or_else(|| Error::UnknownSystemUnit {
+        start: name_start,
+        end: name_start + name.len(),
+        unit: name.to_string(),
+        value: count,
+        supported: SUPPORTED.to_string(),
+    })?;
+    bps.checked_mul(u128::from(count))
+        .ok_or(Error::NumberOverflow)
+}
+
+/// Parses a carrier rate like `T1`, `2xE1` or `OC-12`
+///
+/// A rate is a signal name, optionally preceded by a count of signals and
+/// `x`, e.g. `4xT1` or `4 x T1`. Several rates can be summed with `+`, e.g.
+/// `OC-3 + 2xDS3`. See the [module documentation](self) for the supported
+/// signals.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::compat::telecom::parse_carrier_rate;
+/// use human_bandwidth::Error;
+///
+/// assert_eq!(parse_carrier_rate("T1"), Ok(Bandwidth::from_kbps(1_544)));
+/// assert_eq!(parse_carrier_rate("4 x T1"), Ok(Bandwidth::from_kbps(6_176)));
+/// assert_eq!(parse_carrier_rate("stm-1 + 2xE1"), Ok(Bandwidth::from_kbps(159_616)));
+/// assert!(matches!(
+///     parse_carrier_rate("2xJ1"),
+///     Err(Error::UnknownSystemUnit { start: 2, end: 4, .. })
+/// ));
+/// ```
+pub fn parse_carrier_rate(s: &str) -> Result<Bandwidth, Error> {
+    if s.is_empty() {
+        return Err(Error::Empty);
+    } else if s.trim().is_empty() {
+        return Err(Error::OnlyWhitespace { len: s.len() });
+    }
+    let mut total: u128 = 0;
+    let mut offset = 0;
+    for term in s.split('+') {
+        total = total
+            .checked_add(parse_term(term, offset)?)
+            .ok_or(Error::NumberOverflow)?;
+        offset += term.len() + 1;
+    }
+    bandwidth_from_bps(total)
+}
+
+/// Formats a bandwidth as a carrier rate of `hierarchy`, if it is one
+///
+/// Optical levels are preferred, e.g. `OC-3` or `STM-1`. Otherwise, the
+/// bandwidth is written as a count of the fastest electrical signal it is a
+/// multiple of, e.g. `T1` or `2xE1`. Returns `None` for zero and for
+/// bandwidths which are no multiple of any signal.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::compat::telecom::{format_carrier_rate, Hierarchy};
+///
+/// let european = |kbps| format_carrier_rate(Bandwidth::from_kbps(kbps), Hierarchy::European);
+/// assert_eq!(european(155_520).as_deref(), Some("STM-1"));
+/// assert_eq!(european(51_840).as_deref(), Some("STM-0"));
+/// assert_eq!(european(4_096).as_deref(), Some("2xE1"));
+/// assert_eq!(european(1_000), None);
+/// ```
+pub fn format_carrier_rate(bw: Bandwidth, hierarchy: Hierarchy) -> Option<String> {
+    let bps = bandwidth_as_bps(bw);
+    if bps == 0 {
+        return None;
+    }
+    match hierarchy {
+        Hierarchy::NorthAmerican if bps % OC1 == 0 => return Some(format!("OC-{}", bps / OC1)),
+        Hierarchy::European if bps % STM1 == 0 => return Some(format!("STM-{}", bps / STM1)),
+        Hierarchy::European if bps == OC1 => return Some("STM-0".to_string()),
+        _ => {}
+    }
+    let &(name, rate) = hierarchy
+        .signals()
+        .iter()
+        .find(|(_, rate)| bps % rate == 0)?;
+    match bps / rate {
+        1 => Some(name.to_string()),
+        count => Some(format!("{}x{}", count, name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let kbps = |kbps| Ok(Bandwidth::from_kbps(kbps));
+        assert_eq!(parse_carrier_rate("DS0"), kbps(64));
+        assert_eq!(parse_carrier_rate("ds1"), kbps(1_544));
+        assert_eq!(parse_carrier_rate(" T3 "), kbps(44_736));
+        assert_eq!(parse_carrier_rate("E4"), kbps(139_264));
+        assert_eq!(parse_carrier_rate("2xE1"), kbps(4_096));
+        assert_eq!(parse_carrier_rate("2X e1"), kbps(4_096));
+        assert_eq!(parse_carrier_rate("3\u{d7}T1"), kbps(4_632));
+        assert_eq!(parse_carrier_rate("0xT1"), kbps(0));
+        assert_eq!(parse_carrier_rate("OC-1"), kbps(51_840));
+        assert_eq!(parse_carrier_rate("OC12"), kbps(622_080));
+        assert_eq!(parse_carrier_rate("STS-3"), kbps(155_520));
+        assert_eq!(parse_carrier_rate("STM-0"), kbps(51_840));
+        assert_eq!(parse_carrier_rate("STM-64"), kbps(9_953_280));
+        assert_eq!(parse_carrier_rate("2xOC-3"), kbps(311_040));
+        assert_eq!(parse_carrier_rate("T1 + T1+E1"), kbps(5_136));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_carrier_rate(""), Err(Error::Empty));
+        assert_eq!(
+            parse_carrier_rate("  "),
+            Err(Error::OnlyWhitespace { len: 2 })
+        );
+        assert_eq!(
+            parse_carrier_rate("T1 + 2 x J1"),
+            Err(Error::UnknownSystemUnit {
+                start: 9,
+                end: 11,
+                unit: "J1".to_string(),
+                value: 2,
+                supported: SUPPORTED.to_string(),
+            })
+        );
+        assert_eq!(
+            parse_carrier_rate("2x").unwrap_err().to_string(),
+            format!("bandwidth unit needed, supported units: {}", SUPPORTED)
+        );
+        for s in ["OC-0", "OC-", "OC-1.5", "STM-x", "10Mbps", "T1 +", "x3"] {
+            assert!(
+                matches!(parse_carrier_rate(s), Err(Error::UnknownSystemUnit { .. })),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            parse_carrier_rate("99999999999999999999xT1"),
+            Err(Error::NumberOverflow)
+        );
+        assert_eq!(
+            parse_carrier_rate("18446744073709551615xSTM-256"),
+            Err(Error::NumberOverflow)
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let format = |kbps, hierarchy| format_carrier_rate(Bandwidth::from_kbps(kbps), hierarchy);
+        let na = Hierarchy::NorthAmerican;
+        let eu = Hierarchy::European;
+        assert_eq!(format(0, na), None);
+        assert_eq!(format(64, na).as_deref(), Some("DS0"));
+        assert_eq!(format(1_544, na).as_deref(), Some("T1"));
+        assert_eq!(format(3_088, na).as_deref(), Some("2xT1"));
+        assert_eq!(format(89_472, na).as_deref(), Some("2xT3"));
+        assert_eq!(format(155_520, na).as_deref(), Some("OC-3"));
+        assert_eq!(format(2_048, na).as_deref(), Some("32xDS0"));
+        assert_eq!(format(1_000, na), None);
+        assert_eq!(format(2_048, eu).as_deref(), Some("E1"));
+        assert_eq!(format(51_840, eu).as_deref(), Some("STM-0"));
+        assert_eq!(format(103_680, eu).as_deref(), Some("1620xE0"));
+        assert_eq!(format(622_080, eu).as_deref(), Some("STM-4"));
+        assert_eq!(format(139_264, eu).as_deref(), Some("E4"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for s in ["T1", "3xT2", "OC-48", "STM-16", "E3", "5xE1", "DS0"] {
+            let bw = parse_carrier_rate(s).unwrap();
+            for hierarchy in [Hierarchy::NorthAmerican, Hierarchy::European] {
+                if let Some(formatted) = format_carrier_rate(bw, hierarchy) {
+                    assert_eq!(parse_carrier_rate(&formatted), Ok(bw));
+                }
+            }
+        }
+    }
+}
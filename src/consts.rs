@@ -0,0 +1,132 @@
+//! Multipliers of the bandwidth units
+//!
+//! These are the exact factors used by the crate itself, so that downstream
+//! computations agree with parsing and formatting. The module also names the
+//! standard link rates, see [`STANDARD_RATES`].
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::consts::{BITS_PER_BYTE, MEBI};
+//!
+//! // 2MiB/s in bits per second
+//! let bps = 2 * MEBI * BITS_PER_BYTE;
+//! let parsed = human_bandwidth::parse_bandwidth_borrowed("16.777216Mbps");
+//! assert_eq!(parsed, Ok(Bandwidth::from_bps(bps)));
+//! ```
+
+use bandwidth::Bandwidth;
+
+/// The decimal prefix `k`, 10^3
+pub const KILO: u64 = 1_000;
+/// The decimal prefix `M`, 10^6
+pub const MEGA: u64 = 1_000 * KILO;
+/// The decimal prefix `G`, 10^9
+pub const GIGA: u64 = 1_000 * MEGA;
+/// The decimal prefix `T`, 10^12
+pub const TERA: u64 = 1_000 * GIGA;
+/// The decimal prefix `P`, 10^15
+pub const PETA: u64 = 1_000 * TERA;
+/// The decimal prefix `E`, 10^18
+pub const EXA: u64 = 1_000 * PETA;
+
+/// The binary prefix `Ki`, 2^10
+pub const KIBI: u64 = 1 << 10;
+/// The binary prefix `Mi`, 2^20
+pub const MEBI: u64 = 1 << 20;
+/// The binary prefix `Gi`, 2^30
+pub const GIBI: u64 = 1 << 30;
+/// The binary prefix `Ti`, 2^40
+pub const TEBI: u64 = 1 << 40;
+/// The binary prefix `Pi`, 2^50
+pub const PEBI: u64 = 1 << 50;
+/// The binary prefix `Ei`, 2^60
+pub const EXBI: u64 = 1 << 60;
+
+/// The number of bits in a Byte
+pub const BITS_PER_BYTE: u64 = 8;
+
+/// Standard link rates by their usual names, in lowercase
+///
+/// Names cover the Ethernet generations, e.g. `gigabit` or `10g`, so that
+/// firmware and command line tools can accept them without runtime
+/// initialization. Several names can share a rate.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::consts::STANDARD_RATES;
+///
+/// const GIGABIT: Bandwidth = Bandwidth::new(1, 0);
+///
+/// let (name, _) = STANDARD_RATES.iter().find(|(_, rate)| *rate == GIGABIT).unwrap();
+/// assert_eq!(*name, "1g");
+/// ```
+pub const STANDARD_RATES: &[(&str, Bandwidth)] = &[
+    ("10m", Bandwidth::new(0, 10 * MEGA as u32)),
+    ("ethernet", Bandwidth::new(0, 10 * MEGA as u32)),
+    ("100m", Bandwidth::new(0, 100 * MEGA as u32)),
+    ("fast-ethernet", Bandwidth::new(0, 100 * MEGA as u32)),
+    ("1g", Bandwidth::new(1, 0)),
+    ("gigabit", Bandwidth::new(1, 0)),
+    ("2.5g", Bandwidth::new(2, 500 * MEGA as u32)),
+    ("5g", Bandwidth::new(5, 0)),
+    ("10g", Bandwidth::new(10, 0)),
+    ("25g", Bandwidth::new(25, 0)),
+    ("40g", Bandwidth::new(40, 0)),
+    ("50g", Bandwidth::new(50, 0)),
+    ("100g", Bandwidth::new(100, 0)),
+    ("200g", Bandwidth::new(200, 0)),
+    ("400g", Bandwidth::new(400, 0)),
+    ("800g", Bandwidth::new(800, 0)),
+];
+
+/// Looks a name of [`STANDARD_RATES`] up, ignoring ASCII case and
+/// surrounding whitespace
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::consts::standard_rate;
+///
+/// assert_eq!(standard_rate("Gigabit"), Some(Bandwidth::from_gbps(1)));
+/// assert_eq!(standard_rate(" 10G "), Some(Bandwidth::from_gbps(10)));
+/// assert_eq!(standard_rate("10Gbps"), None);
+/// ```
+pub fn standard_rate(name: &str) -> Option<Bandwidth> {
+    let name = name.trim();
+    STANDARD_RATES
+        .iter()
+        .find(|(standard, _)| standard.eq_ignore_ascii_case(name))
+        .map(|(_, rate)| *rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_rates() {
+        for (name, rate) in STANDARD_RATES {
+            assert_eq!(name.to_ascii_lowercase(), *name);
+            if let Some(number) = name.strip_suffix('g').or_else(|| name.strip_suffix('m')) {
+                let unit = if name.ends_with('g') { "Gbps" } else { "Mbps" };
+                assert_eq!(
+                    crate::parse_bandwidth_borrowed(&format!("{}{}", number, unit)).as_ref(),
+                    Ok(rate),
+                    "{}",
+                    name
+                );
+            }
+        }
+        assert_eq!(
+            standard_rate("FAST-ETHERNET"),
+            Some(Bandwidth::from_mbps(100))
+        );
+        assert_eq!(standard_rate("2.5G"), Some(Bandwidth::from_mbps(2_500)));
+        assert_eq!(standard_rate(""), None);
+    }
+}
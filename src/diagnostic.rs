@@ -0,0 +1,303 @@
+//! Errors with the string they were reported on, for diagnostics pointing at
+//! the offending token
+//!
+//! An [`Error`] only records byte offsets, e.g. the start of an invalid
+//! character. A [`SpannedError`], built with [`Error::with_input`], keeps the
+//! parsed string and the byte span of the whole offending token, so that
+//! command line tools can underline it. With the `miette` feature, it
+//! implements `miette::Diagnostic`, labelling the span and giving the
+//! suggestion of [`Error::suggestion`] as help.
+//!
+//! # Example
+//!
+//! ```
+//! use human_bandwidth::parse_bandwidth;
+//!
+//! let input = "10Mbps 5Mpbs";
+//! let err = parse_bandwidth(input).map_err(|err| err.with_input(input)).unwrap_err();
+//! assert_eq!(err.span(), 8..12);
+//! assert_eq!(&err.input()[err.span()], "Mpbs");
+//! assert_eq!(err.label(), "unknown unit");
+//! assert_eq!(
+//!     err.to_string(),
+//!     r#"invalid bandwidth "10Mbps 5Mpbs": unknown bandwidth unit "Mpbs", did you mean "Mbps"?"#
+//! );
+//! ```
+
+use alloc::string::{String, ToString};
+use core::{fmt, ops::Range};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+use crate::Error;
+
+/// An [`Error`] with the string it was reported on and the span of the
+/// offending token, see [`Error::with_input`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedError {
+    input: String,
+    error: Error,
+    span: Range<usize>,
+}
+
+impl SpannedError {
+    /// Attaches the string an error was reported on
+    ///
+    /// The offsets of `error` which are not inside `input` are clamped to its
+    /// end.
+    pub fn new(input: &str, error: Error) -> Self {
+        SpannedError {
+            input: input.to_string(),
+            span: token_span(input, &error),
+            error,
+        }
+    }
+
+    /// The string the error was reported on
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The error
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// The byte span of the offending token inside [`input`](Self::input)
+    ///
+    /// It is the unit for the errors on units, the rest of the rate span for
+    /// [`Error::NumberExpected`] and [`Error::ExtraSpan`], the digits which
+    /// are not read for [`Error::TooManyDecimals`], the character for the
+    /// other errors at an offset, and the whole string otherwise. It is
+    /// empty when the token is missing, e.g. for a number without a unit.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// A short description of the offending token, e.g. `unknown unit`
+    pub fn label(&self) -> &'static str {
+        match self.error {
+            Error::InvalidCharacter(_) => "invalid character",
+            Error::NumberExpected(_) => "expected a number",
+            Error::UnknownUnit { ref unit, .. } if unit.is_empty() => "unit needed",
+            Error::UnknownUnit { .. } => "unknown unit",
+            #[cfg(feature = "binary-system")]
+            Error::UnknownBinaryUnit { ref unit, .. } if unit.is_empty() => "unit needed",
+            #[cfg(feature = "binary-system")]
+            Error::UnknownBinaryUnit { .. } => "unknown unit",
+            Error::UnknownSystemUnit { ref unit, .. } if unit.is_empty() => "unit needed",
+            Error::UnknownSystemUnit { .. } => "unknown unit",
+            Error::NumberOverflow => "too large",
+            Error::SpaceExpected(_) => "expected a space before",
+            Error::Empty => "empty",
+            Error::InvalidDigitGroup(_) => "invalid digit group",
+            Error::ExtraSpan(_) => "extra rate span",
+            Error::DuplicateUnit { .. } => "unit already used",
+            Error::UnorderedUnit { .. } => "unit larger than the previous one",
+            Error::SeparatorExpected(_) => "expected whitespace before",
+            Error::TooManyDecimals(_) => "digits not read",
+            Error::OnlyWhitespace { .. } => "only whitespace",
+            Error::AmbiguousUnit { .. } => "bits or Bytes",
+        }
+    }
+
+    /// Consumes the `SpannedError`, returning the error
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+impl From<SpannedError> for Error {
+    fn from(err: SpannedError) -> Self {
+        err.error
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for SpannedError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for SpannedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid bandwidth {:?}: {}", self.input, self.error)
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for SpannedError {
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let suggestion = self.error.suggestion()?;
+        Some(Box::new(format!("did you mean {:?}?", suggestion)))
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.input)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let label = miette::LabeledSpan::new_with_span(Some(self.label().to_string()), self.span());
+        Some(Box::new(core::iter::once(label)))
+    }
+}
+
+/// The span of the token an error is reported on, see [`SpannedError::span`]
+fn token_span(input: &str, error: &Error) -> Range<usize> {
+    // The offset clamped to the input and moved back to a char boundary
+    let at = |offset: usize| {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    };
+    let char_at = |offset: usize| {
+        let start = at(offset);
+        let len = input[start..].chars().next().map_or(0, char::len_utf8);
+        start..start + len
+    };
+    let rest_of_span = |offset: usize| {
+        let start = at(offset);
+        let len = input[start..]
+            .find(char::is_whitespace)
+            .unwrap_or(input.len() - start);
+        start..start + len
+    };
+    match *error {
+        Error::InvalidCharacter(offset)
+        | Error::SpaceExpected(offset)
+        | Error::InvalidDigitGroup(offset)
+        | Error::SeparatorExpected(offset) => char_at(offset),
+        Error::NumberExpected(offset) | Error::ExtraSpan(offset) => rest_of_span(offset),
+        Error::TooManyDecimals(offset) => {
+            let start = at(offset);
+            let len = input[start..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(input.len() - start);
+            start..start + len
+        }
+        Error::UnknownUnit { start, end, .. }
+        | Error::UnknownSystemUnit { start, end, .. }
+        | Error::DuplicateUnit { start, end }
+        | Error::UnorderedUnit { start, end }
+        | Error::AmbiguousUnit { start, end } => at(start)..at(end).max(at(start)),
+        #[cfg(feature = "binary-system")]
+        Error::UnknownBinaryUnit { start, end, .. } => at(start)..at(end).max(at(start)),
+        Error::NumberOverflow | Error::Empty | Error::OnlyWhitespace { .. } => 0..input.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_bandwidth, ParseOptions};
+
+    fn spanned(input: &str, options: &ParseOptions) -> (String, &'static str) {
+        let err = SpannedError::new(input, options.parse(input).unwrap_err());
+        (err.input()[err.span()].to_string(), err.label())
+    }
+
+    #[test]
+    fn test_span() {
+        let options = ParseOptions::new();
+        assert_eq!(
+            spanned("10Mbps!", &options),
+            ("!".into(), "invalid character")
+        );
+        assert_eq!(
+            spanned("fast", &options),
+            ("fast".into(), "expected a number")
+        );
+        assert_eq!(
+            spanned("1Mbps x1 2", &options),
+            ("x1".into(), "expected a number")
+        );
+        assert_eq!(
+            spanned("10 Mpbs", &options),
+            ("Mpbs".into(), "unknown unit")
+        );
+        assert_eq!(spanned("10", &options), ("".into(), "unit needed"));
+        assert_eq!(spanned("", &options), ("".into(), "empty"));
+        assert_eq!(spanned(" \t", &options), (" \t".into(), "only whitespace"));
+        let overflow = "18446744073709551616Gbps";
+        assert_eq!(spanned(overflow, &options), (overflow.into(), "too large"));
+
+        let options = ParseOptions::new().multiple_spans(false);
+        assert_eq!(
+            spanned("1Mbps 500kbps", &options),
+            ("500kbps".into(), "extra rate span")
+        );
+        let options = ParseOptions::new().strict_spans(true);
+        assert_eq!(
+            spanned("1Mbps 2Mbps", &options),
+            ("Mbps".into(), "unit already used")
+        );
+        let options = ParseOptions::new().strict_fraction(true).fraction_digits(2);
+        assert_eq!(
+            spanned("1.23456 Mbps", &options),
+            ("456".into(), "digits not read")
+        );
+        let options = ParseOptions::new().iso80000(true);
+        assert_eq!(
+            spanned("1Mbit/s", &options),
+            ("M".into(), "expected a space before")
+        );
+    }
+
+    #[test]
+    fn test_out_of_input() {
+        let err = SpannedError::new("é", Error::InvalidCharacter(1));
+        assert_eq!(err.span(), 0..2);
+        let err = SpannedError::new("1", Error::InvalidCharacter(5));
+        assert_eq!(err.span(), 1..1);
+        let unit = Error::UnknownUnit {
+            start: 4,
+            end: 2,
+            unit: "x".to_string(),
+            value: 1,
+        };
+        assert_eq!(SpannedError::new("1x", unit).span(), 2..2);
+    }
+
+    #[test]
+    fn test_error() {
+        let err = parse_bandwidth("1 kbs").unwrap_err().with_input("1 kbs");
+        assert_eq!(err.error(), &parse_bandwidth("1 kbs").unwrap_err());
+        #[cfg(feature = "std")]
+        assert!(err.source().is_some());
+        assert_eq!(Error::from(err.clone()), err.into_error());
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn test_miette() {
+        use miette::Diagnostic;
+
+        let err = parse_bandwidth("10 Mpbs")
+            .unwrap_err()
+            .with_input("10 Mpbs");
+        assert_eq!(
+            err.help().map(|help| help.to_string()).as_deref(),
+            Some(r#"did you mean "Mbps"?"#)
+        );
+        let labels: Vec<_> = err.labels().unwrap().collect();
+        assert_eq!(
+            labels,
+            [miette::LabeledSpan::new(
+                Some("unknown unit".to_string()),
+                3,
+                4
+            )]
+        );
+        let source = err.source_code().unwrap();
+        let contents = source.read_span(&labels[0].inner().clone(), 0, 0).unwrap();
+        assert_eq!(contents.data(), b"Mpbs");
+        assert!(parse_bandwidth("10Mbps!")
+            .unwrap_err()
+            .with_input("10Mbps!")
+            .help()
+            .is_none());
+    }
+}
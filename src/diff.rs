@@ -0,0 +1,133 @@
+//! Changes between two bandwidths
+//!
+//! [`diff_strs`] compares two configuration values, e.g. before and after a
+//! pull request, and summarizes the change for humans.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::diff::diff_strs;
+//!
+//! let diff = diff_strs("10Mbps", "25 Mbit/s").unwrap();
+//! assert_eq!(diff.absolute(), Bandwidth::from_mbps(15));
+//! assert_eq!(diff.relative(), Some(1.5));
+//! assert_eq!(
+//!     format!("this PR raises the limit {}", diff),
+//!     "this PR raises the limit from 10Mbps to 25Mbps (+150%)",
+//! );
+//! ```
+
+use alloc::format;
+use core::{cmp::Ordering, fmt};
+
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, bandwidth_from_bps, format_bandwidth, parse_bandwidth, Error};
+
+/// The change from an old bandwidth to a new one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BandwidthDiff {
+    /// The bandwidth before the change
+    pub old: Bandwidth,
+    /// The bandwidth after the change
+    pub new: Bandwidth,
+}
+
+impl BandwidthDiff {
+    /// Create the change from `old` to `new`
+    pub fn new(old: Bandwidth, new: Bandwidth) -> Self {
+        BandwidthDiff { old, new }
+    }
+
+    /// Whether the bandwidth increased, decreased or stayed the same
+    pub fn direction(&self) -> Ordering {
+        self.new.cmp(&self.old)
+    }
+
+    /// The magnitude of the change, whatever its direction
+    pub fn absolute(&self) -> Bandwidth {
+        let (old, new) = (bandwidth_as_bps(self.old), bandwidth_as_bps(self.new));
+        // The difference of two bandwidths always fits in a bandwidth
+        bandwidth_from_bps(old.abs_diff(new)).unwrap_or(Bandwidth::MAX)
+    }
+
+    /// The change relative to the old bandwidth, e.g. `1.5` for +150% or
+    /// `-0.6` for -60%
+    ///
+    /// Returns `None` when the old bandwidth is zero.
+    pub fn relative(&self) -> Option<f64> {
+        let old = bandwidth_as_bps(self.old);
+        if old == 0 {
+            return None;
+        }
+        let new = bandwidth_as_bps(self.new);
+        Some((new as f64 - old as f64) / old as f64)
+    }
+}
+
+impl fmt::Display for BandwidthDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.old == self.new {
+            return write!(f, "unchanged at {}", format_bandwidth(self.new));
+        }
+        write!(
+            f,
+            "from {} to {}",
+            format_bandwidth(self.old),
+            format_bandwidth(self.new)
+        )?;
+        if let Some(relative) = self.relative() {
+            let percent = format!("{:+.1}", relative * 100.0);
+            write!(f, " ({}%)", percent.trim_end_matches(".0"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses two bandwidths and returns the change from `old` to `new`
+///
+/// See [`parse_bandwidth`] for the supported format.
+pub fn diff_strs(old: &str, new: &str) -> Result<BandwidthDiff, Error> {
+    Ok(BandwidthDiff::new(
+        parse_bandwidth(old)?,
+        parse_bandwidth(new)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let diff = diff_strs("25Mbps", "10Mbps").unwrap();
+        assert_eq!(diff.direction(), Ordering::Less);
+        assert_eq!(diff.absolute(), Bandwidth::from_mbps(15));
+        assert_eq!(diff.relative(), Some(-0.6));
+        assert_eq!(diff.to_string(), "from 25Mbps to 10Mbps (-60%)");
+
+        let diff = diff_strs("3Gbps", "4Gbps").unwrap();
+        assert_eq!(diff.direction(), Ordering::Greater);
+        assert_eq!(diff.to_string(), "from 3Gbps to 4Gbps (+33.3%)");
+
+        let diff = diff_strs("1Gbps", "1000Mbps").unwrap();
+        assert_eq!(diff.direction(), Ordering::Equal);
+        assert_eq!(diff.absolute(), Bandwidth::new(0, 0));
+        assert_eq!(diff.relative(), Some(0.0));
+        assert_eq!(diff.to_string(), "unchanged at 1Gbps");
+
+        let diff = diff_strs("0bps", "5kbps").unwrap();
+        assert_eq!(diff.relative(), None);
+        assert_eq!(diff.to_string(), "from 0bps to 5kbps");
+    }
+
+    #[test]
+    fn test_error() {
+        assert_eq!(diff_strs("", "1Mbps"), Err(Error::Empty));
+        assert!(matches!(
+            diff_strs("1Mbps", "1MB/s"),
+            Err(Error::UnknownUnit { .. })
+        ));
+    }
+}
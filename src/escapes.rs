@@ -0,0 +1,193 @@
+//! Decoding of the URL and HTML escapes mangling units, see
+//! [`ParseOptions::decode_escapes`]
+
+use alloc::borrow::Cow;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{map_offsets, Error, ParseOptions, ParseWarning};
+
+/// The escapes decoded, in lowercase, and the character they stand for
+const ESCAPES: [(&str, char); 9] = [
+    ("%2f", '/'),
+    ("%20", ' '),
+    ("%c2%a0", '\u{a0}'),
+    ("&#47;", '/'),
+    ("&#x2f;", '/'),
+    ("&sol;", '/'),
+    ("&nbsp;", '\u{a0}'),
+    ("&#160;", '\u{a0}'),
+    ("&#xa0;", '\u{a0}'),
+];
+
+/// A string with its escapes decoded, which maps the offsets in the decoded
+/// string back to the original one
+pub(crate) struct Decoded<'a> {
+    src: &'a str,
+    decoded: Cow<'a, str>,
+    /// The end of each escape in the decoded string, and how many Bytes
+    /// shorter the decoded string is up to there
+    shifts: Vec<(usize, usize)>,
+}
+
+impl<'a> Decoded<'a> {
+    /// Decodes the escapes of `src` if `options` asks for it
+    pub(crate) fn new(src: &'a str, options: &ParseOptions) -> Self {
+        let mut decoded = Decoded {
+            src,
+            decoded: Cow::Borrowed(src),
+            shifts: Vec::new(),
+        };
+        if !options.is_decode_escapes() || !src.contains(['%', '&']) {
+            return decoded;
+        }
+        let mut out = String::with_capacity(src.len());
+        let mut rest = src;
+        while let Some(at) = rest.find(['%', '&']) {
+            out.push_str(&rest[..at]);
+            rest = &rest[at..];
+            let escape = ESCAPES.iter().find(|(escape, _)| {
+                rest.get(..escape.len())
+                    .is_some_and(|s| s.eq_ignore_ascii_case(escape))
+            });
+            match escape {
+                Some((escape, c)) => {
+                    out.push(*c);
+                    rest = &rest[escape.len()..];
+                    let shift = src.len() - rest.len() - out.len();
+                    decoded.shifts.push((out.len(), shift));
+                }
+                None => {
+                    out.push_str(&rest[..1]);
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        if !decoded.shifts.is_empty() {
+            decoded.decoded = Cow::Owned(out);
+        }
+        decoded
+    }
+
+    /// The decoded string
+    pub(crate) fn as_str(&self) -> &str {
+        &self.decoded
+    }
+
+    /// Whether an escape was decoded
+    pub(crate) fn is_decoded(&self) -> bool {
+        !self.shifts.is_empty()
+    }
+
+    /// The offset in the original string of an offset in the decoded one
+    fn offset(&self, at: usize) -> usize {
+        let shifted = self.shifts.iter().take_while(|(end, _)| *end <= at).last();
+        at + shifted.map_or(0, |(_, shift)| *shift)
+    }
+
+    /// Maps an error on the decoded string to the original one, the units
+    /// being reported as written
+    pub(crate) fn error(&self, error: impl Into<Error>) -> Error {
+        let error = error.into();
+        if !self.is_decoded() {
+            return error;
+        }
+        match map_offsets(error, |at| self.offset(at)) {
+            Error::OnlyWhitespace { .. } => Error::OnlyWhitespace {
+                len: self.src.len(),
+            },
+            Error::UnknownUnit {
+                start, end, value, ..
+            } => Error::UnknownUnit {
+                start,
+                end,
+                unit: self.src[start..end].to_string(),
+                value,
+            },
+            #[cfg(feature = "binary-system")]
+            Error::UnknownBinaryUnit {
+                start, end, value, ..
+            } => Error::UnknownBinaryUnit {
+                start,
+                end,
+                unit: self.src[start..end].to_string(),
+                value,
+            },
+            Error::UnknownSystemUnit {
+                start,
+                end,
+                value,
+                supported,
+                ..
+            } => Error::UnknownSystemUnit {
+                start,
+                end,
+                unit: self.src[start..end].to_string(),
+                value,
+                supported,
+            },
+            error => error,
+        }
+    }
+
+    /// Maps a warning on the decoded string to the original one
+    pub(crate) fn warning(&self, warning: ParseWarning) -> ParseWarning {
+        match warning {
+            ParseWarning::AssumedUnit {
+                start,
+                end,
+                assumed,
+                ..
+            } if self.is_decoded() => {
+                let (start, end) = (self.offset(start), self.offset(end));
+                ParseWarning::AssumedUnit {
+                    start,
+                    end,
+                    unit: self.src[start..end].to_string(),
+                    assumed,
+                }
+            }
+            warning => warning,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(s: &str) -> String {
+        Decoded::new(s, &ParseOptions::new().decode_escapes(true))
+            .as_str()
+            .to_string()
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("10Mbit%2Fs"), "10Mbit/s");
+        assert_eq!(decode("10%20Mbit%2fs"), "10 Mbit/s");
+        assert_eq!(decode("10&nbsp;Mbit&#47;s"), "10\u{a0}Mbit/s");
+        assert_eq!(decode("10&#160;MB&#X2F;s"), "10\u{a0}MB/s");
+        assert_eq!(decode("10%C2%A0MB&sol;s"), "10\u{a0}MB/s");
+        assert_eq!(decode("10% &#48; %2"), "10% &#48; %2");
+        assert_eq!(decode("%%2F&&nbsp;"), "%/&\u{a0}");
+        assert_eq!(decode("é%2Fé"), "é/é");
+        let decoded = Decoded::new("%2F", &ParseOptions::new());
+        assert!(!decoded.is_decoded());
+        assert_eq!(decoded.as_str(), "%2F");
+    }
+
+    #[test]
+    fn test_offset() {
+        let options = ParseOptions::new().decode_escapes(true);
+        let decoded = Decoded::new("1&nbsp;Mbit%2Fs", &options);
+        assert_eq!(decoded.as_str(), "1\u{a0}Mbit/s");
+        let offsets: Vec<_> = (0..=decoded.as_str().len())
+            .map(|at| decoded.offset(at))
+            .collect();
+        assert_eq!(offsets, [0, 1, 2, 7, 8, 9, 10, 11, 14, 15]);
+    }
+}
@@ -0,0 +1,322 @@
+//! Step by step breakdown of a parsed bandwidth
+//!
+//! When a configured value does not end up as expected, [`explain`] shows how
+//! each rate span of the string was read, what it contributed and where
+//! fractions were rounded.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::explain::explain;
+//!
+//! let explanation = explain("1.5Mbps 12.3456bps").unwrap();
+//! assert_eq!(explanation.total, Bandwidth::from_bps(1_500_012));
+//! assert_eq!(
+//!     explanation.to_string(),
+//!     "1.5Mbps = 1500000bps\n\
+//!      12.3456bps = 12.3456bps, truncated to 12bps\n\
+//!      total: 1.500012Mbps (1500012bps)",
+//! );
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use bandwidth::Bandwidth;
+
+use crate::{
+    bandwidth_as_bps, bandwidth_from_bps, si_interval_unit_name, si_unit, si_unit_name,
+    split_interval, DecimalBandwidth, Error, Parser,
+};
+
+/// How a bandwidth string was parsed, see [`explain`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The rate spans, in the order of the string
+    pub spans: Vec<Span>,
+    /// The parsed bandwidth, sum of the contributions of the spans
+    pub total: Bandwidth,
+    /// Whether the string was read in the binary prefix system
+    pub binary: bool,
+}
+
+/// A number and its unit inside the explained string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// Start of the span inside the original string
+    pub start: usize,
+    /// End of the span inside the original string
+    pub end: usize,
+    /// The span verbatim
+    pub text: String,
+    /// The canonical spelling of the unit, e.g. `Mbps` for `mbit/s`
+    pub unit: &'static str,
+    /// The exact value in the base unit (`bps` or `B/s`), e.g. `12.3456bps`
+    pub exact: String,
+    /// The value in the base unit once the fraction is dropped, if there was any
+    pub rounded: Option<String>,
+    /// The amount added to the total
+    pub contribution: Bandwidth,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounding = if self.binary { "rounded" } else { "truncated" };
+        for span in &self.spans {
+            write!(f, "{} = {}", span.text, span.exact)?;
+            if let Some(rounded) = &span.rounded {
+                write!(f, ", {} to {}", rounding, rounded)?;
+            }
+            if self.binary {
+                write!(f, " = {}bps", bandwidth_as_bps(span.contribution))?;
+            }
+            writeln!(f)?;
+        }
+        write!(
+            f,
+            "total: {} ({}bps)",
+            DecimalBandwidth(self.total),
+            bandwidth_as_bps(self.total)
+        )
+    }
+}
+
+/// Parses a bandwidth and explains how each of its spans was read
+///
+/// The string is parsed like [`parse_bandwidth`](crate::parse_bandwidth), or
+/// if it fails and the `binary-system` feature is enabled, like
+/// [`parse_binary_bandwidth`](crate::binary_system::parse_binary_bandwidth).
+/// The error of [`parse_bandwidth`](crate::parse_bandwidth) is returned when
+/// neither succeeds.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "binary-system")]
+/// # {
+/// use human_bandwidth::explain::explain;
+///
+/// let explanation = explain("1.5MiB/s 0.3kiB/s").unwrap();
+/// assert!(explanation.binary);
+/// assert_eq!(explanation.spans[1].exact, "307.2B/s");
+/// assert_eq!(explanation.spans[1].rounded.as_deref(), Some("307B/s"));
+/// assert_eq!(
+///     explanation.to_string(),
+///     "1.5MiB/s = 1572864B/s = 12582912bps\n\
+///      0.3kiB/s = 307.2B/s, rounded to 307B/s = 2456bps\n\
+///      total: 12.585368Mbps (12585368bps)",
+/// );
+/// # }
+/// ```
+pub fn explain(s: &str) -> Result<Explanation, Error> {
+    let error = match explain_with(s, false, read_si_unit) {
+        Ok(explanation) => return Ok(explanation),
+        Err(error) => error,
+    };
+    #[cfg(feature = "binary-system")]
+    if let Ok(explanation) = explain_with(s, true, read_binary_unit) {
+        return Ok(explanation);
+    }
+    Err(error)
+}
+
+/// Adds a span to the parser, returning the name of its unit, the base unit
+/// and the value of the unit in the base unit
+type ReadUnit = fn(
+    &mut Parser,
+    u128,
+    u64,
+    u32,
+    usize,
+    usize,
+) -> Result<(&'static str, &'static str, u128), Error>;
+
+fn explain_with(s: &str, binary: bool, read_unit: ReadUnit) -> Result<Explanation, Error> {
+    let mut spans = Vec::new();
+    let mut previous_end = 0;
+    let total = Parser::new(s).parse_with(
+        |parser, n, fraction, fraction_cnt, start, end| -> Result<(), Error> {
+            let before = parser.current;
+            let (unit, base, multiplier) =
+                read_unit(parser, n, fraction, fraction_cnt, start, end)?;
+            let contribution = bandwidth_from_bps(parser.current - before)?;
+
+            let int = n.saturating_mul(multiplier);
+            let scale = 10u128.pow(fraction_cnt);
+            let fraction = fraction as u128 * multiplier;
+            let (int, remainder) = (int.saturating_add(fraction / scale), fraction % scale);
+            let (exact, rounded) = if remainder == 0 {
+                (format!("{}{}", int, base), None)
+            } else {
+                let digits = format!("{:0width$}", remainder, width = fraction_cnt as usize);
+                let up = binary && remainder * 2 >= scale;
+                (
+                    format!("{}.{}{}", int, digits.trim_end_matches('0'), base),
+                    Some(format!("{}{}", int + u128::from(up), base)),
+                )
+            };
+
+            let text = s[previous_end..end].trim_start();
+            spans.push(Span {
+                start: end - text.len(),
+                end,
+                text: text.to_string(),
+                unit,
+                exact,
+                rounded,
+                contribution,
+            });
+            previous_end = end;
+            Ok(())
+        },
+    )?;
+    Ok(Explanation {
+        spans,
+        total,
+        binary,
+    })
+}
+
+fn read_si_unit(
+    parser: &mut Parser,
+    n: u128,
+    fraction: u64,
+    fraction_cnt: u32,
+    start: usize,
+    end: usize,
+) -> Result<(&'static str, &'static str, u128), Error> {
+    parser.parse_unit(n, fraction, fraction_cnt, start, end)?;
+    let unit = &parser.src[start..end];
+    // Units per interval are explained in bits per interval
+    if let Some((base, interval)) = split_interval(unit) {
+        if let Some(exponent) = si_unit(&[base, "/s"].concat()) {
+            return Ok((
+                si_interval_unit_name(exponent, interval),
+                si_interval_unit_name(0, interval),
+                10u128.pow(exponent),
+            ));
+        }
+    }
+    let exponent = si_unit(unit).unwrap_or(0);
+    Ok((si_unit_name(exponent), "bps", 10u128.pow(exponent)))
+}
+
+#[cfg(feature = "binary-system")]
+fn read_binary_unit(
+    parser: &mut Parser,
+    n: u128,
+    fraction: u64,
+    fraction_cnt: u32,
+    start: usize,
+    end: usize,
+) -> Result<(&'static str, &'static str, u128), Error> {
+    use crate::binary_system::{binary_interval_unit, binary_unit_name};
+
+    parser.parse_binary_unit(n, fraction, fraction_cnt, start, end)?;
+    let (power, interval) = binary_interval_unit(&parser.src[start..end]).unwrap_or_default();
+    Ok((
+        binary_unit_name(power, interval),
+        binary_unit_name(0, interval),
+        1 << (10 * power),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain("  2Gbps 5 mbit/s  7b/s").unwrap();
+        assert!(!explanation.binary);
+        assert_eq!(explanation.total, Bandwidth::new(2, 5_000_007));
+        assert_eq!(
+            explanation.spans,
+            [
+                Span {
+                    start: 2,
+                    end: 7,
+                    text: "2Gbps".to_string(),
+                    unit: "Gbps",
+                    exact: "2000000000bps".to_string(),
+                    rounded: None,
+                    contribution: Bandwidth::new(2, 0),
+                },
+                Span {
+                    start: 8,
+                    end: 16,
+                    text: "5 mbit/s".to_string(),
+                    unit: "Mbps",
+                    exact: "5000000bps".to_string(),
+                    rounded: None,
+                    contribution: Bandwidth::from_mbps(5),
+                },
+                Span {
+                    start: 18,
+                    end: 22,
+                    text: "7b/s".to_string(),
+                    unit: "bps",
+                    exact: "7bps".to_string(),
+                    rounded: None,
+                    contribution: Bandwidth::from_bps(7),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rounding() {
+        let explanation = explain("1.0000001kbps").unwrap();
+        assert_eq!(explanation.spans[0].exact, "1000.0001bps");
+        assert_eq!(explanation.spans[0].rounded.as_deref(), Some("1000bps"));
+        assert_eq!(explanation.total, Bandwidth::from_bps(1_000));
+    }
+
+    #[test]
+    fn test_interval() {
+        let explanation = explain("36 Gbit/h 1.5kb/min").unwrap();
+        assert_eq!(explanation.spans[0].unit, "Gbit/h");
+        assert_eq!(explanation.spans[0].exact, "36000000000bit/h");
+        assert_eq!(explanation.spans[1].unit, "kbit/min");
+        assert_eq!(explanation.spans[1].exact, "1500bit/min");
+        assert_eq!(explanation.total, Bandwidth::from_bps(10_000_025));
+    }
+
+    #[test]
+    fn test_error() {
+        assert_eq!(explain(""), Err(Error::Empty));
+        assert!(matches!(
+            explain("10Mbps 3parsecs"),
+            Err(Error::UnknownUnit { .. })
+        ));
+    }
+
+    #[cfg(feature = "binary-system")]
+    #[test]
+    fn test_binary() {
+        let explanation = explain("1.5MiB/s 200KiB/s").unwrap();
+        assert!(explanation.binary);
+        assert_eq!(explanation.spans[0].unit, "MiB/s");
+        assert_eq!(explanation.spans[1].unit, "kiB/s");
+        assert_eq!(
+            explanation.spans[1].contribution,
+            Bandwidth::from_bps(1_638_400)
+        );
+        assert_eq!(explanation.total, Bandwidth::from_bps(14_221_312));
+        // The rounding of binary units is to the nearest Byte
+        let explanation = explain("0.0009kiB/s").unwrap();
+        assert_eq!(explanation.spans[0].exact, "0.9216B/s");
+        assert_eq!(explanation.spans[0].rounded.as_deref(), Some("1B/s"));
+        assert_eq!(explanation.total, Bandwidth::from_bps(8));
+        // Per-interval units are explained in Bytes per interval
+        let explanation = explain("4GiB/h").unwrap();
+        assert_eq!(explanation.spans[0].unit, "GiB/h");
+        assert_eq!(explanation.spans[0].exact, "4294967296B/h");
+        assert_eq!(explanation.total, Bandwidth::from_bps(9_544_372));
+    }
+}
@@ -0,0 +1,140 @@
+//! Bandwidths mentioned in free-form text
+//!
+//! Log lines and descriptions mention rates among other words, e.g.
+//! `eth0: link up, 2.5 Gbps full duplex`. [`extract_first`] finds the first
+//! expression which reads as a bandwidth, so that such lines can be sorted or
+//! filtered by rate without a regular expression.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::extract::extract_first;
+//!
+//! let mut lines = [
+//!     "eth1: link up, 10 Gbps full duplex",
+//!     "eth0: link up, 2.5 Gbps full duplex",
+//!     "wlan0: link up, 866.7Mbit/s",
+//! ];
+//! lines.sort_by_key(|line| extract_first(line).map(|(rate, _)| rate));
+//! assert_eq!(lines[0], "wlan0: link up, 866.7Mbit/s");
+//! assert_eq!(lines[2], "eth1: link up, 10 Gbps full duplex");
+//! ```
+
+use core::ops::Range;
+
+use bandwidth::Bandwidth;
+
+use crate::{parse_bandwidth, spelled::per_interval_len};
+
+/// Finds the first bandwidth in `s`, with its byte range
+///
+/// A bandwidth is a number, optionally followed by whitespace on the same
+/// line, and a unit accepted by [`parse_bandwidth`], e.g. `10Mbps` or
+/// `1.5 Mbit/s`. The number must start a word and the unit must end one, so
+/// that `v2.10` or `10Mbpsx` are skipped. A unit followed by a slash, like in
+/// `10Mbps/port`, ends before it when the longer one is not a unit. A unit
+/// spelled out may end with `per` and an interval, like in `10 megabits per
+/// second`. Only a single rate span is read: `1Mbps 500kbps` gives `1Mbps`.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::extract::extract_first;
+///
+/// let line = "12:00:01 transfer done: 3 files, 12.5 Mbps average";
+/// let (rate, range) = extract_first(line).unwrap();
+/// assert_eq!(rate, Bandwidth::from_kbps(12_500));
+/// assert_eq!(&line[range], "12.5 Mbps");
+/// assert_eq!(extract_first("no rate in 3 files"), None);
+/// ```
+pub fn extract_first(s: &str) -> Option<(Bandwidth, Range<usize>)> {
+    let mut prev = None;
+    for (start, c) in s.char_indices() {
+        let at_word_start = !prev.is_some_and(|p: char| p.is_alphanumeric() || p == '.');
+        prev = Some(c);
+        if !c.is_ascii_digit() || !at_word_start {
+            continue;
+        }
+        if let Some(found) = bandwidth_at(s, start) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// The bandwidth whose number starts at `start`, if any
+fn bandwidth_at(s: &str, start: usize) -> Option<(Bandwidth, Range<usize>)> {
+    let rest = &s[start..];
+    let mut number = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    if let Some(fraction) = rest[number..].strip_prefix('.') {
+        let digits = fraction
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(fraction.len());
+        if digits > 0 {
+            number += 1 + digits;
+        }
+    }
+    let after_number = &rest[number..];
+    let unit_start = number + after_number.len()
+        - after_number
+            .trim_start_matches(|c: char| c.is_whitespace() && c != '\n' && c != '\r')
+            .len();
+    let mut unit_len = rest[unit_start..]
+        .find(|c: char| !c.is_ascii_alphabetic() && c != '/')
+        .unwrap_or(rest.len() - unit_start);
+    let after_unit = &rest[unit_start + unit_len..];
+    unit_len += per_interval_len(&rest[unit_start..unit_start + unit_len], after_unit).unwrap_or(0);
+    let unit = &rest[unit_start..unit_start + unit_len];
+    if unit_len == 0 || rest[unit_start + unit_len..].starts_with(char::is_alphanumeric) {
+        return None;
+    }
+    // The longest unit first, then the ones ending before a slash
+    let ends = core::iter::once(unit_len).chain(unit.rmatch_indices('/').map(|(at, _)| at));
+    ends.filter(|end| *end > 0).find_map(|end| {
+        let end = start + unit_start + end;
+        let bw = parse_bandwidth(&s[start..end]).ok()?;
+        Some((bw, start..end))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(s: &str) -> Option<(Bandwidth, &str)> {
+        extract_first(s).map(|(bw, range)| (bw, &s[range]))
+    }
+
+    #[test]
+    fn test_extract_first() {
+        let mbps = Bandwidth::from_mbps;
+        assert_eq!(extract("10Mbps"), Some((mbps(10), "10Mbps")));
+        assert_eq!(extract("rate=10 Mbit/s."), Some((mbps(10), "10 Mbit/s")));
+        assert_eq!(extract("(1.5Gbps)"), Some((mbps(1_500), "1.5Gbps")));
+        assert_eq!(extract("1 Mbps 500kbps"), Some((mbps(1), "1 Mbps")));
+        assert_eq!(extract("10Mbps/port"), Some((mbps(10), "10Mbps")));
+        assert_eq!(extract("3 files, 2 Mbps"), Some((mbps(2), "2 Mbps")));
+        assert_eq!(extract("v2.10 at 4Mbps"), Some((mbps(4), "4Mbps")));
+        assert_eq!(extract("10Mbpsx 20Mbps"), Some((mbps(20), "20Mbps")));
+        assert_eq!(extract("x10Mbps 30Mbps"), Some((mbps(30), "30Mbps")));
+        assert_eq!(extract("5. Mbps 6Mbps"), Some((mbps(6), "6Mbps")));
+        assert_eq!(extract("é 7 Mbps"), Some((mbps(7), "7 Mbps")));
+        assert_eq!(
+            extract("up to 10 megabits per second."),
+            Some((mbps(10), "10 megabits per second"))
+        );
+        assert_eq!(extract("2 megabits per port"), None);
+        assert_eq!(extract("8\nMbps"), None);
+        assert_eq!(extract("9 / s"), None);
+        assert_eq!(
+            extract("\u{a0}10\u{a0}Mbps"),
+            Some((mbps(10), "10\u{a0}Mbps"))
+        );
+        assert_eq!(extract(""), None);
+        assert_eq!(extract("100%"), None);
+    }
+}
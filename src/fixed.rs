@@ -0,0 +1,147 @@
+//! Integer values of bandwidths in a chosen unit
+//!
+//! Protocols and kernel interfaces, like `tc` netlink messages or eBPF maps,
+//! often take bandwidths as integer fields in kbps or Mbps. [`to_fixed`] and
+//! [`from_fixed`] convert to and from them with explicit rounding.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::fixed::{from_fixed, to_fixed, Rounding, Unit};
+//!
+//! let bw = Bandwidth::from_bps(1_500_500);
+//! assert_eq!(to_fixed(bw, Unit::Kbps, Rounding::Down), 1_500);
+//! assert_eq!(to_fixed(bw, Unit::Kbps, Rounding::Up), 1_501);
+//! assert_eq!(to_fixed(bw, Unit::Mbps, Rounding::Nearest), 2);
+//! assert_eq!(from_fixed(1_500, Unit::Kbps), Ok(Bandwidth::from_kbps(1_500)));
+//! ```
+
+use bandwidth::Bandwidth;
+
+pub use crate::Unit;
+use crate::{bandwidth_as_bps, bandwidth_from_bps, Error};
+
+/// How to round values falling between two integers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Towards zero, e.g. `1.9kbps` to `1kbps`
+    Down,
+    /// Away from zero, e.g. `1.1kbps` to `2kbps`
+    Up,
+    /// To the closest integer, and away from zero for halves, e.g. `1.5kbps`
+    /// to `2kbps`
+    Nearest,
+}
+
+/// Returns a bandwidth as an integer number of `unit`, rounded as requested
+///
+/// The result saturates at `u64::MAX`, e.g. for bandwidths over 2^64 bps in
+/// [`Unit::Bps`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::fixed::{to_fixed, Rounding, Unit};
+///
+/// let bw = Bandwidth::new(2, 500_000_000);
+/// assert_eq!(to_fixed(bw, Unit::Gbps, Rounding::Down), 2);
+/// assert_eq!(to_fixed(bw, Unit::Gbps, Rounding::Nearest), 3);
+/// assert_eq!(to_fixed(bw, Unit::Mbps, Rounding::Up), 2_500);
+/// ```
+pub fn to_fixed(bw: Bandwidth, unit: Unit, rounding: Rounding) -> u64 {
+    let bps = bandwidth_as_bps(bw);
+    let divisor = u128::from(unit.bps());
+    let (quotient, remainder) = (bps / divisor, bps % divisor);
+    let up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => remainder > 0,
+        Rounding::Nearest => remainder * 2 >= divisor,
+    };
+    u64::try_from(quotient + u128::from(up)).unwrap_or(u64::MAX)
+}
+
+/// Creates a bandwidth from an integer number of `unit`
+///
+/// Returns [`Error::NumberOverflow`] when the bandwidth can not be
+/// represented, which can only happen for [`Unit::Tbps`] and the larger
+/// units.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{fixed::{from_fixed, Unit}, Error};
+///
+/// assert_eq!(from_fixed(25, Unit::Mbps), Ok(Bandwidth::from_mbps(25)));
+/// assert_eq!(from_fixed(2, Unit::Pbps), Ok(Bandwidth::new(2_000_000, 0)));
+/// assert_eq!(from_fixed(u64::MAX, Unit::Tbps), Err(Error::NumberOverflow));
+/// ```
+pub fn from_fixed(value: u64, unit: Unit) -> Result<Bandwidth, Error> {
+    bandwidth_from_bps(u128::from(value) * u128::from(unit.bps()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_fixed() {
+        let bw = Bandwidth::from_bps(1_499);
+        assert_eq!(to_fixed(bw, Unit::Bps, Rounding::Up), 1_499);
+        assert_eq!(to_fixed(bw, Unit::Kbps, Rounding::Down), 1);
+        assert_eq!(to_fixed(bw, Unit::Kbps, Rounding::Up), 2);
+        assert_eq!(to_fixed(bw, Unit::Kbps, Rounding::Nearest), 1);
+        assert_eq!(to_fixed(bw, Unit::Tbps, Rounding::Down), 0);
+        assert_eq!(to_fixed(bw, Unit::Tbps, Rounding::Up), 1);
+        assert_eq!(to_fixed(bw, Unit::Tbps, Rounding::Nearest), 0);
+
+        let zero = Bandwidth::new(0, 0);
+        for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+            assert_eq!(to_fixed(zero, Unit::Gbps, rounding), 0);
+        }
+    }
+
+    #[test]
+    fn test_saturation() {
+        assert_eq!(
+            to_fixed(Bandwidth::MAX, Unit::Bps, Rounding::Down),
+            u64::MAX
+        );
+        assert_eq!(
+            to_fixed(Bandwidth::MAX, Unit::Gbps, Rounding::Down),
+            u64::MAX
+        );
+        assert_eq!(
+            to_fixed(Bandwidth::MAX, Unit::Ebps, Rounding::Down),
+            18_446_744_073
+        );
+    }
+
+    #[test]
+    fn test_from_fixed() {
+        assert_eq!(from_fixed(0, Unit::Tbps), Ok(Bandwidth::new(0, 0)));
+        assert_eq!(from_fixed(7, Unit::Bps), Ok(Bandwidth::from_bps(7)));
+        assert_eq!(from_fixed(9_420, Unit::Gbps), Ok(Bandwidth::new(9_420, 0)));
+        assert_eq!(
+            from_fixed(u64::MAX, Unit::Gbps),
+            Ok(Bandwidth::new(u64::MAX, 0))
+        );
+        assert_eq!(
+            from_fixed(18, Unit::Ebps),
+            Ok(Bandwidth::new(18_000_000_000, 0))
+        );
+        assert_eq!(from_fixed(u64::MAX, Unit::Pbps), Err(Error::NumberOverflow));
+        for unit in Unit::ALL {
+            let bw = from_fixed(1_234, unit).unwrap();
+            assert_eq!(to_fixed(bw, unit, Rounding::Down), 1_234);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Unit::Bps.to_string(), "bps");
+        assert_eq!(Unit::Tbps.to_string(), "Tbps");
+    }
+}
@@ -0,0 +1,103 @@
+//! Single precision conversions for embedded targets
+//!
+//! Sensor and radio firmware often runs on cores without double precision
+//! hardware, where `f64` arithmetic and 64-bit divisions are emulated. These
+//! helpers only use `f32` arithmetic, integer casts and no 64-bit division, and
+//! do not depend on `std`.
+//!
+//! # Precision
+//!
+//! An `f32` holds about 7 significant decimal digits: bandwidths are exact up
+//! to 16.777216Mbps (2^24 bps) and are otherwise off by at most a few
+//! hundred-thousandths of their value, e.g. up to about 64bps around 1Gbps.
+//! Use [`fixed`](crate::fixed) when exact values are required.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::float::{as_mbps_f32, from_mbps_f32};
+//!
+//! assert_eq!(as_mbps_f32(Bandwidth::from_kbps(1_500)), 1.5);
+//! assert_eq!(from_mbps_f32(2_500.5), Bandwidth::from_kbps(2_500_500));
+//! ```
+
+use bandwidth::Bandwidth;
+
+use crate::consts::GIGA;
+
+/// Returns a bandwidth in Mbps, see the [module documentation](self) for its
+/// precision
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::float::as_mbps_f32;
+///
+/// assert_eq!(as_mbps_f32(Bandwidth::new(9_420, 0)), 9_420_000.0);
+/// assert_eq!(as_mbps_f32(Bandwidth::from_bps(250)), 0.00025);
+/// ```
+pub fn as_mbps_f32(bw: Bandwidth) -> f32 {
+    bw.as_gbps() as f32 * 1_000.0 + bw.subgbps_bps() as f32 / 1_000_000.0
+}
+
+/// Creates a bandwidth from a number of Mbps, see the
+/// [module documentation](self) for its precision
+///
+/// The conversion saturates: negative numbers and NaN give zero, and numbers
+/// too large for a bandwidth give [`Bandwidth::MAX`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::float::from_mbps_f32;
+///
+/// assert_eq!(from_mbps_f32(0.25), Bandwidth::from_kbps(250));
+/// assert_eq!(from_mbps_f32(-1.0), Bandwidth::new(0, 0));
+/// assert_eq!(from_mbps_f32(f32::INFINITY), Bandwidth::MAX);
+/// ```
+pub fn from_mbps_f32(mbps: f32) -> Bandwidth {
+    // Casts from floats truncate towards zero and saturate
+    let gbps = (mbps / 1_000.0) as u64;
+    let rest = mbps - gbps as f32 * 1_000.0;
+    let bps = ((rest * 1_000_000.0) as u32).min(GIGA as u32 - 1);
+    Bandwidth::new(gbps, bps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_mbps_f32() {
+        assert_eq!(as_mbps_f32(Bandwidth::new(0, 0)), 0.0);
+        assert_eq!(as_mbps_f32(Bandwidth::from_mbps(10)), 10.0);
+        assert_eq!(as_mbps_f32(Bandwidth::new(2, 500_000_000)), 2_500.0);
+        assert_eq!(as_mbps_f32(Bandwidth::from_bps(16_777_216)), 16.777216);
+        let max = as_mbps_f32(Bandwidth::MAX);
+        assert!((max / 1.8446744e22 - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_from_mbps_f32() {
+        assert_eq!(from_mbps_f32(0.0), Bandwidth::new(0, 0));
+        assert_eq!(from_mbps_f32(1.5), Bandwidth::from_kbps(1_500));
+        assert_eq!(from_mbps_f32(12.345), Bandwidth::from_bps(12_345_000));
+        assert_eq!(from_mbps_f32(3_000.0), Bandwidth::from_gbps(3));
+        assert_eq!(from_mbps_f32(f32::NAN), Bandwidth::new(0, 0));
+        assert_eq!(from_mbps_f32(f32::MAX), Bandwidth::MAX);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for bps in [1, 999, 1_000_000, 16_777_216, 123_456_789_000] {
+            let bw = Bandwidth::from_bps(bps);
+            let back = from_mbps_f32(as_mbps_f32(bw));
+            let error = (back.as_gbps() * 1_000_000_000 + u64::from(back.subgbps_bps()))
+                .abs_diff(bps) as f64;
+            assert!(error <= bps as f64 * 1e-6, "{} gave {:?}", bps, back);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Harness checking that parsing untrusted input never panics
+//!
+//! [`check`] parses raw bytes with [`parse_untrusted`] and with the other
+//! parsing entry points, and asserts the properties which hold for every
+//! input. It is the body of a `cargo fuzz` target:
+//!
+//! ```ignore
+//! #![no_main]
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| human_bandwidth::fuzz::check(data));
+//! ```
+//!
+//! Without a fuzzing engine, [`inputs`] generates pseudo-random inputs made
+//! of the fragments which stress the parser: long runs of digits or
+//! whitespace, units, separators and invalid UTF-8.
+//!
+//! # Example
+//!
+//! ```
+//! use human_bandwidth::fuzz::{check, inputs};
+//!
+//! for input in inputs(42).take(1_000) {
+//!     check(&input);
+//! }
+//! ```
+
+use alloc::{string::ToString, vec::Vec};
+use bandwidth::Bandwidth;
+
+use crate::{
+    format_bandwidth, parse_bandwidth, parse_bandwidth_borrowed, parse_untrusted,
+    stream::StreamParser, Error, ParseOptions,
+};
+
+/// Parses `data` in every mode and panics if a property is violated
+///
+/// The properties are:
+///
+/// * [`parse_untrusted`] returns, and agrees with
+///   [`parse_bandwidth`] on valid UTF-8
+/// * a parsed bandwidth is read back from its formatted string
+/// * [`parse_bandwidth_borrowed`] reports the same errors as
+///   [`parse_bandwidth`]
+/// * [`ParseOptions::parse_recovering`] returns the same bandwidth when
+///   there is no error
+/// * the [saturating](ParseOptions::saturating) mode only reads the overflows
+///   differently, as [`Bandwidth::MAX`]
+/// * a [`StreamParser`] fed the string in chunks agrees with
+///   [`parse_bandwidth`]
+pub fn check(data: &[u8]) {
+    let parsed = parse_untrusted(data);
+    let s = match core::str::from_utf8(data) {
+        Ok(s) => s,
+        Err(err) => {
+            assert_eq!(parsed, Err(Error::InvalidCharacter(err.valid_up_to())));
+            return;
+        }
+    };
+    assert_eq!(parsed, parse_bandwidth(s));
+    assert_eq!(parse_bandwidth_borrowed(s).map_err(Error::from), parsed);
+    let mut stream = StreamParser::new();
+    let streamed = s
+        .split_inclusive(|c: char| c.len_utf8() > 1 || c == 'b' || c == ' ')
+        .try_for_each(|chunk| stream.feed(chunk))
+        .and_then(|_| stream.finish());
+    assert_eq!(streamed, parsed);
+    if let Ok(val) = parsed {
+        let formatted = format_bandwidth(val).to_string();
+        assert_eq!(parse_bandwidth(&formatted), Ok(val), "{:?}", formatted);
+    }
+
+    for options in [
+        ParseOptions::new(),
+        ParseOptions::new().fuzzy(true).decimal_comma(true),
+        ParseOptions::new().iso80000(true).digit_grouping(true),
+        ParseOptions::new()
+            .bps_means_bytes(true)
+            .quadrant_units(true),
+        ParseOptions::new()
+            .digit_separators(false)
+            .spaces_in_numbers(false)
+            .newlines(false)
+            .single_spaces(true)
+            .multiple_spans(false),
+        ParseOptions::strict(),
+        ParseOptions::new().decode_escapes(true),
+    ] {
+        let parsed = options.parse(s);
+        let (val, errors) = options.parse_recovering(s);
+        if errors.is_empty() {
+            assert_eq!(parsed, Ok(val));
+        }
+        // Only the overflows are read differently when saturating
+        match (options.saturating(true).parse(s), parsed) {
+            (Ok(val), Err(error)) => {
+                assert_eq!((val, error), (Bandwidth::MAX, Error::NumberOverflow))
+            }
+            (saturated, parsed @ Ok(_)) => assert_eq!(saturated, parsed),
+            _ => {}
+        }
+    }
+}
+
+/// The fragments inputs are made of
+const FRAGMENTS: &[&[u8]] = &[
+    b"0",
+    b"1",
+    b"9",
+    b"18446744073709551615",
+    b"99999999999999999999999999",
+    b".",
+    b",",
+    b"'",
+    b"_",
+    b"e",
+    b"E-",
+    b" ",
+    b"\t\n",
+    "\u{3000}".as_bytes(),
+    b"bps",
+    b"kbps",
+    b"Mbps",
+    b"Ebps",
+    b"Mbit/s",
+    b"kB/s",
+    b"MiB/s",
+    b"Mpbs",
+    b"/",
+    b"-",
+    b"%2F",
+    b"&nbsp;",
+    b"\xff",
+    b"\xc3",
+    "\u{e9}".as_bytes(),
+];
+
+/// Pseudo-random inputs for [`check`], the same ones for a given `seed`
+///
+/// Most inputs are short concatenations of fragments, and some repeat a
+/// fragment thousands of times.
+pub fn inputs(seed: u64) -> impl Iterator<Item = Vec<u8>> {
+    // xorshift64*, which must not start from 0
+    let mut state = seed | 1;
+    let mut next = move || {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    };
+    core::iter::repeat_with(move || {
+        let mut input = Vec::new();
+        let pick = |n: u64| FRAGMENTS[(n % FRAGMENTS.len() as u64) as usize];
+        if next() % 16 == 0 {
+            let fragment = pick(next());
+            let count = 1 + (next() % 10_000) as usize;
+            for _ in 0..count {
+                input.extend_from_slice(fragment);
+            }
+            input.extend_from_slice(pick(next()));
+        } else {
+            for _ in 0..next() % 12 {
+                input.extend_from_slice(pick(next()));
+            }
+        }
+        input
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inputs() {
+        for seed in 0..8 {
+            for input in inputs(seed).take(2_000) {
+                check(&input);
+            }
+        }
+        assert_eq!(
+            inputs(7).take(10).collect::<Vec<_>>(),
+            inputs(7).take(10).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_pathological() {
+        let digits = vec![b'9'; 1 << 20];
+        check(&digits);
+        let mut fraction = b"1.".to_vec();
+        fraction.extend_from_slice(&digits);
+        fraction.extend_from_slice(b"Mbps");
+        check(&fraction);
+        assert_eq!(
+            parse_untrusted(&fraction),
+            Ok(bandwidth::Bandwidth::from_bps(1_999_999))
+        );
+        let spaces = vec![b' '; 1 << 20];
+        check(&spaces);
+        assert_eq!(
+            parse_untrusted(&spaces),
+            Err(Error::OnlyWhitespace { len: 1 << 20 })
+        );
+        let spans = b"1bps ".repeat(1 << 16);
+        check(&spans);
+        check(b"\xff\xfe");
+        check(b"1Mbps\xc3");
+    }
+}
@@ -0,0 +1,277 @@
+//! Axis labels for charts of bandwidth
+//!
+//! Throughput graphs usually span several orders of magnitude and read best
+//! with a logarithmic scale. [`log_ticks`] computes round tick values along
+//! such an axis together with their labels, and [`format_bucket_label`] the
+//! compact labels of histogram buckets like `10–100Mbit/s`.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::labels::log_ticks;
+//!
+//! let ticks = log_ticks(Bandwidth::from_kbps(1), Bandwidth::from_mbps(1), 1);
+//! let labels: Vec<_> = ticks.iter().map(|(_, label)| label.as_str()).collect();
+//! assert_eq!(labels, ["1kbps", "10kbps", "100kbps", "1Mbps"]);
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, bandwidth_from_bps, fmt_ratio, DecimalBandwidth, Unit};
+
+/// Computes the ticks of a logarithmic axis between `min` and `max` (inclusive)
+///
+/// Each decade gets `per_decade` ticks (at least 1), evenly spaced on the
+/// logarithmic scale and rounded to nice values: `1, 3` for 2 ticks per decade,
+/// `1, 2, 5` for 3, and two significant digits beyond 4 ticks per decade. Ticks
+/// below 1bps are never produced.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::labels::log_ticks;
+///
+/// let ticks = log_ticks(Bandwidth::from_mbps(1), Bandwidth::from_mbps(20), 3);
+/// let labels: Vec<_> = ticks.iter().map(|(_, label)| label.as_str()).collect();
+/// assert_eq!(labels, ["1Mbps", "2Mbps", "5Mbps", "10Mbps", "20Mbps"]);
+/// assert_eq!(ticks[1].0, Bandwidth::from_mbps(2));
+/// ```
+pub fn log_ticks(min: Bandwidth, max: Bandwidth, per_decade: u8) -> Vec<(Bandwidth, String)> {
+    let min = bandwidth_as_bps(min).max(1);
+    let max = bandwidth_as_bps(max);
+    let mantissas = mantissas(per_decade.max(1));
+    let mut ticks = Vec::new();
+    // Mantissas are in tenths of the decade: the first pass covers 1bps to 10bps
+    // where only whole values are kept, then `decade` multiplies them
+    let mut decade: u128 = 1;
+    let mut tenths = true;
+    loop {
+        for mantissa in &mantissas {
+            let bps = if tenths {
+                if mantissa % 10 != 0 {
+                    continue;
+                }
+                mantissa / 10
+            } else {
+                match mantissa.checked_mul(decade) {
+                    Some(bps) => bps,
+                    None => return ticks,
+                }
+            };
+            if bps > max {
+                return ticks;
+            }
+            if bps < min {
+                continue;
+            }
+            match bandwidth_from_bps(bps) {
+                Ok(val) => ticks.push((val, DecimalBandwidth(val).to_string())),
+                Err(_) => return ticks,
+            }
+        }
+        if tenths {
+            tenths = false;
+        } else {
+            decade = match decade.checked_mul(10) {
+                Some(decade) => decade,
+                None => return ticks,
+            };
+        }
+    }
+}
+
+/// Formats the label of a histogram bucket, e.g. `10–100Mbit/s`, or
+/// `≥1Gbit/s` for the last bucket without an upper bound
+///
+/// See [`BucketLabels`] for the format, this uses the default options.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::labels::format_bucket_label;
+///
+/// let label = format_bucket_label(Bandwidth::from_mbps(10), Some(Bandwidth::from_mbps(100)));
+/// assert_eq!(label, "10–100Mbit/s");
+/// assert_eq!(format_bucket_label(Bandwidth::from_gbps(1), None), "≥1Gbit/s");
+/// ```
+pub fn format_bucket_label(lower: Bandwidth, upper: Option<Bandwidth>) -> String {
+    BucketLabels::new().format(lower, upper)
+}
+
+/// Options of the labels of histogram buckets, see [`format_bucket_label`]
+///
+/// Both bounds share the unit of the largest one, written once after them
+/// with its ISO/IEC 80000-13 symbol, e.g. `0.5–2Mbit/s`. The values are
+/// exact, without trailing zeros.
+///
+/// # Example
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::labels::BucketLabels;
+///
+/// let labels = BucketLabels::new().ascii(true);
+/// let label = labels.format(Bandwidth::from_kbps(500), Some(Bandwidth::from_mbps(2)));
+/// assert_eq!(label, "0.5-2Mbit/s");
+/// assert_eq!(labels.format(Bandwidth::from_gbps(1), None), ">=1Gbit/s");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BucketLabels {
+    ascii: bool,
+}
+
+impl BucketLabels {
+    /// Create the default options
+    pub const fn new() -> Self {
+        BucketLabels { ascii: false }
+    }
+
+    /// Write the range with `-` and the open bucket with `>=` rather than
+    /// with an en dash and `≥`, e.g. for terminals without Unicode
+    ///
+    /// Disabled by default.
+    pub const fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Whether the labels are written in ASCII
+    pub const fn is_ascii(&self) -> bool {
+        self.ascii
+    }
+
+    /// Formats the label of the bucket from `lower` to `upper`, or from
+    /// `lower` without an upper bound
+    pub fn format(&self, lower: Bandwidth, upper: Option<Bandwidth>) -> String {
+        let lower = bandwidth_as_bps(lower);
+        match upper.map(bandwidth_as_bps) {
+            Some(upper) => {
+                let unit = Unit::of(lower.max(upper));
+                let dash = if self.ascii { "-" } else { "\u{2013}" };
+                format!(
+                    "{}{}{}{}",
+                    bound(lower, unit),
+                    dash,
+                    bound(upper, unit),
+                    unit.iso_name()
+                )
+            }
+            None => {
+                let unit = Unit::of(lower);
+                let at_least = if self.ascii { ">=" } else { "\u{2265}" };
+                format!("{}{}{}", at_least, bound(lower, unit), unit.iso_name())
+            }
+        }
+    }
+}
+
+/// Writes `bps` in `unit` exactly, without trailing zeros
+fn bound(bps: u128, unit: Unit) -> String {
+    let value = fmt_ratio(bps, u128::from(unit.bps()), unit.exponent() as usize);
+    match value.split_once('.') {
+        Some((int, digits)) => match digits.trim_end_matches('0') {
+            "" => int.to_string(),
+            digits => format!("{}.{}", int, digits),
+        },
+        None => value,
+    }
+}
+
+/// Nice mantissas of a decade in tenths, e.g. `[10, 20, 50]` for 3 ticks per decade
+fn mantissas(per_decade: u8) -> Vec<u128> {
+    let digits = if per_decade <= 4 { 1 } else { 2 };
+    let mut mantissas: Vec<u128> = (0..per_decade)
+        .map(|i| {
+            let value = 10f64.powf(f64::from(i) / f64::from(per_decade));
+            let scale = 10f64.powi(digits - 1);
+            ((value * scale).round() / scale * 10.0).round() as u128
+        })
+        .collect();
+    mantissas.dedup();
+    mantissas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(min: Bandwidth, max: Bandwidth, per_decade: u8) -> Vec<String> {
+        log_ticks(min, max, per_decade)
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect()
+    }
+
+    #[test]
+    fn test_bucket_labels() {
+        let label = |lower, upper| format_bucket_label(lower, upper);
+        assert_eq!(
+            label(Bandwidth::new(0, 0), Some(Bandwidth::from_kbps(1))),
+            "0\u{2013}1kbit/s"
+        );
+        assert_eq!(
+            label(Bandwidth::from_kbps(100), Some(Bandwidth::from_kbps(1_500))),
+            "0.1\u{2013}1.5Mbit/s"
+        );
+        assert_eq!(
+            label(Bandwidth::new(0, 999), Some(Bandwidth::new(0, 1_001))),
+            "0.999\u{2013}1.001kbit/s"
+        );
+        assert_eq!(label(Bandwidth::new(0, 0), None), "\u{2265}0bit/s");
+        assert_eq!(
+            label(Bandwidth::MAX, None),
+            "\u{2265}18446744073.709551615999999999Ebit/s"
+        );
+        assert_eq!(
+            BucketLabels::new()
+                .ascii(true)
+                .format(Bandwidth::from_gbps(1), Some(Bandwidth::from_gbps(10))),
+            "1-10Gbit/s"
+        );
+        assert!(BucketLabels::new().ascii(true).is_ascii());
+    }
+
+    #[test]
+    fn test_mantissas() {
+        assert_eq!(mantissas(1), [10]);
+        assert_eq!(mantissas(2), [10, 30]);
+        assert_eq!(mantissas(3), [10, 20, 50]);
+        assert_eq!(mantissas(4), [10, 20, 30, 60]);
+        assert_eq!(mantissas(5), [10, 16, 25, 40, 63]);
+    }
+
+    #[test]
+    fn test_log_ticks() {
+        assert_eq!(
+            labels(Bandwidth::new(0, 0), Bandwidth::new(0, 100), 1),
+            ["1bps", "10bps", "100bps"]
+        );
+        assert_eq!(
+            labels(Bandwidth::new(0, 0), Bandwidth::new(0, 40), 5),
+            ["1bps", "4bps", "10bps", "16bps", "25bps", "40bps"]
+        );
+        assert_eq!(
+            labels(Bandwidth::from_kbps(2), Bandwidth::from_kbps(40), 2),
+            ["3kbps", "10kbps", "30kbps"]
+        );
+        assert_eq!(
+            labels(Bandwidth::from_mbps(150), Bandwidth::new(2, 0), 0),
+            ["1Gbps"]
+        );
+        assert_eq!(
+            labels(Bandwidth::new(5, 0), Bandwidth::new(1, 0), 1),
+            Vec::<String>::new()
+        );
+        let ticks = log_ticks(Bandwidth::new(0, 1), Bandwidth::MAX, 1);
+        assert_eq!(ticks.len(), 29);
+        assert_eq!(ticks[28].1, "10000000000Ebps");
+    }
+}
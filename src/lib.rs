@@ -4,21 +4,217 @@
 //!
 //! * Parses bandwidth in free form like `2Gbps 340Mbps` or `2.34Gbps`
 //! * Formats bandwidth in similar form `150.024kbps` (default) or `150kbps 24bps` (with feature `display-integer` enabled)
+//! * Converts bandwidths to and from strings with `TryFrom` and `From`, see [`HumanBandwidth`]
+//! * Parses leniently with [`ParseOptions`], e.g. accepting typos like `10Mpbs`, or strictly
+//!   following ISO/IEC 80000-13 like `10 Mbit/s`, or rejecting digit separators, extra rate spans,
+//!   duplicate or unordered units like `1Mbps 2Mbps` and prefixes in the wrong case like `mbps`,
+//!   and reads decimal commas like `15,5Mbps` or grouped digits like `1'000Mbps`
+//! * Accepts legacy spellings of the units like `10meg`, see [`ParseOptions::unit_aliases`]
+//! * Reads the symbol rates of serial lines and modems like `9600baud` or `2.4kBd`, see
+//!   [`ParseOptions::baud`]
+//! * Reads the units which may be in bits or Bytes, like `KBps` or `kb/s`, as either one or rejects
+//!   them, see [`ParseOptions::bit_byte_policy`]
+//! * Reads "unlimited" values too large for a bandwidth as the largest one, see
+//!   [`ParseOptions::saturating`], and rate limits which may be `unlimited` or `none`, see
+//!   [`parse_optional_bandwidth`]
+//! * Decodes the escapes of URLs and HTML forms mangling units like `10Mbit%2Fs`, see
+//!   [`ParseOptions::decode_escapes`]
+//! * Limits the decimals which are read, or rejects the extra ones of high-precision measurements,
+//!   see [`ParseOptions::fraction_digits`] and [`ParseOptions::strict_fraction`]
+//! * Parses bandwidths received in chunks without buffering the whole string (see [`stream`])
+//! * Reports every invalid span of a string at once, see [`ParseOptions::parse_recovering`]
+//! * Validates many strings without allocating on rejection, see [`parse_bandwidth_borrowed`],
+//!   and reads link speeds in firmware without a heap allocator, with the `alloc` feature disabled
+//! * Parses raw bytes from untrusted sources without ever panicking, see [`parse_untrusted`], and
+//!   command line arguments, see [`parse_bandwidth_os`]
+//! * Explains how a string was parsed, span by span (see [`explain`](mod@explain))
+//! * Finds the first bandwidth mentioned in log lines and other free-form text (see [`extract`])
+//! * Lists the plausible readings of ambiguous units like `10mb/s` (see [`ambiguity`])
+//! * Normalizes configuration values into a stable canonical form, and hashes them into identifiers
+//!   stable across architectures (see [`canonical`])
+//! * Summarizes the change between two bandwidths, e.g. `from 10Mbps to 25Mbps (+150%)` (see [`diff`])
+//! * Parses and formats signed deltas of bandwidths like `-1.5Mbps` (see [`signed`])
+//! * Formats for programs or for people with the built-in [`Profile`]s, or for golden files with the
+//!   frozen [`Profile::V1`]
+//! * Fits a bandwidth in a narrow column or display with as many decimals as the width allows, see
+//!   [`fit_to_width`]
+//! * Formats many bandwidths into one buffer, e.g. for CSV exports, see [`format_many_into`]
+//! * Writes and reads decimal or binary prefixes of bits or Bytes, e.g. `Mibit/s` or `MB/s`, see
+//!   [`PrefixBase`] and [`Quantity`]
+//! * Formats columns of bandwidths with a common unit, see [`format_aligned`], and formats and
+//!   parses ranges like `0.5–2Mbps` or `10Mbps-100Mbps` (see [`range`])
+//! * Exposes the unit chosen for a bandwidth to custom number widgets, see [`largest_unit`], and the
+//!   names of every unit for translations, see [`Unit::ALL`]
+//! * Formats sums like `10Mbps + 2.5Mbps + 512kbps = 13.012Mbps`, see [`format_sum`]
+//! * Formats transfer estimates like `about 3m 20s remaining at 12.5MiB/s`, see [`format_eta`]
+//! * Formats bandwidth as approximate phrases like `just under 10Mbps` (see [`approx`])
+//! * Computes labels of logarithmic chart axes and of histogram buckets like `10–100Mbit/s` (see [`labels`])
+//! * Parses rate limit policies like `limit 10Mbit/s burst 2MiB per-ip` (see [`policy`])
+//! * Classifies bandwidths into rate tiers like `<10Mbit/s: slow, _: fast` (see [`tiers`])
+//! * Converts bandwidths into Bytes per simulation tick or token bucket refill without drift (see [`calc`])
+//! * Shares runtime-adjustable limits between threads without locks (see [`atomic`])
+//! * Merges settings from defaults, files, environment and command line, and reports where the
+//!   effective one comes from like `10Mbps (from env RATE_LIMIT)` (see [`merge`])
+//! * Suggests IO chunk sizes for a rate limit, as a [`DataSize`](size::DataSize) (see [`calc::chunk_size_for`])
+//! * Tracks the peak and trough of measured bandwidths over a trailing window, and the rate of Bytes
+//!   counted by many threads (see [`measure`])
+//! * Computes the 95th percentile of measured bandwidths for 95/5 billing (see [`billing`])
+//! * Formats, parses and compares benchmark results like `94.3 ± 2.1 Mbit/s` (see [`stats`])
+//! * Scripts rate changes over time like `0s:1Mbit/s, 60s:unlimited` (see [`schedule`])
+//! * Converts bandwidths to and from integers in a chosen unit with explicit rounding (see [`fixed`])
+//! * Converts bandwidths to and from `f32` Mbps for embedded targets (see [`float`])
+//! * Exports the exact unit multipliers used by the crate and named standard rates like `gigabit` (see [`consts`])
+//! * Parses and formats bandwidths in custom systems of units, e.g. telecom hierarchies (see [`system`])
+//! * Converts telecom carrier rates like `2xE1` or `OC-12` (see [`compat::telecom`])
+//! * Reads and writes the Kubernetes quantities of bandwidth annotations like `500M` or `2Gi` (see
+//!   [`compat::k8s`])
+//! * Reads and writes amounts per minute, hour, day or month like `500GB/h` or `2TB/day`, see
+//!   [`FormatOptions::per`]
+//! * Spreads quotas per month like `1TiB/month` over the actual length of a month (see [`calendar`])
+//! * Suggests the closest supported unit of a misspelled one, like `did you mean "Mbps"?` (see
+//!   [`Error::suggestion`])
+//! * Points at the span of the offending token of an error, e.g. to underline it in a terminal (see
+//!   [`diagnostic`])
+//! * Shows what a setting really means in SI and IEC units of bits and Bytes, with the rounding
+//!   error of each (see [`report`])
+//! * Reads units spelled out like `10 megabits per second` or `4 mebibytes/s`, e.g. in rates
+//!   copied from prose (see [`parse_bandwidth`])
+//! * Remembers the units and the precision a bandwidth was written with, to show it back like
+//!   `1.50MiB/s` in configuration editors (see [`parsed`])
+//! * Tries several syntaxes in order, e.g. SI units then carrier rates like `2xE1`, and reports
+//!   why each one rejected a string (see [`chain`])
+//! * Checks bandwidth literals of the source code at compile time, like `bandwidth!("150Mbps")` (see
+//!   [`literal`])
 //!
 //! ## Features
 //!
+//! * The `std` feature is enabled by default. Without it, the crate is `no_std` and only needs
+//!   `alloc`, keeping the parsers and formatters of both unit systems for embedded targets. The
+//!   modules measuring time or computing with floats ([`billing`], [`measure`], [`approx`],
+//!   [`labels`] and [`stats`]), the functions reading `OsStr` and the `std::error::Error`
+//!   implementations need `std`, and so do the features integrating other crates, except `serde`.
+//!   The [`atomic`] module is only available on targets with 64-bit atomics, which excludes
+//!   `thumbv6m`, `thumbv7m` and `riscv32imc`.
+//! * The `alloc` feature is enabled by `std`. Without it, the crate needs no global allocator and
+//!   keeps the parser whose errors borrow the string, [`parse_bandwidth_borrowed`] and
+//!   [`ParseOptions::parse_borrowed`], the display of [`format_bandwidth`] and the modules
+//!   [`calc`], [`calendar`], [`consts`], [`float`], [`literal`] and [`size`]. Everything returning
+//!   a `String`, a `Vec` or an [`Error`] needs `alloc`, and so does
+//!   [`ParseOptions::decode_escapes`].
 //! * Enable `serde` feature for serde integration.
 //! * Enable `display-integer` feature to display integer values only.
-//! * Enable `binary-system` feature to display in binary prefix system (e.g. `1kiB/s` instead of `8.192kbps`)
+//! * Enable `binary-system` feature to display in binary prefix system (e.g. `1kiB/s` instead of `8.192kbps`),
+//!   and to parse and format binary bit rates like `1Kibit/s`
+//! * Enable `prost` feature for protobuf mapping helpers.
+//! * Enable `utoipa` feature for OpenAPI schemas of the serde wrapper types.
+//! * Enable `web` feature for query string helpers and an axum extractor.
+//! * Enable `fuzz` feature for the harness checking that parsing untrusted input never panics.
+//! * Enable `tokio` feature to push the rates of reloaded configurations to their consumers (see
+//!   [`watch`](mod@watch)).
+//! * Enable `chrono` or `time` feature to give the month of a date of these crates as the calendar
+//!   context of the rates per month (see [`calendar`]).
+//! * Enable `serde_json` or `toml` feature to write bandwidths as nodes of documents of these crates
+//!   (see [`value`](mod@value)).
+//! * Enable `miette` feature to render errors as diagnostics underlining the offending token (see
+//!   [`diagnostic`]).
 
-use std::{error::Error as StdError, fmt, str::Chars};
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+use core::{fmt, str::Chars};
+#[cfg(feature = "std")]
+use std::{error::Error as StdError, ffi::OsStr};
+
+#[cfg(feature = "alloc")]
+pub mod ambiguity;
+#[cfg(feature = "std")]
+pub mod approx;
+#[cfg(target_has_atomic = "64")]
+pub mod atomic;
+#[cfg(feature = "std")]
+pub mod billing;
+#[cfg(all(feature = "alloc", feature = "binary-system"))]
+pub mod binary_bits;
 #[cfg(feature = "binary-system")]
 pub mod binary_system;
+pub mod calc;
+pub mod calendar;
+#[cfg(feature = "alloc")]
+pub mod canonical;
+#[cfg(feature = "alloc")]
+pub mod chain;
+#[cfg(feature = "alloc")]
+pub mod compat;
+pub mod consts;
+#[cfg(feature = "alloc")]
+pub mod diagnostic;
+#[cfg(feature = "alloc")]
+pub mod diff;
+#[cfg(feature = "alloc")]
+mod escapes;
+#[cfg(feature = "alloc")]
+pub mod explain;
+#[cfg(feature = "alloc")]
+pub mod extract;
+#[cfg(feature = "alloc")]
+pub mod fixed;
+pub mod float;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+#[cfg(feature = "std")]
+pub mod labels;
+pub mod literal;
+#[cfg(feature = "std")]
+pub mod measure;
+#[cfg(feature = "alloc")]
+pub mod merge;
 #[cfg(feature = "serde")]
 pub mod option;
+mod options;
+#[cfg(feature = "alloc")]
+pub mod parsed;
+#[cfg(feature = "alloc")]
+pub mod policy;
+#[cfg(feature = "prost")]
+pub mod prost;
+#[cfg(feature = "alloc")]
+pub mod range;
+#[cfg(feature = "alloc")]
+pub mod report;
+#[cfg(feature = "alloc")]
+pub mod schedule;
 #[cfg(feature = "serde")]
 pub mod serde;
+#[cfg(feature = "alloc")]
+pub mod signed;
+pub mod size;
+mod spelled;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "alloc")]
+pub mod stream;
+#[cfg(feature = "alloc")]
+pub mod system;
+#[cfg(feature = "alloc")]
+pub mod tiers;
+#[cfg(feature = "utoipa")]
+pub mod utoipa;
+#[cfg(any(feature = "serde_json", feature = "toml"))]
+pub mod value;
+#[cfg(feature = "tokio")]
+pub mod watch;
+#[cfg(feature = "web")]
+pub mod web;
 
 /// Reexport module
 pub mod re {
@@ -26,11 +222,23 @@ pub mod re {
 }
 
 use bandwidth::Bandwidth;
+use consts::{BITS_PER_BYTE, GIGA};
+use spelled::UnitBuf;
 
-const FRACTION_PART_LIMIT: u32 = 12;
+pub use options::{
+    BitBytePolicy, EnabledUnits, ParseOptions, PrefixBase, Profile, Quantity, UnitPolicy,
+};
+#[cfg(feature = "alloc")]
+pub use options::{FormatOptions, ParseWarning};
 
+/// Most digits of the fractional part which are read, enough for 1bps in
+/// `Ebps`, see [`ParseOptions::fraction_digits`]
+const FRACTION_PART_LIMIT: u32 = 18;
+
+#[cfg(feature = "alloc")]
 /// Error parsing human-friendly bandwidth
 #[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub enum Error {
     /// Invalid character during parsing
     ///
@@ -82,78 +290,737 @@ pub enum Error {
         /// A number associated with the unit
         value: u64,
     },
+    /// Unit in the number is not one of the units of a custom
+    /// [`UnitSystem`](system::UnitSystem)
+    ///
+    /// The two fields are start and end (exclusive) of the slice from
+    /// the original string, containing erroneous value
+    UnknownSystemUnit {
+        /// Start of the invalid unit inside the original string
+        start: usize,
+        /// End of the invalid unit inside the original string
+        end: usize,
+        /// The unit verbatim
+        unit: String,
+        /// A number associated with the unit
+        value: u64,
+        /// The units of the system, separated by commas
+        supported: String,
+    },
     /// The numeric value is too large
     ///
     /// Usually this means value is too large to be useful.
     NumberOverflow,
-    /// The value was an empty string (or consists only whitespace)
+    /// Missing space between a number and its unit
+    ///
+    /// Only reported in ISO/IEC 80000 mode, see [`ParseOptions::iso80000`].
+    ///
+    /// The field is an byte offset of the unit in the string.
+    SpaceExpected(usize),
+    /// The value was an empty string
     Empty,
+    /// The digits of a number are not grouped by three, see
+    /// [`ParseOptions::digit_grouping`]
+    ///
+    /// The field is an byte offset of the separator delimiting the malformed
+    /// group in the string.
+    InvalidDigitGroup(usize),
+    /// A second rate span, while only one is allowed, see
+    /// [`ParseOptions::multiple_spans`]
+    ///
+    /// The field is an byte offset of the span in the string.
+    ExtraSpan(usize),
+    /// The unit of a rate span is the one of a previous span, e.g.
+    /// `1Mbps 2Mbps`, see [`ParseOptions::strict_spans`]
+    DuplicateUnit {
+        /// Start of the unit inside the original string
+        start: usize,
+        /// End of the unit inside the original string
+        end: usize,
+    },
+    /// The unit of a rate span is larger than the one of the previous span,
+    /// e.g. `5kbps 1Gbps`, see [`ParseOptions::strict_spans`]
+    UnorderedUnit {
+        /// Start of the unit inside the original string
+        start: usize,
+        /// End of the unit inside the original string
+        end: usize,
+    },
+    /// Missing whitespace between two rate spans, e.g. `1Mbps2kbps`, see
+    /// [`ParseOptions::strict_spans`]
+    ///
+    /// The field is an byte offset of the second span in the string.
+    SeparatorExpected(usize),
+    /// A fractional part with more digits than read, see
+    /// [`ParseOptions::strict_fraction`]
+    ///
+    /// The field is an byte offset of the first digit beyond the limit in the
+    /// string.
+    TooManyDecimals(usize),
+    /// The value consisted only of whitespace, e.g. an unset variable in a
+    /// template
+    OnlyWhitespace {
+        /// The length of the value in bytes
+        len: usize,
+    },
+    /// A unit whose quantity is a lone `b` or `B`, which may be bits or
+    /// Bytes, e.g. `10Mbps`, see [`BitBytePolicy::Reject`]
+    AmbiguousUnit {
+        /// Start of the unit inside the original string
+        start: usize,
+        /// End of the unit inside the original string
+        end: usize,
+    },
+}
+
+#[cfg(feature = "alloc")]
+impl Error {
+    /// The supported unit closest to an unknown unit, if any is close enough
+    ///
+    /// The unit of [`Error::UnknownUnit`], [`Error::UnknownBinaryUnit`] and
+    /// [`Error::UnknownSystemUnit`] is compared to the units the error lists
+    /// as supported, and the suggestion replaces the list in the message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use human_bandwidth::parse_bandwidth;
+    ///
+    /// let err = parse_bandwidth("10Mpbs").unwrap_err();
+    /// assert_eq!(err.suggestion(), Some("Mbps"));
+    /// assert_eq!(err.to_string(), r#"unknown bandwidth unit "Mpbs", did you mean "Mbps"?"#);
+    /// assert_eq!(parse_bandwidth("10fast").unwrap_err().suggestion(), None);
+    /// ```
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            Error::UnknownUnit { unit, .. } => closest_si_unit(unit),
+            #[cfg(feature = "binary-system")]
+            Error::UnknownBinaryUnit { unit, .. } => closest_binary_unit(unit),
+            Error::UnknownSystemUnit {
+                unit, supported, ..
+            } => closest_unit(unit, supported.split(", ")),
+            _ => None,
+        }
+    }
+
+    /// Attaches the string the error was reported on, to point at the span of
+    /// the offending token (see [`diagnostic`])
+    pub fn with_input(self, input: &str) -> diagnostic::SpannedError {
+        diagnostic::SpannedError::new(input, self)
+    }
+
+    /// The error borrowing its unit, the inverse of [`ParseError::into_owned`]
+    ///
+    /// `None` for [`Error::UnknownSystemUnit`], whose units are not the ones
+    /// of the parser.
+    fn borrowed(&self) -> Option<ParseError<'_>> {
+        Some(match *self {
+            Error::InvalidCharacter(offset) => ParseError::InvalidCharacter(offset),
+            Error::NumberExpected(offset) => ParseError::NumberExpected(offset),
+            Error::UnknownUnit {
+                start,
+                end,
+                ref unit,
+                value,
+            } => ParseError::UnknownUnit {
+                start,
+                end,
+                unit,
+                value,
+            },
+            #[cfg(feature = "binary-system")]
+            Error::UnknownBinaryUnit {
+                start,
+                end,
+                ref unit,
+                value,
+            } => ParseError::UnknownBinaryUnit {
+                start,
+                end,
+                unit,
+                value,
+            },
+            Error::UnknownSystemUnit { .. } => return None,
+            Error::NumberOverflow => ParseError::NumberOverflow,
+            Error::SpaceExpected(offset) => ParseError::SpaceExpected(offset),
+            Error::Empty => ParseError::Empty,
+            Error::InvalidDigitGroup(offset) => ParseError::InvalidDigitGroup(offset),
+            Error::ExtraSpan(offset) => ParseError::ExtraSpan(offset),
+            Error::DuplicateUnit { start, end } => ParseError::DuplicateUnit { start, end },
+            Error::UnorderedUnit { start, end } => ParseError::UnorderedUnit { start, end },
+            Error::SeparatorExpected(offset) => ParseError::SeparatorExpected(offset),
+            Error::TooManyDecimals(offset) => ParseError::TooManyDecimals(offset),
+            Error::OnlyWhitespace { len } => ParseError::OnlyWhitespace { len },
+            Error::AmbiguousUnit { start, end } => ParseError::AmbiguousUnit { start, end },
+        })
+    }
 }
 
+#[cfg(feature = "std")]
 impl StdError for Error {}
 
+#[cfg(feature = "alloc")]
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::InvalidCharacter(offset) => write!(f, "invalid character at {}", offset),
-            Error::NumberExpected(offset) => write!(f, "expected number at {}", offset),
-            Error::UnknownUnit { unit, value, .. } if unit.is_empty() => {
-                write!(
-                    f,
-                    "bandwidth unit needed, for example {0}Mbps or {0}bps",
-                    value,
-                )
+            Error::UnknownSystemUnit {
+                unit, supported, ..
+            } if unit.is_empty() => {
+                write!(f, "bandwidth unit needed, supported units: {}", supported)
             }
-            Error::UnknownUnit { unit, .. } => {
-                write!(
+            Error::UnknownSystemUnit {
+                unit, supported, ..
+            } => match self.suggestion() {
+                Some(suggestion) => write!(
                     f,
-                    "unknown bandwidth unit {:?}, \
-                    supported units: bps, kbps, Mbps, Gbps, Tbps",
-                    unit
-                )
-            }
-            #[cfg(feature = "binary-system")]
-            Error::UnknownBinaryUnit { unit, value, .. } if unit.is_empty() => {
-                write!(
+                    "unknown bandwidth unit {:?}, did you mean {:?}?",
+                    unit, suggestion
+                ),
+                None => write!(
                     f,
-                    "binary bandwidth unit needed, for example {0}MiB/s or {0}B/s",
-                    value,
-                )
-            }
+                    "unknown bandwidth unit {:?}, supported units: {}",
+                    unit, supported
+                ),
+            },
+            // The other variants are written like the borrowed ones
+            err => match err.borrowed() {
+                Some(err) => fmt::Display::fmt(&err, f),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+fn fmt_unknown_unit(f: &mut fmt::Formatter<'_>, unit: &str, value: u64) -> fmt::Result {
+    if unit.is_empty() {
+        write!(
+            f,
+            "bandwidth unit needed, for example {0}Mbps or {0}bps",
+            value,
+        )
+    } else if let Some(suggestion) = closest_si_unit(unit) {
+        write!(
+            f,
+            "unknown bandwidth unit {:?}, did you mean {:?}?",
+            unit, suggestion
+        )
+    } else {
+        write!(f, "unknown bandwidth unit {:?}, supported units: ", unit)?;
+        write_names(f, Unit::ALL.iter().map(|unit| unit.name()))
+    }
+}
+
+#[cfg(feature = "binary-system")]
+fn fmt_unknown_binary_unit(f: &mut fmt::Formatter<'_>, unit: &str, value: u64) -> fmt::Result {
+    if unit.is_empty() {
+        write!(
+            f,
+            "binary bandwidth unit needed, for example {0}MiB/s or {0}B/s",
+            value,
+        )
+    } else if let Some(suggestion) = closest_binary_unit(unit) {
+        write!(
+            f,
+            "unknown binary bandwidth unit {:?}, did you mean {:?}?",
+            unit, suggestion
+        )
+    } else {
+        write!(
+            f,
+            "unknown binary bandwidth unit {:?}, supported units: ",
+            unit
+        )?;
+        write_names(
+            f,
+            binary_system::BinaryUnit::ALL
+                .iter()
+                .map(|unit| unit.name()),
+        )
+    }
+}
+
+/// Writes the names of units separated by commas
+fn write_names<'a>(
+    f: &mut fmt::Formatter<'_>,
+    names: impl IntoIterator<Item = &'a str>,
+) -> fmt::Result {
+    for (i, name) in names.into_iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        f.write_str(name)?;
+    }
+    Ok(())
+}
+
+/// Error parsing human-friendly bandwidth, borrowing from the parsed string
+///
+/// Returned by [`parse_bandwidth_borrowed`] and [`ParseOptions::parse_borrowed`],
+/// which don't allocate when rejecting a string, e.g. when validating many
+/// values. It is the only error without the `alloc` feature, on targets
+/// without a heap allocator, and its message is written without allocating
+/// either. The variants are the ones of [`Error`] reported by
+/// [`parse_bandwidth`] and `parse_binary_bandwidth`, and
+/// [`ParseError::into_owned`] converts to it. A `ParseError` only holds
+/// offsets and slices of the parsed string, so it is `Copy`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum ParseError<'src> {
+    /// Invalid character during parsing, see [`Error::InvalidCharacter`]
+    InvalidCharacter(usize),
+    /// Non-numeric value where number is expected, see [`Error::NumberExpected`]
+    NumberExpected(usize),
+    /// Unit in the number is not one of allowed units, see [`Error::UnknownUnit`]
+    UnknownUnit {
+        /// Start of the invalid unit inside the original string
+        start: usize,
+        /// End of the invalid unit inside the original string
+        end: usize,
+        /// The unit verbatim, borrowed from the original string
+        unit: &'src str,
+        /// A number associated with the unit
+        value: u64,
+    },
+    /// Unit in the number is not one of the binary units, see
+    /// [`Error::UnknownBinaryUnit`]
+    #[cfg(feature = "binary-system")]
+    UnknownBinaryUnit {
+        /// Start of the invalid unit inside the original string
+        start: usize,
+        /// End of the invalid unit inside the original string
+        end: usize,
+        /// The unit verbatim, borrowed from the original string
+        unit: &'src str,
+        /// A number associated with the unit
+        value: u64,
+    },
+    /// The numeric value is too large
+    NumberOverflow,
+    /// Missing space between a number and its unit, see [`Error::SpaceExpected`]
+    SpaceExpected(usize),
+    /// The value was an empty string
+    Empty,
+    /// The digits of a number are not grouped by three, see
+    /// [`Error::InvalidDigitGroup`]
+    InvalidDigitGroup(usize),
+    /// A second rate span, while only one is allowed, see [`Error::ExtraSpan`]
+    ExtraSpan(usize),
+    /// The unit of a rate span is the one of a previous span, see
+    /// [`Error::DuplicateUnit`]
+    DuplicateUnit {
+        /// Start of the unit inside the original string
+        start: usize,
+        /// End of the unit inside the original string
+        end: usize,
+    },
+    /// The unit of a rate span is larger than the one of the previous span,
+    /// see [`Error::UnorderedUnit`]
+    UnorderedUnit {
+        /// Start of the unit inside the original string
+        start: usize,
+        /// End of the unit inside the original string
+        end: usize,
+    },
+    /// Missing whitespace between two rate spans, see
+    /// [`Error::SeparatorExpected`]
+    SeparatorExpected(usize),
+    /// A fractional part with more digits than read, see
+    /// [`Error::TooManyDecimals`]
+    TooManyDecimals(usize),
+    /// The value consisted only of whitespace
+    OnlyWhitespace {
+        /// The length of the value in bytes
+        len: usize,
+    },
+    /// A unit which may be in bits or Bytes, see [`Error::AmbiguousUnit`]
+    AmbiguousUnit {
+        /// Start of the unit inside the original string
+        start: usize,
+        /// End of the unit inside the original string
+        end: usize,
+    },
+}
+
+impl ParseError<'_> {
+    /// The unit closest to an unknown unit, see [`Error::suggestion`]
+    pub fn suggestion(&self) -> Option<&'static str> {
+        match *self {
+            ParseError::UnknownUnit { unit, .. } => closest_si_unit(unit),
+            #[cfg(feature = "binary-system")]
+            ParseError::UnknownBinaryUnit { unit, .. } => closest_binary_unit(unit),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Converts into the owned [`Error`], copying the unit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use human_bandwidth::{parse_bandwidth, parse_bandwidth_borrowed};
+    ///
+    /// let err = parse_bandwidth_borrowed("10Mbsp").unwrap_err();
+    /// assert_eq!(err.into_owned(), parse_bandwidth("10Mbsp").unwrap_err());
+    /// ```
+    pub fn into_owned(self) -> Error {
+        match self {
+            ParseError::InvalidCharacter(offset) => Error::InvalidCharacter(offset),
+            ParseError::NumberExpected(offset) => Error::NumberExpected(offset),
+            ParseError::UnknownUnit {
+                start,
+                end,
+                unit,
+                value,
+            } => Error::UnknownUnit {
+                start,
+                end,
+                unit: unit.to_string(),
+                value,
+            },
+            #[cfg(feature = "binary-system")]
+            ParseError::UnknownBinaryUnit {
+                start,
+                end,
+                unit,
+                value,
+            } => Error::UnknownBinaryUnit {
+                start,
+                end,
+                unit: unit.to_string(),
+                value,
+            },
+            ParseError::NumberOverflow => Error::NumberOverflow,
+            ParseError::SpaceExpected(offset) => Error::SpaceExpected(offset),
+            ParseError::Empty => Error::Empty,
+            ParseError::InvalidDigitGroup(offset) => Error::InvalidDigitGroup(offset),
+            ParseError::ExtraSpan(offset) => Error::ExtraSpan(offset),
+            ParseError::DuplicateUnit { start, end } => Error::DuplicateUnit { start, end },
+            ParseError::UnorderedUnit { start, end } => Error::UnorderedUnit { start, end },
+            ParseError::SeparatorExpected(offset) => Error::SeparatorExpected(offset),
+            ParseError::TooManyDecimals(offset) => Error::TooManyDecimals(offset),
+            ParseError::OnlyWhitespace { len } => Error::OnlyWhitespace { len },
+            ParseError::AmbiguousUnit { start, end } => Error::AmbiguousUnit { start, end },
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ParseError<'_>> for Error {
+    fn from(err: ParseError<'_>) -> Self {
+        err.into_owned()
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for ParseError<'_> {}
+
+impl fmt::Display for ParseError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ParseError::InvalidCharacter(offset) => write!(f, "invalid character at {}", offset),
+            ParseError::NumberExpected(offset) => write!(f, "expected number at {}", offset),
+            ParseError::UnknownUnit { unit, value, .. } => fmt_unknown_unit(f, unit, value),
             #[cfg(feature = "binary-system")]
-            Error::UnknownBinaryUnit { unit, .. } => {
+            ParseError::UnknownBinaryUnit { unit, value, .. } => {
+                fmt_unknown_binary_unit(f, unit, value)
+            }
+            ParseError::NumberOverflow => write!(f, "number is too large"),
+            ParseError::SpaceExpected(offset) => {
+                write!(f, "expected space before unit at {}", offset)
+            }
+            ParseError::Empty => write!(f, "expected a bandwidth, got an empty string"),
+            ParseError::InvalidDigitGroup(offset) => {
+                write!(f, "invalid digit group at {}", offset)
+            }
+            ParseError::ExtraSpan(offset) => {
+                write!(f, "expected a single rate span, got another at {}", offset)
+            }
+            ParseError::DuplicateUnit { start, end } => {
                 write!(
                     f,
-                    "unknown binary bandwidth unit {:?}, \
-                    supported units: B/s, kiB/s, MiB/s, GiB/s, TiB/s",
-                    unit
+                    "unit at {}..{} is used by a previous rate span",
+                    start, end
                 )
             }
-            Error::NumberOverflow => write!(f, "number is too large"),
-            Error::Empty => write!(f, "value was empty"),
+            ParseError::UnorderedUnit { start, end } => write!(
+                f,
+                "unit at {}..{} is larger than the one of the previous rate span",
+                start, end
+            ),
+            ParseError::SeparatorExpected(offset) => {
+                write!(f, "expected whitespace before the rate span at {}", offset)
+            }
+            ParseError::TooManyDecimals(offset) => {
+                write!(f, "too many decimals, the digit at {} is not read", offset)
+            }
+            ParseError::OnlyWhitespace { len } => write!(
+                f,
+                "expected a bandwidth, got only whitespace ({} bytes)",
+                len
+            ),
+            ParseError::AmbiguousUnit { start, end } => write!(
+                f,
+                "unit at {}..{} may be in bits or Bytes, write \"bit\" or \"Byte\" in full",
+                start, end
+            ),
+        }
+    }
+}
+
+/// The denominator of a per-interval unit, e.g. `/h` in `4GiB/h`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Interval {
+    /// Per second, the denominator of the usual units
+    #[default]
+    Second,
+    /// Per minute, written `/min`
+    Minute,
+    /// Per hour, written `/h`
+    Hour,
+    /// Per day, written `/d`, and also read as `/day`
+    Day,
+    /// Per month, written `/month`
+    ///
+    /// A month is 30 days, unless the parser is given its actual length, see
+    /// [`ParseOptions::month_days`].
+    Month,
+}
+
+impl Interval {
+    /// Length of the interval in seconds
+    pub const fn seconds(self) -> u64 {
+        match self {
+            Interval::Second => 1,
+            Interval::Minute => 60,
+            Interval::Hour => 3_600,
+            Interval::Day => 86_400,
+            Interval::Month => 2_592_000,
+        }
+    }
+
+    /// Denominator of the units per interval
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            Interval::Second => "/s",
+            Interval::Minute => "/min",
+            Interval::Hour => "/h",
+            Interval::Day => "/d",
+            Interval::Month => "/month",
         }
     }
 }
 
 /// A wrapper type that allows you to Display a Bandwidth
-#[derive(Debug, Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct FormattedBandwidth(Bandwidth);
 
-trait OverflowOp: Sized {
-    fn mul(self, other: Self) -> Result<Self, Error>;
-    fn add(self, other: Self) -> Result<Self, Error>;
+/// Total amount of bits per second in a bandwidth
+pub(crate) fn bandwidth_as_bps(val: Bandwidth) -> u128 {
+    val.as_gbps() as u128 * GIGA as u128 + val.subgbps_bps() as u128
+}
+
+/// Build a bandwidth from a total amount of bits per second
+#[cfg(feature = "alloc")]
+pub(crate) fn bandwidth_from_bps(bps: u128) -> Result<Bandwidth, Error> {
+    checked_bandwidth(bps).ok_or(Error::NumberOverflow)
+}
+
+/// Build a bandwidth from a total amount of bits per second, if it is not
+/// too large
+fn checked_bandwidth(bps: u128) -> Option<Bandwidth> {
+    let gbps = u64::try_from(bps / GIGA as u128).ok()?;
+    Some(Bandwidth::new(gbps, (bps % GIGA as u128) as u32))
+}
+
+#[cfg(feature = "alloc")]
+/// Moves the offsets of an error by `start` bytes, for an error in a slice
+/// starting at `start`
+pub(crate) fn offset_error(error: Error, start: usize) -> Error {
+    map_offsets(error, |at| start + at)
+}
+
+#[cfg(feature = "alloc")]
+/// Maps the offsets of an error with `offset`, e.g. to the ones of the string
+/// a slice comes from
+pub(crate) fn map_offsets(error: Error, offset: impl Fn(usize) -> usize) -> Error {
+    match error {
+        Error::InvalidCharacter(at) => Error::InvalidCharacter(offset(at)),
+        Error::NumberExpected(at) => Error::NumberExpected(offset(at)),
+        Error::SpaceExpected(at) => Error::SpaceExpected(offset(at)),
+        Error::InvalidDigitGroup(at) => Error::InvalidDigitGroup(offset(at)),
+        Error::ExtraSpan(at) => Error::ExtraSpan(offset(at)),
+        Error::SeparatorExpected(at) => Error::SeparatorExpected(offset(at)),
+        Error::TooManyDecimals(at) => Error::TooManyDecimals(offset(at)),
+        Error::DuplicateUnit { start, end } => Error::DuplicateUnit {
+            start: offset(start),
+            end: offset(end),
+        },
+        Error::UnorderedUnit { start, end } => Error::UnorderedUnit {
+            start: offset(start),
+            end: offset(end),
+        },
+        Error::AmbiguousUnit { start, end } => Error::AmbiguousUnit {
+            start: offset(start),
+            end: offset(end),
+        },
+        Error::UnknownUnit {
+            start,
+            end,
+            unit,
+            value,
+        } => Error::UnknownUnit {
+            start: offset(start),
+            end: offset(end),
+            unit,
+            value,
+        },
+        #[cfg(feature = "binary-system")]
+        Error::UnknownBinaryUnit {
+            start,
+            end,
+            unit,
+            value,
+        } => Error::UnknownBinaryUnit {
+            start: offset(start),
+            end: offset(end),
+            unit,
+            value,
+        },
+        Error::UnknownSystemUnit {
+            start,
+            end,
+            unit,
+            value,
+            supported,
+        } => Error::UnknownSystemUnit {
+            start: offset(start),
+            end: offset(end),
+            unit,
+            value,
+            supported,
+        },
+        error => error,
+    }
+}
+
+/// Largest exponent read, any number with a larger one overflows or is zero
+const EXPONENT_LIMIT: i32 = 1_000;
+
+/// Most decimals read past [`FRACTION_PART_LIMIT`] in case an exponent
+/// follows, e.g. the 27 ones of `18.446744073709551615999999999e27bps`
+const EXTRA_DECIMALS_LIMIT: u32 = 20;
+
+/// Multiplies a number by `10^exponent`, keeping at most `limit` digits of
+/// the fractional part, or `None` if it overflows
+///
+/// The `extra` decimals past the fractional part and their number are
+/// appended to it as long as the digits fit in an `u128`, like the integer
+/// part, spans being only limited by the range of a bandwidth.
+fn scale_exponent(
+    n: u128,
+    fraction: u64,
+    fraction_cnt: u32,
+    extra: (u128, u32),
+    exponent: i32,
+    limit: u32,
+) -> Option<(u128, u64, u32)> {
+    // The digits of the number without its decimal point
+    let mut digits = n
+        .checked_mul(10u128.pow(fraction_cnt))?
+        .checked_add(u128::from(fraction))?;
+    let mut fraction_cnt = fraction_cnt;
+    let (mut more, mut more_cnt) = extra;
+    while more_cnt > 0 {
+        let appended = digits
+            .checked_mul(10u128.pow(more_cnt))
+            .and_then(|digits| digits.checked_add(more));
+        if let Some(appended) = appended {
+            digits = appended;
+            fraction_cnt += more_cnt;
+            break;
+        }
+        // The last decimal is ignored
+        more /= 10;
+        more_cnt -= 1;
+    }
+    let scale = fraction_cnt as i32 - exponent;
+    if scale <= 0 {
+        if digits == 0 {
+            return Some((0, 0, 0));
+        }
+        let n = 10u128
+            .checked_pow(scale.unsigned_abs())
+            .and_then(|multiplier| digits.checked_mul(multiplier))?;
+        return Some((n, 0, 0));
+    }
+    let divisor = match 10u128.checked_pow(scale as u32) {
+        Some(divisor) => divisor,
+        None => return Some((0, 0, 0)),
+    };
+    let mut fraction = digits % divisor;
+    let mut fraction_cnt = scale as u32;
+    if fraction_cnt > limit {
+        // The following digits are ignored
+        fraction /= 10u128.pow(fraction_cnt - limit);
+        fraction_cnt = limit;
+    }
+    Some((digits / divisor, fraction as u64, fraction_cnt))
 }
 
-impl OverflowOp for u64 {
-    fn mul(self, other: Self) -> Result<Self, Error> {
-        self.checked_mul(other).ok_or(Error::NumberOverflow)
+/// The number of a span for the `value` of an error, `u64::MAX` if it is
+/// larger
+pub(crate) fn saturating_value(n: u128) -> u64 {
+    u64::try_from(n).unwrap_or(u64::MAX)
+}
+
+/// The digit groups of the integer part of a number, e.g. `1,000,000`
+///
+/// The first group has one to three digits and the following ones exactly
+/// three, all separated by the same character.
+struct DigitGroups {
+    /// The last separator and its offset
+    separator: Option<(char, usize)>,
+    /// The number of digits since the last separator
+    len: u32,
+}
+
+impl DigitGroups {
+    /// The groups of a number whose first digit is read
+    fn new() -> Self {
+        DigitGroups {
+            separator: None,
+            len: 1,
+        }
+    }
+
+    fn digit(&mut self) {
+        self.len += 1;
     }
-    fn add(self, other: Self) -> Result<Self, Error> {
-        self.checked_add(other).ok_or(Error::NumberOverflow)
+
+    fn separator(&mut self, c: char, at: usize) -> Result<(), ParseError<'static>> {
+        let well_formed = match self.separator {
+            None => self.len <= 3,
+            Some((separator, _)) => separator == c && self.len == 3,
+        };
+        if !well_formed {
+            return Err(ParseError::InvalidDigitGroup(at));
+        }
+        self.separator = Some((c, at));
+        self.len = 0;
+        Ok(())
+    }
+
+    /// Checks the last group, at the end of the integer part
+    fn end(&self) -> Result<(), ParseError<'static>> {
+        match self.separator {
+            Some((_, at)) if self.len != 3 => Err(ParseError::InvalidDigitGroup(at)),
+            _ => Ok(()),
+        }
     }
 }
 
-fn parse_fraction(fraction: u64, fraction_cnt: u32, need_digit: u32) -> u64 {
+const fn parse_fraction(fraction: u64, fraction_cnt: u32, need_digit: u32) -> u64 {
     if need_digit >= fraction_cnt {
         fraction * 10u64.pow(need_digit - fraction_cnt)
     } else {
@@ -161,231 +1028,1594 @@ fn parse_fraction(fraction: u64, fraction_cnt: u32, need_digit: u32) -> u64 {
     }
 }
 
-struct Parser<'a> {
-    iter: Chars<'a>,
-    src: &'a str,
-    current: Bandwidth,
+/// Exponent of ten of a SI unit spelling, e.g. `6` for `Mbps`
+fn si_unit(unit: &str) -> Option<u32> {
+    match unit {
+        "bps" | "bit/s" | "b/s" => Some(0),
+        "kbps" | "Kbps" | "kbit/s" | "Kbit/s" | "kb/s" | "Kb/s" => Some(3),
+        "Mbps" | "mbps" | "Mbit/s" | "mbit/s" | "Mb/s" | "mb/s" => Some(6),
+        "Gbps" | "gbps" | "Gbit/s" | "gbit/s" | "Gb/s" | "gb/s" => Some(9),
+        "Tbps" | "tbps" | "Tbit/s" | "tbit/s" | "Tb/s" | "tb/s" => Some(12),
+        "Pbps" | "pbps" | "Pbit/s" | "pbit/s" | "Pb/s" | "pb/s" => Some(15),
+        "Ebps" | "ebps" | "Ebit/s" | "ebit/s" | "Eb/s" | "eb/s" => Some(18),
+        _ => None,
+    }
 }
 
-impl<'a> Parser<'a> {
-    fn new<'b: 'a>(s: &'b str) -> Self {
-        Parser {
-            iter: s.chars(),
-            src: s,
-            current: Bandwidth::new(0, 0),
-        }
-    }
+/// Splits a unit per minute, hour, day or month into the unit per second
+/// without its `/s` and the interval, e.g. `("GB", Interval::Hour)` for
+/// `GB/h`
+pub(crate) fn split_interval(unit: &str) -> Option<(&str, Interval)> {
+    const SUFFIXES: [(&str, Interval); 5] = [
+        ("/min", Interval::Minute),
+        ("/h", Interval::Hour),
+        ("/d", Interval::Day),
+        ("/day", Interval::Day),
+        ("/month", Interval::Month),
+    ];
+    SUFFIXES
+        .iter()
+        .find_map(|&(suffix, interval)| Some((unit.strip_suffix(suffix)?, interval)))
 }
 
-impl Parser<'_> {
-    fn off(&self) -> usize {
-        self.src.len() - self.iter.as_str().len()
-    }
+#[cfg(feature = "alloc")]
+/// Canonical spelling of a SI unit of bits from its exponent of ten and
+/// interval, e.g. `Mbit/h`
+fn si_interval_unit_name(exponent: u32, interval: Interval) -> &'static str {
+    const NAMES: [[&str; 5]; 7] = [
+        ["bps", "bit/min", "bit/h", "bit/d", "bit/month"],
+        ["kbps", "kbit/min", "kbit/h", "kbit/d", "kbit/month"],
+        ["Mbps", "Mbit/min", "Mbit/h", "Mbit/d", "Mbit/month"],
+        ["Gbps", "Gbit/min", "Gbit/h", "Gbit/d", "Gbit/month"],
+        ["Tbps", "Tbit/min", "Tbit/h", "Tbit/d", "Tbit/month"],
+        ["Pbps", "Pbit/min", "Pbit/h", "Pbit/d", "Pbit/month"],
+        ["Ebps", "Ebit/min", "Ebit/h", "Ebit/d", "Ebit/month"],
+    ];
+    NAMES[(exponent / 3).min(6) as usize][interval as usize]
+}
 
-    fn parse_first_char(&mut self) -> Result<Option<u64>, Error> {
-        let off = self.off();
-        for c in self.iter.by_ref() {
-            match c {
-                '0'..='9' => {
-                    return Ok(Some(c as u64 - '0' as u64));
-                }
-                c if c.is_whitespace() => continue,
+#[cfg(feature = "alloc")]
+/// Canonical spelling of a SI unit from its exponent of ten
+fn si_unit_name(exponent: u32) -> &'static str {
+    Unit::from_exponent(exponent).name()
+}
+
+/// Exponent of ten of an ISO/IEC 80000-13 unit and whether it is in Bytes
+fn iso_unit(unit: &str) -> Option<(u32, bool)> {
+    let (prefix, bytes) = if let Some(prefix) = unit.strip_suffix("bit/s") {
+        (prefix, false)
+    } else {
+        (unit.strip_suffix("B/s")?, true)
+    };
+    let exponent = match prefix {
+        "" => 0,
+        "k" => 3,
+        "M" => 6,
+        "G" => 9,
+        "T" => 12,
+        "P" => 15,
+        "E" => 18,
+        _ => return None,
+    };
+    Some((exponent, bytes))
+}
+
+#[cfg(feature = "alloc")]
+/// ISO/IEC 80000-13 symbol of a SI unit of bits from its exponent of ten
+fn iso_unit_name(exponent: u32) -> &'static str {
+    Unit::from_exponent(exponent).iso_name()
+}
+
+/// Closest SI unit of a misspelled unit, if it is close enough and not ambiguous
+///
+/// The comparison is case insensitive, allows one edit for units up to 4
+/// characters and two edits for longer ones. The units spelled in Bytes are
+/// never assumed, a unit of bits being 8 times smaller, and neither are the
+/// units of the other systems, see [`is_other_system_unit`].
+fn fuzzy_si_unit(unit: &str) -> Option<u32> {
+    const SPELLINGS: [&str; 3] = ["bps", "bit/s", "b/s"];
+
+    if is_byte_spelling(unit) || is_other_system_unit(unit) {
+        return None;
+    }
+    let unit = Folded::new([unit])?;
+    let max_distance = if unit.len <= 4 { 1 } else { 2 };
+    let mut best: Option<(usize, u32)> = None;
+    let mut ambiguous = false;
+    for candidate in Unit::ALL {
+        let exponent = candidate.exponent();
+        let prefix = candidate.name().trim_end_matches("bps");
+        for spelling in SPELLINGS {
+            let spelled = Folded::new([prefix, spelling])?;
+            let distance = edit_distance(unit.as_slice(), spelled.as_slice());
+            match best {
+                Some((d, _)) if d < distance => {}
+                Some((d, e)) if d == distance => ambiguous |= e != exponent,
                 _ => {
-                    return Err(Error::NumberExpected(off));
+                    best = Some((distance, exponent));
+                    ambiguous = false;
                 }
             }
         }
-        Ok(None)
     }
+    match best {
+        Some((distance, exponent)) if distance <= max_distance && !ambiguous => Some(exponent),
+        _ => None,
+    }
+}
 
-    fn parse_unit(
-        &mut self,
-        n: u64,
-        fraction: u64,
-        fraction_cnt: u32,
-        start: usize,
-        end: usize,
-    ) -> Result<(), Error> {
-        let (gbps, bps) = match &self.src[start..end] {
-            "bps" | "bit/s" | "b/s" => (0u64, n),
-            "kbps" | "Kbps" | "kbit/s" | "Kbit/s" | "kb/s" | "Kb/s" => (
-                0u64,
-                n.mul(1000)?
-                    .add(parse_fraction(fraction, fraction_cnt, 3))?,
-            ),
-            "Mbps" | "mbps" | "Mbit/s" | "mbit/s" | "Mb/s" | "mb/s" => (
-                0u64,
-                n.mul(1_000_000)?
-                    .add(parse_fraction(fraction, fraction_cnt, 6))?,
-            ),
-            "Gbps" | "gbps" | "Gbit/s" | "gbit/s" | "Gb/s" | "gb/s" => {
-                (n, parse_fraction(fraction, fraction_cnt, 9))
-            }
-            "Tbps" | "tbps" | "Tbit/s" | "tbit/s" | "Tb/s" | "tb/s" => {
-                let bps = parse_fraction(fraction, fraction_cnt, 12);
-                (n.mul(1000)?.add(bps / 1_000_000_000)?, bps % 1_000_000_000)
-            }
+/// Whether a unit is spelled in Bytes, with `byte` in any case or a `B`
+/// other than the one of `Bit`, e.g. `MB/s`, `KBps` or `Gbyte/s`
+fn is_byte_spelling(unit: &str) -> bool {
+    let is_bit = |rest: &str| {
+        rest.get(..2)
+            .is_some_and(|it| it.eq_ignore_ascii_case("it"))
+    };
+    unit.as_bytes()
+        .windows(4)
+        .any(|it| it.eq_ignore_ascii_case(b"byte"))
+        || unit
+            .match_indices('B')
+            .any(|(i, _)| !is_bit(&unit[i + 1..]))
+}
+
+/// Whether a unit is spelled exactly like one of the other systems of units
+/// of the crate, e.g. the binary `Mibps`, `Tib/s` or `KiB/s` or the baud
+/// `kBd`, which are worth 2.4% to 15% more than the closest SI unit of bits
+fn is_other_system_unit(unit: &str) -> bool {
+    const BINARY_PREFIXES: [&str; 12] = [
+        "Ki", "ki", "Mi", "mi", "Gi", "gi", "Ti", "ti", "Pi", "pi", "Ei", "ei",
+    ];
+    const SPELLINGS: [&str; 8] = ["bit/s", "bps", "b/s", "B/s", "Bps", "Byte/s", "o/s", "ops"];
+
+    let binary = SPELLINGS.iter().any(|spelling| {
+        unit.strip_suffix(spelling)
+            .is_some_and(|prefix| BINARY_PREFIXES.contains(&prefix))
+    });
+    let quadrants = EnabledUnits::ALL.binary_prefixes(true).bytes(true);
+    binary
+        || options::quadrant_unit(unit, false, quadrants).is_some()
+        || options::baud_unit(unit).is_some()
+}
+
+/// Closest SI unit name of an unknown unit, see [`closest_unit`]
+fn closest_si_unit(unit: &str) -> Option<&'static str> {
+    closest_unit(unit, Unit::ALL.iter().map(|unit| unit.name()))
+}
+
+/// Closest binary unit name of an unknown unit, see [`closest_unit`]
+#[cfg(feature = "binary-system")]
+fn closest_binary_unit(unit: &str) -> Option<&'static str> {
+    closest_unit(
+        unit,
+        binary_system::BinaryUnit::ALL
+            .iter()
+            .map(|unit| unit.name()),
+    )
+}
+
+/// Closest of `candidates` to an unknown unit, if it is close enough and not
+/// ambiguous
+///
+/// The comparison is case insensitive, allows one edit for units up to 4
+/// characters and two edits for longer ones, like [`fuzzy_si_unit`], but
+/// keeps at least one character of the unit.
+fn closest_unit<'a>(unit: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    if unit.is_empty() {
+        return None;
+    }
+    let folded = Folded::new([unit])?;
+    let len = folded.len;
+    let max_distance = if len <= 4 { 1 } else { 2 };
+    let mut best: Option<(usize, &str)> = None;
+    let mut ambiguous = false;
+    for candidate in candidates {
+        let Some(spelled) = Folded::new([candidate]) else {
+            continue;
+        };
+        let distance = edit_distance(folded.as_slice(), spelled.as_slice());
+        match best {
+            Some((d, _)) if d < distance => {}
+            Some((d, c)) if d == distance => ambiguous |= c != candidate,
             _ => {
-                return Err(Error::UnknownUnit {
-                    start,
-                    end,
-                    unit: self.src[start..end].to_string(),
-                    value: n,
-                });
+                best = Some((distance, candidate));
+                ambiguous = false;
             }
+        }
+    }
+    match best {
+        Some((distance, candidate)) if distance <= max_distance && distance < len && !ambiguous => {
+            Some(candidate)
+        }
+        _ => None,
+    }
+}
+
+/// Most characters of the units compared by [`edit_distance`], far more than
+/// the longest supported unit plus the allowed edits
+const FOLDED_LIMIT: usize = 32;
+
+/// The lowercase characters of a unit, compared without allocating
+struct Folded {
+    chars: [char; FOLDED_LIMIT],
+    len: usize,
+}
+
+impl Folded {
+    /// Lowercase characters of the concatenated `parts`, `None` beyond
+    /// [`FOLDED_LIMIT`] characters
+    fn new<'a>(parts: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let mut folded = Folded {
+            chars: ['\0'; FOLDED_LIMIT],
+            len: 0,
         };
-        let (gbps, bps) = (gbps + (bps / 1_000_000_000), (bps % 1_000_000_000) as u32);
-        let new_bandwidth = Bandwidth::new(gbps, bps);
-        self.current = self
-            .current
-            .checked_add(new_bandwidth)
-            .ok_or(Error::NumberOverflow)?;
-        Ok(())
+        let chars = parts.into_iter().flat_map(str::chars);
+        for c in chars.flat_map(char::to_lowercase) {
+            *folded.chars.get_mut(folded.len)? = c;
+            folded.len += 1;
+        }
+        Some(folded)
     }
 
-    fn parse(mut self) -> Result<Bandwidth, Error> {
-        let mut n = self.parse_first_char()?.ok_or(Error::Empty)?;
-        let mut decimal = false;
-        let mut fraction: u64 = 0;
-        let mut fraction_cnt: u32 = 0;
-        'outer: loop {
-            let mut off = self.off();
-            while let Some(c) = self.iter.next() {
-                match c {
-                    '0'..='9' => {
-                        if decimal {
-                            if fraction_cnt >= FRACTION_PART_LIMIT {
-                                continue;
-                            }
+    fn as_slice(&self) -> &[char] {
+        &self.chars[..self.len]
+    }
+}
+
+/// Edit distance between two strings, counting transpositions as one edit
+///
+/// Only the last three rows of distances are kept, `b` has at most
+/// [`FOLDED_LIMIT`] characters.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = [[0; FOLDED_LIMIT + 1]; 3];
+    for (j, d) in rows[0].iter_mut().enumerate() {
+        *d = j;
+    }
+    for i in 1..=a.len() {
+        let (row, prev) = (i % 3, (i - 1) % 3);
+        rows[row][0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut d = (rows[prev][j] + 1)
+                .min(rows[row][j - 1] + 1)
+                .min(rows[prev][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d = d.min(rows[(i - 2) % 3][j - 2] + 1);
+            }
+            rows[row][j] = d;
+        }
+    }
+    rows[a.len() % 3][b.len()]
+}
+
+struct Parser<'a> {
+    iter: Chars<'a>,
+    src: &'a str,
+    /// The running total in bits per second, converted into a bandwidth once
+    /// the whole string is parsed
+    current: u128,
+    options: ParseOptions,
+    #[cfg(feature = "alloc")]
+    warnings: Vec<ParseWarning>,
+    /// The errors of the invalid spans, if they are skipped
+    #[cfg(feature = "alloc")]
+    errors: Option<Vec<Error>>,
+    /// The value in bits of the unit of the previous span and the length of
+    /// its interval in seconds
+    last_unit: Option<(u128, u128)>,
+    /// Whether a value overflowed, in the saturating mode
+    saturated: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new<'b: 'a>(s: &'b str) -> Self {
+        Parser::with_options(s, ParseOptions::new())
+    }
+
+    fn with_options<'b: 'a>(s: &'b str, options: ParseOptions) -> Self {
+        Parser {
+            iter: s.chars(),
+            src: s,
+            current: 0,
+            options,
+            #[cfg(feature = "alloc")]
+            warnings: Vec::new(),
+            #[cfg(feature = "alloc")]
+            errors: None,
+            last_unit: None,
+            saturated: false,
+        }
+    }
+}
+
+/// The error of a rate span, see [`Parser::parse_with`]: the errors of the
+/// parser itself borrow from the string, and the ones of its callers may be
+/// owned
+trait SpanError<'a>: From<ParseError<'a>> {
+    /// The error recorded when recovering, see
+    /// [`ParseOptions::parse_recovering`]
+    #[cfg(feature = "alloc")]
+    fn into_error(self) -> Error;
+}
+
+impl<'a> SpanError<'a> for ParseError<'a> {
+    #[cfg(feature = "alloc")]
+    fn into_error(self) -> Error {
+        self.into_owned()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SpanError<'_> for Error {
+    fn into_error(self) -> Error {
+        self
+    }
+}
+
+/// The error borrowing its unit from `src`, the string it comes from
+#[cfg(feature = "alloc")]
+fn borrow_error(err: Error, src: &str) -> ParseError<'_> {
+    match err {
+        Error::InvalidCharacter(offset) => ParseError::InvalidCharacter(offset),
+        Error::NumberExpected(offset) => ParseError::NumberExpected(offset),
+        Error::UnknownUnit {
+            start, end, value, ..
+        } => ParseError::UnknownUnit {
+            start,
+            end,
+            unit: &src[start..end],
+            value,
+        },
+        Error::NumberOverflow => ParseError::NumberOverflow,
+        Error::SpaceExpected(offset) => ParseError::SpaceExpected(offset),
+        Error::Empty => ParseError::Empty,
+        Error::InvalidDigitGroup(offset) => ParseError::InvalidDigitGroup(offset),
+        Error::ExtraSpan(offset) => ParseError::ExtraSpan(offset),
+        Error::DuplicateUnit { start, end } => ParseError::DuplicateUnit { start, end },
+        Error::UnorderedUnit { start, end } => ParseError::UnorderedUnit { start, end },
+        Error::SeparatorExpected(offset) => ParseError::SeparatorExpected(offset),
+        Error::TooManyDecimals(offset) => ParseError::TooManyDecimals(offset),
+        Error::OnlyWhitespace { len } => ParseError::OnlyWhitespace { len },
+        Error::AmbiguousUnit { start, end } => ParseError::AmbiguousUnit { start, end },
+        #[cfg(feature = "binary-system")]
+        Error::UnknownBinaryUnit {
+            start, end, value, ..
+        } => ParseError::UnknownBinaryUnit {
+            start,
+            end,
+            unit: &src[start..end],
+            value,
+        },
+        // Not reported by the built-in units, this is read as an unknown unit
+        // of them since the supported units cannot be borrowed
+        Error::UnknownSystemUnit {
+            start, end, value, ..
+        } => ParseError::UnknownUnit {
+            start,
+            end,
+            unit: &src[start..end],
+            value,
+        },
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn off(&self) -> usize {
+        self.src.len() - self.iter.as_str().len()
+    }
+
+    fn parse_first_char(&mut self) -> Result<Option<u64>, ParseError<'a>> {
+        let off = self.off();
+        while let Some(c) = self.iter.next() {
+            match c {
+                '0'..='9' => {
+                    return Ok(Some(c as u64 - '0' as u64));
+                }
+                c if c.is_whitespace() => {
+                    let at = self.off() - c.len_utf8();
+                    self.check_whitespace(c, at)?;
+                }
+                _ => {
+                    return Err(ParseError::NumberExpected(off));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Exponent of ten of the unit and whether it is in Bytes
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+    fn lookup_si_unit(&mut self, unit: &str, start: usize, end: usize) -> Option<(u32, bool)> {
+        if self.options.is_iso80000() {
+            return iso_unit(unit);
+        }
+        if let Some(exponent) = si_unit(unit).filter(|_| self.options.accepts_prefix_case(unit)) {
+            let bytes = match self.options.get_bit_byte_policy() {
+                BitBytePolicy::CaseSensitive => {
+                    self.options.is_bps_means_bytes() && unit.ends_with("bps")
+                }
+                BitBytePolicy::Bytes => options::is_lone_symbol(unit),
+                BitBytePolicy::Bits | BitBytePolicy::Reject => false,
+            };
+            return Some((exponent, bytes));
+        }
+        if !self.options.is_fuzzy() || unit.is_empty() {
+            return None;
+        }
+        let exponent = fuzzy_si_unit(unit)?;
+        #[cfg(feature = "alloc")]
+        self.warnings.push(ParseWarning::AssumedUnit {
+            start,
+            end,
+            unit: unit.to_string(),
+            assumed: si_unit_name(exponent),
+        });
+        Some((exponent, false))
+    }
+
+    fn parse_unit(
+        &mut self,
+        n: u128,
+        fraction: u64,
+        fraction_cnt: u32,
+        start: usize,
+        end: usize,
+    ) -> Result<(), ParseError<'a>> {
+        if self.options.is_iso80000() && !self.src[..start].ends_with(char::is_whitespace) {
+            return Err(ParseError::SpaceExpected(start));
+        }
+        let src = self.src;
+        let unit = &src[start..end];
+        if let Some(&(_, multiplier)) = self
+            .options
+            .get_custom_units()
+            .iter()
+            .find(|(alias, _)| *alias == unit)
+        {
+            let multiplier = u128::from(multiplier);
+            self.check_order(multiplier, 1, start, end)?;
+            return self.add_multiple(n, fraction, fraction_cnt, multiplier);
+        }
+        let unit = self.options.resolve_alias(unit);
+        let spelled = self.options.spelled_unit(unit);
+        let unit = spelled.as_deref().unwrap_or(unit);
+        let baud = options::baud_unit(unit);
+        if let (Some(baud), Some(bits)) = (baud, self.options.get_bits_per_symbol()) {
+            let multiplier = baud * u128::from(bits);
+            self.check_order(multiplier, 1, start, end)?;
+            return self.add_multiple(n, fraction, fraction_cnt, multiplier);
+        }
+        // A unit per minute, hour, day or month is read as the one per second,
+        // and the amount divided by the length of the interval
+        let per_second;
+        let (unit, seconds) = match self.options.split_interval(unit) {
+            Some((base, interval)) => {
+                per_second = UnitBuf::concat(&[base, "/s"]);
+                match per_second.as_deref() {
+                    Some(per_second) if self.is_exact_unit(per_second) => {
+                        let seconds = self.options.interval_seconds(interval);
+                        (per_second, u128::from(seconds))
+                    }
+                    _ => (unit, 1),
+                }
+            }
+            None => (unit, 1),
+        };
+        // Looked up before the fuzzy mode, which would assume `Mbps` for
+        // `MiB/s`
+        let iso80000 = self.options.is_iso80000();
+        let exact = if iso80000 {
+            iso_unit(unit).is_some()
+        } else {
+            si_unit(unit).is_some()
+        };
+        let quadrant = if !exact && self.options.accepts_prefix_case(unit) {
+            self.options.quadrant_unit(unit)
+        } else {
+            None
+        };
+        // The symbols of the ISO/IEC 80000-13 mode are not ambiguous
+        let lone_symbol = !iso80000 && options::is_lone_symbol(unit);
+        if lone_symbol
+            && self.options.get_bit_byte_policy() == BitBytePolicy::Reject
+            && self.is_exact_unit(unit)
+        {
+            return Err(ParseError::AmbiguousUnit { start, end });
+        }
+        if let Some(multiplier) = quadrant {
+            let multiplier = match self.options.get_bit_byte_policy() {
+                BitBytePolicy::Bits if unit.ends_with("B/s") || unit.ends_with("Bps") => {
+                    multiplier / BITS_PER_BYTE as u128
+                }
+                BitBytePolicy::Bytes if lone_symbol && unit.ends_with("bps") => {
+                    multiplier * BITS_PER_BYTE as u128
+                }
+                _ => multiplier,
+            };
+            self.check_order(multiplier, seconds, start, end)?;
+            let scale = 10u128.pow(fraction_cnt);
+            let fraction = (u128::from(fraction) * multiplier + scale / 2) / scale;
+            return self.add_bps(n.saturating_mul(multiplier).saturating_add(fraction) / seconds);
+        }
+        let (exponent, bytes) = match self.lookup_si_unit(unit, start, end) {
+            Some(unit) => unit,
+            None => {
+                return Err(ParseError::UnknownUnit {
+                    start,
+                    end,
+                    unit: &src[start..end],
+                    value: saturating_value(n),
+                });
+            }
+        };
+        let multiplier = if bytes { BITS_PER_BYTE as u128 } else { 1 };
+        self.check_order(10u128.pow(exponent) * multiplier, seconds, start, end)?;
+        let mut bps = n
+            .saturating_mul(10u128.pow(exponent))
+            .saturating_add(u128::from(parse_fraction(fraction, fraction_cnt, exponent)));
+        if bytes {
+            bps = bps.saturating_mul(BITS_PER_BYTE as u128);
+        }
+        self.add_bps(bps / seconds)
+    }
+
+    /// Adds a span in a unit worth `multiplier` bps, the fraction of a bit
+    /// per second being ignored
+    ///
+    /// A span too large for an `u128` saturates, which overflows the total.
+    fn add_multiple(
+        &mut self,
+        n: u128,
+        fraction: u64,
+        fraction_cnt: u32,
+        multiplier: u128,
+    ) -> Result<(), ParseError<'a>> {
+        let bps = n
+            .saturating_mul(multiplier)
+            .saturating_add(u128::from(fraction) * multiplier / 10u128.pow(fraction_cnt));
+        self.add_bps(bps)
+    }
+
+    /// Whether `unit` is one of the units per second of the options, without
+    /// assuming one in the fuzzy mode
+    fn is_exact_unit(&self, unit: &str) -> bool {
+        let iso80000 = self.options.is_iso80000();
+        let exact = if iso80000 {
+            iso_unit(unit).is_some()
+        } else {
+            si_unit(unit).is_some()
+        };
+        let quadrant = self.options.quadrant_unit(unit).is_some();
+        (exact || quadrant) && self.options.accepts_prefix_case(unit)
+    }
+
+    /// Records the unit worth `multiplier` bits per `seconds` of a span, and
+    /// checks that it is smaller than the one of the previous span in the
+    /// strict mode
+    fn check_order(
+        &mut self,
+        multiplier: u128,
+        seconds: u128,
+        start: usize,
+        end: usize,
+    ) -> Result<(), ParseError<'a>> {
+        if self.options.is_strict_spans() {
+            match self.last_unit {
+                // Compared per second without rounding
+                Some((last, last_seconds)) if last * seconds == multiplier * last_seconds => {
+                    return Err(ParseError::DuplicateUnit { start, end });
+                }
+                Some((last, last_seconds)) if last * seconds < multiplier * last_seconds => {
+                    return Err(ParseError::UnorderedUnit { start, end });
+                }
+                _ => {}
+            }
+        }
+        self.last_unit = Some((multiplier, seconds));
+        Ok(())
+    }
+
+    /// Adds the bits per second of a span to the running total
+    ///
+    /// The total may exceed [`Bandwidth::MAX`] until the end of the string,
+    /// where it is converted once.
+    fn add_bps(&mut self, bps: u128) -> Result<(), ParseError<'a>> {
+        let total = match self.current.checked_add(bps) {
+            Some(total) => total,
+            None => return self.overflow(),
+        };
+        // When recovering, the span overflowing the total is skipped rather
+        // than the whole string
+        #[cfg(feature = "alloc")]
+        if self.errors.is_some() && total > bandwidth_as_bps(Bandwidth::MAX) {
+            self.overflow()?;
+        }
+        self.current = total;
+        Ok(())
+    }
+
+    /// Fails with [`ParseError::NumberOverflow`], or records that the total
+    /// saturates in the saturating mode
+    fn overflow(&mut self) -> Result<(), ParseError<'a>> {
+        if !self.options.is_saturating() {
+            return Err(ParseError::NumberOverflow);
+        }
+        self.saturated = true;
+        Ok(())
+    }
+
+    /// Converts the total into a bandwidth, [`Bandwidth::MAX`] if it
+    /// saturates
+    fn total(&mut self) -> Result<Bandwidth, ParseError<'a>> {
+        match checked_bandwidth(self.current) {
+            Some(val) if !self.saturated => Ok(val),
+            _ => {
+                self.overflow()?;
+                #[cfg(feature = "alloc")]
+                self.warnings.push(ParseWarning::Saturated);
+                Ok(Bandwidth::MAX)
+            }
+        }
+    }
+
+    fn parse(&mut self) -> Result<Bandwidth, ParseError<'a>> {
+        self.parse_with(Self::parse_unit)
+    }
+
+    /// Splits the string into rate spans, `unit` being called with the integer
+    /// part, the fractional part, its number of digits and the bounds of the
+    /// unit of each of them to add it to `self.current`
+    ///
+    /// When recovering, the errors are collected in `self.errors` and the
+    /// invalid spans skipped.
+    fn parse_with<F, E>(&mut self, unit: F) -> Result<Bandwidth, E>
+    where
+        F: FnMut(&mut Self, u128, u64, u32, usize, usize) -> Result<(), E>,
+        E: SpanError<'a>,
+    {
+        self.parse_spans(unit)?;
+        Ok(self.total()?)
+    }
+
+    /// Adds the rate spans to `self.current` like [`Parser::parse_with`],
+    /// without converting the total into a bandwidth
+    fn parse_spans<F, E>(&mut self, mut unit: F) -> Result<(), E>
+    where
+        F: FnMut(&mut Self, u128, u64, u32, usize, usize) -> Result<(), E>,
+        E: SpanError<'a>,
+    {
+        // Whitespace alone is not checked
+        let mut next = match self.src.trim_start() {
+            "" => Ok(None),
+            _ => self.parse_first_char(),
+        };
+        if let Ok(None) = next {
+            return Err(match self.src.len() {
+                0 => ParseError::Empty,
+                len => ParseError::OnlyWhitespace { len },
+            }
+            .into());
+        }
+        let mut first = true;
+        loop {
+            let n = match next {
+                Ok(Some(_)) if !first && !self.options.is_multiple_spans() => {
+                    // The first digit of the span is already read
+                    self.recover(E::from(ParseError::ExtraSpan(self.off() - 1)))?;
+                    next = self.parse_first_char();
+                    continue;
+                }
+                Ok(Some(n)) => n,
+                Ok(None) => return Ok(()),
+                Err(error) => {
+                    self.recover(E::from(error))?;
+                    next = self.parse_first_char();
+                    continue;
+                }
+            };
+            first = false;
+            next = match self.parse_span(n, &mut unit) {
+                Err(error) => {
+                    self.recover(error)?;
+                    self.parse_first_char()
+                }
+                Ok(next) => Ok(next),
+            };
+        }
+    }
+
+    /// Parses a rate span starting with the digit `n`, and returns the first
+    /// digit of the next one
+    fn parse_span<F, E>(&mut self, first: u64, unit: &mut F) -> Result<Option<u64>, E>
+    where
+        F: FnMut(&mut Self, u128, u64, u32, usize, usize) -> Result<(), E>,
+        E: SpanError<'a>,
+    {
+        // The integer part, which only overflows once scaled by the unit if
+        // it fits in an `u128`
+        let mut n = u128::from(first);
+        let mut decimal = false;
+        let mut fraction: u64 = 0;
+        let mut fraction_cnt: u32 = 0;
+        let mut off = self.off();
+        // The offset of the whitespace before the current character
+        let mut space = None;
+        let grouping = self.options.is_digit_grouping();
+        let mut groups = DigitGroups::new();
+        // Whether the number is followed by an exponent, e.g. `1.5e3`
+        let mut scaled = false;
+        // The decimals past the fractional part and their number
+        let mut extra = (0, 0);
+        while let Some(c) = self.iter.next() {
+            if scaled && !(c.is_whitespace() || c.is_ascii_alphabetic() || c == '/') {
+                return Err(ParseError::InvalidCharacter(off).into());
+            }
+            if !c.is_whitespace() {
+                if let (Some(at), '0'..='9' | '.' | '_') = (space.take(), c) {
+                    if grouping && !decimal && c.is_ascii_digit() {
+                        groups.separator(' ', at)?;
+                    } else if !self.options.is_spaces_in_numbers() {
+                        return Err(ParseError::InvalidCharacter(at).into());
+                    }
+                }
+            }
+            match c {
+                '0'..='9' => {
+                    if decimal {
+                        if fraction_cnt < self.options.get_fraction_digits() {
                             fraction = fraction
                                 .checked_mul(10)
                                 .and_then(|x| x.checked_add(c as u64 - '0' as u64))
-                                .ok_or(Error::NumberOverflow)?;
+                                .ok_or(ParseError::NumberOverflow)?;
                             fraction_cnt += 1;
-                        } else {
-                            n = n
-                                .checked_mul(10)
-                                .and_then(|x| x.checked_add(c as u64 - '0' as u64))
-                                .ok_or(Error::NumberOverflow)?;
+                        } else if self.options.is_strict_fraction() {
+                            return Err(ParseError::TooManyDecimals(off).into());
+                        } else if fraction_cnt == FRACTION_PART_LIMIT
+                            && extra.1 < EXTRA_DECIMALS_LIMIT
+                        {
+                            extra = (extra.0 * 10 + u128::from(c as u8 - b'0'), extra.1 + 1);
                         }
+                        // The following digits are ignored otherwise, or
+                        // without an exponent
+                    } else {
+                        n = match n
+                            .checked_mul(10)
+                            .and_then(|x| x.checked_add(u128::from(c as u8 - b'0')))
+                        {
+                            Some(n) => n,
+                            None => {
+                                self.overflow()?;
+                                u128::MAX
+                            }
+                        };
+                        groups.digit();
                     }
-                    c if c.is_whitespace() => {}
-                    '_' => {}
-                    '.' => {
-                        if decimal {
-                            return Err(Error::InvalidCharacter(off));
-                        }
-                        decimal = true;
-                    }
-                    'a'..='z' | 'A'..='Z' | '/' => {
-                        break;
+                }
+                c if c.is_whitespace() => {
+                    self.check_whitespace(c, off)?;
+                    space = space.or(Some(off));
+                }
+                '_' if !self.options.is_digit_separators() => {
+                    return Err(ParseError::InvalidCharacter(off).into());
+                }
+                '_' => {}
+                ',' if self.options.is_decimal_comma() => {
+                    if decimal {
+                        return Err(ParseError::InvalidCharacter(off).into());
                     }
-                    _ => {
-                        return Err(Error::InvalidCharacter(off));
+                    groups.end()?;
+                    decimal = true;
+                }
+                ',' | '\'' if grouping && !decimal => groups.separator(c, off)?,
+                '.' => {
+                    if decimal {
+                        return Err(ParseError::InvalidCharacter(off).into());
                     }
+                    groups.end()?;
+                    decimal = true;
+                }
+                'e' | 'E' if !scaled && self.exponent_follows(off) => {
+                    groups.end()?;
+                    let exponent = self.parse_exponent();
+                    (n, fraction, fraction_cnt) = match scale_exponent(
+                        n,
+                        fraction,
+                        fraction_cnt,
+                        extra,
+                        exponent,
+                        self.options.get_fraction_digits(),
+                    ) {
+                        Some(scaled) => scaled,
+                        None => {
+                            self.overflow()?;
+                            (u128::MAX, 0, 0)
+                        }
+                    };
+                    scaled = true;
+                }
+                'a'..='z' | 'A'..='Z' | '/' => {
+                    break;
+                }
+                _ => {
+                    return Err(ParseError::InvalidCharacter(off).into());
+                }
+            }
+            off = self.off();
+        }
+        if !decimal {
+            groups.end()?;
+        }
+        let start = off;
+        if let Some(len) = self.custom_unit_at(start) {
+            let end = start + len;
+            self.iter = self.src[end..].chars();
+            let added = unit(self, n, fraction, fraction_cnt, start, end);
+            self.recover_unit(added)?;
+            return match self.iter.clone().next() {
+                Some('0'..='9') if self.options.is_strict_spans() => {
+                    Err(ParseError::SeparatorExpected(end).into())
+                }
+                Some(c @ '0'..='9') => {
+                    self.iter.next();
+                    Ok(Some(c as u64 - '0' as u64))
                 }
-                off = self.off();
-            }
-            let start = off;
-            let mut off = self.off();
-            while let Some(c) = self.iter.next() {
-                match c {
-                    '0'..='9' => {
-                        self.parse_unit(n, fraction, fraction_cnt, start, off)?;
-                        n = c as u64 - '0' as u64;
-                        fraction = 0;
-                        decimal = false;
-                        fraction_cnt = 0;
-                        continue 'outer;
+                _ => Ok(self.parse_first_char()?),
+            };
+        }
+        let mut off = self.off();
+        // The offset of the whitespace ending the unit
+        let mut space = None;
+        while let Some(c) = self.iter.next() {
+            match c {
+                '0'..='9' => {
+                    let added = unit(self, n, fraction, fraction_cnt, start, off);
+                    self.recover_unit(added)?;
+                    if self.options.is_strict_spans() {
+                        return Err(ParseError::SeparatorExpected(off).into());
                     }
-                    c if c.is_whitespace() => break,
-                    'a'..='z' | 'A'..='Z' | '/' => {}
-                    _ => {
-                        return Err(Error::InvalidCharacter(off));
+                    return Ok(Some(c as u64 - '0' as u64));
+                }
+                c if c.is_whitespace() => {
+                    space = Some(off);
+                    // A unit spelled out like `megabits` may be followed by
+                    // `per second`
+                    let spelled = self.options.get_enabled_units().is_spelled();
+                    let rest = &self.src[off..];
+                    match spelled::per_interval_len(&self.src[start..off], rest) {
+                        Some(len) if spelled => {
+                            off += len;
+                            self.iter = self.src[off..].chars();
+                        }
+                        _ => {}
                     }
+                    break;
+                }
+                'a'..='z' | 'A'..='Z' | '/' => {}
+                _ => {
+                    return Err(ParseError::InvalidCharacter(off).into());
+                }
+            }
+            off = self.off();
+        }
+        let added = unit(self, n, fraction, fraction_cnt, start, off);
+        self.recover_unit(added)?;
+        // Checked once the unit is read, as is the whitespace around the `per`
+        // of a unit spelled out
+        if let Some(space) = space {
+            let src = self.src;
+            for (i, c) in src[space..self.off()].char_indices() {
+                if c.is_whitespace() {
+                    self.check_whitespace(c, space + i)?;
                 }
-                off = self.off();
             }
-            self.parse_unit(n, fraction, fraction_cnt, start, off)?;
-            n = match self.parse_first_char()? {
-                Some(n) => n,
-                None => return Ok(self.current),
-            };
-            fraction = 0;
-            decimal = false;
-            fraction_cnt = 0;
         }
+        Ok(self.parse_first_char()?)
+    }
+
+    /// Checks the whitespace `c` at `at` against [`ParseOptions::newlines`]
+    /// and [`ParseOptions::single_spaces`]
+    ///
+    /// When recovering, the error is recorded and the tokens around the
+    /// whitespace are still read.
+    fn check_whitespace(&mut self, c: char, at: usize) -> Result<(), ParseError<'a>> {
+        let line_break = !self.options.is_newlines() && options::is_line_break(c);
+        let mut single = true;
+        if self.options.is_single_spaces() {
+            let before = self.src[..at].chars().next_back();
+            // A run of whitespace is checked once, at its first character
+            if !before.is_some_and(char::is_whitespace) {
+                let after = self.src[at + c.len_utf8()..].chars().next();
+                single = c == ' ' && before.is_some() && after.is_some_and(|c| !c.is_whitespace());
+            }
+        }
+        if line_break || !single {
+            self.recover_unit(Err(ParseError::InvalidCharacter(at)))?;
+        }
+        Ok(())
+    }
+
+    /// Whether the `e` at `at` starts the exponent of a number, e.g. `e3` or
+    /// `e-3`, rather than a unit
+    fn exponent_follows(&self, at: usize) -> bool {
+        if !self.options.is_exponent_notation() || self.custom_unit_at(at).is_some() {
+            return false;
+        }
+        let mut peek = self.iter.clone();
+        match peek.next() {
+            Some('+' | '-') => matches!(peek.next(), Some('0'..='9')),
+            Some(c) => c.is_ascii_digit(),
+            None => false,
+        }
+    }
+
+    /// Reads the sign and the digits of an exponent, large exponents being
+    /// clamped as they overflow or round to zero anyway
+    fn parse_exponent(&mut self) -> i32 {
+        let mut peek = self.iter.clone();
+        let negative = match peek.next() {
+            Some('-') => true,
+            Some('+') => false,
+            _ => {
+                peek = self.iter.clone();
+                false
+            }
+        };
+        let mut exponent: i32 = 0;
+        while let Some(digit) = peek.clone().next().and_then(|c| c.to_digit(10)) {
+            peek.next();
+            exponent = (exponent * 10 + digit as i32).min(EXPONENT_LIMIT);
+        }
+        self.iter = peek;
+        if negative {
+            -exponent
+        } else {
+            exponent
+        }
+    }
+
+    /// Length of the longest custom unit or unit alias starting at `start` and
+    /// ending the span, as they may contain any character
+    fn custom_unit_at(&self, start: usize) -> Option<usize> {
+        let rest = &self.src[start..];
+        self.options
+            .get_custom_units()
+            .iter()
+            .map(|(alias, _)| *alias)
+            .chain(
+                self.options
+                    .get_unit_aliases()
+                    .iter()
+                    .map(|(alias, _)| *alias),
+            )
+            .filter(|alias| !alias.is_empty() && rest.starts_with(alias))
+            .filter(|alias| match rest[alias.len()..].chars().next() {
+                Some(c) if c.is_ascii_digit() => !alias.ends_with(|c: char| c.is_ascii_digit()),
+                Some(c) => c.is_whitespace(),
+                None => true,
+            })
+            .map(str::len)
+            .max()
+    }
+
+    /// Records the error and skips the rest of the invalid span when
+    /// recovering, or returns the error
+    fn recover<E: SpanError<'a>>(&mut self, error: E) -> Result<(), E> {
+        self.record(error)?;
+        // The span ends at a whitespace, or at a digit following its unit
+        let mut after_unit = false;
+        loop {
+            let mut peek = self.iter.clone();
+            match peek.next() {
+                Some(c) if c.is_whitespace() => break,
+                Some('0'..='9') if after_unit => break,
+                Some(c) => after_unit = c.is_ascii_alphabetic() || c == '/',
+                None => break,
+            }
+            self.iter = peek;
+        }
+        Ok(())
+    }
+
+    /// Records the error of a unit when recovering, the span being complete
+    fn recover_unit<E: SpanError<'a>>(&mut self, added: Result<(), E>) -> Result<(), E> {
+        added.or_else(|error| self.record(error))
+    }
+
+    /// Records the error when recovering, or returns it
+    fn record<E: SpanError<'a>>(&mut self, error: E) -> Result<(), E> {
+        #[cfg(feature = "alloc")]
+        if let Some(errors) = &mut self.errors {
+            errors.push(error.into_error());
+            return Ok(());
+        }
+        Err(error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Parse bandwidth object `1Gbps 12Mbps 5bps` or `1.012000005Gbps`
+///
+/// The bandwidth object is a concatenation of rate spans. Where each rate
+/// span is an number and a suffix. Supported suffixes:
+///
+/// * `bps`, `bit/s`, `b/s` -- bit per second
+/// * `kbps`, `kbit/s`, `kb/s` -- kilobit per second
+/// * `Mbps`, `Mbit/s`, `Mb/s` -- megabit per second
+/// * `Gbps`, `Gbit/s`, `Gb/s` -- gigabit per second
+/// * `Tbps`, `Tbit/s`, `Tb/s` -- terabit per second
+/// * `Pbps`, `Pbit/s`, `Pb/s` -- petabit per second
+/// * `Ebps`, `Ebit/s`, `Eb/s` -- exabit per second
+///
+/// The `/s` of these units can be replaced by `/min`, `/h`, `/d` (or `/day`)
+/// or `/month` for amounts per minute, hour, day or month, e.g. `500Gbit/h`.
+/// With [`ParseOptions::quadrant_units`], so can the one of the units in
+/// Bytes, e.g. `500GB/h` or `2TB/day`.
+///
+/// The units can also be spelled out, in any case and in the singular or the
+/// plural, like `10 megabits per second`, `1 Gigabit/s` or `500 gigabits per
+/// hour`. So can the units in Bytes with [`ParseOptions::quadrant_units`],
+/// e.g. `4 mebibytes/s`.
+///
+/// While the number can be integer, decimal or in scientific notation like `1.5e3`, the fractional
+/// part less than 1bps will always be ignored, including for the amounts per interval.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth;
+///
+/// assert_eq!(parse_bandwidth("9Tbps 420Gbps"), Ok(Bandwidth::new(9420, 0)));
+/// assert_eq!(parse_bandwidth("32Mbps"), Ok(Bandwidth::new(0, 32_000_000)));
+/// assert_eq!(parse_bandwidth("150.024kbps"), Ok(Bandwidth::new(0, 150_024)));
+/// // The fractional part less than 1bps will always be ignored
+/// assert_eq!(parse_bandwidth("150.02456kbps"), Ok(Bandwidth::new(0, 150_024)));
+/// assert_eq!(parse_bandwidth("36Gbit/h"), Ok(Bandwidth::from_mbps(10)));
+/// assert_eq!(parse_bandwidth("10 megabits per second"), Ok(Bandwidth::from_mbps(10)));
+/// ```
+pub fn parse_bandwidth(s: &str) -> Result<Bandwidth, Error> {
+    Parser::new(s).parse().map_err(ParseError::into_owned)
+}
+
+#[cfg(feature = "alloc")]
+/// Parse bandwidth object like [`parse_bandwidth`] from raw bytes, e.g. a
+/// request body or a header value
+///
+/// This never panics, and runs in time linear in the length of `s`, whatever
+/// the input: invalid UTF-8, long runs of digits or of whitespace. Invalid
+/// UTF-8 is reported as [`Error::InvalidCharacter`] at the first invalid
+/// byte. Callers should still bound the length of what they read.
+///
+/// This contract is exercised by the harness of the [`fuzz`](mod@fuzz)
+/// module, enabled by the `fuzz` feature.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{parse_untrusted, Error};
+///
+/// assert_eq!(parse_untrusted(b"10Mbps"), Ok(Bandwidth::from_mbps(10)));
+/// assert_eq!(parse_untrusted(b"10\xffMbps"), Err(Error::InvalidCharacter(2)));
+/// ```
+pub fn parse_untrusted(s: &[u8]) -> Result<Bandwidth, Error> {
+    parse_bandwidth_bytes(s)
+}
+
+#[cfg(feature = "alloc")]
+/// Borrows `s` as a string, reporting invalid UTF-8 as
+/// [`Error::InvalidCharacter`] at the first invalid byte
+pub(crate) fn utf8_str(s: &[u8]) -> Result<&str, Error> {
+    core::str::from_utf8(s).map_err(|err| Error::InvalidCharacter(err.valid_up_to()))
+}
+
+/// Borrows `s` as a string, reporting invalid Unicode as
+/// [`Error::InvalidCharacter`] at its offset in the bytes of `s` on Unix, or
+/// in its lossy UTF-8 conversion elsewhere
+#[cfg(feature = "std")]
+pub(crate) fn os_str(s: &OsStr) -> Result<&str, Error> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        utf8_str(s.as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        s.to_str().ok_or_else(|| {
+            let lossy = s.to_string_lossy();
+            Error::InvalidCharacter(lossy.find(char::REPLACEMENT_CHARACTER).unwrap_or(0))
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Parse bandwidth object like [`parse_bandwidth`] from bytes, e.g. read from
+/// a raw socket, without copying them
+///
+/// Invalid UTF-8 is reported as [`Error::InvalidCharacter`] at the first
+/// invalid byte, and the offsets of the other errors are in bytes too. This
+/// never panics, see [`parse_untrusted`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{parse_bandwidth_bytes, Error};
+///
+/// assert_eq!(parse_bandwidth_bytes(b"10Mbps 5kbps"), Ok(Bandwidth::from_kbps(10_005)));
+/// assert_eq!(parse_bandwidth_bytes(b"10Mbps \xc3("), Err(Error::InvalidCharacter(7)));
+/// ```
+pub fn parse_bandwidth_bytes(s: &[u8]) -> Result<Bandwidth, Error> {
+    parse_bandwidth(utf8_str(s)?)
+}
+
+/// Parse bandwidth object like [`parse_bandwidth`] from an OS string, e.g. a
+/// command line argument or an environment variable
+///
+/// Strings which are not valid Unicode are reported as
+/// [`Error::InvalidCharacter`] rather than a panic or a lossy conversion. On
+/// Unix, the offsets are in the bytes of `s`.
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_bandwidth_os;
+///
+/// let arg = OsStr::new("100Mbps");
+/// assert_eq!(parse_bandwidth_os(arg), Ok(Bandwidth::from_mbps(100)));
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_bandwidth_os(s: &OsStr) -> Result<Bandwidth, Error> {
+    parse_bandwidth(os_str(s)?)
+}
+
+/// Parse bandwidth object like [`parse_bandwidth`], without allocating on
+/// errors
+///
+/// The unit of [`ParseError::UnknownUnit`] borrows from `s`, which makes
+/// rejecting strings cheap when validating many values. Unlike
+/// [`parse_bandwidth`], it is available without the `alloc` feature. See
+/// `binary_system::parse_binary_bandwidth_borrowed` for the binary units.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{parse_bandwidth_borrowed, ParseError};
+///
+/// assert_eq!(parse_bandwidth_borrowed("9Tbps 420Gbps"), Ok(Bandwidth::new(9420, 0)));
+/// assert_eq!(
+///     parse_bandwidth_borrowed("10 Mbsp"),
+///     Err(ParseError::UnknownUnit { start: 3, end: 7, unit: "Mbsp", value: 10 })
+/// );
+/// ```
+pub fn parse_bandwidth_borrowed(s: &str) -> Result<Bandwidth, ParseError<'_>> {
+    Parser::new(s).parse()
+}
+
+#[cfg(feature = "alloc")]
+/// Keywords of the rate limits which do not limit anything
+const UNLIMITED_KEYWORDS: [&str; 5] = ["unlimited", "infinite", "inf", "none", "0"];
+
+#[cfg(feature = "alloc")]
+/// Parse a rate limit which may be unlimited, e.g. `unlimited` or `10Mbps`
+///
+/// The keywords `unlimited`, `infinite`, `inf` and `none`, in any case, and a
+/// bare `0` are read as `None`, as in many rate limit configurations. Any
+/// other value is parsed with [`parse_bandwidth`], so that `0bps` is a limit
+/// of zero. [`format_optional_bandwidth`] writes the value back.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::parse_optional_bandwidth;
+///
+/// assert_eq!(parse_optional_bandwidth("10Mbps"), Ok(Some(Bandwidth::from_mbps(10))));
+/// assert_eq!(parse_optional_bandwidth("Unlimited"), Ok(None));
+/// assert_eq!(parse_optional_bandwidth(" 0 "), Ok(None));
+/// assert_eq!(parse_optional_bandwidth("0bps"), Ok(Some(Bandwidth::new(0, 0))));
+/// assert!(parse_optional_bandwidth("limited").is_err());
+/// ```
+pub fn parse_optional_bandwidth(s: &str) -> Result<Option<Bandwidth>, Error> {
+    let keyword = s.trim();
+    if UNLIMITED_KEYWORDS
+        .iter()
+        .any(|unlimited| keyword.eq_ignore_ascii_case(unlimited))
+    {
+        return Ok(None);
+    }
+    parse_bandwidth(s).map(Some)
+}
+
+/// Formats bandwidth into a human-readable string
+///
+/// Note: this format is guaranteed to have same value when using
+/// parse_bandwidth, but we can change some details of the exact composition
+/// of the value.
+///
+/// By default it will format the value with the largest possible unit in decimal form.
+/// If you want to display integer values only, enable the `display-integer` feature.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_bandwidth;
+///
+/// // Enabling the `display-integer` feature will display integer values only
+/// # #[cfg(feature = "display-integer")]
+/// # {
+/// let val1 = Bandwidth::new(9420, 0);
+/// assert_eq!(format_bandwidth(val1).to_string(), "9Tbps 420Gbps");
+/// let val2 = Bandwidth::new(0, 32_000_000);
+/// assert_eq!(format_bandwidth(val2).to_string(), "32Mbps");
+/// # }
+///
+/// // Disabling the `display-integer` feature will display decimal values
+/// # #[cfg(not(feature = "display-integer"))]
+/// # {
+/// let val1 = Bandwidth::new(9420, 0);
+/// assert_eq!(format_bandwidth(val1).to_string(), "9.42Tbps");
+/// let val2 = Bandwidth::new(0, 32_000_000);
+/// assert_eq!(format_bandwidth(val2).to_string(), "32Mbps");
+/// # }
+/// ```
+pub fn format_bandwidth(val: Bandwidth) -> FormattedBandwidth {
+    FormattedBandwidth(val)
+}
+
+#[cfg(feature = "alloc")]
+/// Formats a rate limit which may be unlimited, `None` being written
+/// `unlimited`
+///
+/// Limits are formatted with [`format_bandwidth`], and read back by
+/// [`parse_optional_bandwidth`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::format_optional_bandwidth;
+///
+/// assert_eq!(format_optional_bandwidth(Some(Bandwidth::from_mbps(10))), "10Mbps");
+/// assert_eq!(format_optional_bandwidth(None), "unlimited");
+/// ```
+pub fn format_optional_bandwidth(val: Option<Bandwidth>) -> String {
+    match val {
+        Some(val) => format_bandwidth(val).to_string(),
+        None => "unlimited".to_string(),
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Formats a column of bandwidths with one common unit and aligned decimals
+///
+/// The unit is the largest one in which the largest value is at least 1, every
+/// value is then written with the same number of decimals (2 unless set with
+/// [`FormatOptions::precision`]) and padded on the left to the same width.
+/// Values rounded above [`Bandwidth::MAX`] are rounded down instead, so that
+/// they are parsed back.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{format_aligned, FormatOptions};
+///
+/// let values = [
+///     Bandwidth::from_kbps(9_375),
+///     Bandwidth::from_mbps(120),
+///     Bandwidth::from_kbps(500),
+/// ];
+/// assert_eq!(
+///     format_aligned(&values, &FormatOptions::new()),
+///     ["  9.38Mbps", "120.00Mbps", "  0.50Mbps"],
+/// );
+/// assert_eq!(
+///     format_aligned(&values, &FormatOptions::new().precision(1).show_unit(false)),
+///     ["  9.4", "120.0", "  0.5"],
+/// );
+/// ```
+pub fn format_aligned(values: &[Bandwidth], options: &FormatOptions) -> Vec<String> {
+    let largest = values.iter().copied().map(bandwidth_as_bps).max();
+    let largest = largest.unwrap_or(0);
+    let precision = options.get_precision().unwrap_or(2);
+    let (divisor, unit) = options.unit_with_precision(largest, precision);
+    let max = bandwidth_as_bps(Bandwidth::MAX);
+    let mut digits = Vec::new();
+    let numbers: Vec<String> = values
+        .iter()
+        .map(|val| {
+            let mut number = String::new();
+            write_ratio(
+                &mut number,
+                &mut digits,
+                bandwidth_as_bps(*val),
+                divisor,
+                precision,
+                max,
+            );
+            if options.is_decimal_comma() {
+                number = number.replace('.', ",");
+            }
+            number
+        })
+        .collect();
+    let width = numbers.iter().map(String::len).max().unwrap_or(0);
+    numbers
+        .into_iter()
+        .map(|number| format!("{:>width$}{}", number, unit, width = width))
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+/// Formats the components of a sum followed by their total
+///
+/// Each component and the total are written with `options`, e.g.
+/// `10Mbps + 2.5Mbps + 512kbps = 13.012Mbps`. The total is exact, even when it
+/// exceeds [`Bandwidth::MAX`]. Without any component, only the total is
+/// written.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{format_sum, FormatOptions};
+///
+/// let values = [
+///     Bandwidth::from_mbps(10),
+///     Bandwidth::from_kbps(2_500),
+///     Bandwidth::from_kbps(512),
+/// ];
+/// assert_eq!(
+///     format_sum(&values, &FormatOptions::new()),
+///     "10Mbps + 2.5Mbps + 512kbps = 13.012Mbps",
+/// );
+/// assert_eq!(
+///     format_sum(&values, &FormatOptions::new().iso80000(true).precision(1)),
+///     "10.0 Mbit/s + 2.5 Mbit/s + 512.0 kbit/s = 13.0 Mbit/s",
+/// );
+/// ```
+pub fn format_sum(values: &[Bandwidth], options: &FormatOptions) -> String {
+    let total: u128 = values.iter().copied().map(bandwidth_as_bps).sum();
+    let mut s = String::new();
+    for val in values {
+        if !s.is_empty() {
+            s.push_str(" + ");
+        }
+        s.push_str(&options.format(*val));
+    }
+    if !s.is_empty() {
+        s.push_str(" = ");
     }
+    s.push_str(&options.format_bps(total));
+    s
 }
 
-/// Parse bandwidth object `1Gbps 12Mbps 5bps` or `1.012000005Gbps`
+#[cfg(feature = "alloc")]
+/// Formats a bandwidth in at most `max_chars` characters, with as many
+/// decimals as fit, e.g. for narrow columns and small displays
 ///
-/// The bandwidth object is a concatenation of rate spans. Where each rate
-/// span is an number and a suffix. Supported suffixes:
+/// See [`FormatOptions::fit_to_width`] to choose the units.
 ///
-/// * `bps`, `bit/s`, `b/s` -- bit per second
-/// * `kbps`, `kbit/s`, `kb/s` -- kilobit per second
-/// * `Mbps`, `Mbit/s`, `Mb/s` -- megabit per second
-/// * `Gbps`, `Gbit/s`, `Gb/s` -- gigabit per second
-/// * `Tbps`, `Tbit/s`, `Tb/s` -- terabit per second
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::fit_to_width;
 ///
-/// While the number can be integer or decimal, the fractional part less than 1bps will always be
-/// ignored.
+/// let val = Bandwidth::from_bps(1_194_035);
+/// assert_eq!(fit_to_width(val, 12), "1.194035Mbps");
+/// assert_eq!(fit_to_width(val, 8), "1.19Mbps");
+/// assert_eq!(fit_to_width(val, 4), "1Mbps");
+/// assert_eq!(fit_to_width(Bandwidth::from_kbps(999_700), 5), "1Gbps");
+/// ```
+pub fn fit_to_width(val: Bandwidth, max_chars: usize) -> String {
+    FormatOptions::new().fit_to_width(val, max_chars)
+}
+
+#[cfg(feature = "alloc")]
+/// Formats many bandwidths with `options`, separated by `sep`, at the end of
+/// `buf`
+///
+/// See [`FormatOptions::format_many_into`], which shares the work of the units
+/// between the values.
 ///
 /// # Examples
 ///
 /// ```
 /// use bandwidth::Bandwidth;
-/// use human_bandwidth::parse_bandwidth;
+/// use human_bandwidth::{format_many_into, FormatOptions};
 ///
-/// assert_eq!(parse_bandwidth("9Tbps 420Gbps"), Ok(Bandwidth::new(9420, 0)));
-/// assert_eq!(parse_bandwidth("32Mbps"), Ok(Bandwidth::new(0, 32_000_000)));
-/// assert_eq!(parse_bandwidth("150.024kbps"), Ok(Bandwidth::new(0, 150_024)));
-/// // The fractional part less than 1bps will always be ignored
-/// assert_eq!(parse_bandwidth("150.02456kbps"), Ok(Bandwidth::new(0, 150_024)));
+/// let mut csv = String::new();
+/// for row in [[1, 2], [3, 4]] {
+///     let values = row.iter().map(|mbps| Bandwidth::from_mbps(*mbps));
+///     format_many_into(&mut csv, values, ",", &FormatOptions::new());
+///     csv.push('\n');
+/// }
+/// assert_eq!(csv, "1Mbps,2Mbps\n3Mbps,4Mbps\n");
 /// ```
-pub fn parse_bandwidth(s: &str) -> Result<Bandwidth, Error> {
-    Parser::new(s).parse()
+pub fn format_many_into<I>(buf: &mut String, values: I, sep: &str, options: &FormatOptions)
+where
+    I: IntoIterator<Item = Bandwidth>,
+{
+    options.format_many_into(buf, values, sep)
 }
 
-/// Formats bandwidth into a human-readable string
-///
-/// Note: this format is guaranteed to have same value when using
-/// parse_bandwidth, but we can change some details of the exact composition
-/// of the value.
+#[cfg(feature = "alloc")]
+/// Formats the time left to transfer `remaining` at `bw`, like
+/// `about 3m 20s remaining at 12.5MiB/s`
 ///
-/// By default it will format the value with the largest possible unit in decimal form.
-/// If you want to display integer values only, enable the `display-integer` feature.
+/// The time is rounded up and written with its two largest units, from
+/// seconds to days, and the rate in Bytes per second with binary prefixes, see
+/// [`DataSize`](size::DataSize). Transfers slower than 1B/s are reported as
+/// stalled, and transfers lasting more than a year are not estimated.
 ///
 /// # Examples
 ///
 /// ```
 /// use bandwidth::Bandwidth;
-/// use human_bandwidth::format_bandwidth;
-///
-/// // Enabling the `display-integer` feature will display integer values only
-/// # #[cfg(feature = "display-integer")]
-/// # {
-/// let val1 = Bandwidth::new(9420, 0);
-/// assert_eq!(format_bandwidth(val1).to_string(), "9Tbps 420Gbps");
-/// let val2 = Bandwidth::new(0, 32_000_000);
-/// assert_eq!(format_bandwidth(val2).to_string(), "32Mbps");
-/// # }
+/// use human_bandwidth::{format_eta, size::DataSize};
 ///
-/// // Disabling the `display-integer` feature will display decimal values
-/// # #[cfg(not(feature = "display-integer"))]
-/// # {
-/// let val1 = Bandwidth::new(9420, 0);
-/// assert_eq!(format_bandwidth(val1).to_string(), "9.42Tbps");
-/// let val2 = Bandwidth::new(0, 32_000_000);
-/// assert_eq!(format_bandwidth(val2).to_string(), "32Mbps");
-/// # }
+/// let rate = Bandwidth::from_bps(104_857_600);
+/// assert_eq!(
+///     format_eta(DataSize::from_mib(2_500), rate),
+///     "about 3m 20s remaining at 12.5MiB/s"
+/// );
+/// assert_eq!(
+///     format_eta(DataSize::from_mib(3), Bandwidth::new(0, 0)),
+///     "stalled with 3MiB remaining"
+/// );
 /// ```
-pub fn format_bandwidth(val: Bandwidth) -> FormattedBandwidth {
-    FormattedBandwidth(val)
+pub fn format_eta(remaining: size::DataSize, bw: Bandwidth) -> String {
+    const UNITS: [(u128, &str); 4] = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+
+    let bits = remaining.bytes() as u128 * BITS_PER_BYTE as u128;
+    let bps = bandwidth_as_bps(bw);
+    let rate =
+        size::DataSize::from_bytes(u64::try_from(bps / BITS_PER_BYTE as u128).unwrap_or(u64::MAX));
+    if bits == 0 {
+        return "nothing remaining".to_string();
+    } else if rate.bytes() == 0 {
+        return format!("stalled with {} remaining", remaining);
+    }
+    let secs = bits.div_ceil(bps);
+    if secs > 365 * 86_400 {
+        return format!("more than a year remaining at {}/s", rate);
+    }
+    let largest = UNITS
+        .iter()
+        .position(|(unit, _)| secs >= *unit)
+        .unwrap_or(UNITS.len() - 1);
+    let (unit, name) = UNITS[largest];
+    let mut eta = format!("{}{}", secs / unit, name);
+    if let Some((next, next_name)) = UNITS.get(largest + 1) {
+        let count = secs % unit / next;
+        if count > 0 {
+            eta.push_str(&format!(" {}{}", count, next_name));
+        }
+    }
+    format!("about {} remaining at {}/s", eta, rate)
+}
+
+#[cfg(feature = "alloc")]
+/// Exponent of ten of the largest SI unit in which `bps` is at least 1
+fn si_exponent_for(bps: u128) -> u32 {
+    [18, 15, 12, 9, 6, 3]
+        .into_iter()
+        .find(|exponent| bps >= 10u128.pow(*exponent))
+        .unwrap_or(0)
+}
+
+/// Writes `bps / 10^exponent` with exactly `precision` decimals
+///
+/// The rounding is to the nearest with ties to even, but down rather than
+/// above [`Bandwidth::MAX`] so that the number is parsed back.
+#[cfg(feature = "std")]
+fn fmt_scaled(bps: u128, exponent: u32, precision: usize) -> String {
+    let mut out = String::new();
+    write_ratio(
+        &mut out,
+        &mut Vec::new(),
+        bps,
+        10u128.pow(exponent),
+        precision,
+        bandwidth_as_bps(Bandwidth::MAX),
+    );
+    out
+}
+
+/// Writes `amount / divisor` with exactly `precision` decimals, `divisor`
+/// being at most `u128::MAX / 10`
+///
+/// The rounding is to the nearest with ties to even.
+#[cfg(any(feature = "std", feature = "serde"))]
+fn fmt_ratio(amount: u128, divisor: u128, precision: usize) -> String {
+    let mut out = String::new();
+    write_ratio(
+        &mut out,
+        &mut Vec::new(),
+        amount,
+        divisor,
+        precision,
+        u128::MAX,
+    );
+    out
+}
+
+#[cfg(feature = "alloc")]
+/// `bps / divisor` rounded to `precision` decimals like [`fmt_ratio`], back in
+/// bits per second and rounded down, or `None` if it does not fit
+///
+/// Used to choose the unit of a value after rounding it, when it may carry
+/// to the next unit.
+fn rounded_bps(bps: u128, divisor: u128, precision: usize) -> Option<u128> {
+    let scale = 10u128.checked_pow(u32::try_from(precision).ok()?)?;
+    let scaled = bps.checked_mul(scale)?;
+    let mut rounded = scaled / divisor;
+    let remainder = scaled % divisor;
+    if remainder * 2 > divisor || (remainder * 2 == divisor && rounded % 2 == 1) {
+        rounded += 1;
+    }
+    Some(rounded.checked_mul(divisor)? / scale)
 }
 
-fn item(f: &mut fmt::Formatter, started: &mut bool, name: &str, value: u32) -> fmt::Result {
+#[cfg(feature = "alloc")]
+/// Writes `amount / divisor` like [`fmt_ratio`] at the end of `out`, `digits`
+/// being scratch space reused across calls
+///
+/// An `amount` of at most `max` is rounded down rather than above `max`, e.g.
+/// for [`Bandwidth::MAX`] which would not be parsed back otherwise.
+fn write_ratio(
+    out: &mut String,
+    digits: &mut Vec<u8>,
+    amount: u128,
+    divisor: u128,
+    precision: usize,
+    max: u128,
+) {
+    let mut int = amount / divisor;
+    let mut remainder = amount % divisor;
+    digits.clear();
+    for _ in 0..precision {
+        remainder *= 10;
+        digits.push((remainder / divisor) as u8);
+        remainder %= divisor;
+    }
+    // Rounding up adds `(divisor - remainder) / 10^precision` bps
+    let above_max = match max.checked_sub(amount) {
+        Some(headroom) => u32::try_from(precision)
+            .ok()
+            .and_then(|precision| 10u128.checked_pow(precision))
+            .and_then(|scale| headroom.checked_mul(scale))
+            .is_some_and(|headroom| divisor - remainder > headroom),
+        None => false,
+    };
+    let odd = digits.last().map_or(int % 2 == 1, |digit| digit % 2 == 1);
+    if !above_max && (remainder * 2 > divisor || (remainder * 2 == divisor && odd)) {
+        match digits.iter().rposition(|digit| *digit < 9) {
+            Some(index) => {
+                digits[index] += 1;
+                digits[index + 1..].iter_mut().for_each(|digit| *digit = 0);
+            }
+            None => {
+                int += 1;
+                digits.iter_mut().for_each(|digit| *digit = 0);
+            }
+        }
+    }
+    // Writing into a `String` never fails
+    let _ = write!(out, "{}", int);
+    if precision > 0 {
+        out.push('.');
+        out.extend(digits.iter().map(|digit| char::from(b'0' + digit)));
+    }
+}
+
+fn item(f: &mut fmt::Formatter, started: &mut bool, name: &str, value: u64) -> fmt::Result {
     if value > 0 {
         if *started {
             f.write_str(" ")?;
@@ -396,28 +2626,130 @@ fn item(f: &mut fmt::Formatter, started: &mut bool, name: &str, value: u32) -> f
     Ok(())
 }
 
-#[derive(Copy, Clone)]
+/// A SI unit of bits per second, see [`largest_unit`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(usize)]
-enum LargestUnit {
+pub enum Unit {
+    /// Bits per second
     Bps = 0,
+    /// Kilobits per second
     Kbps = 1,
+    /// Megabits per second
     Mbps = 2,
+    /// Gigabits per second
     Gbps = 3,
+    /// Terabits per second
     Tbps = 4,
+    /// Petabits per second
+    Pbps = 5,
+    /// Exabits per second
+    Ebps = 6,
+}
+
+/// The canonical name and the ISO/IEC 80000-13 symbol of each [`Unit`]
+const UNIT_NAMES: [(&str, &str); 7] = [
+    ("bps", "bit/s"),
+    ("kbps", "kbit/s"),
+    ("Mbps", "Mbit/s"),
+    ("Gbps", "Gbit/s"),
+    ("Tbps", "Tbit/s"),
+    ("Pbps", "Pbit/s"),
+    ("Ebps", "Ebit/s"),
+];
+
+impl Unit {
+    /// Every unit, from the smallest to the largest
+    ///
+    /// The names of these units are the ones listed by the errors of unknown
+    /// units and assumed by [`ParseOptions::fuzzy`], so that translations and
+    /// user interfaces can map each of them to a description.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use human_bandwidth::Unit;
+    ///
+    /// let names: Vec<_> = Unit::ALL.iter().map(|unit| unit.name()).collect();
+    /// assert_eq!(names, ["bps", "kbps", "Mbps", "Gbps", "Tbps", "Pbps", "Ebps"]);
+    /// ```
+    pub const ALL: [Unit; 7] = [
+        Unit::Bps,
+        Unit::Kbps,
+        Unit::Mbps,
+        Unit::Gbps,
+        Unit::Tbps,
+        Unit::Pbps,
+        Unit::Ebps,
+    ];
+
+    /// The largest unit in which `bps` is at least 1, or [`Unit::Bps`]
+    fn of(bps: u128) -> Self {
+        Unit::ALL
+            .into_iter()
+            .rev()
+            .find(|unit| bps >= u128::from(unit.bps()))
+            .unwrap_or(Unit::Bps)
+    }
+
+    #[cfg(feature = "alloc")]
+    /// The unit of an exponent of ten, the largest one above `Ebps`
+    fn from_exponent(exponent: u32) -> Self {
+        Unit::ALL[(exponent / 3).min(6) as usize]
+    }
+
+    /// The exponent of ten of the unit, e.g. `6` for [`Unit::Mbps`]
+    pub const fn exponent(self) -> u32 {
+        3 * self as u32
+    }
+
+    /// The canonical name of the unit, e.g. `"Mbps"`, as written by
+    /// [`format_bandwidth`]
+    pub const fn name(self) -> &'static str {
+        UNIT_NAMES[self as usize].0
+    }
+
+    /// The ISO/IEC 80000-13 symbol of the unit, e.g. `"Mbit/s"`
+    pub const fn iso_name(self) -> &'static str {
+        UNIT_NAMES[self as usize].1
+    }
+
+    /// The number of bits per second in one of this unit, e.g. `1_000_000`
+    /// for [`Unit::Mbps`]
+    pub const fn bps(self) -> u64 {
+        10u64.pow(self.exponent())
+    }
 }
 
-impl fmt::Display for LargestUnit {
+impl fmt::Display for Unit {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            LargestUnit::Bps => f.write_str("bps"),
-            LargestUnit::Kbps => f.write_str("kbps"),
-            LargestUnit::Mbps => f.write_str("Mbps"),
-            LargestUnit::Gbps => f.write_str("Gbps"),
-            LargestUnit::Tbps => f.write_str("Tbps"),
-        }
+        f.write_str(self.name())
     }
 }
 
+/// Returns the unit [`format_bandwidth`] writes a bandwidth in as a decimal
+/// number, i.e. the largest one in which it is at least 1, and the value in
+/// this unit
+///
+/// User interfaces drawing their own number widgets can pick the same unit
+/// as the crate. The value is approximate for bandwidths with more than about
+/// 15 significant digits.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{largest_unit, Unit};
+///
+/// assert_eq!(largest_unit(Bandwidth::from_kbps(1_500)), (Unit::Mbps, 1.5));
+/// assert_eq!(largest_unit(Bandwidth::from_kbps(999)), (Unit::Kbps, 999.0));
+/// assert_eq!(largest_unit(Bandwidth::new(0, 0)), (Unit::Bps, 0.0));
+/// ```
+pub fn largest_unit(val: Bandwidth) -> (Unit, f64) {
+    let bps = bandwidth_as_bps(val);
+    let unit = Unit::of(bps);
+    (unit, bps as f64 / unit.bps() as f64)
+}
+
 impl FormattedBandwidth {
     #[deprecated(since = "0.1.4", note = "please use `core::ops::Deref` instead")]
     /// Returns a reference to the [`Bandwidth`][] that is being formatted.
@@ -437,7 +2769,9 @@ impl FormattedBandwidth {
             return Ok(());
         }
 
-        let tbps = gbps / 1_000;
+        let ebps = gbps / 1_000_000_000;
+        let pbps = gbps / 1_000_000 % 1_000;
+        let tbps = gbps / 1_000 % 1_000;
         let gbps = gbps % 1_000;
 
         let mbps = bps / 1_000_000;
@@ -445,11 +2779,13 @@ impl FormattedBandwidth {
         let bps = bps % 1_000;
 
         let started = &mut false;
-        item(f, started, "Tbps", tbps as u32)?;
-        item(f, started, "Gbps", gbps as u32)?;
-        item(f, started, "Mbps", mbps)?;
-        item(f, started, "kbps", kbps)?;
-        item(f, started, "bps", bps)?;
+        item(f, started, "Ebps", ebps)?;
+        item(f, started, "Pbps", pbps)?;
+        item(f, started, "Tbps", tbps)?;
+        item(f, started, "Gbps", gbps)?;
+        item(f, started, "Mbps", mbps.into())?;
+        item(f, started, "kbps", kbps.into())?;
+        item(f, started, "bps", bps.into())?;
         Ok(())
     }
 
@@ -460,80 +2796,263 @@ impl FormattedBandwidth {
         let gbps = self.0.as_gbps();
         let bps = self.0.subgbps_bps();
 
-        if gbps == 0 && bps == 0 {
-            f.write_str("0bps")?;
-            return Ok(());
-        }
+        if gbps == 0 && bps == 0 {
+            f.write_str("0bps")?;
+            return Ok(());
+        }
+
+        let ebps = gbps / 1_000_000_000;
+        let pbps = gbps / 1_000_000 % 1_000;
+        let tbps = gbps / 1_000 % 1_000;
+        let gbps = gbps % 1_000;
+
+        let mbps = (bps / 1_000_000) as u64;
+        let kbps = (bps / 1_000 % 1_000) as u64;
+        let bps = (bps % 1_000) as u64;
+
+        let largest_unit = Unit::of(bandwidth_as_bps(self.0));
+
+        let values = [bps, kbps, mbps, gbps, tbps, pbps, ebps];
+        let mut index = largest_unit as usize;
+        let mut zeros = 0;
+        let mut dot = true;
+        write!(f, "{}", values[index])?;
+        loop {
+            if index == 0 {
+                write!(f, "{}", largest_unit)?;
+                break;
+            }
+            index -= 1;
+            let value = values[index];
+            if value == 0 {
+                zeros += 3;
+                continue;
+            } else {
+                if dot {
+                    f.write_str(".")?;
+                    dot = false;
+                }
+                if zeros > 0 {
+                    write!(f, "{:0width$}", 0, width = zeros)?;
+                    zeros = 0;
+                }
+                if value % 10 != 0 {
+                    write!(f, "{:03}", value)?;
+                } else if value % 100 != 0 {
+                    write!(f, "{:02}", value / 10)?;
+                    zeros += 1;
+                } else {
+                    write!(f, "{}", value / 100)?;
+                    zeros += 2;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Displays a bandwidth in decimal form, whether `display-integer` is enabled or not
+pub(crate) struct DecimalBandwidth(pub(crate) Bandwidth);
+
+impl fmt::Display for DecimalBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        FormattedBandwidth(self.0).fmt_decimal(f)
+    }
+}
+
+impl fmt::Display for FormattedBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(not(feature = "display-integer"))]
+        self.fmt_decimal(f)?;
+        #[cfg(feature = "display-integer")]
+        self.fmt_integer(f)?;
+        Ok(())
+    }
+}
+
+impl core::ops::Deref for FormattedBandwidth {
+    type Target = Bandwidth;
+
+    fn deref(&self) -> &Bandwidth {
+        &self.0
+    }
+}
+
+impl core::ops::DerefMut for FormattedBandwidth {
+    fn deref_mut(&mut self) -> &mut Bandwidth {
+        &mut self.0
+    }
+}
+
+/// Shows the raw amount of bits per second alongside its decimal rendering,
+/// e.g. `FormattedBandwidth(1_500_000_000bps = 1.5Gbps)`
+impl fmt::Debug for FormattedBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FormattedBandwidth({}bps = {})",
+            Grouped(bandwidth_as_bps(self.0)),
+            DecimalBandwidth(self.0)
+        )
+    }
+}
+
+/// Displays an integer with its digits grouped by three with underscores, like
+/// a Rust literal
+pub(crate) struct Grouped(pub(crate) u128);
+
+impl fmt::Display for Grouped {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The digits from the least significant one, 39 for `u128::MAX`
+        let mut digits = [0; 39];
+        let (mut n, mut len) = (self.0, 0);
+        loop {
+            digits[len] = (n % 10) as u8;
+            n /= 10;
+            len += 1;
+            if n == 0 {
+                break;
+            }
+        }
+        for i in (0..len).rev() {
+            write!(f, "{}", digits[i])?;
+            if i > 0 && i % 3 == 0 {
+                f.write_str("_")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Parses a bandwidth with [`parse_bandwidth`], so that the wrapper can both
+/// read and write the human-readable format
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::FormattedBandwidth;
+///
+/// let formatted: FormattedBandwidth = "1Gbps 500Mbps".parse().unwrap();
+/// assert_eq!(*formatted, Bandwidth::new(1, 500_000_000));
+/// # #[cfg(not(feature = "display-integer"))]
+/// assert_eq!(formatted.to_string(), "1.5Gbps");
+/// ```
+impl core::str::FromStr for FormattedBandwidth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        parse_bandwidth(s).map(FormattedBandwidth)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<FormattedBandwidth> for String {
+    fn from(val: FormattedBandwidth) -> String {
+        val.to_string()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for FormattedBandwidth {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Error> {
+        s.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<String> for FormattedBandwidth {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Error> {
+        s.parse()
+    }
+}
+
+/// A bandwidth which converts to and from its human-readable form
+///
+/// Unlike [`FormattedBandwidth`], it can be built from a [`Bandwidth`] with
+/// `From` and compared. With the `serde` feature, it is serialized as a
+/// string through its `String` conversions.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::HumanBandwidth;
+///
+/// let limit = HumanBandwidth::try_from("1Gbps 500Mbps").unwrap();
+/// assert_eq!(limit, HumanBandwidth::from(Bandwidth::new(1, 500_000_000)));
+/// # #[cfg(not(feature = "display-integer"))]
+/// assert_eq!(String::from(limit), "1.5Gbps");
+/// assert!(HumanBandwidth::try_from(String::from("1.5")).is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct HumanBandwidth(pub Bandwidth);
+
+impl fmt::Display for HumanBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        FormattedBandwidth(self.0).fmt(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::str::FromStr for HumanBandwidth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        parse_bandwidth(s).map(HumanBandwidth)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for HumanBandwidth {
+    type Error = Error;
 
-        let tbps = gbps / 1_000;
-        let gbps = gbps % 1_000;
+    fn try_from(s: &str) -> Result<Self, Error> {
+        s.parse()
+    }
+}
 
-        let mbps = (bps / 1_000_000) as u64;
-        let kbps = (bps / 1_000 % 1_000) as u64;
-        let bps = (bps % 1_000) as u64;
+#[cfg(feature = "alloc")]
+impl TryFrom<String> for HumanBandwidth {
+    type Error = Error;
 
-        let largest_unit = if tbps > 0 {
-            LargestUnit::Tbps
-        } else if gbps > 0 {
-            LargestUnit::Gbps
-        } else if mbps > 0 {
-            LargestUnit::Mbps
-        } else if kbps > 0 {
-            LargestUnit::Kbps
-        } else {
-            LargestUnit::Bps
-        };
+    fn try_from(s: String) -> Result<Self, Error> {
+        s.parse()
+    }
+}
 
-        let values = [bps, kbps, mbps, gbps, tbps];
-        let mut index = largest_unit as usize;
-        let mut zeros = 0;
-        let mut dot = true;
-        write!(f, "{}", values[index])?;
-        loop {
-            if index == 0 {
-                write!(f, "{}", largest_unit)?;
-                break;
-            }
-            index -= 1;
-            let value = values[index];
-            if value == 0 {
-                zeros += 3;
-                continue;
-            } else {
-                if dot {
-                    f.write_str(".")?;
-                    dot = false;
-                }
-                if zeros > 0 {
-                    write!(f, "{:0width$}", 0, width = zeros)?;
-                    zeros = 0;
-                }
-                if value % 10 != 0 {
-                    write!(f, "{:03}", value)?;
-                } else if value % 100 != 0 {
-                    write!(f, "{:02}", value / 10)?;
-                    zeros += 1;
-                } else {
-                    write!(f, "{}", value / 100)?;
-                    zeros += 2;
-                }
-            }
-        }
-        Ok(())
+#[cfg(feature = "alloc")]
+impl From<HumanBandwidth> for String {
+    fn from(val: HumanBandwidth) -> String {
+        val.to_string()
     }
 }
 
-impl fmt::Display for FormattedBandwidth {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        #[cfg(not(feature = "display-integer"))]
-        self.fmt_decimal(f)?;
-        #[cfg(feature = "display-integer")]
-        self.fmt_integer(f)?;
-        Ok(())
+impl From<Bandwidth> for HumanBandwidth {
+    fn from(val: Bandwidth) -> HumanBandwidth {
+        HumanBandwidth(val)
     }
 }
 
-impl core::ops::Deref for FormattedBandwidth {
+impl From<HumanBandwidth> for Bandwidth {
+    fn from(val: HumanBandwidth) -> Bandwidth {
+        val.0
+    }
+}
+
+impl core::ops::Deref for HumanBandwidth {
     type Target = Bandwidth;
 
     fn deref(&self) -> &Bandwidth {
@@ -541,13 +3060,13 @@ impl core::ops::Deref for FormattedBandwidth {
     }
 }
 
-impl core::ops::DerefMut for FormattedBandwidth {
+impl core::ops::DerefMut for HumanBandwidth {
     fn deref_mut(&mut self) -> &mut Bandwidth {
         &mut self.0
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use bandwidth::Bandwidth;
@@ -749,16 +3268,49 @@ mod tests {
 
     #[test]
     fn test_overflow() {
+        // Spans are only limited by the range of a bandwidth
         assert_eq!(
             parse_bandwidth("100000000000000000000bps"),
+            Ok(Bandwidth::new(100_000_000_000, 0))
+        );
+        assert_eq!(
+            parse_bandwidth("1000000000000000000000bps"),
+            Ok(Bandwidth::new(1_000_000_000_000, 0))
+        );
+        assert_eq!(
+            parse_bandwidth("18446744073709551615999Mbps"),
+            Ok(Bandwidth::new(u64::MAX, 999_000_000))
+        );
+        assert_eq!(
+            parse_bandwidth("18446744073709551615999999999bps"),
+            Ok(Bandwidth::MAX)
+        );
+        assert_eq!(
+            parse_bandwidth("18446744073709551616000000000bps"),
             Err(Error::NumberOverflow)
         );
         assert_eq!(
-            parse_bandwidth("100000000000000000kbps"),
+            parse_bandwidth(&format!("{}0bps", u128::MAX)),
             Err(Error::NumberOverflow)
         );
+        assert_eq!(
+            parse_bandwidth("100000000000000000kbps"),
+            Ok(Bandwidth::new(100_000_000_000, 0))
+        );
         assert_eq!(
             parse_bandwidth("100000000000000Mbps"),
+            Ok(Bandwidth::new(100_000_000_000, 0))
+        );
+        assert_eq!(
+            parse_bandwidth("18446744073709551615Gbps 999999999bps"),
+            Ok(Bandwidth::MAX)
+        );
+        assert_eq!(
+            parse_bandwidth("18446744073709551615Gbps 1Gbps"),
+            Err(Error::NumberOverflow)
+        );
+        assert_eq!(
+            parse_bandwidth(&"18446744073709551615Gbps ".repeat(20)),
             Err(Error::NumberOverflow)
         );
         assert_eq!(
@@ -771,6 +3323,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_optional_bandwidth() {
+        for keyword in ["unlimited", "INFINITE", "Inf", "none", "0", "\tnone\n"] {
+            assert_eq!(parse_optional_bandwidth(keyword), Ok(None), "{:?}", keyword);
+        }
+        assert_eq!(
+            parse_optional_bandwidth("1Gbps 500Mbps"),
+            Ok(Some(Bandwidth::from_mbps(1_500)))
+        );
+        assert_eq!(
+            parse_optional_bandwidth("00"),
+            Err(Error::UnknownUnit {
+                start: 2,
+                end: 2,
+                unit: String::new(),
+                value: 0,
+            })
+        );
+        assert_eq!(parse_optional_bandwidth(""), Err(Error::Empty));
+        assert_eq!(
+            parse_optional_bandwidth("nonee"),
+            Err(Error::NumberExpected(0))
+        );
+
+        for val in [None, Some(Bandwidth::new(0, 0)), Some(Bandwidth::new(3, 0))] {
+            assert_eq!(
+                parse_optional_bandwidth(&format_optional_bandwidth(val)),
+                Ok(val)
+            );
+        }
+    }
+
     #[test]
     fn test_nice_error_message() {
         assert_eq!(
@@ -784,8 +3368,61 @@ mod tests {
         assert_eq!(
             parse_bandwidth("10 byte/s").unwrap_err().to_string(),
             "unknown bandwidth unit \"byte/s\", \
-                    supported units: bps, kbps, Mbps, Gbps, Tbps"
+                    supported units: bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps"
+        );
+
+        assert_eq!(
+            parse_bandwidth("").unwrap_err().to_string(),
+            "expected a bandwidth, got an empty string"
         );
+        assert_eq!(
+            parse_bandwidth(" \t\n").unwrap_err(),
+            Error::OnlyWhitespace { len: 3 }
+        );
+        assert_eq!(
+            parse_bandwidth("\u{a0}").unwrap_err().to_string(),
+            "expected a bandwidth, got only whitespace (2 bytes)"
+        );
+    }
+
+    #[test]
+    fn test_suggestion() {
+        let suggestion = |s| {
+            parse_bandwidth(s)
+                .unwrap_err()
+                .suggestion()
+                .map(str::to_string)
+        };
+        assert_eq!(suggestion("10Mpbs").as_deref(), Some("Mbps"));
+        assert_eq!(suggestion("10 GBPS").as_deref(), Some("Gbps"));
+        assert_eq!(suggestion("10kbs").as_deref(), Some("kbps"));
+        assert_eq!(suggestion("10 byte/s"), None);
+        assert_eq!(suggestion("10 x"), None);
+        assert_eq!(suggestion("10"), None);
+        assert_eq!(suggestion("10Mbps!"), None);
+        assert_eq!(
+            parse_bandwidth("10 Mbsp").unwrap_err().to_string(),
+            "unknown bandwidth unit \"Mbsp\", did you mean \"Mbps\"?"
+        );
+
+        let err = parse_bandwidth_borrowed("1 Tbp").unwrap_err();
+        assert_eq!(err.suggestion(), Some("Tbps"));
+        assert_eq!(err.to_string(), err.into_owned().to_string());
+        assert_eq!(
+            parse_bandwidth_borrowed("1").unwrap_err().suggestion(),
+            None
+        );
+
+        // Equally close to several units
+        assert_eq!(closest_unit("xbps", ["kbps", "Mbps"]), None);
+        assert_eq!(closest_unit("Kbps", ["kbps", "Mbps"]), Some("kbps"));
+        assert_eq!(closest_unit("", ["b"]), None);
+
+        // Units too long to be compared
+        let long = "megabitspersecondandthensomemores";
+        assert_eq!(closest_unit(&long[1..], [long]), None);
+        assert_eq!(closest_unit(long, [long, "Mbps"]), None);
+        assert_eq!(edit_distance(&['a', 'b', 'c'], &['b', 'a', 'c', 'd']), 2);
     }
 
     #[test]
@@ -917,4 +3554,450 @@ mod tests {
             TestDecimal::from(format_bandwidth(Bandwidth::new(9420, 0))).to_string(),
         );
     }
+
+    #[test]
+    fn test_format_aligned() {
+        let options = FormatOptions::new();
+        assert_eq!(format_aligned(&[], &options), Vec::<String>::new());
+        assert_eq!(
+            format_aligned(&[Bandwidth::new(0, 0), Bandwidth::new(0, 5)], &options),
+            ["0.00bps", "5.00bps"]
+        );
+        assert_eq!(
+            format_aligned(
+                &[Bandwidth::new(1, 250_000_000), Bandwidth::from_kbps(15)],
+                &options.precision(5)
+            ),
+            ["1.25000Gbps", "0.00002Gbps"]
+        );
+        // Ties to even
+        assert_eq!(
+            format_aligned(
+                &[Bandwidth::from_bps(2_500), Bandwidth::from_bps(3_500)],
+                &options.precision(0)
+            ),
+            ["2kbps", "4kbps"]
+        );
+        assert_eq!(
+            format_aligned(
+                &[Bandwidth::new(12_000, 0), Bandwidth::from_bps(999_999_999)],
+                &options.show_unit(false)
+            ),
+            ["12.00", " 0.00"]
+        );
+        // The unit is chosen after rounding
+        assert_eq!(
+            format_aligned(&[Bandwidth::from_bps(999_999)], &options),
+            ["1.00Mbps"]
+        );
+        assert_eq!(
+            format_aligned(
+                &[Bandwidth::from_bps(999_994), Bandwidth::from_bps(500)],
+                &options
+            ),
+            ["999.99kbps", "  0.50kbps"]
+        );
+        // Ties to even
+        assert_eq!(
+            format_aligned(&[Bandwidth::from_bps(999_500)], &options.precision(0)),
+            ["1Mbps"]
+        );
+        let tera = Bandwidth::from_bps(999_999_999_999_999);
+        assert_eq!(format_aligned(&[tera], &options), ["1.00Pbps"]);
+        // Unless no larger unit is allowed
+        assert_eq!(
+            format_aligned(&[tera], &options.max_unit(Unit::Tbps)),
+            ["1000.00Tbps"]
+        );
+        // Rounded down at the top of the range, so that it is parsed back
+        let values = format_aligned(
+            &[Bandwidth::MAX, Bandwidth::new(5, 0)],
+            &options.precision(2),
+        );
+        assert_eq!(values, ["18446744073.70Ebps", "          0.00Ebps"]);
+        assert!(parse_bandwidth(values[0].trim_start()).is_ok());
+    }
+
+    #[test]
+    fn test_format_sum() {
+        let options = FormatOptions::new();
+        assert_eq!(format_sum(&[], &options), "0bps");
+        assert_eq!(
+            format_sum(&[Bandwidth::from_kbps(1)], &options),
+            "1kbps = 1kbps"
+        );
+        assert_eq!(
+            format_sum(
+                &[
+                    Bandwidth::from_gbps(1),
+                    Bandwidth::new(0, 0),
+                    Bandwidth::from_bps(7)
+                ],
+                &options
+            ),
+            "1Gbps + 0bps + 7bps = 1.000000007Gbps"
+        );
+        assert_eq!(
+            format_sum(&[Bandwidth::MAX, Bandwidth::MAX], &options),
+            "18446744073.709551615999999999Ebps + 18446744073.709551615999999999Ebps \
+             = 36893488147.419103231999999998Ebps"
+        );
+    }
+
+    #[test]
+    fn test_format_eta() {
+        let eta =
+            |bytes, bps| format_eta(size::DataSize::from_bytes(bytes), Bandwidth::from_bps(bps));
+        assert_eq!(eta(1, 8), "about 1s remaining at 1B/s");
+        // Rounded up
+        assert_eq!(eta(1, 80), "about 1s remaining at 10B/s");
+        assert_eq!(eta(11, 80), "about 2s remaining at 10B/s");
+        assert_eq!(eta(60, 8), "about 1m remaining at 1B/s");
+        assert_eq!(eta(3_599, 8), "about 59m 59s remaining at 1B/s");
+        assert_eq!(eta(3_661, 8), "about 1h 1m remaining at 1B/s");
+        assert_eq!(eta(90_000, 8), "about 1d 1h remaining at 1B/s");
+        assert_eq!(eta(365 * 86_400, 8), "about 365d remaining at 1B/s");
+        assert_eq!(
+            eta(365 * 86_400 + 1, 8),
+            "more than a year remaining at 1B/s"
+        );
+        assert_eq!(eta(0, 0), "nothing remaining");
+        assert_eq!(eta(1_024, 7), "stalled with 1kiB remaining");
+        assert_eq!(
+            format_eta(size::DataSize::from_bytes(u64::MAX), Bandwidth::MAX),
+            "about 1s remaining at 16777216TiB/s"
+        );
+    }
+
+    #[test]
+    fn test_unit_names() {
+        for unit in Unit::ALL {
+            assert_eq!(si_unit(unit.name()), Some(unit.exponent()));
+            assert_eq!(iso_unit(unit.iso_name()), Some((unit.exponent(), false)));
+            assert_eq!(Unit::from_exponent(unit.exponent()), unit);
+            assert_eq!(unit.to_string(), unit.name());
+        }
+        assert_eq!(fuzzy_si_unit("Pbsp"), Some(Unit::Pbps.exponent()));
+        assert_eq!(fuzzy_si_unit("MBIT/s"), Some(Unit::Mbps.exponent()));
+        for unit in ["GB/s", "Gbyte/s", "MB/s", "KBps", "MBYTES/s", "gByte/s"] {
+            assert_eq!(fuzzy_si_unit(unit), None, "{}", unit);
+        }
+        assert_eq!(
+            parse_bandwidth("1x").unwrap_err().to_string(),
+            "unknown bandwidth unit \"x\", supported units: bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps"
+        );
+    }
+
+    #[test]
+    fn test_parse_borrowed() {
+        for s in [
+            "",
+            "  ",
+            "1",
+            "10 Mbsp",
+            "1Mbps x",
+            "10Mbps 5",
+            "99999999999999999999bps",
+        ] {
+            assert_eq!(
+                parse_bandwidth_borrowed(s).map_err(Error::from),
+                parse_bandwidth(s)
+            );
+            if let Err(err) = parse_bandwidth_borrowed(s) {
+                assert_eq!(err.to_string(), parse_bandwidth(s).unwrap_err().to_string());
+            }
+        }
+        let src = String::from("1Gbps 10Mbsp");
+        match parse_bandwidth_borrowed(&src) {
+            Err(ParseError::UnknownUnit { unit, .. }) => {
+                assert_eq!(unit, "Mbsp");
+                assert!(std::ptr::eq(unit, &src[8..]));
+            }
+            res => panic!("unexpected {:?}", res),
+        }
+        assert_eq!(
+            ParseOptions::new().parse_borrowed("10 Mb"),
+            Err(ParseError::UnknownUnit {
+                start: 3,
+                end: 5,
+                unit: "Mb",
+                value: 10
+            })
+        );
+        let system_unit = Error::UnknownSystemUnit {
+            start: 2,
+            end: 4,
+            unit: "E9".to_string(),
+            value: 10,
+            supported: "E1, T1".to_string(),
+        };
+        assert_eq!(
+            borrow_error(system_unit, "10E9"),
+            ParseError::UnknownUnit {
+                start: 2,
+                end: 4,
+                unit: "E9",
+                value: 10
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(
+            parse_bandwidth_bytes(b"1Gbps 5bps"),
+            Ok(Bandwidth::new(1, 5))
+        );
+        assert_eq!(
+            parse_bandwidth_bytes(b"1Gbps \xe2\x82"),
+            Err(Error::InvalidCharacter(6))
+        );
+        assert_eq!(
+            parse_bandwidth_bytes("1Gbps \u{e9}".as_bytes()),
+            Err(Error::NumberExpected(6))
+        );
+        assert_eq!(parse_bandwidth_bytes(b""), Err(Error::Empty));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_os() {
+        assert_eq!(
+            parse_bandwidth_os(OsStr::new("  3kbps")),
+            Ok(Bandwidth::from_kbps(3))
+        );
+        assert_eq!(
+            parse_bandwidth_os(OsStr::new("3kbps\u{e9}")).map_err(|err| err.to_string()),
+            parse_bandwidth("3kbps\u{e9}").map_err(|err| err.to_string())
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            assert_eq!(
+                parse_bandwidth_os(OsStr::from_bytes(b"12\xffkbps")),
+                Err(Error::InvalidCharacter(2))
+            );
+        }
+    }
+
+    #[test]
+    fn test_peta_exa() {
+        const PETA: u128 = 1_000_000_000_000_000;
+        for (s, bps) in [
+            ("1Pbps", PETA),
+            ("2.5Pbit/s", 2_500 * PETA / 1_000),
+            ("3pb/s 1bps", 3 * PETA + 1),
+            ("1Ebps", 1_000 * PETA),
+            ("0.000000000000000001Ebps", 1),
+            ("0.0000000000000000019Ebps", 1),
+            ("1.0000000000000000019bps", 1),
+            ("18446744073Ebps", 18_446_744_073_000 * PETA),
+        ] {
+            assert_eq!(parse_bandwidth(s).map(bandwidth_as_bps), Ok(bps), "{}", s);
+        }
+        assert_eq!(
+            ParseOptions::new().iso80000(true).parse("1 PB/s"),
+            Ok(Bandwidth::new(8_000_000, 0))
+        );
+        // Only a value above the maximum bandwidth overflows
+        assert_eq!(
+            parse_bandwidth("18446744073.709551615999999999Ebps"),
+            Ok(Bandwidth::MAX)
+        );
+        assert_eq!(
+            parse_bandwidth("18446744073.709551616Ebps"),
+            Err(Error::NumberOverflow)
+        );
+        assert_eq!(
+            parse_bandwidth("18446744074Ebps"),
+            Err(Error::NumberOverflow)
+        );
+
+        let val = Bandwidth::new(1_500_000, 0);
+        assert_eq!(FormatOptions::new().format(val), "1.5Pbps");
+        assert_eq!(
+            FormatOptions::new()
+                .iso80000(true)
+                .format(Bandwidth::new(2_000_000_000, 0)),
+            "2 Ebit/s"
+        );
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(format_bandwidth(val).to_string(), "1.5Pbps");
+        #[cfg(feature = "display-integer")]
+        assert_eq!(format_bandwidth(val).to_string(), "1Pbps 500Tbps");
+        assert_eq!(
+            DecimalBandwidth(Bandwidth::MAX).to_string(),
+            "18446744073.709551615999999999Ebps"
+        );
+    }
+
+    #[test]
+    fn test_spelled_units() {
+        for (s, bps) in [
+            ("10 megabits per second", 10_000_000),
+            ("1 Megabit per second", 1_000_000),
+            ("1.5 GIGABITS/S", 1_500_000_000),
+            ("2 kilobits/sec 5 bits per second", 2_005),
+            ("36 gigabits per hour", 10_000_000),
+            ("1 terabit\tper\nday", 11_574_074),
+            ("3 exabits/second", 3_000_000_000_000_000_000),
+        ] {
+            assert_eq!(parse_bandwidth(s).map(bandwidth_as_bps), Ok(bps), "{}", s);
+        }
+        let options = ParseOptions::new().quadrant_units(true);
+        assert_eq!(
+            options.parse("4 mebibytes/s"),
+            Ok(Bandwidth::from_bps(4 * 8 * 1024 * 1024))
+        );
+        assert_eq!(
+            options.parse("1 kilobyte per second 1 kibibit per second"),
+            Ok(Bandwidth::from_bps(8_000 + 1_024))
+        );
+        // A Byte spelled out is never read as a bit
+        let options = options.bit_byte_policy(BitBytePolicy::Reject);
+        assert_eq!(
+            options.parse("2 megabytes per second"),
+            Ok(Bandwidth::from_mbps(16))
+        );
+        assert_eq!(
+            ParseOptions::new()
+                .iso80000(true)
+                .parse("1 kilobit per second"),
+            Ok(Bandwidth::from_kbps(1))
+        );
+        assert_eq!(
+            ParseOptions::new()
+                .strict_spans(true)
+                .parse("1 megabit per second 1 kilobit per second"),
+            Ok(Bandwidth::from_kbps(1_001))
+        );
+
+        // A quantity without an interval is not a rate
+        for s in ["10 megabits", "10 megabits per port"] {
+            assert_eq!(
+                parse_bandwidth(s),
+                Err(Error::UnknownUnit {
+                    start: 3,
+                    end: 11,
+                    unit: "megabits".to_string(),
+                    value: 10,
+                }),
+                "{}",
+                s
+            );
+        }
+        // The units in Bytes need the quadrant units
+        assert_eq!(
+            parse_bandwidth("4 mebibytes per second"),
+            Err(Error::UnknownUnit {
+                start: 2,
+                end: 22,
+                unit: "mebibytes per second".to_string(),
+                value: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_largest_unit() {
+        for (bps, unit, mantissa) in [
+            (999, Unit::Bps, 999.0),
+            (1_000, Unit::Kbps, 1.0),
+            (999_999_999, Unit::Mbps, 999.999999),
+            (2_340_000_000, Unit::Gbps, 2.34),
+            (9_420_000_000_001, Unit::Tbps, 9.420000000001),
+        ] {
+            let val = Bandwidth::from_bps(bps);
+            assert_eq!(largest_unit(val), (unit, mantissa));
+            // Same unit as the formatting
+            #[cfg(not(feature = "display-integer"))]
+            assert!(format_bandwidth(val)
+                .to_string()
+                .ends_with(&unit.to_string()));
+        }
+        let (unit, mantissa) = largest_unit(Bandwidth::MAX);
+        assert_eq!(unit, Unit::Ebps);
+        assert!((mantissa - 18_446_744_073.709_552).abs() < 1e-6);
+        assert_eq!(Unit::Gbps.bps(), 1_000_000_000);
+    }
+
+    #[test]
+    fn test_formatted_bandwidth_from_str() {
+        let formatted: FormattedBandwidth = "2Gbps 340Mbps".parse().unwrap();
+        assert_eq!(*formatted, Bandwidth::new(2, 340_000_000));
+        let formatted: std::collections::HashSet<_> = ["2340Mbps", "2.34Gbps", "2Gbps 340Mbps"]
+            .iter()
+            .map(|s| s.parse::<FormattedBandwidth>().unwrap())
+            .collect();
+        assert_eq!(formatted.len(), 1);
+        assert_eq!(
+            "7 bps".parse::<FormattedBandwidth>().map(|f| *f),
+            Ok(Bandwidth::from_bps(7))
+        );
+        assert_eq!(
+            "1MiB/s".parse::<FormattedBandwidth>().map(|f| *f),
+            parse_bandwidth("1MiB/s")
+        );
+        for val in [
+            Bandwidth::new(0, 0),
+            Bandwidth::from_bps(15),
+            Bandwidth::new(8_700, 32_000_000),
+        ] {
+            let s = String::from(format_bandwidth(val));
+            assert_eq!(s.parse::<FormattedBandwidth>().map(|f| *f), Ok(val));
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(
+            format!("{:?}", format_bandwidth(Bandwidth::new(1, 500_000_000))),
+            "FormattedBandwidth(1_500_000_000bps = 1.5Gbps)"
+        );
+        assert_eq!(
+            format!("{:?}", format_bandwidth(Bandwidth::from_bps(999))),
+            "FormattedBandwidth(999bps = 999bps)"
+        );
+        assert_eq!(
+            format!("{:?}", format_bandwidth(Bandwidth::new(0, 0))),
+            "FormattedBandwidth(0bps = 0bps)"
+        );
+        assert_eq!(Grouped(1_000).to_string(), "1_000");
+        assert_eq!(Grouped(123_456_789).to_string(), "123_456_789");
+        assert_eq!(Grouped(12_345).to_string(), "12_345");
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            FormattedBandwidth::try_from("2Gbps 340Mbps").map(|f| *f),
+            Ok(Bandwidth::new(2, 340_000_000))
+        );
+        assert_eq!(
+            FormattedBandwidth::try_from(String::from("7bps")).map(|f| *f),
+            Ok(Bandwidth::from_bps(7))
+        );
+        assert_eq!(
+            HumanBandwidth::try_from("10 Mbps"),
+            Ok(HumanBandwidth(Bandwidth::from_mbps(10)))
+        );
+        assert_eq!(
+            HumanBandwidth::try_from(String::from("")),
+            Err(Error::Empty)
+        );
+        assert!(matches!(
+            HumanBandwidth::try_from("1 Mpbs"),
+            Err(Error::UnknownUnit { .. })
+        ));
+
+        fn convert<T: TryFrom<&'static str, Error = Error>>(s: &'static str) -> Result<T, Error> {
+            T::try_from(s)
+        }
+        let human: HumanBandwidth = convert("1.5kbps").unwrap();
+        assert_eq!(Bandwidth::from(human), Bandwidth::from_bps(1_500));
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(String::from(human), "1.5kbps");
+        #[cfg(feature = "display-integer")]
+        assert_eq!(String::from(human), "1kbps 500bps");
+    }
 }
@@ -0,0 +1,318 @@
+//! Bandwidths checked at compile time
+//!
+//! [`bandwidth!`](crate::bandwidth!) parses a string in a constant, so that a
+//! typo in a rate written in the source code, like `10Mpbs`, fails the build
+//! instead of returning an error at runtime. [`parse_literal`] is the `const`
+//! parser behind it.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::bandwidth;
+//!
+//! const UPLINK: Bandwidth = bandwidth!("1Gbps 500Mbps");
+//! assert_eq!(UPLINK, Bandwidth::new(1, 500_000_000));
+//! assert_eq!(bandwidth!("150Mbps"), Bandwidth::from_mbps(150));
+//! ```
+//!
+//! An invalid literal does not compile:
+//!
+//! ```compile_fail
+//! let rate = human_bandwidth::bandwidth!("10Mpbs");
+//! ```
+
+use bandwidth::Bandwidth;
+
+use crate::{consts::GIGA, parse_fraction, FRACTION_PART_LIMIT};
+
+/// Parses a bandwidth literal at compile time into a [`Bandwidth`]
+///
+/// The literal is checked by [`parse_literal`] when the crate using it is
+/// built, and invalid ones are compile errors.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::bandwidth;
+///
+/// const LIMITS: [Bandwidth; 2] = [bandwidth!("10Mbit/s"), bandwidth!("2.5 Gbps")];
+/// assert_eq!(LIMITS[1], Bandwidth::from_mbps(2_500));
+/// ```
+#[macro_export]
+macro_rules! bandwidth {
+    ($s:expr) => {{
+        const BANDWIDTH: $crate::re::bandwidth::Bandwidth = $crate::literal::parse_literal($s);
+        BANDWIDTH
+    }};
+}
+
+/// The SI units accepted by [`parse_literal`], the spellings of
+/// [`parse_bandwidth`](crate::parse_bandwidth) with their exponent of ten
+const UNITS: [(&str, u32); 39] = [
+    ("bps", 0),
+    ("bit/s", 0),
+    ("b/s", 0),
+    ("kbps", 3),
+    ("Kbps", 3),
+    ("kbit/s", 3),
+    ("Kbit/s", 3),
+    ("kb/s", 3),
+    ("Kb/s", 3),
+    ("Mbps", 6),
+    ("mbps", 6),
+    ("Mbit/s", 6),
+    ("mbit/s", 6),
+    ("Mb/s", 6),
+    ("mb/s", 6),
+    ("Gbps", 9),
+    ("gbps", 9),
+    ("Gbit/s", 9),
+    ("gbit/s", 9),
+    ("Gb/s", 9),
+    ("gb/s", 9),
+    ("Tbps", 12),
+    ("tbps", 12),
+    ("Tbit/s", 12),
+    ("tbit/s", 12),
+    ("Tb/s", 12),
+    ("tb/s", 12),
+    ("Pbps", 15),
+    ("pbps", 15),
+    ("Pbit/s", 15),
+    ("pbit/s", 15),
+    ("Pb/s", 15),
+    ("pb/s", 15),
+    ("Ebps", 18),
+    ("ebps", 18),
+    ("Ebit/s", 18),
+    ("ebit/s", 18),
+    ("Eb/s", 18),
+    ("eb/s", 18),
+];
+
+/// Parses a bandwidth like [`parse_bandwidth`](crate::parse_bandwidth), in a
+/// `const` context
+///
+/// The literal is made of rate spans like `1Gbps 500Mbps`, each one a number
+/// with an optional fractional part, optionally followed by whitespace, and a
+/// SI unit of bits. Digits may be separated by `_`. The exponents, the
+/// whitespace inside numbers and the options of
+/// [`ParseOptions`](crate::ParseOptions) are not supported: the literals
+/// which are accepted give the same bandwidth as `parse_bandwidth`.
+///
+/// # Panics
+///
+/// Panics if the literal is invalid, which is a compile error when it is
+/// evaluated in a constant, e.g. by [`bandwidth!`](crate::bandwidth!).
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::literal::parse_literal;
+///
+/// const RATE: Bandwidth = parse_literal("1_500.5 kbit/s");
+/// assert_eq!(RATE, Bandwidth::from_bps(1_500_500));
+/// ```
+pub const fn parse_literal(s: &str) -> Bandwidth {
+    let bytes = s.as_bytes();
+    let mut at = skip_whitespace(bytes, 0);
+    if at == bytes.len() {
+        panic!("invalid bandwidth literal: empty");
+    }
+    let mut total: u128 = 0;
+    while at < bytes.len() {
+        if !bytes[at].is_ascii_digit() {
+            panic!("invalid bandwidth literal: expected a number");
+        }
+        let mut n: u128 = 0;
+        while at < bytes.len() && (bytes[at].is_ascii_digit() || bytes[at] == b'_') {
+            if bytes[at] != b'_' {
+                n = match n.checked_mul(10) {
+                    Some(n) => match n.checked_add((bytes[at] - b'0') as u128) {
+                        Some(n) => n,
+                        None => panic!("invalid bandwidth literal: number too large"),
+                    },
+                    None => panic!("invalid bandwidth literal: number too large"),
+                };
+            }
+            at += 1;
+        }
+        let mut fraction: u64 = 0;
+        let mut fraction_cnt: u32 = 0;
+        if at < bytes.len() && bytes[at] == b'.' {
+            at += 1;
+            while at < bytes.len() && (bytes[at].is_ascii_digit() || bytes[at] == b'_') {
+                // The following digits are ignored
+                if bytes[at] != b'_' && fraction_cnt < FRACTION_PART_LIMIT {
+                    fraction = fraction * 10 + (bytes[at] - b'0') as u64;
+                    fraction_cnt += 1;
+                }
+                at += 1;
+            }
+        }
+        let start = skip_whitespace(bytes, at);
+        let mut end = start;
+        while end < bytes.len() && (bytes[end].is_ascii_alphabetic() || bytes[end] == b'/') {
+            end += 1;
+        }
+        if start == end {
+            if end == bytes.len() {
+                panic!("invalid bandwidth literal: expected a unit");
+            }
+            panic!("invalid bandwidth literal: invalid character");
+        }
+        let exponent = unit_exponent(bytes, start, end);
+        let fraction = parse_fraction(fraction, fraction_cnt, exponent) as u128;
+        let bps = match n.checked_mul(10u128.pow(exponent)) {
+            Some(bps) => bps.checked_add(fraction),
+            None => None,
+        };
+        total = match bps {
+            Some(bps) => match total.checked_add(bps) {
+                Some(total) => total,
+                None => panic!("invalid bandwidth literal: number too large"),
+            },
+            None => panic!("invalid bandwidth literal: number too large"),
+        };
+        // The next span may follow the unit without whitespace
+        if end < bytes.len() && !bytes[end].is_ascii_whitespace() && !bytes[end].is_ascii_digit() {
+            panic!("invalid bandwidth literal: invalid character");
+        }
+        at = skip_whitespace(bytes, end);
+    }
+    let gbps = total / GIGA as u128;
+    if gbps > u64::MAX as u128 {
+        panic!("invalid bandwidth literal: number too large");
+    }
+    Bandwidth::new(gbps as u64, (total % GIGA as u128) as u32)
+}
+
+/// The offset of the first byte from `at` which is not ASCII whitespace
+const fn skip_whitespace(bytes: &[u8], mut at: usize) -> usize {
+    while at < bytes.len() && bytes[at].is_ascii_whitespace() {
+        at += 1;
+    }
+    at
+}
+
+/// The exponent of ten of the unit between `start` and `end`
+const fn unit_exponent(bytes: &[u8], start: usize, end: usize) -> u32 {
+    let mut i = 0;
+    while i < UNITS.len() {
+        let (unit, exponent) = UNITS[i];
+        if eq_at(bytes, start, end, unit.as_bytes()) {
+            return exponent;
+        }
+        i += 1;
+    }
+    panic!("invalid bandwidth literal: unknown unit")
+}
+
+/// Whether the bytes between `start` and `end` are `unit`
+const fn eq_at(bytes: &[u8], start: usize, end: usize, unit: &[u8]) -> bool {
+    if end - start != unit.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < unit.len() {
+        if bytes[start + i] != unit[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_bandwidth_borrowed, si_unit};
+
+    #[test]
+    fn test_parse_literal() {
+        for literal in [
+            "0bps",
+            "150Mbps",
+            "1Gbps 500Mbps",
+            "1Gbps500Mbps",
+            "  2.5 Gbps\t",
+            "1_000_000 bit/s",
+            "1.5_5 Mb/s",
+            "150.02456kbps",
+            "0.000000000000000001Ebps",
+            "1.0000000000000000019Ebps",
+            "1Mbps 1Mbps",
+            "18446744073709551615Gbps 999999999bps",
+            "1000000000000000000000bps",
+            "18446744073709551615999Mbps",
+        ] {
+            assert_eq!(
+                Ok(parse_literal(literal)),
+                parse_bandwidth_borrowed(literal),
+                "{}",
+                literal
+            );
+        }
+    }
+
+    #[test]
+    fn test_constant() {
+        const RATE: Bandwidth = crate::bandwidth!("9Tbps 420Gbps");
+        assert_eq!(RATE, Bandwidth::new(9420, 0));
+        assert_eq!(crate::bandwidth!("32Mbps"), Bandwidth::from_mbps(32));
+    }
+
+    #[test]
+    fn test_invalid() {
+        for (literal, message) in [
+            ("", "empty"),
+            (" \t", "empty"),
+            ("Mbps", "expected a number"),
+            ("1Mbps kbps", "expected a number"),
+            ("10", "expected a unit"),
+            ("10Mpbs", "unknown unit"),
+            ("10MiB/s", "unknown unit"),
+            ("10Mbps!", "invalid character"),
+            ("1.2.3Mbps", "invalid character"),
+            ("1 000Mbps", "invalid character"),
+            ("1.5e3Mbps", "unknown unit"),
+            ("18446744073709551616000000000bps", "number too large"),
+            ("18446744073709551615Gbps 1Gbps", "number too large"),
+            (
+                "340282366920938463463374607431768211456bps",
+                "number too large",
+            ),
+        ] {
+            let panic = std::panic::catch_unwind(|| parse_literal(literal)).unwrap_err();
+            let panic = panic.downcast_ref::<&str>().unwrap();
+            assert_eq!(
+                *panic,
+                format!("invalid bandwidth literal: {}", message),
+                "{}",
+                literal
+            );
+        }
+    }
+
+    #[test]
+    fn test_units() {
+        let prefixes = [
+            "", "k", "K", "m", "M", "g", "G", "t", "T", "p", "P", "e", "E",
+        ];
+        for prefix in prefixes {
+            for suffix in ["bps", "bit/s", "b/s"] {
+                let unit = format!("{}{}", prefix, suffix);
+                let found = UNITS.iter().find(|(name, _)| *name == unit);
+                assert_eq!(
+                    found.map(|(_, exponent)| *exponent),
+                    si_unit(&unit),
+                    "{}",
+                    unit
+                );
+            }
+        }
+    }
+}
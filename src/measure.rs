@@ -0,0 +1,322 @@
+//! Tracking of measured bandwidths over time
+//!
+//! [`WindowedExtrema`] keeps the peak and the trough of the samples measured
+//! over a trailing window, e.g. for alerting on bursts or preparing billing
+//! figures. [`ShardedCounter`] counts the Bytes transferred by many threads
+//! with little contention, and turns them into a rate.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::measure::WindowedExtrema;
+//!
+//! let start = Instant::now();
+//! let mut extrema = WindowedExtrema::new(Duration::from_secs(60));
+//! extrema.record(start, Bandwidth::from_mbps(80));
+//! extrema.record(start + Duration::from_secs(30), Bandwidth::from_mbps(20));
+//! extrema.record(start + Duration::from_secs(70), Bandwidth::from_mbps(50));
+//! // The 80Mbps sample is more than a minute old
+//! assert_eq!(extrema.peak(), Some(Bandwidth::from_mbps(50)));
+//! assert_eq!(extrema.trough(), Some(Bandwidth::from_mbps(20)));
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_from_bps, consts::BITS_PER_BYTE};
+
+/// The peak and the trough of bandwidth samples over a trailing window
+///
+/// Samples are kept in two monotonic queues, so that recording a sample takes
+/// an amortized constant time and the extrema are read in constant time.
+#[derive(Debug, Clone)]
+pub struct WindowedExtrema {
+    window: Duration,
+    last: Option<Instant>,
+    /// Samples in decreasing order of bandwidth, the peak first
+    peaks: VecDeque<(Instant, Bandwidth)>,
+    /// Samples in increasing order of bandwidth, the trough first
+    troughs: VecDeque<(Instant, Bandwidth)>,
+}
+
+impl WindowedExtrema {
+    /// Create a tracker of the samples at most `window` old
+    pub fn new(window: Duration) -> Self {
+        WindowedExtrema {
+            window,
+            last: None,
+            peaks: VecDeque::new(),
+            troughs: VecDeque::new(),
+        }
+    }
+
+    /// The length of the trailing window
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Records a bandwidth measured at `at`, and forgets the samples older
+    /// than the window before `at`
+    ///
+    /// Samples are expected in chronological order: a sample older than the
+    /// previous one is recorded as if it was measured with the previous one.
+    pub fn record(&mut self, at: Instant, bw: Bandwidth) {
+        let at = match self.last {
+            Some(last) if at < last => last,
+            _ => at,
+        };
+        self.last = Some(at);
+        while matches!(self.peaks.back(), Some((_, peak)) if *peak <= bw) {
+            self.peaks.pop_back();
+        }
+        self.peaks.push_back((at, bw));
+        while matches!(self.troughs.back(), Some((_, trough)) if *trough >= bw) {
+            self.troughs.pop_back();
+        }
+        self.troughs.push_back((at, bw));
+        self.expire(at);
+    }
+
+    /// Forgets the samples older than the window before `now`
+    ///
+    /// Call it before reading the extrema when no sample was recorded
+    /// recently.
+    pub fn expire(&mut self, now: Instant) {
+        let window = self.window;
+        let expired = |(at, _): &(Instant, Bandwidth)| now.saturating_duration_since(*at) > window;
+        while self.peaks.front().is_some_and(expired) {
+            self.peaks.pop_front();
+        }
+        while self.troughs.front().is_some_and(expired) {
+            self.troughs.pop_front();
+        }
+    }
+
+    /// The largest bandwidth within the window, if any
+    pub fn peak(&self) -> Option<Bandwidth> {
+        self.peaks.front().map(|(_, bw)| *bw)
+    }
+
+    /// The smallest bandwidth within the window, if any
+    pub fn trough(&self) -> Option<Bandwidth> {
+        self.troughs.front().map(|(_, bw)| *bw)
+    }
+
+    /// Forgets every sample
+    pub fn clear(&mut self) {
+        self.last = None;
+        self.peaks.clear();
+        self.troughs.clear();
+    }
+}
+
+/// A counter of Bytes shared by many threads, e.g. the workers of a proxy
+///
+/// Each thread adds to one of several shards with a relaxed atomic add, the
+/// shards being on distinct cache lines so that threads don't contend.
+/// [`aggregate_rate`](Self::aggregate_rate) sums and resets the shards.
+///
+/// # Example
+///
+/// ```
+/// use std::{sync::Arc, thread, time::Duration};
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{format_bandwidth, measure::ShardedCounter};
+///
+/// let counter = Arc::new(ShardedCounter::new(4));
+/// let workers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let counter = Arc::clone(&counter);
+///         thread::spawn(move || (0..1_000).for_each(|_| counter.add(1_500)))
+///     })
+///     .collect();
+/// workers.into_iter().for_each(|worker| worker.join().unwrap());
+///
+/// // Called once per second, e.g. by a reporting task
+/// let rate = counter.aggregate_rate(Duration::from_secs(1));
+/// assert_eq!(format_bandwidth(rate).to_string(), "48Mbps");
+/// assert_eq!(counter.total(), 0);
+/// ```
+#[derive(Debug)]
+pub struct ShardedCounter {
+    shards: Box<[Shard]>,
+}
+
+/// A shard of a [`ShardedCounter`], alone on its cache line
+#[derive(Debug, Default)]
+#[repr(align(64))]
+struct Shard(AtomicU64);
+
+impl ShardedCounter {
+    /// Create a counter with `shards` shards, at least 1
+    ///
+    /// One shard per CPU core is usually enough, see
+    /// [`default`](Self::default).
+    pub fn new(shards: usize) -> Self {
+        ShardedCounter {
+            shards: (0..shards.max(1)).map(|_| Shard::default()).collect(),
+        }
+    }
+
+    /// The number of shards
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Adds `bytes` to the shard of the current thread
+    ///
+    /// The threads are assigned shards in turn, the first time they add to
+    /// any counter.
+    pub fn add(&self, bytes: u64) {
+        self.add_to(thread_index(), bytes);
+    }
+
+    /// Adds `bytes` to a given shard, e.g. the one of a flow or a CPU, modulo
+    /// the number of shards
+    pub fn add_to(&self, shard: usize, bytes: u64) {
+        self.shards[shard % self.shards.len()]
+            .0
+            .fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// The Bytes counted since the last aggregation, saturating at
+    /// `u64::MAX`
+    pub fn total(&self) -> u64 {
+        let total: u128 = self
+            .shards
+            .iter()
+            .map(|shard| u128::from(shard.0.load(Ordering::Relaxed)))
+            .sum();
+        u64::try_from(total).unwrap_or(u64::MAX)
+    }
+
+    /// Resets the shards and returns the rate of the Bytes they counted over
+    /// `window`, the time since the last aggregation
+    ///
+    /// The rate is rounded down to the bit per second, and saturates at
+    /// [`Bandwidth::MAX`], e.g. for an empty window with Bytes counted.
+    pub fn aggregate_rate(&self, window: Duration) -> Bandwidth {
+        let bytes: u128 = self
+            .shards
+            .iter()
+            .map(|shard| u128::from(shard.0.swap(0, Ordering::Relaxed)))
+            .sum();
+        let bits = bytes * u128::from(BITS_PER_BYTE);
+        if bits == 0 {
+            return Bandwidth::new(0, 0);
+        }
+        match window.as_nanos() {
+            0 => Bandwidth::MAX,
+            nanos => bandwidth_from_bps(bits * 1_000_000_000 / nanos).unwrap_or(Bandwidth::MAX),
+        }
+    }
+}
+
+/// One shard per available CPU core
+impl Default for ShardedCounter {
+    fn default() -> Self {
+        ShardedCounter::new(std::thread::available_parallelism().map_or(1, |cores| cores.get()))
+    }
+}
+
+/// The index of the current thread, assigned in turn on the first call
+fn thread_index() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    thread_local! {
+        static INDEX: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    INDEX.with(|index| *index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extrema() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut extrema = WindowedExtrema::new(Duration::from_secs(10));
+        assert_eq!(extrema.peak(), None);
+        assert_eq!(extrema.trough(), None);
+
+        let samples = [5, 3, 8, 8, 1, 6, 7, 2, 9, 4, 4, 6, 5, 3, 7];
+        for (secs, mbps) in samples.iter().enumerate() {
+            extrema.record(at(secs as u64 * 2), Bandwidth::from_mbps(*mbps));
+            // The window covers the last 6 samples
+            let window = &samples[secs.saturating_sub(5)..=secs];
+            assert_eq!(
+                extrema.peak(),
+                window.iter().max().map(|mbps| Bandwidth::from_mbps(*mbps))
+            );
+            assert_eq!(
+                extrema.trough(),
+                window.iter().min().map(|mbps| Bandwidth::from_mbps(*mbps))
+            );
+        }
+
+        extrema.expire(at(33));
+        assert_eq!(extrema.peak(), Some(Bandwidth::from_mbps(7)));
+        assert_eq!(extrema.trough(), Some(Bandwidth::from_mbps(3)));
+        extrema.expire(at(100));
+        assert_eq!(extrema.peak(), None);
+        assert_eq!(extrema.trough(), None);
+    }
+
+    #[test]
+    fn test_out_of_order() {
+        let start = Instant::now();
+        let mut extrema = WindowedExtrema::new(Duration::from_secs(10));
+        extrema.record(start + Duration::from_secs(20), Bandwidth::from_mbps(1));
+        // Recorded at 20s rather than at 0s
+        extrema.record(start, Bandwidth::from_mbps(2));
+        assert_eq!(extrema.peak(), Some(Bandwidth::from_mbps(2)));
+        assert_eq!(extrema.trough(), Some(Bandwidth::from_mbps(1)));
+
+        extrema.clear();
+        assert_eq!(extrema.peak(), None);
+        extrema.record(start, Bandwidth::from_mbps(3));
+        assert_eq!(extrema.peak(), Some(Bandwidth::from_mbps(3)));
+    }
+
+    #[test]
+    fn test_sharded_counter() {
+        let counter = ShardedCounter::new(0);
+        assert_eq!(counter.shards(), 1);
+        assert_eq!(counter.aggregate_rate(Duration::ZERO), Bandwidth::new(0, 0));
+
+        let counter = ShardedCounter::new(3);
+        for shard in 0..6 {
+            counter.add_to(shard, 1_000);
+        }
+        counter.add(250);
+        assert_eq!(counter.total(), 6_250);
+        assert_eq!(
+            counter.aggregate_rate(Duration::from_millis(500)),
+            Bandwidth::from_kbps(100)
+        );
+        assert_eq!(counter.total(), 0);
+
+        counter.add(1);
+        assert_eq!(
+            counter.aggregate_rate(Duration::from_secs(3)),
+            Bandwidth::from_bps(2)
+        );
+        counter.add(1);
+        assert_eq!(counter.aggregate_rate(Duration::ZERO), Bandwidth::MAX);
+        for shard in 0..3 {
+            counter.add_to(shard, u64::MAX);
+        }
+        assert_eq!(counter.total(), u64::MAX);
+        assert_eq!(
+            counter.aggregate_rate(Duration::from_nanos(1)),
+            Bandwidth::MAX
+        );
+        assert!(ShardedCounter::default().shards() >= 1);
+    }
+}
@@ -0,0 +1,261 @@
+//! Settings overridden by configuration layers
+//!
+//! Services usually read a rate limit from several layers: a built-in
+//! default, a configuration file, an environment variable and a command line
+//! flag, each one overriding the previous ones. [`resolve`] picks the value of
+//! the last layer which sets it, and [`Layered`] also remembers which layer it
+//! is, so that the effective setting can be logged with its provenance.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::merge::{Layered, Source};
+//!
+//! let limit = Layered::new()
+//!     .with(Source::Default, Some(Bandwidth::from_mbps(1)))
+//!     .parse(Source::File("/etc/app.toml".to_string()), Some("5Mbit/s"))
+//!     .unwrap()
+//!     .parse(Source::Env("RATE_LIMIT".to_string()), Some("10Mbps"))
+//!     .unwrap()
+//!     .parse(Source::Cli("--rate".to_string()), None)
+//!     .unwrap();
+//! assert_eq!(limit.value(), Some(Bandwidth::from_mbps(10)));
+//! assert_eq!(limit.to_string(), "10Mbps (from env RATE_LIMIT)");
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+use bandwidth::Bandwidth;
+
+#[cfg(feature = "std")]
+use crate::parse_bandwidth_os;
+use crate::{format_bandwidth, parse_bandwidth, Error, FormatOptions};
+
+/// Returns the value of the last layer which sets one
+///
+/// The layers are ordered by increasing priority, e.g. the default, the
+/// configuration file, the environment and the command line.
+///
+/// # Example
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::merge::resolve;
+///
+/// let default = Some(Bandwidth::from_mbps(1));
+/// let env = Some(Bandwidth::from_mbps(10));
+/// assert_eq!(resolve(&[default, env, None]), env);
+/// assert_eq!(resolve(&[None, None]), None);
+/// ```
+pub fn resolve(layers: &[Option<Bandwidth>]) -> Option<Bandwidth> {
+    layers.iter().rev().find_map(|layer| *layer)
+}
+
+/// Where a setting comes from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// The built-in default, written `default`
+    Default,
+    /// A configuration file, written `file` and its path
+    File(String),
+    /// An environment variable, written `env` and its name
+    Env(String),
+    /// A command line argument, written `CLI` and the flag
+    Cli(String),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => f.write_str("default"),
+            Source::File(path) => write!(f, "file {}", path),
+            Source::Env(name) => write!(f, "env {}", name),
+            Source::Cli(flag) => write!(f, "CLI {}", flag),
+        }
+    }
+}
+
+/// Error parsing the value of a layer
+#[derive(Debug, PartialEq, Clone)]
+pub struct LayerError {
+    /// The layer of the invalid value
+    pub source: Source,
+    /// Error parsing the value
+    pub error: Error,
+}
+
+#[cfg(feature = "std")]
+impl StdError for LayerError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for LayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid bandwidth in {}: {}", self.source, self.error)
+    }
+}
+
+/// A setting overridden by layers, remembering the layer of its value
+///
+/// Layers are added by increasing priority, a layer without a value keeping
+/// the value of the previous ones. It is written as the value and its
+/// provenance, like `10Mbps (from env RATE_LIMIT)`, or `unset`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Layered {
+    value: Option<(Bandwidth, Source)>,
+}
+
+impl Layered {
+    /// Create a setting without value
+    pub fn new() -> Self {
+        Layered { value: None }
+    }
+
+    /// Overrides the setting with `value` from `source`, if it is set
+    pub fn with(mut self, source: Source, value: Option<Bandwidth>) -> Self {
+        if let Some(value) = value {
+            self.value = Some((value, source));
+        }
+        self
+    }
+
+    /// Overrides the setting with `value` from `source` parsed with
+    /// [`parse_bandwidth`], if it is set
+    pub fn parse(self, source: Source, value: Option<&str>) -> Result<Self, LayerError> {
+        let value = match value {
+            Some(value) => parse_bandwidth(value).map_err(|error| LayerError {
+                source: source.clone(),
+                error,
+            })?,
+            None => return Ok(self),
+        };
+        Ok(self.with(source, Some(value)))
+    }
+
+    /// Overrides the setting with the environment variable `name`, if it is
+    /// set
+    ///
+    /// The value is parsed with [`parse_bandwidth_os`], so that a value which
+    /// is not valid Unicode is an error rather than unset.
+    #[cfg(feature = "std")]
+    pub fn env(self, name: &str) -> Result<Self, LayerError> {
+        let source = Source::Env(name.to_string());
+        let value = match std::env::var_os(name) {
+            Some(value) => parse_bandwidth_os(&value).map_err(|error| LayerError {
+                source: source.clone(),
+                error,
+            })?,
+            None => return Ok(self),
+        };
+        Ok(self.with(source, Some(value)))
+    }
+
+    /// The effective value
+    pub fn value(&self) -> Option<Bandwidth> {
+        self.value.as_ref().map(|(value, _)| *value)
+    }
+
+    /// The layer of the effective value
+    pub fn source(&self) -> Option<&Source> {
+        self.value.as_ref().map(|(_, source)| source)
+    }
+
+    /// Writes the value with `options` and its provenance, like
+    /// `10 Mbit/s (from env RATE_LIMIT)`, or `unset`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{merge::{Layered, Source}, FormatOptions};
+    ///
+    /// let limit = Layered::new().with(Source::Cli("--rate".to_string()), Some(Bandwidth::from_mbps(10)));
+    /// let options = FormatOptions::new().iso80000(true);
+    /// assert_eq!(limit.describe(&options), "10 Mbit/s (from CLI --rate)");
+    /// ```
+    pub fn describe(&self, options: &FormatOptions) -> String {
+        match &self.value {
+            Some((value, source)) => format!("{} (from {})", options.format(*value), source),
+            None => "unset".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Layered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some((value, source)) => {
+                write!(f, "{} (from {})", format_bandwidth(*value), source)
+            }
+            None => f.write_str("unset"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let bps = |bps| Some(Bandwidth::from_bps(bps));
+        assert_eq!(resolve(&[]), None);
+        assert_eq!(resolve(&[bps(1), bps(2), bps(3)]), bps(3));
+        assert_eq!(resolve(&[bps(1), None, bps(3), None]), bps(3));
+    }
+
+    #[test]
+    fn test_layered() {
+        let limit = Layered::new();
+        assert_eq!(limit.value(), None);
+        assert_eq!(limit.to_string(), "unset");
+        assert_eq!(limit.describe(&FormatOptions::new()), "unset");
+
+        let limit = limit
+            .with(Source::Default, Some(Bandwidth::from_kbps(64)))
+            .with(Source::Env("RATE".to_string()), None);
+        assert_eq!(limit.source(), Some(&Source::Default));
+        assert_eq!(limit.to_string(), "64kbps (from default)");
+
+        let file = Source::File("app.toml".to_string());
+        let error = limit.clone().parse(file.clone(), Some("fast")).unwrap_err();
+        assert_eq!(
+            error,
+            LayerError {
+                source: file.clone(),
+                error: Error::NumberExpected(0),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid bandwidth in file app.toml: expected number at 0"
+        );
+        let limit = limit.parse(file, Some("2Mbps")).unwrap();
+        assert_eq!(limit.to_string(), "2Mbps (from file app.toml)");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_env() {
+        let name = "HUMAN_BANDWIDTH_TEST_MERGE_ENV";
+        let limit = Layered::new().with(Source::Default, Some(Bandwidth::from_kbps(1)));
+        assert_eq!(limit.clone().env(name), Ok(limit.clone()));
+        std::env::set_var(name, "3 Mbps");
+        assert_eq!(
+            limit.clone().env(name).map(|limit| limit.to_string()),
+            Ok(format!("3Mbps (from env {})", name))
+        );
+        std::env::set_var(name, "3Mbs");
+        assert!(limit.env(name).is_err());
+        std::env::remove_var(name);
+    }
+}
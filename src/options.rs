@@ -0,0 +1,3752 @@
+#[cfg(feature = "alloc")]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+use bandwidth::Bandwidth;
+
+#[cfg(feature = "alloc")]
+use crate::{
+    bandwidth_as_bps, bandwidth_from_bps, borrow_error, escapes::Decoded, iso_unit_name,
+    range::BandwidthRange, rounded_bps, si_exponent_for, si_unit_name, write_ratio, Error, Unit,
+};
+use crate::{
+    calendar::CalendarDate, spelled::UnitBuf, Interval, ParseError, Parser, FRACTION_PART_LIMIT,
+};
+
+/// Options to customize the parsing of a bandwidth
+///
+/// The default options parse exactly like [`parse_bandwidth`](crate::parse_bandwidth).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{ParseOptions, ParseWarning};
+///
+/// let options = ParseOptions::new().fuzzy(true);
+/// let (val, warnings) = options.parse_with_warnings("10Mpbs").unwrap();
+/// assert_eq!(val, Bandwidth::from_mbps(10));
+/// assert_eq!(warnings[0].to_string(), "unknown unit \"Mpbs\" interpreted as \"Mbps\"");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    fuzzy: bool,
+    iso80000: bool,
+    bps_means_bytes: bool,
+    bit_byte_policy: BitBytePolicy,
+    custom_units: &'static [(&'static str, u64)],
+    bits_per_symbol: Option<u32>,
+    enabled_units: EnabledUnits,
+    unit_aliases: &'static [(&'static str, &'static str)],
+    digit_separators: bool,
+    spaces_in_numbers: bool,
+    newlines: bool,
+    single_spaces: bool,
+    multiple_spans: bool,
+    decimal_comma: bool,
+    digit_grouping: bool,
+    exponent_notation: bool,
+    strict_spans: bool,
+    case_sensitive_prefixes: bool,
+    saturating: bool,
+    #[cfg(feature = "alloc")]
+    decode_escapes: bool,
+    fraction_digits: u32,
+    strict_fraction: bool,
+    month_days: u8,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions::new()
+    }
+}
+
+impl ParseOptions {
+    /// Create the default options
+    pub const fn new() -> Self {
+        ParseOptions {
+            fuzzy: false,
+            iso80000: false,
+            bps_means_bytes: false,
+            bit_byte_policy: BitBytePolicy::CaseSensitive,
+            custom_units: &[],
+            bits_per_symbol: None,
+            enabled_units: EnabledUnits::ALL,
+            unit_aliases: &[],
+            digit_separators: true,
+            spaces_in_numbers: true,
+            newlines: true,
+            single_spaces: false,
+            multiple_spans: true,
+            decimal_comma: false,
+            digit_grouping: false,
+            exponent_notation: true,
+            strict_spans: false,
+            case_sensitive_prefixes: false,
+            saturating: false,
+            #[cfg(feature = "alloc")]
+            decode_escapes: false,
+            fraction_digits: FRACTION_PART_LIMIT,
+            strict_fraction: false,
+            month_days: 30,
+        }
+    }
+
+    /// Create options rejecting the input which is likely a mistake, with
+    /// [`case_sensitive_prefixes`](Self::case_sensitive_prefixes) and
+    /// [`strict_spans`](Self::strict_spans), and accepting only the symbols
+    /// of the units, see [`EnabledUnits::SYMBOLS`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::strict();
+    /// assert_eq!(options.parse("1Gbps 500Mbps"), Ok(Bandwidth::from_mbps(1_500)));
+    /// assert!(matches!(options.parse("10mbps"), Err(Error::UnknownUnit { .. })));
+    /// assert!(matches!(options.parse("1Mbps 1Mbps"), Err(Error::DuplicateUnit { .. })));
+    /// assert!(matches!(options.parse("1 megabit/s"), Err(Error::UnknownUnit { .. })));
+    /// # }
+    /// ```
+    pub const fn strict() -> Self {
+        ParseOptions::new()
+            .case_sensitive_prefixes(true)
+            .strict_spans(true)
+            .enabled_units(EnabledUnits::SYMBOLS)
+    }
+
+    /// Accept units with small typos, e.g. `Mpbs`, `MBit/s` or `Kbps/s`
+    ///
+    /// A unit is accepted when, ignoring the case, it is one or two edits
+    /// (depending on its length) away from a single supported unit. The units
+    /// spelled in Bytes, like `MB/s` or `Gbyte/s`, and the exact units of the
+    /// other systems, like the binary `Mibit/s` or `Tib/s`, are never assumed
+    /// to be SI units of bits and stay unknown, with the closest SI unit as
+    /// the [suggestion](Error::suggestion) if any is close enough. Each
+    /// assumed unit is reported as a [`ParseWarning::AssumedUnit`] by
+    /// [`parse_with_warnings`](Self::parse_with_warnings).
+    ///
+    /// Disabled by default.
+    pub const fn fuzzy(mut self, fuzzy: bool) -> Self {
+        self.fuzzy = fuzzy;
+        self
+    }
+
+    /// Whether near-miss units are accepted
+    pub const fn is_fuzzy(&self) -> bool {
+        self.fuzzy
+    }
+
+    /// Strictly follow the ISO/IEC 80000-13 symbols
+    ///
+    /// Only the `bit/s` and `B/s` units are accepted, with the prefixes `k`,
+    /// `M`, `G`, `T`, `P` and `E`, and a space is required between the number and its
+    /// unit: `1.5 Mbit/s 200 kB/s`. Abbreviations like `Mbps` are rejected.
+    /// The fuzzy mode is ignored.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().iso80000(true);
+    /// assert_eq!(options.parse("10 Mbit/s"), Ok(Bandwidth::from_mbps(10)));
+    /// assert_eq!(options.parse("1 kB/s"), Ok(Bandwidth::from_kbps(8)));
+    /// assert_eq!(options.parse("10Mbit/s"), Err(Error::SpaceExpected(2)));
+    /// assert!(options.parse("10 Mbps").is_err());
+    /// # }
+    /// ```
+    pub const fn iso80000(mut self, iso80000: bool) -> Self {
+        self.iso80000 = iso80000;
+        self
+    }
+
+    /// Whether the ISO/IEC 80000-13 symbols are strictly followed
+    pub const fn is_iso80000(&self) -> bool {
+        self.iso80000
+    }
+
+    /// Read the `bps` family of units as Bytes per second, like `tc` and
+    /// several vendor tools do
+    ///
+    /// Only the units spelled exactly `bps`, `kbps`, `Mbps`... are affected:
+    /// `bit/s` and `b/s` remain bits per second, and neither the units assumed
+    /// by the fuzzy mode nor the ISO/IEC 80000-13 mode are affected.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::ParseOptions;
+    ///
+    /// let options = ParseOptions::new().bps_means_bytes(true);
+    /// assert_eq!(options.parse("100kbps"), Ok(Bandwidth::from_kbps(800)));
+    /// assert_eq!(options.parse("100kbit/s"), Ok(Bandwidth::from_kbps(100)));
+    /// # }
+    /// ```
+    pub const fn bps_means_bytes(mut self, bps_means_bytes: bool) -> Self {
+        self.bps_means_bytes = bps_means_bytes;
+        self
+    }
+
+    /// Whether the `bps` family of units is read as Bytes per second
+    pub const fn is_bps_means_bytes(&self) -> bool {
+        self.bps_means_bytes
+    }
+
+    /// Set how the units whose quantity is a lone `b` or `B` are read, e.g.
+    /// `Kbps`, `KBps` or `kb/s`
+    ///
+    /// Data sources disagree on these symbols: `tc` reads `kbps` as Bytes,
+    /// and some tools lowercase every unit. The units spelling `bit` or
+    /// `Byte` in full, e.g. `Mbit/s` or `MByte/s` with
+    /// [`quadrant_units`](Self::quadrant_units), are not affected, nor are
+    /// the symbols of the ISO/IEC 80000-13 mode. Policies other than
+    /// [`BitBytePolicy::CaseSensitive`] override
+    /// [`bps_means_bytes`](Self::bps_means_bytes).
+    ///
+    /// Defaults to [`BitBytePolicy::CaseSensitive`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{BitBytePolicy, Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().bit_byte_policy(BitBytePolicy::Bytes);
+    /// assert_eq!(options.parse("100kb/s"), Ok(Bandwidth::from_kbps(800)));
+    /// assert_eq!(options.parse("100kbit/s"), Ok(Bandwidth::from_kbps(100)));
+    /// let options = ParseOptions::new().bit_byte_policy(BitBytePolicy::Reject);
+    /// assert_eq!(options.parse("100kbps"), Err(Error::AmbiguousUnit { start: 3, end: 7 }));
+    /// # }
+    /// ```
+    pub const fn bit_byte_policy(mut self, policy: BitBytePolicy) -> Self {
+        self.bit_byte_policy = policy;
+        self
+    }
+
+    /// How the units whose quantity is a lone `b` or `B` are read
+    pub const fn get_bit_byte_policy(&self) -> BitBytePolicy {
+        self.bit_byte_policy
+    }
+
+    /// Also accept the units of every [`PrefixBase`] and [`Quantity`], like
+    /// [`FormatOptions`] writes them: `Mbit/s`, `Mibit/s`, `MB/s` and `MiB/s`
+    ///
+    /// The bits can be spelled `bit/s` or `bps`, the Bytes `B/s`, `Bps` or
+    /// `Byte/s`, and only `bit/s` and `B/s` in the ISO/IEC 80000-13 mode. The
+    /// binary prefixes are `Ki`, `Mi`, `Gi`, `Ti`, `Pi` and `Ei`. The
+    /// fractional part less than 1bps of these units is rounded to the
+    /// nearest, with ties away from zero.
+    ///
+    /// It is a shorthand enabling both [`EnabledUnits::binary_prefixes`] and
+    /// [`EnabledUnits::bytes`], which accept the units of a single prefix
+    /// base or quantity, e.g. `MB/s` but not `MiB/s`. Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::ParseOptions;
+    ///
+    /// let options = ParseOptions::new().quadrant_units(true);
+    /// assert_eq!(options.parse("1Mbit/s"), Ok(Bandwidth::from_bps(1_000_000)));
+    /// assert_eq!(options.parse("1Mibit/s"), Ok(Bandwidth::from_bps(1_048_576)));
+    /// assert_eq!(options.parse("1MB/s"), Ok(Bandwidth::from_bps(8_000_000)));
+    /// assert_eq!(options.parse("1MiB/s"), Ok(Bandwidth::from_bps(8_388_608)));
+    /// # }
+    /// ```
+    pub const fn quadrant_units(mut self, quadrant_units: bool) -> Self {
+        self.enabled_units = self
+            .enabled_units
+            .binary_prefixes(quadrant_units)
+            .bytes(quadrant_units);
+        self
+    }
+
+    /// Whether the units of every prefix base and quantity are accepted
+    pub const fn is_quadrant_units(&self) -> bool {
+        self.enabled_units.binary_prefixes && self.enabled_units.bytes
+    }
+
+    /// Additional units, as their exact spelling and their value in bits per
+    /// second
+    ///
+    /// These are looked up before the built-in units, which they may override,
+    /// and in every mode. They are never assumed by the fuzzy mode. Aliases
+    /// must start with an ASCII letter, and may then contain any character,
+    /// e.g. `OC-48`.
+    ///
+    /// None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::ParseOptions;
+    ///
+    /// const TELECOM: &[(&str, u64)] = &[("E1", 2_048_000), ("T3", 44_736_000)];
+    ///
+    /// let options = ParseOptions::new().custom_units(TELECOM);
+    /// assert_eq!(options.parse("2E1"), Ok(Bandwidth::from_kbps(4_096)));
+    /// assert_eq!(options.parse("1T3 0.5E1"), Ok(Bandwidth::from_kbps(45_760)));
+    /// # }
+    /// ```
+    pub const fn custom_units(mut self, units: &'static [(&'static str, u64)]) -> Self {
+        self.custom_units = units;
+        self
+    }
+
+    /// The additional units and their value in bits per second
+    pub const fn get_custom_units(&self) -> &'static [(&'static str, u64)] {
+        self.custom_units
+    }
+
+    /// Accept symbol rates in baud, e.g. `9600baud` or `2.4kBd`, each symbol
+    /// carrying `bits_per_symbol` bits
+    ///
+    /// The units are `Bd` or `baud`, and the same with the prefixes `k`, `M`
+    /// and `G`, e.g. `MBd` or `Mbaud`, for the serial lines and modems whose
+    /// configuration files count symbols. They are looked up after the
+    /// [custom units](Self::custom_units) and the
+    /// [aliases](Self::unit_aliases), and may be mixed with the units of
+    /// bits, e.g. `1MBd 200kbps`.
+    ///
+    /// Disabled by default. This enables the baud units of
+    /// [`enabled_units`](Self::enabled_units) too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits_per_symbol` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::ParseOptions;
+    ///
+    /// assert!(ParseOptions::new().parse("9600baud").is_err());
+    /// assert_eq!(ParseOptions::new().baud(1).parse("9600baud"), Ok(Bandwidth::from_bps(9_600)));
+    /// // 16-QAM carries 4 bits per symbol
+    /// let qam16 = ParseOptions::new().baud(4);
+    /// assert_eq!(qam16.parse("2.4 kBd"), Ok(Bandwidth::from_bps(9_600)));
+    /// # }
+    /// ```
+    pub const fn baud(mut self, bits_per_symbol: u32) -> Self {
+        assert!(bits_per_symbol > 0, "a symbol carries at least one bit");
+        self.bits_per_symbol = Some(bits_per_symbol);
+        self.enabled_units = self.enabled_units.baud(true);
+        self
+    }
+
+    /// The number of bits per symbol of the rates in baud, if they are
+    /// accepted
+    pub const fn get_bits_per_symbol(&self) -> Option<u32> {
+        match self.bits_per_symbol {
+            Some(bits) if self.enabled_units.is_baud() => Some(bits),
+            _ => None,
+        }
+    }
+
+    /// Set the kinds of units accepted besides the symbols of bits per
+    /// second, e.g. to reject the units spelled out or per hour in the input
+    /// of untrusted users
+    ///
+    /// Defaults to [`EnabledUnits::ALL`], and to [`EnabledUnits::SYMBOLS`]
+    /// with [`strict`](Self::strict). The units of the binary prefixes and
+    /// in Bytes enabled by [`quadrant_units`](Self::quadrant_units) are
+    /// replaced too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{EnabledUnits, Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().enabled_units(EnabledUnits::SYMBOLS);
+    /// assert_eq!(options.parse("10Mbit/s"), Ok(Bandwidth::from_mbps(10)));
+    /// assert!(options.parse("10 megabits per second").is_err());
+    /// assert!(matches!(options.parse("36Gbit/h"), Err(Error::UnknownUnit { .. })));
+    ///
+    /// let options = options.enabled_units(EnabledUnits::SYMBOLS.per_interval(true));
+    /// assert_eq!(options.parse("36Gbit/h"), Ok(Bandwidth::from_mbps(10)));
+    ///
+    /// let options = options.enabled_units(EnabledUnits::SYMBOLS.bytes(true));
+    /// assert_eq!(options.parse("1MB/s"), Ok(Bandwidth::from_mbps(8)));
+    /// assert!(matches!(options.parse("1MiB/s"), Err(Error::UnknownUnit { .. })));
+    /// assert!(matches!(options.parse("1Mibit/s"), Err(Error::UnknownUnit { .. })));
+    /// # }
+    /// ```
+    pub const fn enabled_units(mut self, enabled_units: EnabledUnits) -> Self {
+        self.enabled_units = enabled_units;
+        self
+    }
+
+    /// The kinds of units accepted besides the symbols of bits per second
+    pub const fn get_enabled_units(&self) -> EnabledUnits {
+        self.enabled_units
+    }
+
+    /// Additional spellings of the built-in units, as the exact spelling and
+    /// the unit it stands for, e.g. `("meg", "Mbps")`
+    ///
+    /// The aliases are looked up after the [custom units](Self::custom_units)
+    /// and, like them, must start with an ASCII letter and may then contain
+    /// any character. The target is read as if it were written instead of the
+    /// alias, so that it must be a unit of the parser in use, e.g. an
+    /// ISO/IEC 80000-13 symbol in that mode or a binary unit for the binary
+    /// parsers. The errors report the alias as written.
+    ///
+    /// None by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::ParseOptions;
+    ///
+    /// const LEGACY: &[(&str, &str)] = &[("meg", "Mbps"), ("gig", "Gbps"), ("k", "kbps")];
+    ///
+    /// let options = ParseOptions::new().unit_aliases(LEGACY);
+    /// assert_eq!(options.parse("1gig 500meg"), Ok(Bandwidth::from_mbps(1_500)));
+    /// assert_eq!(options.parse("64k"), Ok(Bandwidth::from_kbps(64)));
+    /// assert_eq!(options.parse("2Mbps"), Ok(Bandwidth::from_mbps(2)));
+    /// # }
+    /// ```
+    pub const fn unit_aliases(mut self, aliases: &'static [(&'static str, &'static str)]) -> Self {
+        self.unit_aliases = aliases;
+        self
+    }
+
+    /// The additional spellings and the units they stand for
+    pub const fn get_unit_aliases(&self) -> &'static [(&'static str, &'static str)] {
+        self.unit_aliases
+    }
+
+    /// Value in bits per second of a unit of the prefix bases and quantities
+    /// which are enabled, see [`quadrant_unit`]
+    pub(crate) fn quadrant_unit(&self, unit: &str) -> Option<u128> {
+        quadrant_unit(unit, self.iso80000, self.enabled_units)
+    }
+
+    /// The symbol of a unit spelled out like
+    /// [`spelled_unit`](crate::spelled::spelled_unit), if these units are
+    /// enabled
+    pub(crate) fn spelled_unit(&self, unit: &str) -> Option<UnitBuf> {
+        if !self.enabled_units.spelled {
+            return None;
+        }
+        crate::spelled::spelled_unit(unit, self.iso80000)
+    }
+
+    /// Splits a unit per interval like [`split_interval`](crate::split_interval),
+    /// if these units are enabled
+    pub(crate) fn split_interval<'u>(&self, unit: &'u str) -> Option<(&'u str, Interval)> {
+        if !self.enabled_units.per_interval {
+            return None;
+        }
+        crate::split_interval(unit)
+    }
+
+    /// The unit an alias stands for, or `unit` itself
+    pub(crate) fn resolve_alias<'u>(&self, unit: &'u str) -> &'u str {
+        self.unit_aliases
+            .iter()
+            .find(|(alias, _)| *alias == unit)
+            .map_or(unit, |(_, target)| *target)
+    }
+
+    /// Accept `_` as a digit separator inside numbers, e.g. `1_000Mbps`
+    ///
+    /// Enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().digit_separators(false);
+    /// assert_eq!(options.parse("1_000Mbps"), Err(Error::InvalidCharacter(1)));
+    /// # }
+    /// ```
+    pub const fn digit_separators(mut self, digit_separators: bool) -> Self {
+        self.digit_separators = digit_separators;
+        self
+    }
+
+    /// Whether `_` is accepted inside numbers
+    pub const fn is_digit_separators(&self) -> bool {
+        self.digit_separators
+    }
+
+    /// Accept whitespace between the digits of a number, e.g. `1 000Mbps`
+    ///
+    /// Whitespace between a number and its unit, and between rate spans, is
+    /// always accepted.
+    ///
+    /// Enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().spaces_in_numbers(false);
+    /// assert_eq!(options.parse("1 000Mbps"), Err(Error::InvalidCharacter(1)));
+    /// assert_eq!(options.parse("1000 Mbps"), Ok(Bandwidth::from_gbps(1)));
+    /// # }
+    /// ```
+    pub const fn spaces_in_numbers(mut self, spaces_in_numbers: bool) -> Self {
+        self.spaces_in_numbers = spaces_in_numbers;
+        self
+    }
+
+    /// Whether whitespace is accepted between the digits of a number
+    pub const fn is_spaces_in_numbers(&self) -> bool {
+        self.spaces_in_numbers
+    }
+
+    /// Accept line breaks as whitespace, e.g. in `1Gbps\n500Mbps`
+    ///
+    /// When disabled, a line break anywhere in the value is reported as
+    /// [`Error::InvalidCharacter`], e.g. for values of line-based formats.
+    ///
+    /// Enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().newlines(false);
+    /// assert_eq!(options.parse("1Gbps\r\n500Mbps"), Err(Error::InvalidCharacter(5)));
+    /// assert!(ParseOptions::new().parse("1Gbps\r\n500Mbps").is_ok());
+    /// # }
+    /// ```
+    pub const fn newlines(mut self, newlines: bool) -> Self {
+        self.newlines = newlines;
+        self
+    }
+
+    /// Whether line breaks are accepted as whitespace
+    pub const fn is_newlines(&self) -> bool {
+        self.newlines
+    }
+
+    /// Only accept whitespace made of a single space between two tokens, e.g.
+    /// `1Gbps 500Mbps` or `1.5 Mbps`, as machine-generated values are written
+    ///
+    /// The value may not start nor end with whitespace, and every run of
+    /// whitespace, between rate spans, between a number and its unit or
+    /// inside a number with [`spaces_in_numbers`](Self::spaces_in_numbers),
+    /// must be a single ASCII space. Its first character is reported as
+    /// [`Error::InvalidCharacter`] otherwise. Whitespace inside custom units
+    /// and aliases is not checked.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// // Tightened for a strict machine-generated format
+    /// let options = ParseOptions::new()
+    ///     .digit_separators(false)
+    ///     .spaces_in_numbers(false)
+    ///     .newlines(false)
+    ///     .single_spaces(true);
+    /// assert_eq!(options.parse("1Gbps 500 Mbps"), Ok(Bandwidth::from_mbps(1_500)));
+    /// assert_eq!(options.parse("1Gbps  500Mbps"), Err(Error::InvalidCharacter(5)));
+    /// assert_eq!(options.parse("1Gbps\t500Mbps"), Err(Error::InvalidCharacter(5)));
+    /// assert_eq!(options.parse(" 1Gbps"), Err(Error::InvalidCharacter(0)));
+    /// assert_eq!(options.parse("1Gbps "), Err(Error::InvalidCharacter(5)));
+    /// # }
+    /// ```
+    pub const fn single_spaces(mut self, single_spaces: bool) -> Self {
+        self.single_spaces = single_spaces;
+        self
+    }
+
+    /// Whether whitespace must be a single space between two tokens
+    pub const fn is_single_spaces(&self) -> bool {
+        self.single_spaces
+    }
+
+    /// Accept several rate spans which are summed, e.g. `1Gbps 500Mbps`
+    ///
+    /// When disabled, a second span is reported as [`Error::ExtraSpan`].
+    ///
+    /// Enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// // Tightened for values typed by untrusted users
+    /// let options = ParseOptions::new()
+    ///     .digit_separators(false)
+    ///     .spaces_in_numbers(false)
+    ///     .multiple_spans(false);
+    /// assert_eq!(options.parse("1.5Gbps"), Ok(Bandwidth::from_mbps(1_500)));
+    /// assert_eq!(options.parse("1Gbps 500Mbps"), Err(Error::ExtraSpan(6)));
+    /// # }
+    /// ```
+    pub const fn multiple_spans(mut self, multiple_spans: bool) -> Self {
+        self.multiple_spans = multiple_spans;
+        self
+    }
+
+    /// Whether several rate spans are accepted
+    pub const fn is_multiple_spans(&self) -> bool {
+        self.multiple_spans
+    }
+
+    /// Also accept a comma as the decimal separator, e.g. `15,5Mbps` like in
+    /// many European locales
+    ///
+    /// A number has at most one decimal separator, either a point or a comma:
+    /// thousands separators like in `1.000,5Mbps` are rejected.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().decimal_comma(true);
+    /// assert_eq!(options.parse("15,5Mbps"), Ok(Bandwidth::from_kbps(15_500)));
+    /// assert_eq!(options.parse("15.5Mbps"), Ok(Bandwidth::from_kbps(15_500)));
+    /// assert_eq!(options.parse("1.000,5Mbps"), Err(Error::InvalidCharacter(5)));
+    /// assert_eq!(ParseOptions::new().parse("15,5Mbps"), Err(Error::InvalidCharacter(2)));
+    /// # }
+    /// ```
+    pub const fn decimal_comma(mut self, decimal_comma: bool) -> Self {
+        self.decimal_comma = decimal_comma;
+        self
+    }
+
+    /// Whether a comma is accepted as the decimal separator
+    pub const fn is_decimal_comma(&self) -> bool {
+        self.decimal_comma
+    }
+
+    /// Also accept the digits of the integer part of numbers grouped by
+    /// three, e.g. `1,000Mbps`, `1'000Mbps` or `1 000 Mbps`
+    ///
+    /// The groups are separated by commas, apostrophes or whitespace, the same
+    /// one in a number. The first group has one to three digits and the
+    /// following ones exactly three, otherwise [`Error::InvalidDigitGroup`] is
+    /// reported. With [`decimal_comma`](Self::decimal_comma), the comma is
+    /// the decimal separator rather than a group separator.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().digit_grouping(true);
+    /// assert_eq!(options.parse("1,000Mbps"), Ok(Bandwidth::from_gbps(1)));
+    /// assert_eq!(options.parse("1'000'000.5 kbps"), Ok(Bandwidth::from_bps(1_000_000_500)));
+    /// assert_eq!(options.parse("1 000 Mbps"), Ok(Bandwidth::from_gbps(1)));
+    /// assert_eq!(options.parse("10,00Mbps"), Err(Error::InvalidDigitGroup(2)));
+    /// # }
+    /// ```
+    pub const fn digit_grouping(mut self, digit_grouping: bool) -> Self {
+        self.digit_grouping = digit_grouping;
+        self
+    }
+
+    /// Whether the digits of numbers may be grouped by three
+    pub const fn is_digit_grouping(&self) -> bool {
+        self.digit_grouping
+    }
+
+    /// Accept numbers in scientific notation, e.g. `1.5e3Mbps` or `2E-3 Gbps`
+    ///
+    /// The exponent is an `e` or `E` followed by an optional sign and digits,
+    /// so that `2Ebps` remains 2 exabits per second. A custom unit starting
+    /// with `e` or `E` takes precedence, e.g. `2E1` with an `E1` unit.
+    ///
+    /// Enabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// assert_eq!(ParseOptions::new().parse("1.5e3Mbps"), Ok(Bandwidth::from_mbps(1_500)));
+    /// let options = ParseOptions::new().exponent_notation(false);
+    /// assert!(matches!(options.parse("1.5e3Mbps"), Err(Error::UnknownUnit { .. })));
+    /// # }
+    /// ```
+    pub const fn exponent_notation(mut self, exponent_notation: bool) -> Self {
+        self.exponent_notation = exponent_notation;
+        self
+    }
+
+    /// Whether numbers in scientific notation are accepted
+    pub const fn is_exponent_notation(&self) -> bool {
+        self.exponent_notation
+    }
+
+    /// Reject the rate spans which are likely typos rather than summing them:
+    /// a unit used twice like `1Mbps 2Mbps`, units not in decreasing order
+    /// like `5kbps 1Gbps`, and spans not separated by whitespace like
+    /// `1Mbps2kbps`
+    ///
+    /// The units are compared by value, e.g. `Mbps` and `Mbit/s` are the same
+    /// unit. The errors are [`Error::DuplicateUnit`],
+    /// [`Error::UnorderedUnit`] and [`Error::SeparatorExpected`].
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().strict_spans(true);
+    /// assert_eq!(options.parse("1Gbps 500Mbps"), Ok(Bandwidth::from_mbps(1_500)));
+    /// assert_eq!(options.parse("1Mbps 2Mbit/s"), Err(Error::DuplicateUnit { start: 7, end: 13 }));
+    /// assert_eq!(options.parse("5kbps 1Gbps"), Err(Error::UnorderedUnit { start: 7, end: 11 }));
+    /// assert_eq!(options.parse("1Mbps2kbps"), Err(Error::SeparatorExpected(5)));
+    /// # }
+    /// ```
+    pub const fn strict_spans(mut self, strict_spans: bool) -> Self {
+        self.strict_spans = strict_spans;
+        self
+    }
+
+    /// Whether duplicate, unordered and unseparated rate spans are rejected
+    pub const fn is_strict_spans(&self) -> bool {
+        self.strict_spans
+    }
+
+    /// Accept the unit prefixes only in their standard case: `k` for kilo,
+    /// `Ki` or `ki` for kibi, and upper case for the larger prefixes
+    ///
+    /// By default, `mbps` and `Kbps` are read as `Mbps` and `kbps`, and `miB/s`
+    /// as `MiB/s`, although `m` is the prefix of milli. With this option, such
+    /// units are unknown, unless [`fuzzy`](Self::fuzzy) assumes the intended
+    /// unit with a warning. Custom units are always case-sensitive.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().case_sensitive_prefixes(true);
+    /// assert_eq!(options.parse("10Mbps 5kbps"), Ok(Bandwidth::from_kbps(10_005)));
+    /// assert!(matches!(options.parse("10mbps"), Err(Error::UnknownUnit { .. })));
+    /// assert!(matches!(options.parse("5Kbps"), Err(Error::UnknownUnit { .. })));
+    /// # }
+    /// ```
+    pub const fn case_sensitive_prefixes(mut self, case_sensitive_prefixes: bool) -> Self {
+        self.case_sensitive_prefixes = case_sensitive_prefixes;
+        self
+    }
+
+    /// Whether the unit prefixes are only accepted in their standard case
+    pub const fn is_case_sensitive_prefixes(&self) -> bool {
+        self.case_sensitive_prefixes
+    }
+
+    /// Whether the prefix of a known unit is in a case accepted by these
+    /// options
+    pub(crate) fn accepts_prefix_case(&self, unit: &str) -> bool {
+        if !self.case_sensitive_prefixes {
+            return true;
+        }
+        let mut chars = unit.chars();
+        match chars.next() {
+            Some('m' | 'g' | 't' | 'p' | 'e') => false,
+            Some('K') => chars.next() == Some('i'),
+            _ => true,
+        }
+    }
+
+    /// Read the values too large for a bandwidth as [`Bandwidth::MAX`] rather
+    /// than failing with [`Error::NumberOverflow`]
+    ///
+    /// This is meant for the "unlimited" values of imported configurations,
+    /// like `99999999999Ebps`. A number too large for the parser, e.g. with
+    /// more than 20 digits, saturates as well even if its unit is small. The
+    /// other errors are still reported, and the saturation is reported as a
+    /// [`ParseWarning::Saturated`] by
+    /// [`parse_with_warnings`](Self::parse_with_warnings).
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().saturating(true);
+    /// assert_eq!(options.parse("99999999999Ebps"), Ok(Bandwidth::MAX));
+    /// assert_eq!(options.parse("1e40bps"), Ok(Bandwidth::MAX));
+    /// assert_eq!(options.parse("10Mbps"), Ok(Bandwidth::from_mbps(10)));
+    /// assert!(options.parse("99999999999Ebpx").is_err());
+    /// # }
+    /// ```
+    pub const fn saturating(mut self, saturating: bool) -> Self {
+        self.saturating = saturating;
+        self
+    }
+
+    /// Whether the values too large for a bandwidth saturate
+    pub const fn is_saturating(&self) -> bool {
+        self.saturating
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Decode the escapes of `/` and of spaces in URLs and HTML before
+    /// parsing, e.g. `10Mbit%2Fs` or `10&nbsp;Mbit&#47;s`
+    ///
+    /// The escapes are `%2F`, `&#47;`, `&#x2F;` and `&sol;` for `/`, `%20`
+    /// for a space and `%C2%A0`, `&nbsp;`, `&#160;` and `&#xA0;` for a
+    /// non-breaking space, in any case. The offsets of the errors are in the
+    /// string as written, and their units are reported with their escapes.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().iso80000(true).decode_escapes(true);
+    /// assert_eq!(options.parse("10%20Mbit%2Fs"), Ok(Bandwidth::from_mbps(10)));
+    /// assert_eq!(options.parse("10&nbsp;Mbit&#47;s"), Ok(Bandwidth::from_mbps(10)));
+    /// assert!(matches!(
+    ///     options.parse("10&nbsp;Mbit&#47;x"),
+    ///     Err(Error::UnknownUnit { start: 8, end: 18, .. })
+    /// ));
+    /// ```
+    pub const fn decode_escapes(mut self, decode_escapes: bool) -> Self {
+        self.decode_escapes = decode_escapes;
+        self
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Whether the URL and HTML escapes are decoded
+    pub const fn is_decode_escapes(&self) -> bool {
+        self.decode_escapes
+    }
+
+    /// Number of digits of the fractional part which are read, the following
+    /// ones being ignored unless [`strict_fraction`](Self::strict_fraction)
+    /// is enabled
+    ///
+    /// 18 by default, the most allowed, which is enough for 1bps in `Ebps`.
+    /// Digits moved below the limit by an exponent, e.g. `1e-20Ebps`, are
+    /// always ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is more than 18.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::ParseOptions;
+    ///
+    /// let options = ParseOptions::new().fraction_digits(3);
+    /// assert_eq!(options.parse("1.23456Gbps"), Ok(Bandwidth::from_mbps(1_234)));
+    /// # }
+    /// ```
+    pub const fn fraction_digits(mut self, digits: u32) -> Self {
+        assert!(
+            digits <= FRACTION_PART_LIMIT,
+            "at most 18 fractional digits are read"
+        );
+        self.fraction_digits = digits;
+        self
+    }
+
+    /// Number of digits of the fractional part which are read
+    pub const fn get_fraction_digits(&self) -> u32 {
+        self.fraction_digits
+    }
+
+    /// Reject the digits of the fractional part beyond
+    /// [`fraction_digits`](Self::fraction_digits), rather than ignoring them
+    ///
+    /// The first rejected digit is reported as [`Error::TooManyDecimals`].
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().strict_fraction(true);
+    /// assert_eq!(options.parse("1.5Mbps"), Ok(Bandwidth::from_kbps(1_500)));
+    /// let precise = "0.1234567890123456789Ebps";
+    /// assert_eq!(options.parse(precise), Err(Error::TooManyDecimals(20)));
+    /// let options = options.fraction_digits(6);
+    /// assert_eq!(options.parse("1.2345678Mbps"), Err(Error::TooManyDecimals(8)));
+    /// # }
+    /// ```
+    pub const fn strict_fraction(mut self, strict_fraction: bool) -> Self {
+        self.strict_fraction = strict_fraction;
+        self
+    }
+
+    /// Whether the digits beyond the fractional limit are rejected
+    pub const fn is_strict_fraction(&self) -> bool {
+        self.strict_fraction
+    }
+
+    /// Number of days in a month, for the rates per
+    /// [`Interval::Month`](crate::Interval::Month)
+    ///
+    /// 30 by default, the usual convention of quotas and billing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `days` is 0.
+    pub const fn month_days(mut self, days: u8) -> Self {
+        assert!(days > 0, "a month has at least one day");
+        self.month_days = days;
+        self
+    }
+
+    /// Spread the rates per month over the actual length of the month of
+    /// `date`, see the [`calendar`](crate::calendar) module
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "chrono")]
+    /// # {
+    /// use bandwidth::Bandwidth;
+    /// use chrono::NaiveDate;
+    /// use human_bandwidth::ParseOptions;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2024, 2, 10).unwrap();
+    /// let options = ParseOptions::new().month_of(&date);
+    /// assert_eq!(options.get_month_days(), 29);
+    /// # }
+    /// ```
+    pub fn month_of<D: CalendarDate + ?Sized>(self, date: &D) -> Self {
+        self.month_days(date.days_in_month())
+    }
+
+    /// Number of days in a month
+    pub const fn get_month_days(&self) -> u8 {
+        self.month_days
+    }
+
+    /// Length of `interval` in seconds, with the month length of these
+    /// options
+    pub(crate) const fn interval_seconds(&self, interval: crate::Interval) -> u64 {
+        match interval {
+            crate::Interval::Month => self.month_days as u64 * crate::Interval::Day.seconds(),
+            interval => interval.seconds(),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Parse a bandwidth with these options
+    ///
+    /// See [`parse_bandwidth`](crate::parse_bandwidth) for the supported format.
+    pub fn parse(&self, s: &str) -> Result<Bandwidth, Error> {
+        let decoded = Decoded::new(s, self);
+        let parsed = Parser::with_options(decoded.as_str(), *self).parse();
+        parsed.map_err(|error| decoded.error(error))
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Parse a bandwidth in binary units with these options
+    ///
+    /// See [`parse_binary_bandwidth`](crate::binary_system::parse_binary_bandwidth)
+    /// for the supported format. The options on units other than
+    /// [`case_sensitive_prefixes`](Self::case_sensitive_prefixes) do not apply
+    /// to binary units.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::strict();
+    /// assert_eq!(options.parse_binary("1MiB/s"), Ok(Bandwidth::from_bps(8 << 20)));
+    /// assert!(matches!(options.parse_binary("1miB/s"), Err(Error::UnknownBinaryUnit { .. })));
+    /// ```
+    #[cfg(feature = "binary-system")]
+    pub fn parse_binary(&self, s: &str) -> Result<Bandwidth, Error> {
+        let decoded = Decoded::new(s, self);
+        let parsed = Parser::with_options(decoded.as_str(), *self).parse_binary();
+        parsed.map_err(|error| decoded.error(error))
+    }
+
+    /// Parse a bandwidth with these options, the errors borrowing from `s`
+    ///
+    /// See [`parse_bandwidth_borrowed`](crate::parse_bandwidth_borrowed). Only
+    /// the errors of strings with escapes are allocated, see
+    /// [`decode_escapes`](Self::decode_escapes), which needs the `alloc`
+    /// feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use human_bandwidth::{ParseError, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().iso80000(true);
+    /// assert_eq!(options.parse_borrowed("10Mbit/s"), Err(ParseError::SpaceExpected(2)));
+    /// ```
+    pub fn parse_borrowed<'src>(&self, s: &'src str) -> Result<Bandwidth, ParseError<'src>> {
+        #[cfg(feature = "alloc")]
+        if Decoded::new(s, self).is_decoded() {
+            // The escapes are rare enough for their errors to be allocated
+            return self.parse(s).map_err(|error| borrow_error(error, s));
+        }
+        Parser::with_options(s, *self).parse()
+    }
+
+    /// Parse a bandwidth with binary units and these options, the errors
+    /// borrowing from `s`
+    ///
+    /// See [`parse_binary_bandwidth_borrowed`](crate::binary_system::parse_binary_bandwidth_borrowed).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use human_bandwidth::{ParseError, ParseOptions};
+    ///
+    /// let options = ParseOptions::strict();
+    /// assert!(matches!(
+    ///     options.parse_binary_borrowed("1miB/s"),
+    ///     Err(ParseError::UnknownBinaryUnit { unit: "miB/s", .. })
+    /// ));
+    /// ```
+    #[cfg(feature = "binary-system")]
+    pub fn parse_binary_borrowed<'src>(&self, s: &'src str) -> Result<Bandwidth, ParseError<'src>> {
+        #[cfg(feature = "alloc")]
+        if Decoded::new(s, self).is_decoded() {
+            return self.parse_binary(s).map_err(|error| borrow_error(error, s));
+        }
+        Parser::with_options(s, *self).parse_binary()
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Parse a bandwidth with these options, also returning what was assumed
+    /// to make sense of the input
+    pub fn parse_with_warnings(&self, s: &str) -> Result<(Bandwidth, Vec<ParseWarning>), Error> {
+        let decoded = Decoded::new(s, self);
+        let mut parser = Parser::with_options(decoded.as_str(), *self);
+        let val = parser.parse().map_err(|error| decoded.error(error))?;
+        let warnings = parser.warnings.into_iter();
+        Ok((
+            val,
+            warnings.map(|warning| decoded.warning(warning)).collect(),
+        ))
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Parse a bandwidth with these options, skipping the invalid rate spans
+    ///
+    /// Instead of stopping at the first error, every invalid span is recorded
+    /// and parsing resumes at the next one, so that editors can report all the
+    /// problems at once. Returns the sum of the valid spans and the errors in
+    /// the order of the string, which are empty when the whole string is valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{Error, ParseOptions};
+    ///
+    /// let (val, errors) = ParseOptions::new().parse_recovering("1Mbps 2Mbpx 3..5kbps 4kbps");
+    /// assert_eq!(val, Bandwidth::from_kbps(1_004));
+    /// assert_eq!(errors.len(), 2);
+    /// assert!(matches!(errors[0], Error::UnknownUnit { start: 7, end: 11, .. }));
+    /// assert_eq!(errors[1], Error::InvalidCharacter(14));
+    /// ```
+    pub fn parse_recovering(&self, s: &str) -> (Bandwidth, Vec<Error>) {
+        let decoded = Decoded::new(s, self);
+        let mut parser = Parser::with_options(decoded.as_str(), *self);
+        parser.errors = Some(Vec::new());
+        let parsed = parser.parse();
+        let mut errors = parser.errors.take().unwrap_or_default();
+        errors = errors
+            .into_iter()
+            .map(|error| decoded.error(error))
+            .collect();
+        let parsed = parsed.map_err(|error| decoded.error(error));
+        match parsed {
+            Ok(val) => (val, errors),
+            Err(error) => {
+                errors.push(error);
+                // The total never exceeds the maximum when recovering
+                let val = bandwidth_from_bps(parser.current).unwrap_or(Bandwidth::MAX);
+                (val, errors)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Something assumed by a lenient parsing, see [`ParseOptions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// A unit was not recognized and the closest one was used instead
+    AssumedUnit {
+        /// Start of the unit inside the original string
+        start: usize,
+        /// End of the unit inside the original string
+        end: usize,
+        /// The unit verbatim
+        unit: String,
+        /// The unit used instead
+        assumed: &'static str,
+    },
+    /// The value was too large and [`Bandwidth::MAX`] was used instead, see
+    /// [`ParseOptions::saturating`]
+    Saturated,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::AssumedUnit { unit, assumed, .. } => {
+                write!(f, "unknown unit {:?} interpreted as {:?}", unit, assumed)
+            }
+            ParseWarning::Saturated => {
+                write!(f, "value too large, read as the largest bandwidth")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Options to customize the formatting of bandwidths
+///
+/// # Example
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::FormatOptions;
+///
+/// let val = Bandwidth::from_kbps(1_536);
+/// assert_eq!(FormatOptions::new().format(val), "1.536Mbps");
+/// assert_eq!(FormatOptions::new().precision(1).format(val), "1.5Mbps");
+/// assert_eq!(FormatOptions::new().iso80000(true).format(val), "1.536 Mbit/s");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    precision: Option<usize>,
+    hide_unit: bool,
+    iso80000: bool,
+    engineering: bool,
+    unit_policy: UnitPolicy,
+    keep_trailing_zeros: bool,
+    trim_trailing_zeros: bool,
+    decimal_comma: bool,
+    prefix_base: PrefixBase,
+    quantity: Quantity,
+    max_unit: Unit,
+    interval: Interval,
+    /// Set by a versioned profile, until the options are customized
+    frozen: Option<Frozen>,
+}
+
+#[cfg(feature = "alloc")]
+impl FormatOptions {
+    /// Create the default options: exact decimal values with their unit, like
+    /// [`format_bandwidth`](crate::format_bandwidth) without `display-integer`
+    pub const fn new() -> Self {
+        FormatOptions {
+            precision: None,
+            hide_unit: false,
+            iso80000: false,
+            engineering: false,
+            unit_policy: UnitPolicy::OneToThousand,
+            keep_trailing_zeros: false,
+            trim_trailing_zeros: false,
+            decimal_comma: false,
+            prefix_base: PrefixBase::Decimal,
+            quantity: Quantity::Bits,
+            max_unit: Unit::Ebps,
+            interval: Interval::Second,
+            frozen: None,
+        }
+    }
+
+    /// The options writing the canonical form of the
+    /// [`canonical`](crate::canonical) module, frozen like a versioned profile
+    pub(crate) const fn canonical() -> Self {
+        let mut options = FormatOptions::new().iso80000(true).max_unit(Unit::Tbps);
+        options.frozen = Some(Frozen::Canonical);
+        options
+    }
+
+    /// Create the options of a built-in [`Profile`], which can then be
+    /// customized further
+    ///
+    /// The output of a versioned profile like [`Profile::V1`] is frozen only
+    /// as long as its options are not customized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{FormatOptions, Profile};
+    ///
+    /// let val = Bandwidth::from_kbps(1_536);
+    /// assert_eq!(FormatOptions::from_profile(Profile::Machine).format(val), "1.536Mbps");
+    /// assert_eq!(FormatOptions::from_profile(Profile::Human).format(val), "1.5 Mbit/s");
+    /// ```
+    pub const fn from_profile(profile: Profile) -> Self {
+        match profile {
+            Profile::Machine => FormatOptions::new(),
+            Profile::Human => FormatOptions::new()
+                .iso80000(true)
+                .precision(1)
+                .trim_trailing_zeros(true),
+            Profile::V1 => {
+                let mut options = FormatOptions::new();
+                options.frozen = Some(Frozen::V1);
+                options
+            }
+        }
+    }
+
+    /// Set the number of decimals, rounding to the nearest with ties to even
+    ///
+    /// By default the value is written exactly, without trailing zeros, while
+    /// [`format_aligned`](crate::format_aligned) uses 2 decimals. The trailing
+    /// zeros of these decimals are kept unless
+    /// [`trim_trailing_zeros`](Self::trim_trailing_zeros) is set.
+    pub const fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self.frozen = None;
+        self
+    }
+
+    /// Whether the unit is written after the number, e.g. it can be omitted
+    /// when it is already shown in the header of a table
+    pub const fn show_unit(mut self, show_unit: bool) -> Self {
+        self.hide_unit = !show_unit;
+        self.frozen = None;
+        self
+    }
+
+    /// Use the ISO/IEC 80000-13 symbols, e.g. `1.5 Mbit/s` instead of `1.5Mbps`
+    ///
+    /// Disabled by default.
+    pub const fn iso80000(mut self, iso80000: bool) -> Self {
+        self.iso80000 = iso80000;
+        self.frozen = None;
+        self
+    }
+
+    /// Use the engineering notation: no unit prefix, and an exponent which is
+    /// a multiple of 3, e.g. `12.5e6bps` or `12.5e6 bit/s` with
+    /// [`iso80000`](Self::iso80000)
+    ///
+    /// The exponent is chosen after rounding, so that the number is in
+    /// `[1, 1000)` unless it is 0, and is always written, even when it is 0.
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::FormatOptions;
+    ///
+    /// let options = FormatOptions::new().engineering(true);
+    /// assert_eq!(options.format(Bandwidth::from_kbps(12_500)), "12.5e6bps");
+    /// assert_eq!(options.iso80000(true).format(Bandwidth::from_bps(750)), "750e0 bit/s");
+    /// ```
+    pub const fn engineering(mut self, engineering: bool) -> Self {
+        self.engineering = engineering;
+        self.frozen = None;
+        self
+    }
+
+    /// Set the range in which the value is kept by the choice of its unit
+    ///
+    /// Defaults to [`UnitPolicy::OneToThousand`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{FormatOptions, UnitPolicy};
+    ///
+    /// let options = FormatOptions::new().unit_policy(UnitPolicy::TenthToHundred);
+    /// assert_eq!(options.format(Bandwidth::from_mbps(500)), "0.5Gbps");
+    /// assert_eq!(options.format(Bandwidth::from_mbps(50)), "50Mbps");
+    /// ```
+    pub const fn unit_policy(mut self, unit_policy: UnitPolicy) -> Self {
+        self.unit_policy = unit_policy;
+        self.frozen = None;
+        self
+    }
+
+    /// Always write all the decimals, even when they are zeros
+    ///
+    /// Without a [`precision`](Self::precision), the number gets all the
+    /// decimals down to the bit per second, e.g. `1.500000Mbps`, which keeps
+    /// the length of the strings stable in fixed-column reports. With a
+    /// precision, the number already gets exactly that many decimals.
+    ///
+    /// [`format_aligned`](crate::format_aligned) always keeps them. Disabled
+    /// by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::FormatOptions;
+    ///
+    /// let options = FormatOptions::new();
+    /// assert_eq!(options.format(Bandwidth::from_kbps(1_500)), "1.5Mbps");
+    /// let options = options.keep_trailing_zeros(true);
+    /// assert_eq!(options.format(Bandwidth::from_kbps(1_500)), "1.500000Mbps");
+    /// assert_eq!(options.precision(2).format(Bandwidth::from_kbps(1_500)), "1.50Mbps");
+    /// ```
+    pub const fn keep_trailing_zeros(mut self, keep_trailing_zeros: bool) -> Self {
+        self.keep_trailing_zeros = keep_trailing_zeros;
+        self.frozen = None;
+        self
+    }
+
+    /// Remove the trailing zeros of the decimals set with
+    /// [`precision`](Self::precision), which is then the maximum number of
+    /// decimals, e.g. `1.5Mbps` rather than `1.50Mbps`
+    ///
+    /// It has no effect without a precision. Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::FormatOptions;
+    ///
+    /// let options = FormatOptions::new().precision(2);
+    /// assert_eq!(options.format(Bandwidth::from_kbps(1_500)), "1.50Mbps");
+    /// let options = options.trim_trailing_zeros(true);
+    /// assert_eq!(options.format(Bandwidth::from_kbps(1_500)), "1.5Mbps");
+    /// assert_eq!(options.format(Bandwidth::from_kbps(2_004)), "2Mbps");
+    /// ```
+    pub const fn trim_trailing_zeros(mut self, trim_trailing_zeros: bool) -> Self {
+        self.trim_trailing_zeros = trim_trailing_zeros;
+        self.frozen = None;
+        self
+    }
+
+    /// Write a comma as the decimal separator, e.g. `1,5 Mbit/s` for people
+    /// of many European locales
+    ///
+    /// The output is read back with [`ParseOptions::decimal_comma`], but not
+    /// by [`parse_bandwidth`](crate::parse_bandwidth). Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{FormatOptions, ParseOptions, Profile};
+    ///
+    /// let options = FormatOptions::from_profile(Profile::Human).decimal_comma(true);
+    /// assert_eq!(options.format(Bandwidth::from_kbps(1_536)), "1,5 Mbit/s");
+    /// assert_eq!(
+    ///     ParseOptions::new().decimal_comma(true).parse("1,5 Mbit/s"),
+    ///     Ok(Bandwidth::from_kbps(1_500))
+    /// );
+    /// ```
+    pub const fn decimal_comma(mut self, decimal_comma: bool) -> Self {
+        self.decimal_comma = decimal_comma;
+        self.frozen = None;
+        self
+    }
+
+    /// Set the prefixes of the units, e.g. `Mibit/s` instead of `Mbit/s`
+    ///
+    /// Defaults to [`PrefixBase::Decimal`]. Combined with
+    /// [`quantity`](Self::quantity), bandwidths can be written in any of the
+    /// four families of units, which are read back by
+    /// [`ParseOptions::quadrant_units`]. The engineering notation is only used
+    /// with decimal prefixes of bits.
+    ///
+    /// By default, the values in binary units are rounded to 3 decimals per
+    /// prefix, e.g. 6 for `Mibit/s`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{FormatOptions, ParseOptions, PrefixBase, Quantity};
+    ///
+    /// let val = ParseOptions::new().quadrant_units(true).parse("1.5MiB/s").unwrap();
+    /// let options = FormatOptions::new();
+    /// assert_eq!(options.format(val), "12.582912Mbps");
+    /// assert_eq!(options.quantity(Quantity::Bytes).format(val), "1.572864MB/s");
+    /// let options = options.prefix_base(PrefixBase::Binary);
+    /// assert_eq!(options.format(val), "12Mibps");
+    /// assert_eq!(options.iso80000(true).quantity(Quantity::Bytes).format(val), "1.5 MiB/s");
+    /// ```
+    pub const fn prefix_base(mut self, prefix_base: PrefixBase) -> Self {
+        self.prefix_base = prefix_base;
+        self.frozen = None;
+        self
+    }
+
+    /// Set what is counted per second, e.g. `MB/s` instead of `Mbit/s`
+    ///
+    /// Defaults to [`Quantity::Bits`]. By default, values in Bytes get 3 more
+    /// decimals than in bits, so that they remain exact to the bit per
+    /// second with decimal prefixes, e.g. `0.125B/s`. See
+    /// [`prefix_base`](Self::prefix_base).
+    pub const fn quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = quantity;
+        self.frozen = None;
+        self
+    }
+
+    /// Set the largest unit in which values are written
+    ///
+    /// Larger values are written with a number of at least 1000 in this unit,
+    /// e.g. to keep the units of existing files. With binary prefixes, the
+    /// prefix of the same rank is the largest one, e.g. `Ti` for
+    /// [`Unit::Tbps`]. The engineering notation is not affected.
+    ///
+    /// Defaults to [`Unit::Ebps`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{FormatOptions, Unit};
+    ///
+    /// let val = Bandwidth::new(2_000_000, 0);
+    /// assert_eq!(FormatOptions::new().format(val), "2Pbps");
+    /// assert_eq!(FormatOptions::new().max_unit(Unit::Tbps).format(val), "2000Tbps");
+    /// ```
+    pub const fn max_unit(mut self, max_unit: Unit) -> Self {
+        self.max_unit = max_unit;
+        self.frozen = None;
+        self
+    }
+
+    /// Write the amount per `interval` instead of per second, e.g. `500GB/h`
+    ///
+    /// The units of bits are then written with the ISO/IEC 80000-13 symbol,
+    /// e.g. `Mbit/h`, and a month is 30 days. The output is read back by
+    /// [`ParseOptions::parse`], with [`ParseOptions::quadrant_units`] for the
+    /// units in Bytes.
+    ///
+    /// Defaults to [`Interval::Second`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{FormatOptions, Interval, ParseOptions, Quantity};
+    ///
+    /// let options = FormatOptions::new().quantity(Quantity::Bytes).per(Interval::Hour);
+    /// let val = ParseOptions::new().quadrant_units(true).parse("500GB/h").unwrap();
+    /// assert_eq!(val, Bandwidth::from_bps(1_111_111_111));
+    /// assert_eq!(options.precision(1).format(val), "500.0GB/h");
+    /// assert_eq!(FormatOptions::new().per(Interval::Day).precision(1).format(val), "96.0Tbit/d");
+    /// ```
+    pub const fn per(mut self, interval: Interval) -> Self {
+        self.interval = interval;
+        self.frozen = None;
+        self
+    }
+
+    /// The maximum number of decimals, if set
+    pub const fn get_precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// Whether the unit is written after the number
+    pub const fn is_showing_unit(&self) -> bool {
+        !self.hide_unit
+    }
+
+    /// Whether the ISO/IEC 80000-13 symbols are used
+    pub const fn is_iso80000(&self) -> bool {
+        self.iso80000
+    }
+
+    /// Whether the engineering notation is used
+    pub const fn is_engineering(&self) -> bool {
+        self.engineering
+    }
+
+    /// The range in which the value is kept by the choice of its unit
+    pub const fn get_unit_policy(&self) -> UnitPolicy {
+        self.unit_policy
+    }
+
+    /// Whether all the decimals are written, even when they are zeros
+    pub const fn is_keeping_trailing_zeros(&self) -> bool {
+        self.keep_trailing_zeros
+    }
+
+    /// Whether the trailing zeros of the decimals set with a precision are
+    /// removed
+    pub const fn is_trimming_trailing_zeros(&self) -> bool {
+        self.trim_trailing_zeros
+    }
+
+    /// Whether a comma is written as the decimal separator
+    pub const fn is_decimal_comma(&self) -> bool {
+        self.decimal_comma
+    }
+
+    /// The prefixes of the units
+    pub const fn get_prefix_base(&self) -> PrefixBase {
+        self.prefix_base
+    }
+
+    /// What is counted per second
+    pub const fn get_quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    /// The largest unit in which values are written
+    pub const fn get_max_unit(&self) -> Unit {
+        self.max_unit
+    }
+
+    /// The interval the amounts are written per
+    pub const fn get_interval(&self) -> Interval {
+        self.interval
+    }
+
+    /// Whether the output is frozen by a versioned [`Profile`]
+    pub const fn is_frozen(&self) -> bool {
+        self.frozen.is_some()
+    }
+
+    /// Format a bandwidth with the largest unit in which it is at least 1
+    pub fn format(&self, val: Bandwidth) -> String {
+        self.format_bps(self.amount(val))
+    }
+
+    /// Format a bandwidth in at most `max_chars` characters, with as many
+    /// decimals as fit
+    ///
+    /// The decimals are rounded away one by one, then the value is written
+    /// in the next unit with [`UnitPolicy::TenthToHundred`], e.g. `1Gbps`
+    /// rather than `1000Mbps`. A nonzero value is never written as zero. When
+    /// nothing fits, the shortest rendering is returned, which may be longer
+    /// than `max_chars`. At the top of the range, the value is rounded down
+    /// rather than above [`Bandwidth::MAX`], so that it is parsed back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{FormatOptions, PrefixBase, Quantity};
+    ///
+    /// let options = FormatOptions::new()
+    ///     .iso80000(true)
+    ///     .prefix_base(PrefixBase::Binary)
+    ///     .quantity(Quantity::Bytes);
+    /// let val = Bandwidth::from_bps(10_016_290);
+    /// assert_eq!(options.format(val), "1.194034815 MiB/s");
+    /// assert_eq!(options.fit_to_width(val, 10), "1.19 MiB/s");
+    /// assert_eq!(options.fit_to_width(val, 5), "1 MiB/s");
+    /// ```
+    pub fn fit_to_width(&self, val: Bandwidth, max_chars: usize) -> String {
+        let bps = self.amount(val);
+        let mut shortest: Option<String> = None;
+        for options in [*self, self.unit_policy(UnitPolicy::TenthToHundred)] {
+            let exact = options.format_bps(bps);
+            let decimals = exact.find(['.', ',']).map_or(0, |dot| {
+                exact[dot + 1..]
+                    .bytes()
+                    .take_while(u8::is_ascii_digit)
+                    .count()
+            });
+            let candidates = core::iter::once(exact).chain((0..decimals).rev().map(|p| {
+                options
+                    .precision(p)
+                    .trim_trailing_zeros(!options.keep_trailing_zeros)
+                    .format_bps(bps)
+            }));
+            for s in candidates {
+                let mut number = s.split(|c: char| !c.is_ascii_digit() && c != '.' && c != ',');
+                let zero = |n: &str| n.bytes().all(|b| matches!(b, b'0' | b'.' | b','));
+                if bps > 0 && number.next().is_some_and(zero) {
+                    continue;
+                }
+                if s.chars().count() <= max_chars {
+                    return s;
+                }
+                if shortest
+                    .as_ref()
+                    .map_or(true, |t| s.chars().count() < t.chars().count())
+                {
+                    shortest = Some(s);
+                }
+            }
+        }
+        shortest.unwrap_or_else(|| self.format_bps(bps))
+    }
+
+    /// Format a range of bandwidths, both ends in the unit of the largest one,
+    /// e.g. `0.5–2Mbps`
+    ///
+    /// The ends are separated by an en dash, and the unit is written once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{range::BandwidthRange, FormatOptions, PrefixBase};
+    ///
+    /// let range = BandwidthRange::new(Bandwidth::from_kbps(512), Bandwidth::from_kbps(2_048));
+    /// assert_eq!(FormatOptions::new().format_range(range), "0.512–2.048Mbps");
+    /// let options = FormatOptions::new().prefix_base(PrefixBase::Binary).precision(2);
+    /// assert_eq!(options.format_range(range), "0.49–1.95Mibps");
+    /// ```
+    pub fn format_range(&self, range: BandwidthRange) -> String {
+        let (min, max) = (self.amount(range.min), self.amount(range.max));
+        let (divisor, suffix) = self.unit_for(min.max(max));
+        format!(
+            "{}\u{2013}{}{}",
+            self.number(min, divisor),
+            self.number(max, divisor),
+            suffix
+        )
+    }
+
+    /// The amount of bits per interval of a bandwidth
+    fn amount(&self, val: Bandwidth) -> u128 {
+        bandwidth_as_bps(val) * u128::from(self.interval.seconds())
+    }
+
+    /// Writes `bps` in the unit worth `divisor` bps, with the precision
+    fn number(&self, bps: u128, divisor: u128) -> String {
+        let mut number = String::new();
+        let precision = self.precision_for(divisor);
+        self.write_number(&mut number, &mut Vec::new(), bps, divisor, precision);
+        number
+    }
+
+    /// Writes `bps` in the unit worth `divisor` bps with `precision` decimals
+    /// at the end of `out`, `digits` being scratch space reused across calls
+    fn write_number(
+        &self,
+        out: &mut String,
+        digits: &mut Vec<u8>,
+        bps: u128,
+        divisor: u128,
+        precision: usize,
+    ) {
+        let start = out.len();
+        write_ratio(
+            out,
+            digits,
+            bps,
+            divisor,
+            precision,
+            self.amount(Bandwidth::MAX),
+        );
+        let trim = match self.precision {
+            Some(_) => self.trim_trailing_zeros,
+            None => !self.keep_trailing_zeros,
+        };
+        if trim && out[start..].contains('.') {
+            // The number has a decimal point, which stops the trimming
+            let len = out.trim_end_matches('0').trim_end_matches('.').len();
+            out.truncate(len);
+        }
+        if self.decimal_comma {
+            if let Some(dot) = out[start..].find('.') {
+                out.replace_range(start + dot..=start + dot, ",");
+            }
+        }
+    }
+
+    /// Number of decimals written in the unit worth `divisor` bps
+    fn precision_for(&self, divisor: u128) -> usize {
+        self.precision
+            .unwrap_or_else(|| self.default_precision(divisor))
+    }
+
+    /// Format many bandwidths separated by `sep` at the end of `buf`
+    ///
+    /// The output is the one of [`format`](Self::format) for each value, but
+    /// the units and the scratch space are shared by all the values, which
+    /// saves allocations when exporting many rates, e.g. to a CSV file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::FormatOptions;
+    ///
+    /// let values = (1..=3).map(|i| Bandwidth::from_kbps(i * 500));
+    /// let mut line = String::from("rates,");
+    /// FormatOptions::new().precision(1).format_many_into(&mut line, values, ",");
+    /// assert_eq!(line, "rates,500.0kbps,1.0Mbps,1.5Mbps");
+    /// ```
+    pub fn format_many_into<I>(&self, buf: &mut String, values: I, sep: &str)
+    where
+        I: IntoIterator<Item = Bandwidth>,
+    {
+        let mut digits = Vec::new();
+        // The units used so far: their value in bps, suffix and precision
+        let mut units: Vec<(u128, String, usize)> = Vec::new();
+        for (i, val) in values.into_iter().enumerate() {
+            if i > 0 {
+                buf.push_str(sep);
+            }
+            let bps = self.amount(val);
+            if let Some(frozen) = self.frozen {
+                buf.push_str(&frozen.format(bps));
+                continue;
+            }
+            let divisor = self.divisor_for(bps);
+            let index = match units.iter().position(|(unit, ..)| *unit == divisor) {
+                Some(index) => index,
+                None => {
+                    let precision = self.precision_for(divisor);
+                    units.push((divisor, self.suffix_for(divisor), precision));
+                    units.len() - 1
+                }
+            };
+            let (_, suffix, precision) = &units[index];
+            self.write_number(buf, &mut digits, bps, divisor, *precision);
+            buf.push_str(suffix);
+        }
+    }
+
+    /// Format an amount of bits per second, which may exceed [`Bandwidth::MAX`]
+    pub(crate) fn format_bps(&self, bps: u128) -> String {
+        if let Some(frozen) = self.frozen {
+            return frozen.format(bps);
+        }
+        let (divisor, suffix) = self.unit_for(bps);
+        format!("{}{}", self.number(bps, divisor), suffix)
+    }
+
+    /// The value in bits per second of the unit used to write `bps`, and what
+    /// is written after the number
+    pub(crate) fn unit_for(&self, bps: u128) -> (u128, String) {
+        let divisor = self.divisor_for(bps);
+        (divisor, self.suffix_for(divisor))
+    }
+
+    /// The value in bits per second of the unit used to write `bps` with
+    /// `precision` decimals, and what is written after the number
+    pub(crate) fn unit_with_precision(&self, bps: u128, precision: usize) -> (u128, String) {
+        let divisor = self.carried_divisor(bps, self.unrounded_divisor_for(bps), precision);
+        (divisor, self.suffix_for(divisor))
+    }
+
+    /// The value in bits per second of the unit used to write `bps`
+    fn divisor_for(&self, bps: u128) -> u128 {
+        let divisor = self.unrounded_divisor_for(bps);
+        self.carried_divisor(bps, divisor, self.precision_for(divisor))
+    }
+
+    /// The unit of `bps` rounded to `precision` decimals in the unit worth
+    /// `divisor` bps, which the rounding may carry to the next unit, e.g.
+    /// 999.96kbps written 1.0Mbps rather than 1000.0kbps
+    fn carried_divisor(&self, bps: u128, divisor: u128, precision: usize) -> u128 {
+        match rounded_bps(bps, divisor, precision) {
+            Some(rounded) => self.unrounded_divisor_for(rounded).max(divisor),
+            None => divisor,
+        }
+    }
+
+    /// The value in bits per second of the unit in which `bps` is at least 1,
+    /// before rounding
+    fn unrounded_divisor_for(&self, bps: u128) -> u128 {
+        let bps = match self.unit_policy {
+            UnitPolicy::OneToThousand => bps,
+            UnitPolicy::TenthToHundred => bps.saturating_mul(10),
+        };
+        if self.is_decimal_bits() {
+            return 10u128.pow(self.exponent_for(bps));
+        }
+        let bits = self.quantity.bits();
+        let index = (1..=self.max_unit as u32)
+            .rev()
+            .find(|&index| bps >= self.prefix_base.scale(index) * bits)
+            .unwrap_or(0);
+        self.prefix_base.scale(index) * bits
+    }
+
+    /// What is written after the number in the unit worth `divisor` bps
+    fn suffix_for(&self, divisor: u128) -> String {
+        if self.is_decimal_bits() {
+            return self.suffix(divisor.to_string().len() as u32 - 1);
+        }
+        if self.hide_unit {
+            return String::new();
+        }
+        let bits = self.quantity.bits();
+        let index = (0..=Unit::Ebps as u32)
+            .find(|&index| self.prefix_base.scale(index) * bits == divisor)
+            .unwrap_or(0);
+        let space = if self.iso80000 { " " } else { "" };
+        let symbol = self
+            .quantity
+            .symbol(self.iso80000 || self.interval != Interval::Second);
+        let prefix = self.prefix_base.prefix(index);
+        format!("{}{}{}", space, prefix, self.per_interval(symbol))
+    }
+
+    /// A unit per second written per the interval, e.g. `MB/h` for `MB/s`
+    fn per_interval(&self, unit: &str) -> String {
+        match unit.strip_suffix("/s") {
+            Some(base) => format!("{}{}", base, self.interval.suffix()),
+            None => unit.to_string(),
+        }
+    }
+
+    /// Whether the units are decimal prefixes of bits, e.g. `Mbps`
+    fn is_decimal_bits(&self) -> bool {
+        self.prefix_base == PrefixBase::Decimal && self.quantity == Quantity::Bits
+    }
+
+    /// Number of decimals written by default in the unit worth `divisor` bps:
+    /// exact for decimal prefixes, and for Bytes up to the decimals read back
+    /// by the parser, which are not enough for 1bps in `EB/s`
+    fn default_precision(&self, divisor: u128) -> usize {
+        let digits = divisor.to_string().len() - 1;
+        match self.quantity {
+            Quantity::Bits => digits,
+            Quantity::Bytes => (digits + 3).min(FRACTION_PART_LIMIT as usize),
+        }
+    }
+
+    /// Exponent of ten of the unit used to write `bps`, already scaled by the
+    /// unit policy
+    fn exponent_for(&self, bps: u128) -> u32 {
+        if self.engineering {
+            let mut exponent = 0;
+            while bps >= 10u128.pow(exponent + 3) {
+                exponent += 3;
+            }
+            exponent
+        } else {
+            si_exponent_for(bps).min(3 * self.max_unit as u32)
+        }
+    }
+
+    /// What is written after the number for the given exponent of ten
+    fn suffix(&self, exponent: u32) -> String {
+        if self.engineering {
+            format!("e{}{}", exponent, self.unit_suffix(0))
+        } else {
+            self.unit_suffix(exponent)
+        }
+    }
+
+    fn unit_suffix(&self, exponent: u32) -> String {
+        if self.hide_unit {
+            String::new()
+        } else if self.iso80000 {
+            format!(" {}", self.per_interval(iso_unit_name(exponent)))
+        } else if self.interval != Interval::Second {
+            self.per_interval(iso_unit_name(exponent))
+        } else {
+            si_unit_name(exponent).to_string()
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// An output which must never change, so it does not share the code of the
+/// other options on purpose
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frozen {
+    /// The output of [`Profile::V1`]
+    V1,
+    /// The output of [`CanonicalProfile`](crate::canonical::CanonicalProfile)
+    Canonical,
+}
+
+#[cfg(feature = "alloc")]
+impl Frozen {
+    fn format(self, bps: u128) -> String {
+        let (units, sep) = match self {
+            Frozen::V1 => (["bps", "kbps", "Mbps", "Gbps", "Tbps"], ""),
+            Frozen::Canonical => (["bit/s", "kbit/s", "Mbit/s", "Gbit/s", "Tbit/s"], " "),
+        };
+        let index = (1..units.len())
+            .rev()
+            .find(|&index| bps >= 10u128.pow(3 * index as u32))
+            .unwrap_or(0);
+        let scale = 10u128.pow(3 * index as u32);
+        let (int, fraction) = (bps / scale, bps % scale);
+        if fraction == 0 {
+            return format!("{}{}{}", int, sep, units[index]);
+        }
+        let digits = format!("{:0width$}", fraction, width = 3 * index);
+        let digits = digits.trim_end_matches('0');
+        format!("{}.{}{}{}", int, digits, sep, units[index])
+    }
+}
+
+/// Range in which a formatted value is kept by the choice of its unit, see
+/// [`FormatOptions::unit_policy`]
+///
+/// Values smaller than the range in `bps` are written in `bps` anyway, as well
+/// as values larger than the range in the largest unit, see
+/// [`FormatOptions::max_unit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UnitPolicy {
+    /// The value is kept in `[1, 1000)`, e.g. `500Mbps`
+    #[default]
+    OneToThousand,
+    /// The value is kept in `[0.1, 100)`, e.g. `0.5Gbps`
+    TenthToHundred,
+}
+
+/// The prefixes of the units, see [`FormatOptions::prefix_base`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PrefixBase {
+    /// Powers of 1000: `k`, `M`, `G`, `T`, `P` and `E`
+    #[default]
+    Decimal,
+    /// Powers of 1024: `Ki`, `Mi`, `Gi`, `Ti`, `Pi` and `Ei`
+    Binary,
+}
+
+impl PrefixBase {
+    /// Value of the prefix at `index`, e.g. `1_048_576` for `Mi`
+    const fn scale(self, index: u32) -> u128 {
+        match self {
+            PrefixBase::Decimal => 10u128.pow(3 * index),
+            PrefixBase::Binary => 1 << (10 * index),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn prefix(self, index: u32) -> &'static str {
+        let prefixes = match self {
+            PrefixBase::Decimal => ["", "k", "M", "G", "T", "P", "E"],
+            PrefixBase::Binary => ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"],
+        };
+        prefixes[index as usize]
+    }
+}
+
+/// What is counted per second, see [`FormatOptions::quantity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Quantity {
+    /// Bits, e.g. `Mbit/s` or `Mbps`
+    #[default]
+    Bits,
+    /// Bytes, e.g. `MB/s`
+    Bytes,
+}
+
+impl Quantity {
+    /// Number of bits of one unit
+    const fn bits(self) -> u128 {
+        match self {
+            Quantity::Bits => 1,
+            Quantity::Bytes => 8,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn symbol(self, iso80000: bool) -> &'static str {
+        match (self, iso80000) {
+            (Quantity::Bits, true) => "bit/s",
+            (Quantity::Bits, false) => "bps",
+            (Quantity::Bytes, _) => "B/s",
+        }
+    }
+}
+
+/// How the units whose quantity is a lone `b` or `B` are read, see
+/// [`ParseOptions::bit_byte_policy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BitBytePolicy {
+    /// `b` is a bit and `B` a Byte, e.g. `kb/s` is 1000bps and `kB/s`
+    /// 8000bps
+    #[default]
+    CaseSensitive,
+    /// Both are bits, e.g. for sources writing `KBps` for kilobits
+    Bits,
+    /// Both are Bytes, e.g. for sources writing `kbps` for kilobytes
+    Bytes,
+    /// These units are rejected with
+    /// [`Error::AmbiguousUnit`](crate::Error::AmbiguousUnit): `bit` and
+    /// `Byte` must be spelled in full
+    Reject,
+}
+
+/// The kinds of units accepted besides the symbols of bits per second like
+/// `Mbps` or `Mbit/s`, see [`ParseOptions::enabled_units`]
+///
+/// The units of the binary prefixes and in Bytes are accepted with
+/// [`binary_prefixes`](Self::binary_prefixes) and [`bytes`](Self::bytes), or
+/// both with [`ParseOptions::quadrant_units`], and the custom units and
+/// aliases are always accepted, being given explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnabledUnits {
+    spelled: bool,
+    per_interval: bool,
+    baud: bool,
+    binary_prefixes: bool,
+    bytes: bool,
+}
+
+impl Default for EnabledUnits {
+    fn default() -> Self {
+        EnabledUnits::ALL
+    }
+}
+
+impl EnabledUnits {
+    /// Every kind of unit of the decimal prefixes of bits, the baud units
+    /// being only accepted with [`ParseOptions::baud`]
+    pub const ALL: EnabledUnits = EnabledUnits {
+        spelled: true,
+        per_interval: true,
+        baud: true,
+        binary_prefixes: false,
+        bytes: false,
+    };
+
+    /// Only the symbols of the units per second, e.g. `Mbps`, or `MiB/s`
+    /// with [`ParseOptions::quadrant_units`]
+    pub const SYMBOLS: EnabledUnits = EnabledUnits {
+        spelled: false,
+        per_interval: false,
+        baud: false,
+        binary_prefixes: false,
+        bytes: false,
+    };
+
+    /// Accept the units spelled out, e.g. `10 megabits per second`
+    pub const fn spelled(mut self, spelled: bool) -> Self {
+        self.spelled = spelled;
+        self
+    }
+
+    /// Whether the units spelled out are accepted
+    pub const fn is_spelled(&self) -> bool {
+        self.spelled
+    }
+
+    /// Accept the units per minute, hour, day or month, e.g. `500Gbit/h`
+    pub const fn per_interval(mut self, per_interval: bool) -> Self {
+        self.per_interval = per_interval;
+        self
+    }
+
+    /// Whether the units per minute, hour, day or month are accepted
+    pub const fn is_per_interval(&self) -> bool {
+        self.per_interval
+    }
+
+    /// Accept the symbol rates in baud, e.g. `9600baud`, with the bits per
+    /// symbol of [`ParseOptions::baud`]
+    pub const fn baud(mut self, baud: bool) -> Self {
+        self.baud = baud;
+        self
+    }
+
+    /// Whether the symbol rates in baud are accepted
+    pub const fn is_baud(&self) -> bool {
+        self.baud
+    }
+
+    /// Accept the binary prefixes of [`ParseOptions::quadrant_units`], e.g.
+    /// `Mibit/s`, or `MiB/s` with [`bytes`](Self::bytes)
+    pub const fn binary_prefixes(mut self, binary_prefixes: bool) -> Self {
+        self.binary_prefixes = binary_prefixes;
+        self
+    }
+
+    /// Whether the binary prefixes are accepted
+    pub const fn is_binary_prefixes(&self) -> bool {
+        self.binary_prefixes
+    }
+
+    /// Accept the units in Bytes of [`ParseOptions::quadrant_units`], e.g.
+    /// `MB/s`, or `MiB/s` with [`binary_prefixes`](Self::binary_prefixes)
+    pub const fn bytes(mut self, bytes: bool) -> Self {
+        self.bytes = bytes;
+        self
+    }
+
+    /// Whether the units in Bytes are accepted
+    pub const fn is_bytes(&self) -> bool {
+        self.bytes
+    }
+}
+
+/// Value in baud of a unit of symbol rate, e.g. `1_000` for `kBd`, see
+/// [`ParseOptions::baud`]
+pub(crate) fn baud_unit(unit: &str) -> Option<u128> {
+    match unit {
+        "Bd" | "baud" => Some(1),
+        "kBd" | "kbaud" => Some(1_000),
+        "MBd" | "Mbaud" => Some(1_000_000),
+        "GBd" | "Gbaud" => Some(1_000_000_000),
+        _ => None,
+    }
+}
+
+/// Whether the quantity of a unit is a lone `b` or `B`, e.g. `Mbps` or
+/// `kB/s`, rather than `bit` or `Byte`, see [`BitBytePolicy`]
+pub(crate) fn is_lone_symbol(unit: &str) -> bool {
+    ["bps", "Bps", "b/s", "B/s"]
+        .iter()
+        .any(|symbol| unit.ends_with(symbol))
+}
+
+/// Whether `c` ends a line, see [`ParseOptions::newlines`]
+pub(crate) fn is_line_break(c: char) -> bool {
+    matches!(
+        c,
+        '\n' | '\r' | '\u{b}' | '\u{c}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+/// Value in bits per second of a unit of the prefix bases and quantities
+/// `enabled`, e.g. `8 << 20` for `MiB/s`, see [`ParseOptions::quadrant_units`]
+pub(crate) fn quadrant_unit(unit: &str, iso80000: bool, enabled: EnabledUnits) -> Option<u128> {
+    let (prefix, quantity) = if let Some(prefix) = unit.strip_suffix("bit/s") {
+        (prefix, Quantity::Bits)
+    } else if let Some(prefix) = unit.strip_suffix("B/s") {
+        (prefix, Quantity::Bytes)
+    } else if iso80000 {
+        return None;
+    } else if let Some(prefix) = unit.strip_suffix("Byte/s") {
+        (prefix, Quantity::Bytes)
+    } else if let Some(prefix) = unit.strip_suffix("bps") {
+        (prefix, Quantity::Bits)
+    } else {
+        (unit.strip_suffix("Bps")?, Quantity::Bytes)
+    };
+    let (prefix_base, index) = match prefix {
+        "" => (PrefixBase::Decimal, 0),
+        "k" | "K" => (PrefixBase::Decimal, 1),
+        "M" => (PrefixBase::Decimal, 2),
+        "G" => (PrefixBase::Decimal, 3),
+        "T" => (PrefixBase::Decimal, 4),
+        "P" => (PrefixBase::Decimal, 5),
+        "E" => (PrefixBase::Decimal, 6),
+        "Ki" | "ki" => (PrefixBase::Binary, 1),
+        "Mi" => (PrefixBase::Binary, 2),
+        "Gi" => (PrefixBase::Binary, 3),
+        "Ti" => (PrefixBase::Binary, 4),
+        "Pi" => (PrefixBase::Binary, 5),
+        "Ei" => (PrefixBase::Binary, 6),
+        _ => return None,
+    };
+    let accepted = match (prefix_base, quantity) {
+        // The other spellings of the decimal units of bits, e.g. `Kbit/s`
+        (PrefixBase::Decimal, Quantity::Bits) => enabled.binary_prefixes || enabled.bytes,
+        (PrefixBase::Binary, Quantity::Bits) => enabled.binary_prefixes,
+        (PrefixBase::Decimal, Quantity::Bytes) => enabled.bytes,
+        (PrefixBase::Binary, Quantity::Bytes) => enabled.binary_prefixes && enabled.bytes,
+    };
+    accepted.then(|| prefix_base.scale(index) * quantity.bits())
+}
+
+/// A built-in set of formatting options, see [`FormatOptions::from_profile`]
+///
+/// The same value can be logged with [`Profile::Machine`] and displayed with
+/// [`Profile::Human`], both being parsed back by
+/// [`parse_bandwidth`](crate::parse_bandwidth).
+///
+/// These profiles may be improved in future versions. Golden files and
+/// on-disk caches should use a versioned profile like [`Profile::V1`], whose
+/// output never changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Profile {
+    /// For logs and other programs: ASCII only, no whitespace, the largest
+    /// `bps` unit in which the value is at least 1 and every decimal, e.g.
+    /// `1.536Mbps`
+    ///
+    /// The output does not depend on the crate features nor on the locale.
+    #[default]
+    Machine,
+    /// For people: a space before the ISO/IEC 80000-13 unit and at most one
+    /// decimal, e.g. `1.5 Mbit/s`
+    ///
+    /// The output is meant to be read, not compared: it may be tuned for
+    /// readability in future versions. It is localized with
+    /// [`FormatOptions::decimal_comma`], e.g. `1,5 Mbit/s`, which is then
+    /// parsed back with [`ParseOptions::decimal_comma`].
+    Human,
+    /// The output of [`Profile::Machine`] when this profile was introduced,
+    /// e.g. `1.536Mbps`, guaranteed never to change in later releases
+    ///
+    /// The value is written exactly, in the largest of `bps`, `kbps`, `Mbps`,
+    /// `Gbps` and `Tbps` in which it is at least 1, without trailing zeros.
+    V1,
+}
+
+#[cfg(feature = "alloc")]
+impl Profile {
+    /// The formatting options of this profile
+    pub const fn format_options(&self) -> FormatOptions {
+        FormatOptions::from_profile(*self)
+    }
+
+    /// Format a bandwidth with this profile
+    pub fn format(&self, val: Bandwidth) -> String {
+        self.format_options().format(val)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let options = ParseOptions::default();
+        assert_eq!(options.parse("9Tbps 420Gbps"), Ok(Bandwidth::new(9420, 0)));
+        assert_eq!(
+            options.parse_with_warnings("1.5Mbps"),
+            Ok((Bandwidth::new(0, 1_500_000), vec![]))
+        );
+        assert!(options.parse("10Mpbs").is_err());
+    }
+
+    #[test]
+    fn test_fuzzy() {
+        let options = ParseOptions::new().fuzzy(true);
+        assert_eq!(options.parse("10Mpbs"), Ok(Bandwidth::from_mbps(10)));
+        assert_eq!(options.parse("10MBit/s"), Ok(Bandwidth::from_mbps(10)));
+        assert_eq!(options.parse("3Kbps/s"), Ok(Bandwidth::from_kbps(3)));
+        assert_eq!(options.parse("2gbs"), Ok(Bandwidth::new(2, 0)));
+        assert_eq!(
+            options.parse("1.5Tbsp 5kbps"),
+            Ok(Bandwidth::new(1_500, 5_000))
+        );
+        assert_eq!(
+            options.parse_with_warnings("5Gbps 12mpbs"),
+            Ok((
+                Bandwidth::new(5, 12_000_000),
+                vec![ParseWarning::AssumedUnit {
+                    start: 8,
+                    end: 12,
+                    unit: "mpbs".to_string(),
+                    assumed: "Mbps",
+                }]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_rejected() {
+        let options = ParseOptions::new().fuzzy(true);
+        // Equally close to bps, kbps, Mbps...
+        assert!(matches!(
+            options.parse("10xbps"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        assert!(matches!(
+            options.parse("10Mbananas"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        // Units in Bytes are 8 times larger than the closest units of bits
+        for s in ["1GB/s", "1Gbyte/s", "10MB/s", "3KBps/s"] {
+            assert!(
+                matches!(
+                    options.parse_with_warnings(s),
+                    Err(Error::UnknownUnit { .. })
+                ),
+                "{}",
+                s
+            );
+        }
+        // Binary units are 2.4% to 15% larger than the closest SI units
+        for s in ["1Mibps", "1Mibit/s", "1Tib/s", "1kiB/s", "1Gio/s", "1kBd"] {
+            let err = options.parse_with_warnings(s).unwrap_err();
+            assert!(matches!(err, Error::UnknownUnit { .. }), "{}", s);
+        }
+        assert_eq!(
+            options.parse("1Mibps").unwrap_err().to_string(),
+            r#"unknown bandwidth unit "Mibps", did you mean "Mbps"?"#
+        );
+        let binary = FormatOptions::new().prefix_base(PrefixBase::Binary);
+        let val = Bandwidth::from_bps(1 << 20);
+        assert!(options.parse(&binary.format(val)).is_err());
+        assert_eq!(
+            options.parse("1Mibps"),
+            options.fuzzy(false).parse("1Mibps")
+        );
+        assert_eq!(
+            options.parse("10"),
+            Err(Error::UnknownUnit {
+                start: 2,
+                end: 2,
+                unit: String::new(),
+                value: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn test_iso80000() {
+        let options = ParseOptions::new().iso80000(true);
+        assert_eq!(
+            options.parse("1 Gbit/s 500 Mbit/s 8 bit/s"),
+            Ok(Bandwidth::new(1, 500_000_008))
+        );
+        assert_eq!(options.parse("2.5 MB/s"), Ok(Bandwidth::from_mbps(20)));
+        assert_eq!(options.parse("1 TB/s"), Ok(Bandwidth::new(8_000, 0)));
+        assert_eq!(options.parse("3 B/s"), Ok(Bandwidth::from_bps(24)));
+        assert_eq!(
+            options.parse("1 Mbit/s 2kbit/s"),
+            Err(Error::SpaceExpected(10))
+        );
+        for unit in ["bps", "Kbit/s", "mbit/s", "Mb/s", "KB/s", "Mbyte/s"] {
+            assert!(matches!(
+                options.parse(&format!("1 {}", unit)),
+                Err(Error::UnknownUnit { .. })
+            ));
+        }
+        // The fuzzy mode does not apply
+        assert!(options.fuzzy(true).parse("1 Mbits/s").is_err());
+    }
+
+    #[test]
+    fn test_recovering() {
+        let options = ParseOptions::new();
+        assert_eq!(
+            options.parse_recovering("1Gbps 2Mbps"),
+            (Bandwidth::new(1, 2_000_000), vec![])
+        );
+        assert_eq!(
+            options.parse_recovering("  "),
+            (Bandwidth::new(0, 0), vec![Error::OnlyWhitespace { len: 2 }])
+        );
+
+        let (val, errors) = options.parse_recovering("Mbps 1kbps 2kb!ps 3bps9Xbps5bps 7");
+        assert_eq!(val, Bandwidth::from_bps(1_008));
+        assert_eq!(
+            errors,
+            [
+                Error::NumberExpected(0),
+                Error::InvalidCharacter(14),
+                Error::UnknownUnit {
+                    start: 23,
+                    end: 27,
+                    unit: "Xbps".to_string(),
+                    value: 9,
+                },
+                Error::UnknownUnit {
+                    start: 33,
+                    end: 33,
+                    unit: String::new(),
+                    value: 7,
+                },
+            ]
+        );
+
+        // Overflows skip the span, whether it is the number or the total
+        let (val, errors) = options.parse_recovering(
+            "99999999999999999999999999999bps 1kbps 18446744073709551615Gbps 1Gbps",
+        );
+        assert_eq!(val, Bandwidth::new(18_446_744_073_709_551_615, 1_000));
+        assert_eq!(errors, [Error::NumberOverflow, Error::NumberOverflow]);
+
+        // The strict modes are respected
+        let (val, errors) = options
+            .iso80000(true)
+            .parse_recovering("1 Mbit/s 2kbit/s 3 kbit/s");
+        assert_eq!(val, Bandwidth::from_kbps(1_003));
+        assert_eq!(errors, [Error::SpaceExpected(10)]);
+    }
+
+    #[test]
+    fn test_custom_units() {
+        const UNITS: &[(&str, u64)] = &[("OC-48", 2_488_320_000), ("DS0", 64_000), ("kbps", 1_024)];
+        let options = ParseOptions::new().custom_units(UNITS);
+        assert_eq!(options.get_custom_units(), UNITS);
+        assert_eq!(
+            options.parse("1OC-48 2 DS0"),
+            Ok(Bandwidth::new(2, 488_448_000))
+        );
+        // Fractions less than 1bps are ignored
+        assert_eq!(options.parse("0.0000001DS0"), Ok(Bandwidth::new(0, 0)));
+        assert_eq!(options.parse("1.5DS0"), Ok(Bandwidth::from_kbps(96)));
+        // Built-in units may be overridden
+        assert_eq!(
+            options.parse("2kbps 1Mbps"),
+            Ok(Bandwidth::from_bps(1_002_048))
+        );
+        assert_eq!(
+            options.parse("2DS0 1OC-48"),
+            Ok(Bandwidth::new(2, 488_448_000))
+        );
+        // A digit after a unit ending with a digit is ambiguous
+        assert!(options.parse("1DS01kbps").is_err());
+        assert!(options.parse("1OC-481kbps").is_err());
+        // Units are case sensitive, even in fuzzy mode
+        assert!(matches!(
+            options.fuzzy(true).parse("1ds0"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        assert_eq!(
+            options.parse("18446744073709551615OC-48"),
+            Err(Error::NumberOverflow)
+        );
+    }
+
+    #[test]
+    fn test_bps_means_bytes() {
+        let options = ParseOptions::new().bps_means_bytes(true);
+        assert_eq!(
+            options.parse("1Gbps 2mbps 3bps"),
+            Ok(Bandwidth::new(8, 16_000_024))
+        );
+        assert_eq!(
+            options.parse("1Gbit/s 2mb/s 3b/s"),
+            Ok(Bandwidth::new(1, 2_000_003))
+        );
+        assert_eq!(options.parse("1.5kbps"), Ok(Bandwidth::from_kbps(12)));
+        assert_eq!(
+            options.fuzzy(true).parse("1Mpbs"),
+            Ok(Bandwidth::from_mbps(1))
+        );
+        let options = options.iso80000(true);
+        assert_eq!(options.parse("1 Mbit/s"), Ok(Bandwidth::from_mbps(1)));
+        assert_eq!(options.parse("1 MB/s"), Ok(Bandwidth::from_mbps(8)));
+        assert_eq!(
+            ParseOptions::new().parse("1kbps"),
+            Ok(Bandwidth::from_kbps(1))
+        );
+    }
+
+    #[test]
+    fn test_bit_byte_policy() {
+        let options = ParseOptions::new().quadrant_units(true);
+        assert_eq!(options.get_bit_byte_policy(), BitBytePolicy::CaseSensitive);
+        assert_eq!(
+            options.parse("1Kbps 1KBps 1kb/s 1kB/s"),
+            Ok(Bandwidth::from_kbps(18))
+        );
+        assert_eq!(options.parse("1MByte/s"), Ok(Bandwidth::from_mbps(8)));
+
+        let bits = options.bit_byte_policy(BitBytePolicy::Bits);
+        assert_eq!(
+            bits.parse("1Kbps 1KBps 1kb/s 1kB/s"),
+            Ok(Bandwidth::from_kbps(4))
+        );
+        assert_eq!(bits.parse("1KiBps 1B/s"), Ok(Bandwidth::from_bps(1_025)));
+        assert_eq!(bits.parse("1MByte/s"), Ok(Bandwidth::from_mbps(8)));
+        assert_eq!(
+            bits.bps_means_bytes(true).parse("1kbps"),
+            Ok(Bandwidth::from_kbps(1))
+        );
+
+        let bytes = options.bit_byte_policy(BitBytePolicy::Bytes);
+        assert_eq!(
+            bytes.parse("1Kbps 1KBps 1kb/s 1kB/s"),
+            Ok(Bandwidth::from_kbps(32))
+        );
+        assert_eq!(bytes.parse("1Kibps"), Ok(Bandwidth::from_bps(8_192)));
+        assert_eq!(bytes.parse("1Mbit/s"), Ok(Bandwidth::from_mbps(1)));
+        assert_eq!(bytes.parse("36Gb/h"), Ok(Bandwidth::from_mbps(80)));
+
+        let reject = options.bit_byte_policy(BitBytePolicy::Reject);
+        assert_eq!(
+            reject.parse("1Mbit/s 1MByte/s 1Kibit/s"),
+            Ok(Bandwidth::from_bps(9_001_024))
+        );
+        for (input, start, end) in [
+            ("1Kbps", 1, 5),
+            ("1 KBps", 2, 6),
+            ("1Mbit/s 1kb/s", 9, 13),
+            ("1kB/s", 1, 5),
+            ("1MiBps", 1, 6),
+            ("1Gb/h", 1, 5),
+        ] {
+            assert_eq!(
+                reject.parse(input),
+                Err(Error::AmbiguousUnit { start, end }),
+                "{}",
+                input
+            );
+        }
+        assert!(matches!(
+            reject.parse("1Kbs"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        assert_eq!(
+            reject.iso80000(true).parse("1 kB/s"),
+            Ok(Bandwidth::from_kbps(8))
+        );
+        let err = reject.parse("10Mbps").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"unit at 2..6 may be in bits or Bytes, write "bit" or "Byte" in full"#
+        );
+        assert_eq!(err.clone().with_input("10Mbps").label(), "bits or Bytes");
+        assert_eq!(
+            reject.parse_borrowed("10Mbps"),
+            Err(ParseError::AmbiguousUnit { start: 2, end: 6 })
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let options = FormatOptions::new();
+        assert_eq!(options.format(Bandwidth::new(0, 0)), "0bps");
+        assert_eq!(options.format(Bandwidth::new(9_420, 0)), "9.42Tbps");
+        assert_eq!(options.format(Bandwidth::new(0, 150_024)), "150.024kbps");
+        assert_eq!(
+            options.precision(0).format(Bandwidth::new(0, 150_624)),
+            "151kbps"
+        );
+        assert_eq!(options.precision(2).format(Bandwidth::new(0, 7)), "7.00bps");
+        assert_eq!(
+            options.precision(2).format(Bandwidth::new(0, 7_126)),
+            "7.13kbps"
+        );
+        assert_eq!(options.show_unit(false).format(Bandwidth::new(2, 0)), "2");
+        // The unit is chosen after rounding
+        let options = options.precision(3);
+        let carried = Bandwidth::from_bps(999_999_600);
+        assert_eq!(options.format(carried), "1.000Gbps");
+        assert_eq!(
+            options.format(Bandwidth::from_bps(999_999_400)),
+            "999.999Mbps"
+        );
+        let mut line = String::new();
+        options.format_many_into(&mut line, [carried, Bandwidth::from_mbps(1)], ",");
+        assert_eq!(line, "1.000Gbps,1.000Mbps");
+        assert_eq!(
+            options.format_range(BandwidthRange::new(Bandwidth::from_mbps(500), carried)),
+            "0.500\u{2013}1.000Gbps"
+        );
+        assert_eq!(options.max_unit(Unit::Mbps).format(carried), "1000.000Mbps");
+        let options = FormatOptions::new().iso80000(true);
+        assert_eq!(options.format(Bandwidth::new(0, 0)), "0 bit/s");
+        assert_eq!(options.format(Bandwidth::new(0, 12_000)), "12 kbit/s");
+        assert_eq!(options.format(Bandwidth::new(1, 500_000_000)), "1.5 Gbit/s");
+        assert_eq!(options.format(Bandwidth::new(3_000, 0)), "3 Tbit/s");
+    }
+
+    #[test]
+    fn test_max_unit_round_trip() {
+        for unit in [Unit::Bps, Unit::Kbps, Unit::Mbps] {
+            let options = FormatOptions::new().max_unit(unit);
+            for bandwidth in [
+                Bandwidth::new(10_518_193_033_455_800_429, 0),
+                Bandwidth::new(u64::MAX, 0),
+                Bandwidth::MAX,
+            ] {
+                let text = options.format(bandwidth);
+                assert_eq!(crate::parse_bandwidth(&text), Ok(bandwidth), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_engineering() {
+        let options = FormatOptions::new().engineering(true);
+        assert_eq!(options.format(Bandwidth::new(0, 0)), "0e0bps");
+        assert_eq!(options.format(Bandwidth::new(0, 999)), "999e0bps");
+        assert_eq!(options.format(Bandwidth::new(0, 1_000)), "1e3bps");
+        assert_eq!(options.format(Bandwidth::new(4_200_000, 0)), "4.2e15bps");
+        assert_eq!(
+            options.format(Bandwidth::MAX),
+            "18.446744073709551615999999999e27bps"
+        );
+        // Read back near the largest bandwidth, past `u64::MAX` bps
+        for val in [
+            Bandwidth::MAX,
+            Bandwidth::new(u64::MAX, 999_999_998),
+            Bandwidth::new(u64::MAX / 2, 123_456_789),
+            Bandwidth::new(18_446_744_073, 709_551_616),
+        ] {
+            let s = options.format(val);
+            assert_eq!(crate::parse_bandwidth(&s), Ok(val), "{}", s);
+        }
+        assert_eq!(
+            options.precision(1).format(Bandwidth::new(0, 12_345_678)),
+            "12.3e6bps"
+        );
+        // The exponent of the rounded value
+        assert_eq!(
+            options.precision(1).format(Bandwidth::from_bps(999_960)),
+            "1.0e6bps"
+        );
+        assert_eq!(
+            options.precision(1).format(Bandwidth::from_bps(999_940)),
+            "999.9e3bps"
+        );
+        assert_eq!(
+            options.show_unit(false).format(Bandwidth::new(0, 12_000)),
+            "12e3"
+        );
+        assert_eq!(
+            crate::format_aligned(
+                &[Bandwidth::new(1, 0), Bandwidth::new(0, 25_000_000)],
+                &options.iso80000(true)
+            ),
+            ["1.00e9 bit/s", "0.02e9 bit/s"]
+        );
+    }
+
+    #[test]
+    fn test_unit_policy() {
+        let options = FormatOptions::new().unit_policy(UnitPolicy::TenthToHundred);
+        assert_eq!(options.format(Bandwidth::new(0, 0)), "0bps");
+        assert_eq!(options.format(Bandwidth::new(0, 99)), "99bps");
+        assert_eq!(options.format(Bandwidth::new(0, 100)), "0.1kbps");
+        assert_eq!(options.format(Bandwidth::new(0, 99_999)), "99.999kbps");
+        assert_eq!(options.format(Bandwidth::new(120, 0)), "0.12Tbps");
+        assert_eq!(options.format(Bandwidth::new(4_200, 0)), "4.2Tbps");
+        assert_eq!(
+            options.engineering(true).format(Bandwidth::new(0, 250_000)),
+            "0.25e6bps"
+        );
+        assert_eq!(
+            crate::format_aligned(
+                &[
+                    Bandwidth::new(0, 500_000_000),
+                    Bandwidth::new(0, 12_000_000)
+                ],
+                &options
+            ),
+            ["0.50Gbps", "0.01Gbps"]
+        );
+    }
+
+    #[test]
+    fn test_keep_trailing_zeros() {
+        let options = FormatOptions::new().keep_trailing_zeros(true);
+        assert_eq!(options.format(Bandwidth::new(0, 0)), "0bps");
+        assert_eq!(options.format(Bandwidth::new(0, 7)), "7bps");
+        assert_eq!(options.format(Bandwidth::new(0, 1_500)), "1.500kbps");
+        assert_eq!(options.format(Bandwidth::new(2, 0)), "2.000000000Gbps");
+        assert_eq!(options.precision(0).format(Bandwidth::new(2, 0)), "2Gbps");
+        assert_eq!(
+            options.precision(3).format(Bandwidth::new(0, 7)),
+            "7.000bps"
+        );
+        assert_eq!(
+            options
+                .engineering(true)
+                .precision(2)
+                .format(Bandwidth::new(0, 1_000_000)),
+            "1.00e6bps"
+        );
+    }
+
+    #[test]
+    fn test_trim_trailing_zeros() {
+        let options = FormatOptions::new().precision(2);
+        assert!(!options.is_trimming_trailing_zeros());
+        assert_eq!(options.format(Bandwidth::new(0, 1_500)), "1.50kbps");
+        let options = options.trim_trailing_zeros(true);
+        assert!(options.is_trimming_trailing_zeros());
+        assert_eq!(options.format(Bandwidth::new(0, 1_500)), "1.5kbps");
+        assert_eq!(options.format(Bandwidth::new(0, 1_504)), "1.5kbps");
+        assert_eq!(options.format(Bandwidth::new(0, 1_996)), "2kbps");
+        assert_eq!(options.format(Bandwidth::new(0, 1_234)), "1.23kbps");
+        assert_eq!(options.precision(0).format(Bandwidth::new(2, 0)), "2Gbps");
+        // Without precision, only keep_trailing_zeros applies
+        assert_eq!(
+            FormatOptions::new()
+                .trim_trailing_zeros(true)
+                .keep_trailing_zeros(true)
+                .format(Bandwidth::new(0, 1_500)),
+            "1.500kbps"
+        );
+    }
+
+    #[test]
+    fn test_format_many_into() {
+        let values: Vec<_> = [0, 7, 999, 1_000, 1_536_000, 8_388_608, 12_500_000_000]
+            .iter()
+            .map(|bps| Bandwidth::from_bps(*bps))
+            .chain([Bandwidth::new(4_200, 1), Bandwidth::MAX])
+            .collect();
+        let all = [
+            FormatOptions::new(),
+            FormatOptions::new().precision(2).keep_trailing_zeros(true),
+            FormatOptions::new().precision(2).trim_trailing_zeros(true),
+            FormatOptions::new().engineering(true).iso80000(true),
+            FormatOptions::new().unit_policy(UnitPolicy::TenthToHundred),
+            FormatOptions::new().prefix_base(PrefixBase::Binary),
+            FormatOptions::new()
+                .quantity(Quantity::Bytes)
+                .show_unit(false),
+            FormatOptions::from_profile(Profile::Human),
+            FormatOptions::from_profile(Profile::V1),
+        ];
+        for options in all {
+            let mut buf = "|".to_string();
+            options.format_many_into(&mut buf, values.iter().copied(), ", ");
+            let expected: Vec<_> = values.iter().map(|val| options.format(*val)).collect();
+            assert_eq!(buf, format!("|{}", expected.join(", ")), "{:?}", options);
+        }
+        let mut buf = String::new();
+        FormatOptions::new().format_many_into(&mut buf, [], ",");
+        assert_eq!(buf, "");
+    }
+
+    #[test]
+    fn test_fit_to_width() {
+        let options = FormatOptions::new();
+        let val = Bandwidth::new(0, 123_456_789);
+        for (width, s) in [
+            (0, "123Mbps"),
+            (8, "123Mbps"),
+            (9, "123.5Mbps"),
+            (10, "123.46Mbps"),
+            (13, "123.45679Mbps"),
+            (14, "123.456789Mbps"),
+            (100, "123.456789Mbps"),
+        ] {
+            assert_eq!(options.fit_to_width(val, width), s);
+        }
+        assert_eq!(options.fit_to_width(Bandwidth::new(0, 0), 0), "0bps");
+        assert_eq!(options.fit_to_width(Bandwidth::new(0, 400), 4), "400bps");
+        let val = Bandwidth::new(0, 999_999_999);
+        assert_eq!(options.fit_to_width(val, 5), "1Gbps");
+        assert_eq!(options.fit_to_width(val, 9), "1Gbps");
+        let options = options.keep_trailing_zeros(true);
+        assert_eq!(options.fit_to_width(Bandwidth::new(2, 0), 7), "2.0Gbps");
+        let options = options.show_unit(false);
+        assert_eq!(options.fit_to_width(Bandwidth::from_kbps(2_250), 3), "2.2");
+
+        // Rounded down at the top of the range, so that it is parsed back
+        let options = FormatOptions::new();
+        assert_eq!(
+            options.fit_to_width(Bandwidth::MAX, 20),
+            "18446744073.7095Ebps"
+        );
+        assert_eq!(options.fit_to_width(Bandwidth::MAX, 8), "18446744073Ebps");
+        for width in 0..40 {
+            let s = options.fit_to_width(Bandwidth::MAX, width);
+            let parsed = crate::parse_bandwidth(&s).map(bandwidth_as_bps);
+            assert!(
+                matches!(parsed, Ok(bps) if bandwidth_as_bps(Bandwidth::MAX) - bps < 10u128.pow(18)),
+                "{} {:?}",
+                s,
+                parsed
+            );
+            let s = options
+                .per(Interval::Hour)
+                .fit_to_width(Bandwidth::MAX, width);
+            assert!(crate::parse_bandwidth(&s).is_ok(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_profiles() {
+        let val = Bandwidth::new(9_420, 1);
+        assert_eq!(Profile::Machine.format(val), "9.420000000001Tbps");
+        assert_eq!(Profile::Human.format(val), "9.4 Tbit/s");
+        assert_eq!(Profile::Human.format(Bandwidth::from_kbps(12)), "12 kbit/s");
+        // Rounded up to the next unit
+        assert_eq!(
+            Profile::Human.format(Bandwidth::from_bps(999_960_000)),
+            "1 Gbit/s"
+        );
+        assert_eq!(
+            Profile::Human.format(Bandwidth::from_bps(999_940_000)),
+            "999.9 Mbit/s"
+        );
+        assert_eq!(Profile::default(), Profile::Machine);
+        for profile in [Profile::Machine, Profile::Human] {
+            let s = profile.format(Bandwidth::from_mbps(25));
+            assert_eq!(crate::parse_bandwidth(&s), Ok(Bandwidth::from_mbps(25)));
+        }
+        assert!(Profile::Machine.format(val).is_ascii());
+    }
+
+    #[test]
+    fn test_format_decimal_comma() {
+        let options = FormatOptions::from_profile(Profile::Human).decimal_comma(true);
+        let val = Bandwidth::from_kbps(1_536);
+        assert_eq!(options.format(val), "1,5 Mbit/s");
+        assert_eq!(options.format(Bandwidth::from_mbps(2)), "2 Mbit/s");
+        assert_eq!(
+            ParseOptions::new()
+                .decimal_comma(true)
+                .parse(&options.format(val)),
+            Ok(Bandwidth::from_kbps(1_500))
+        );
+        let options = FormatOptions::new().decimal_comma(true);
+        assert_eq!(options.format(val), "1,536Mbps");
+        assert_eq!(options.engineering(true).format(val), "1,536e6bps");
+        assert_eq!(options.fit_to_width(val, 7), "1,5Mbps");
+        assert_eq!(options.fit_to_width(Bandwidth::from_bps(1), 1), "1bps");
+        assert_eq!(
+            options.precision(1).format_range(BandwidthRange::new(
+                Bandwidth::from_kbps(500),
+                Bandwidth::from_kbps(2_500)
+            )),
+            "0,5\u{2013}2,5Mbps"
+        );
+        assert_eq!(
+            crate::format_aligned(&[val, Bandwidth::from_mbps(20)], &options),
+            [" 1,54Mbps", "20,00Mbps"]
+        );
+    }
+
+    #[test]
+    fn test_tightened() {
+        let lenient = ParseOptions::default();
+        assert_eq!(lenient, ParseOptions::new());
+        assert_eq!(
+            lenient.parse("1_0 0Mbps 2kbps"),
+            Ok(Bandwidth::from_kbps(100_002))
+        );
+
+        let options = ParseOptions::new().digit_separators(false);
+        assert_eq!(options.parse("1 00Mbps"), Ok(Bandwidth::from_mbps(100)));
+        assert_eq!(options.parse("10_0Mbps"), Err(Error::InvalidCharacter(2)));
+
+        let options = ParseOptions::new().spaces_in_numbers(false);
+        assert_eq!(
+            options.parse(" 1_00 Mbps 2 kbps"),
+            Ok(Bandwidth::from_kbps(100_002))
+        );
+        assert_eq!(options.parse("10 0Mbps"), Err(Error::InvalidCharacter(2)));
+        assert_eq!(options.parse("10  .5Mbps"), Err(Error::InvalidCharacter(2)));
+
+        let options = ParseOptions::new().newlines(false);
+        assert_eq!(
+            options.parse(" 1 Mbps\t2 kbps "),
+            Ok(Bandwidth::from_kbps(1_002))
+        );
+        for (s, at) in [
+            ("\n1Mbps", 0),
+            ("1\n000Mbps", 1),
+            ("1\nMbps", 1),
+            ("1Mbps\n2kbps", 5),
+            ("1Mbps \u{2028}2kbps", 6),
+            ("1Mbps\r\n", 5),
+            ("1 megabit\nper second", 9),
+            ("1 megabit per\nsecond", 13),
+        ] {
+            assert_eq!(
+                options.parse(s),
+                Err(Error::InvalidCharacter(at)),
+                "{:?}",
+                s
+            );
+        }
+        assert_eq!(options.parse("\n"), Err(Error::OnlyWhitespace { len: 1 }));
+
+        let options = ParseOptions::new().single_spaces(true);
+        for s in [
+            "1Mbps 2kbps",
+            "1 Mbps 2 kbps",
+            "1 000 Mbps",
+            "1 megabit per second",
+        ] {
+            assert!(options.parse(s).is_ok(), "{:?}", s);
+        }
+        for (s, at) in [
+            (" 1Mbps", 0),
+            ("1Mbps ", 5),
+            ("1Mbps  2kbps", 5),
+            ("1Mbps\t2kbps", 5),
+            ("1\u{a0}Mbps", 1),
+            ("1  Mbps", 1),
+            ("1 megabit  per second", 9),
+        ] {
+            assert_eq!(
+                options.parse(s),
+                Err(Error::InvalidCharacter(at)),
+                "{:?}",
+                s
+            );
+        }
+        assert_eq!(options.parse("  "), Err(Error::OnlyWhitespace { len: 2 }));
+        assert_eq!(
+            options.parse_recovering("1Mbps  2kbps\t3bps"),
+            (
+                Bandwidth::from_bps(1_002_003),
+                vec![Error::InvalidCharacter(5), Error::InvalidCharacter(12)]
+            )
+        );
+        let mut stream = crate::stream::StreamParser::with_options(options);
+        stream.feed("1Mbps ").unwrap();
+        stream.feed("2kbps").unwrap();
+        assert_eq!(stream.finish(), Ok(Bandwidth::from_kbps(1_002)));
+
+        let options = ParseOptions::new().multiple_spans(false);
+        assert_eq!(options.parse(" 2.5 Mbps "), Ok(Bandwidth::from_kbps(2_500)));
+        assert_eq!(options.parse("1Mbps2kbps"), Err(Error::ExtraSpan(5)));
+        assert_eq!(
+            options.parse_recovering("1Mbps 2kbps 3bps"),
+            (
+                Bandwidth::from_mbps(1),
+                vec![Error::ExtraSpan(6), Error::ExtraSpan(12)]
+            )
+        );
+        assert_eq!(
+            options.parse_borrowed("1Mbps 2kbps"),
+            Err(ParseError::ExtraSpan(6))
+        );
+        assert_eq!(
+            Error::ExtraSpan(6).to_string(),
+            "expected a single rate span, got another at 6"
+        );
+    }
+
+    #[test]
+    fn test_decimal_comma() {
+        let options = ParseOptions::new().decimal_comma(true);
+        assert_eq!(
+            options.parse("0,5 kbps 2,25bps"),
+            Ok(Bandwidth::from_bps(502))
+        );
+        assert_eq!(options.parse("1,5,0Mbps"), Err(Error::InvalidCharacter(3)));
+        assert_eq!(options.parse("1,5.0Mbps"), Err(Error::InvalidCharacter(3)));
+        assert_eq!(
+            options.parse_recovering("1,5Mbps 2,x 3kbps"),
+            (
+                Bandwidth::from_kbps(1_503),
+                vec![Error::UnknownUnit {
+                    start: 10,
+                    end: 11,
+                    unit: "x".to_string(),
+                    value: 2,
+                }]
+            )
+        );
+        let options = options.iso80000(true);
+        assert_eq!(options.parse("2,5 Mbit/s"), Ok(Bandwidth::from_kbps(2_500)));
+    }
+
+    #[test]
+    fn test_digit_grouping() {
+        let options = ParseOptions::new().digit_grouping(true);
+        for (s, bps) in [
+            ("999,999bps", 999_999),
+            ("1,234,567bps", 1_234_567),
+            ("1'000bps 2,000bps", 3_000),
+            ("1\u{202f}000\u{202f}000bps", 1_000_000),
+            ("1_0,000bps", 10_000),
+            ("12 Mbps", 12_000_000),
+            ("1,000.1234kbps", 1_000_123),
+        ] {
+            assert_eq!(options.parse(s), Ok(Bandwidth::from_bps(bps)), "{}", s);
+        }
+        for (s, at) in [
+            ("1234,567bps", 4),
+            ("1,23bps", 1),
+            ("1,2345bps", 1),
+            ("1,000'000bps", 5),
+            ("1,,000bps", 2),
+            ("1 2Mbps", 1),
+            ("1,000 00Mbps", 5),
+        ] {
+            assert_eq!(options.parse(s), Err(Error::InvalidDigitGroup(at)), "{}", s);
+        }
+        assert_eq!(
+            options.parse("1.000,5Mbps"),
+            Err(Error::InvalidCharacter(5))
+        );
+        assert_eq!(
+            ParseOptions::new().parse("1'000bps"),
+            Err(Error::InvalidCharacter(1))
+        );
+
+        let options = options.decimal_comma(true);
+        assert_eq!(
+            options.parse("1 000,5kbps"),
+            Ok(Bandwidth::from_bps(1_000_500))
+        );
+        assert_eq!(
+            options.parse("1'000,5kbps"),
+            Ok(Bandwidth::from_bps(1_000_500))
+        );
+        assert_eq!(
+            options.parse_borrowed("10 00,5kbps"),
+            Err(ParseError::InvalidDigitGroup(2))
+        );
+    }
+
+    #[test]
+    fn test_exponent_notation() {
+        let options = ParseOptions::new();
+        for (s, bps) in [
+            ("1e3bps", 1_000),
+            ("2E6 bps", 2_000_000),
+            ("1.5e3Mbps", 1_500_000_000),
+            ("1.5E+3 kbps", 1_500_000),
+            ("2.5e-3Mbps", 2_500),
+            ("25e-1 Ebps", 2_500_000_000_000_000_000),
+            ("1e-18Ebps", 1),
+            ("1e-19Ebps", 0),
+            ("0e999999bps", 0),
+            ("1e-999999Ebps", 0),
+            ("1e0bps 2e1bps", 21),
+            ("2Ebps", 2_000_000_000_000_000_000),
+            ("1_0e2kbps", 1_000_000),
+        ] {
+            assert_eq!(options.parse(s), Ok(Bandwidth::from_bps(bps)), "{}", s);
+        }
+        // The integer part may exceed `u64::MAX`, like the total
+        assert_eq!(
+            options.parse("1e20bps"),
+            Ok(Bandwidth::new(100_000_000_000, 0))
+        );
+        assert_eq!(
+            options.parse("18.446744073709551615999999999e27bps"),
+            Ok(Bandwidth::MAX)
+        );
+        assert_eq!(options.parse("1e29bps"), Err(Error::NumberOverflow));
+        assert_eq!(options.parse("1e38Ebps"), Err(Error::NumberOverflow));
+        assert_eq!(options.parse("1e999999bps"), Err(Error::NumberOverflow));
+        assert_eq!(options.parse("1e3.5bps"), Err(Error::InvalidCharacter(3)));
+        assert_eq!(options.parse("1e3 5bps"), Err(Error::InvalidCharacter(4)));
+        assert!(matches!(
+            options.parse("1e bps"),
+            Err(Error::UnknownUnit {
+                start: 1,
+                end: 2,
+                ..
+            })
+        ));
+
+        const TELECOM: &[(&str, u64)] = &[("E1", 2_048_000)];
+        let options = options.custom_units(TELECOM);
+        assert_eq!(options.parse("2E1"), Ok(Bandwidth::from_kbps(4_096)));
+        assert_eq!(options.parse("2E1bps"), Ok(Bandwidth::from_bps(20)));
+    }
+
+    #[test]
+    fn test_strict_spans() {
+        let options = ParseOptions::new().strict_spans(true);
+        assert_eq!(
+            options.parse("1Ebps 2Pbps 3Tbps 4Gbps 5Mbps 6kbps 7bps"),
+            Ok(Bandwidth::new(1_002_003_004, 5_006_007))
+        );
+        let iso = options.iso80000(true);
+        assert_eq!(iso.parse("1 MB/s 7 Mbit/s"), Ok(Bandwidth::from_mbps(15)));
+        assert_eq!(
+            iso.parse("7 Mbit/s 1 MB/s"),
+            Err(Error::UnorderedUnit { start: 11, end: 15 })
+        );
+        assert_eq!(
+            options.parse("1kbps 1bps 1kbps"),
+            Err(Error::UnorderedUnit { start: 12, end: 16 })
+        );
+        assert_eq!(
+            options.parse("1kbps 2Kbps"),
+            Err(Error::DuplicateUnit { start: 7, end: 11 })
+        );
+        assert_eq!(
+            options.parse_recovering("3Mbps 1Gbps 2kbps1bps"),
+            (
+                Bandwidth::from_kbps(3_002),
+                vec![
+                    Error::UnorderedUnit { start: 7, end: 11 },
+                    Error::SeparatorExpected(17)
+                ]
+            )
+        );
+        assert_eq!(
+            options.parse_borrowed("1bps 1bps"),
+            Err(ParseError::DuplicateUnit { start: 6, end: 9 })
+        );
+
+        const TELECOM: &[(&str, u64)] = &[("DS", 44_736_000), ("E1", 2_048_000), ("T1", 1_544_000)];
+        let options = options.custom_units(TELECOM);
+        assert_eq!(options.parse("1E1 1T1"), Ok(Bandwidth::from_kbps(3_592)));
+        assert_eq!(options.parse("1DS1T1"), Err(Error::SeparatorExpected(3)));
+        assert_eq!(
+            options.parse("1T1 1E1"),
+            Err(Error::UnorderedUnit { start: 5, end: 7 })
+        );
+        // Lenient by default
+        assert_eq!(
+            ParseOptions::new().parse("1kbps 2kbps1Mbps"),
+            Ok(Bandwidth::from_kbps(1_003))
+        );
+    }
+
+    #[test]
+    fn test_case_sensitive_prefixes() {
+        let options = ParseOptions::new().case_sensitive_prefixes(true);
+        assert_eq!(
+            options.parse("1Ebps 1Pbit/s 1Tb/s 1Gbps 1Mbps 1kbps 1bps"),
+            Ok(Bandwidth::new(1_001_001_001, 1_001_001))
+        );
+        for unit in ["mbps", "Kbps", "gbit/s", "tb/s", "pbps", "ebps"] {
+            assert_eq!(
+                options.parse(&format!("1{}", unit)),
+                Err(Error::UnknownUnit {
+                    start: 1,
+                    end: 1 + unit.len(),
+                    unit: unit.to_string(),
+                    value: 1,
+                })
+            );
+            assert!(ParseOptions::new().parse(&format!("1{}", unit)).is_ok());
+        }
+        let quadrants = options.quadrant_units(true);
+        assert_eq!(
+            quadrants.parse("1KiB/s 1kiB/s 1kB/s"),
+            Ok(Bandwidth::from_bps(2 * 8 * 1_024 + 8_000))
+        );
+        assert!(quadrants.parse("1KB/s").is_err());
+        assert!(quadrants.parse("1mB/s").is_err());
+        assert_eq!(
+            options.fuzzy(true).parse_with_warnings("1mbps"),
+            Ok((
+                Bandwidth::from_mbps(1),
+                vec![ParseWarning::AssumedUnit {
+                    start: 1,
+                    end: 5,
+                    unit: "mbps".to_string(),
+                    assumed: "Mbps",
+                }]
+            ))
+        );
+        assert_eq!(
+            ParseOptions::strict().parse("1Mbps 1kbps"),
+            Ok(Bandwidth::from_kbps(1_001))
+        );
+        assert!(ParseOptions::strict().parse("1mbps").is_err());
+    }
+
+    #[test]
+    fn test_saturating() {
+        let options = ParseOptions::new().saturating(true);
+        for s in [
+            "18446744073709551616000000000bps",
+            "18446744073709551615Gbps 1000000000bps",
+            "1e29bps",
+            "1e999999bps",
+            "99999999999999999999Ebps 99999999999999999999Ebps",
+            "1Ebps 18446744073709551615Ebps",
+        ] {
+            assert!(ParseOptions::new().parse(s).is_err(), "{}", s);
+            assert_eq!(
+                options.parse_with_warnings(s),
+                Ok((Bandwidth::MAX, vec![ParseWarning::Saturated])),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            options.parse("18446744073709551615Gbps 999999999bps"),
+            Ok(Bandwidth::MAX)
+        );
+        assert_eq!(
+            options.parse_with_warnings("1Mbps"),
+            Ok((Bandwidth::from_mbps(1), vec![]))
+        );
+        assert!(matches!(
+            options.parse("99999999999999999999bps 1Mbpx"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        assert_eq!(
+            options.parse_recovering("99999999999999999999Ebps 1x 1Mbps"),
+            (
+                Bandwidth::MAX,
+                vec![Error::UnknownUnit {
+                    start: 26,
+                    end: 27,
+                    unit: "x".to_string(),
+                    value: 1,
+                }]
+            )
+        );
+        assert_eq!(
+            ParseWarning::Saturated.to_string(),
+            "value too large, read as the largest bandwidth"
+        );
+    }
+
+    #[test]
+    fn test_fraction_digits() {
+        let options = ParseOptions::new();
+        assert_eq!(options.get_fraction_digits(), 18);
+        assert_eq!(
+            options.parse("0.1234567890123456789Ebps"),
+            Ok(Bandwidth::from_bps(123_456_789_012_345_678))
+        );
+        let options = options.fraction_digits(2);
+        assert_eq!(options.parse("1.239Mbps"), Ok(Bandwidth::from_kbps(1_230)));
+        assert_eq!(
+            options.parse("1.239e1Mbps"),
+            Ok(Bandwidth::from_kbps(12_300))
+        );
+        assert_eq!(
+            options.parse("1239e-3Mbps"),
+            Ok(Bandwidth::from_kbps(1_230))
+        );
+        let options = options.fraction_digits(0);
+        assert_eq!(
+            options.parse("1.9Mbps 0.5Mbps"),
+            Ok(Bandwidth::from_mbps(1))
+        );
+
+        let options = ParseOptions::new().strict_fraction(true).fraction_digits(2);
+        assert!(options.is_strict_fraction());
+        assert_eq!(options.parse("1.23Mbps"), Ok(Bandwidth::from_kbps(1_230)));
+        assert_eq!(options.parse("1.230Mbps"), Err(Error::TooManyDecimals(4)));
+        assert_eq!(
+            options.parse_borrowed("2Mbps 1.230kbps"),
+            Err(ParseError::TooManyDecimals(10))
+        );
+        assert_eq!(
+            Error::TooManyDecimals(4).to_string(),
+            "too many decimals, the digit at 4 is not read"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 18 fractional digits are read")]
+    fn test_fraction_digits_limit() {
+        let _ = ParseOptions::new().fraction_digits(19);
+    }
+
+    #[test]
+    fn test_decode_escapes() {
+        let options = ParseOptions::new().decode_escapes(true);
+        assert_eq!(
+            options.parse("1Mbit%2fs 500&#x2F;&#47;"),
+            Err(Error::UnknownUnit {
+                start: 13,
+                end: 24,
+                unit: "&#x2F;&#47;".to_string(),
+                value: 500,
+            })
+        );
+        assert_eq!(
+            options.parse("1Mbit%2Fs&nbsp;500kb&sol;s"),
+            Ok(Bandwidth::from_kbps(1_500))
+        );
+        assert_eq!(
+            options.parse_borrowed("1Mbit%2Fs 2Mbit%2Fx"),
+            Err(ParseError::UnknownUnit {
+                start: 11,
+                end: 19,
+                unit: "Mbit%2Fx",
+                value: 2,
+            })
+        );
+        assert_eq!(
+            options.parse_borrowed("%20&nbsp;"),
+            Err(ParseError::OnlyWhitespace { len: 9 })
+        );
+        assert_eq!(
+            options.parse_borrowed("1Mbps%2"),
+            Err(ParseError::InvalidCharacter(5))
+        );
+        assert_eq!(
+            options.fuzzy(true).parse_with_warnings("1&nbsp;Mbit%2Fss"),
+            Ok((
+                Bandwidth::from_mbps(1),
+                vec![ParseWarning::AssumedUnit {
+                    start: 7,
+                    end: 16,
+                    unit: "Mbit%2Fss".to_string(),
+                    assumed: "Mbps",
+                }]
+            ))
+        );
+        assert_eq!(
+            options.parse_recovering("1Mbit%2Fx 2kbps &#160;x"),
+            (
+                Bandwidth::from_kbps(2),
+                vec![
+                    Error::UnknownUnit {
+                        start: 1,
+                        end: 9,
+                        unit: "Mbit%2Fx".to_string(),
+                        value: 1,
+                    },
+                    Error::NumberExpected(16),
+                ]
+            )
+        );
+        assert_eq!(
+            ParseOptions::new().parse("1Mbit%2Fs"),
+            Err(Error::InvalidCharacter(5))
+        );
+        #[cfg(feature = "binary-system")]
+        assert_eq!(
+            options.parse_binary("1MiB%2Fs"),
+            Ok(Bandwidth::from_bps(8 << 20))
+        );
+    }
+
+    #[test]
+    fn test_unit_aliases() {
+        const ALIASES: &[(&str, &str)] = &[
+            ("meg", "Mbps"),
+            ("Mb", "kbps"),
+            ("MB/s", "MiB/s"),
+            ("T-bit", "Tbps"),
+            ("nope", "Nbps"),
+        ];
+        let options = ParseOptions::new().unit_aliases(ALIASES);
+        assert_eq!(options.parse("1meg 2Mb"), Ok(Bandwidth::from_kbps(1_002)));
+        assert_eq!(options.parse("1Mbps"), Ok(Bandwidth::from_mbps(1)));
+        assert_eq!(options.parse("8T-bit"), Ok(Bandwidth::from_gbps(8_000)));
+        assert_eq!(
+            options.parse("1nope"),
+            Err(Error::UnknownUnit {
+                start: 1,
+                end: 5,
+                unit: "nope".to_string(),
+                value: 1,
+            })
+        );
+        assert!(options.parse("1MB/s").is_err());
+        assert_eq!(
+            options.quadrant_units(true).parse("1MB/s"),
+            Ok(Bandwidth::from_bps(8 << 20))
+        );
+        assert!(options.iso80000(true).parse("1 meg").is_err());
+
+        const ISO: &[(&str, &str)] = &[("meg", "Mbit/s")];
+        let options = ParseOptions::new().iso80000(true).unit_aliases(ISO);
+        assert_eq!(options.parse("1 meg"), Ok(Bandwidth::from_mbps(1)));
+        // Custom units come first
+        const CUSTOM: &[(&str, u64)] = &[("meg", 1)];
+        assert_eq!(
+            options.custom_units(CUSTOM).parse("3 meg"),
+            Ok(Bandwidth::from_bps(3))
+        );
+    }
+
+    #[test]
+    fn test_baud() {
+        let options = ParseOptions::new().baud(2);
+        assert_eq!(options.get_bits_per_symbol(), Some(2));
+        assert_eq!(ParseOptions::new().get_bits_per_symbol(), None);
+        assert_eq!(options.parse("300Bd"), Ok(Bandwidth::from_bps(600)));
+        assert_eq!(options.parse("1.2kbaud"), Ok(Bandwidth::from_bps(2_400)));
+        assert_eq!(options.parse("1MBd 1Mbaud"), Ok(Bandwidth::from_mbps(4)));
+        assert_eq!(options.parse("2GBd"), Ok(Bandwidth::new(4, 0)));
+        assert_eq!(
+            options.parse("1MBd 200kbps"),
+            Ok(Bandwidth::from_kbps(2_200))
+        );
+        assert_eq!(options.parse("0.0001Bd"), Ok(Bandwidth::from_bps(0)));
+        assert!(matches!(
+            options.parse("1 Kbaud"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        assert!(matches!(
+            ParseOptions::new().parse("9600 baud"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        let strict = options.strict_spans(true);
+        assert_eq!(strict.parse("1kBd 1kbps"), Ok(Bandwidth::from_kbps(3)));
+        assert_eq!(
+            strict.baud(1).parse("1kBd 2kbit/s"),
+            Err(Error::DuplicateUnit { start: 6, end: 12 })
+        );
+        const SYMBOLS: &[(&str, u64)] = &[("Bd", 8)];
+        assert_eq!(
+            options.custom_units(SYMBOLS).parse("1Bd"),
+            Ok(Bandwidth::from_bps(8))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "a symbol carries at least one bit")]
+    fn test_baud_zero() {
+        let _ = ParseOptions::new().baud(0);
+    }
+
+    #[test]
+    fn test_enabled_units() {
+        assert_eq!(ParseOptions::new().get_enabled_units(), EnabledUnits::ALL);
+        let strict = ParseOptions::strict();
+        assert_eq!(strict.get_enabled_units(), EnabledUnits::SYMBOLS);
+        for s in [
+            "10 megabits per second",
+            "10 Megabit/s",
+            "1 gigabits/s",
+            "36Gbit/h",
+            "1Mbit/min",
+            "2TB/day",
+        ] {
+            let parsed = ParseOptions::new().quadrant_units(true).parse(s);
+            assert!(parsed.is_ok(), "{} {:?}", s, parsed);
+            assert!(
+                matches!(
+                    strict.quadrant_units(true).parse(s),
+                    Err(Error::UnknownUnit { .. })
+                ),
+                "{}",
+                s
+            );
+        }
+        assert!(strict.parse("10 megabits per second").is_err());
+        assert_eq!(strict.parse("10Mbit/s"), Ok(Bandwidth::from_mbps(10)));
+        assert_eq!(
+            strict.quadrant_units(true).parse("1MB/s"),
+            Ok(Bandwidth::from_mbps(8))
+        );
+
+        let options = ParseOptions::new().enabled_units(EnabledUnits::ALL.spelled(false));
+        assert!(options.parse("10 megabits per second").is_err());
+        assert!(options.parse("10 megabits/s").is_err());
+        assert_eq!(options.parse("36Gbit/h"), Ok(Bandwidth::from_mbps(10)));
+        let options = ParseOptions::new().enabled_units(EnabledUnits::ALL.per_interval(false));
+        assert!(options.parse("36Gbit/h").is_err());
+        assert!(options.parse("36 gigabits per hour").is_err());
+        assert_eq!(
+            options.parse("10 megabits per second"),
+            Ok(Bandwidth::from_mbps(10))
+        );
+
+        // The prefix bases and quantities are enabled one by one
+        let quadrants = ParseOptions::new().quadrant_units(true);
+        assert_eq!(
+            quadrants.get_enabled_units(),
+            EnabledUnits::ALL.binary_prefixes(true).bytes(true)
+        );
+        assert!(!quadrants.quadrant_units(false).is_quadrant_units());
+        for (enabled, accepted) in [
+            (
+                EnabledUnits::ALL.bytes(true),
+                ["1MB/s", "1MByte/s", "1kBps"],
+            ),
+            (
+                EnabledUnits::ALL.binary_prefixes(true),
+                ["1Mibit/s", "1Mibps", "1kibit/s"],
+            ),
+        ] {
+            let options = ParseOptions::new().enabled_units(enabled);
+            assert!(!options.is_quadrant_units());
+            for s in accepted {
+                assert_eq!(options.parse(s), quadrants.parse(s), "{}", s);
+            }
+            for s in ["1MiB/s", "1MiByte/s", "2 mebibytes per second"] {
+                assert!(
+                    matches!(options.parse(s), Err(Error::UnknownUnit { .. })),
+                    "{}",
+                    s
+                );
+                assert!(quadrants.parse(s).is_ok(), "{}", s);
+            }
+        }
+        let bytes = strict.enabled_units(EnabledUnits::SYMBOLS.bytes(true));
+        assert_eq!(bytes.parse("1MB/s"), Ok(Bandwidth::from_mbps(8)));
+        assert!(bytes.parse("1Mibit/s").is_err());
+        assert!(bytes.parse("1 megabyte per second").is_err());
+
+        // The baud units are enabled by the number of bits per symbol
+        let baud = ParseOptions::new().baud(1);
+        let disabled = baud.enabled_units(EnabledUnits::ALL.baud(false));
+        assert_eq!(disabled.get_bits_per_symbol(), None);
+        assert!(matches!(
+            disabled.parse("9600baud"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        assert!(strict.parse("9600baud").is_err());
+        assert_eq!(
+            strict.baud(1).parse("9600baud"),
+            Ok(Bandwidth::from_bps(9_600))
+        );
+    }
+
+    #[test]
+    fn test_intervals() {
+        let options = ParseOptions::new();
+        assert_eq!(options.parse("36Gbit/h"), Ok(Bandwidth::from_mbps(10)));
+        assert_eq!(options.parse("600Mb/min"), Ok(Bandwidth::from_mbps(10)));
+        assert_eq!(options.parse("864Gbit/d"), Ok(Bandwidth::from_mbps(10)));
+        assert_eq!(options.parse("864Gbit/day"), Ok(Bandwidth::from_mbps(10)));
+        assert_eq!(options.parse("1bit/min"), Ok(Bandwidth::from_bps(0)));
+        assert!(matches!(
+            options.parse("500GB/h"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        assert!(matches!(
+            options.fuzzy(true).parse("10Mpbs/h"),
+            Err(Error::UnknownUnit { .. })
+        ));
+        let quadrant = options.quadrant_units(true);
+        assert_eq!(
+            quadrant.parse("500GB/h"),
+            Ok(Bandwidth::from_bps(1_111_111_111))
+        );
+        assert_eq!(
+            quadrant.parse("2TB/day"),
+            Ok(Bandwidth::from_bps(185_185_185))
+        );
+        assert_eq!(
+            quadrant.parse("30MiB/min"),
+            Ok(Bandwidth::from_bps(4_194_304))
+        );
+        assert_eq!(
+            quadrant.parse("2.592TB/month"),
+            Ok(Bandwidth::from_bps(8_000_000))
+        );
+        assert_eq!(
+            quadrant.month_days(31).parse("2.678400TB/month"),
+            Ok(Bandwidth::from_bps(8_000_000))
+        );
+        assert_eq!(
+            options.iso80000(true).parse("36 Gbit/h"),
+            Ok(Bandwidth::from_mbps(10))
+        );
+        // Compared per second in the strict mode
+        let strict = quadrant.strict_spans(true);
+        assert_eq!(
+            strict.parse("1Gbit/s 2Gbit/h"),
+            Ok(Bandwidth::from_bps(1_000_555_555))
+        );
+        assert_eq!(
+            strict.parse("1kB/min 1kbit/s"),
+            Err(Error::UnorderedUnit { start: 9, end: 15 })
+        );
+        assert_eq!(
+            strict.parse("1kbit/s 60kbit/min"),
+            Ok(Bandwidth::from_kbps(2))
+        );
+        assert_eq!(
+            strict.parse("1kbit/h 1kbit/h"),
+            Err(Error::DuplicateUnit { start: 9, end: 15 })
+        );
+
+        let val = Bandwidth::from_mbps(10);
+        for (base, quantity, expected, iso) in [
+            (PrefixBase::Decimal, Quantity::Bits, "36Gbit/h", "36 Gbit/h"),
+            (
+                PrefixBase::Binary,
+                Quantity::Bits,
+                "33.527612686Gibit/h",
+                "33.527612686 Gibit/h",
+            ),
+            (PrefixBase::Decimal, Quantity::Bytes, "4.5GB/h", "4.5 GB/h"),
+            (
+                PrefixBase::Binary,
+                Quantity::Bytes,
+                "4.19095158577GiB/h",
+                "4.19095158577 GiB/h",
+            ),
+        ] {
+            let options = FormatOptions::new()
+                .prefix_base(base)
+                .quantity(quantity)
+                .per(Interval::Hour);
+            assert_eq!(options.get_interval(), Interval::Hour);
+            assert_eq!(options.format(val), expected);
+            assert_eq!(options.iso80000(true).format(val), iso);
+        }
+        let options = FormatOptions::new().per(Interval::Day);
+        assert_eq!(options.format(val), "864Gbit/d");
+        assert_eq!(quadrant.parse(&options.format(val)), Ok(val));
+        assert_eq!(options.per(Interval::Month).format(val), "25.92Tbit/month");
+        assert_eq!(options.per(Interval::Second).format(val), "10Mbps");
+        assert_eq!(
+            options.format_range(BandwidthRange::new(Bandwidth::from_kbps(1), val)),
+            "0.0864\u{2013}864Gbit/d"
+        );
+        assert!(!FormatOptions::from_profile(Profile::V1)
+            .per(Interval::Minute)
+            .is_frozen());
+    }
+
+    #[test]
+    fn test_quadrants() {
+        let val = Bandwidth::from_bps(12_582_912);
+        for (base, quantity, expected, iso) in [
+            (
+                PrefixBase::Decimal,
+                Quantity::Bits,
+                "12.582912Mbps",
+                "12.582912 Mbit/s",
+            ),
+            (PrefixBase::Binary, Quantity::Bits, "12Mibps", "12 Mibit/s"),
+            (
+                PrefixBase::Decimal,
+                Quantity::Bytes,
+                "1.572864MB/s",
+                "1.572864 MB/s",
+            ),
+            (PrefixBase::Binary, Quantity::Bytes, "1.5MiB/s", "1.5 MiB/s"),
+        ] {
+            let options = FormatOptions::new().prefix_base(base).quantity(quantity);
+            assert_eq!(options.format(val), expected);
+            assert_eq!(options.iso80000(true).format(val), iso);
+            // Read back in any mode
+            let parse = ParseOptions::new().quadrant_units(true);
+            assert_eq!(parse.parse(expected), Ok(val));
+            assert_eq!(parse.iso80000(true).parse(iso), Ok(val));
+            assert_eq!(parse.fuzzy(true).parse(expected), Ok(val));
+        }
+        let bytes = FormatOptions::new().quantity(Quantity::Bytes);
+        assert_eq!(bytes.format(Bandwidth::from_bps(1)), "0.125B/s");
+        assert_eq!(bytes.format(Bandwidth::from_bps(8_001)), "1.000125kB/s");
+        let binary = FormatOptions::new().prefix_base(PrefixBase::Binary);
+        assert_eq!(binary.format(Bandwidth::from_bps(1_023)), "1023bps");
+        // Rounded to 3 decimals per prefix, with ties to even
+        assert_eq!(
+            binary.format(Bandwidth::from_bps(1_000_000)),
+            "976.562Kibps"
+        );
+        assert_eq!(
+            binary.precision(1).format(Bandwidth::from_bps(1_535)),
+            "1.5Kibps"
+        );
+        assert_eq!(binary.format(Bandwidth::from_bps(3 << 40)), "3Tibps");
+        assert_eq!(binary.format(Bandwidth::from_bps(3 << 60)), "3Eibps");
+        assert_eq!(
+            bytes.format(Bandwidth::from_bps(8 * 10u64.pow(15))),
+            "1PB/s"
+        );
+        assert_eq!(
+            bytes
+                .max_unit(Unit::Tbps)
+                .format(Bandwidth::from_bps(8 * 10u64.pow(15))),
+            "1000TB/s"
+        );
+        assert_eq!(
+            binary
+                .quantity(Quantity::Bytes)
+                .max_unit(Unit::Pbps)
+                .format(Bandwidth::from_bps(8 << 60)),
+            "1024PiB/s"
+        );
+        assert_eq!(
+            binary.show_unit(false).format(Bandwidth::from_bps(2_048)),
+            "2"
+        );
+        assert_eq!(
+            crate::format_aligned(
+                &[Bandwidth::from_bps(1_536), Bandwidth::from_bps(10_240)],
+                &binary
+            ),
+            [" 1.50Kibps", "10.00Kibps"]
+        );
+
+        let options = ParseOptions::new().quadrant_units(true);
+        assert_eq!(
+            options.parse("1.5Kibps 1kBps"),
+            Ok(Bandwidth::from_bps(9_536))
+        );
+        assert_eq!(options.parse("0.0005Kibit/s"), Ok(Bandwidth::from_bps(1)));
+        assert_eq!(options.parse("2Tibps"), Ok(Bandwidth::from_bps(2 << 40)));
+        assert_eq!(options.parse("1PB/s"), Ok(Bandwidth::new(8_000_000, 0)));
+        assert_eq!(options.parse("1EB/s"), Ok(Bandwidth::new(8_000_000_000, 0)));
+        assert_eq!(options.parse("1PiB/s"), Ok(Bandwidth::from_bps(8 << 50)));
+        assert_eq!(options.parse("1Eibit/s"), Ok(Bandwidth::from_bps(1 << 60)));
+        // The exact spellings keep their meaning
+        assert_eq!(
+            options.bps_means_bytes(true).parse("1kbps"),
+            Ok(Bandwidth::from_kbps(8))
+        );
+        assert!(options.iso80000(true).parse("1 MiBps").is_err());
+        assert!(ParseOptions::new().parse("1MiB/s").is_err());
+    }
+
+    #[test]
+    fn test_profile_v1() {
+        // Golden values, which must never change
+        for (bps, expected) in [
+            (0, "0bps"),
+            (999, "999bps"),
+            (1_000, "1kbps"),
+            (1_536_000, "1.536Mbps"),
+            (1_000_000_001, "1.000000001Gbps"),
+            (9_420_000_000_001, "9.420000000001Tbps"),
+            (u64::MAX, "18446744.073709551615Tbps"),
+        ] {
+            assert_eq!(Profile::V1.format(Bandwidth::from_bps(bps)), expected);
+            assert_eq!(
+                crate::parse_bandwidth(expected),
+                Ok(Bandwidth::from_bps(bps))
+            );
+        }
+        assert_eq!(
+            Profile::V1.format(Bandwidth::MAX),
+            "18446744073709551.615999999999Tbps"
+        );
+        assert_eq!(
+            Profile::V1.format(Bandwidth::new(3, 0)),
+            Profile::Machine.format(Bandwidth::new(3, 0))
+        );
+        let options = FormatOptions::from_profile(Profile::V1);
+        assert!(options.is_frozen());
+        assert!(!options.precision(1).is_frozen());
+        assert_eq!(
+            options.precision(1).format(Bandwidth::from_kbps(1_536)),
+            "1.5Mbps"
+        );
+    }
+}
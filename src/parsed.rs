@@ -0,0 +1,353 @@
+//! Bandwidths which remember how they were written
+//!
+//! A configuration editor which reads `1.50MiB/s` should show it back as
+//! `1.50MiB/s`, not as `12.582912Mbps`. A [`ParsedBandwidth`] keeps the units
+//! and the number of decimals of each rate span along with the value, and
+//! writes a new value in the same units, so that the intent of the user
+//! survives a round trip.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::parsed::ParsedBandwidth;
+//!
+//! let mut limit: ParsedBandwidth = "1.50MiB/s".parse().unwrap();
+//! assert_eq!(limit.value(), Bandwidth::from_bps(12_582_912));
+//! assert_eq!(limit.to_string(), "1.50MiB/s");
+//! limit.set(Bandwidth::from_bps(16_777_216));
+//! assert_eq!(limit.to_string(), "2.00MiB/s");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, bandwidth_from_bps, escapes::Decoded, Error, ParseOptions, Parser};
+
+/// A bandwidth with the units and the precision it was written with, see the
+/// [module documentation](self)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParsedBandwidth {
+    value: Bandwidth,
+    spans: Vec<WrittenSpan>,
+}
+
+/// A rate span as it was written, e.g. `1.50MiB/s`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WrittenSpan {
+    unit: String,
+    decimals: u32,
+    spaced: bool,
+    /// The value of the unit, in bits per `seconds`
+    bits: u128,
+    seconds: u128,
+    /// The bits per second of the span
+    bps: u128,
+}
+
+impl WrittenSpan {
+    /// The unit verbatim, e.g. `MiB/s` or `megabits per second`
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// The number of decimals of the number, e.g. 2 for `1.50MiB/s`
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    /// Whether the number is separated from the unit by whitespace
+    pub fn is_spaced(&self) -> bool {
+        self.spaced
+    }
+
+    /// The part of the bandwidth written in this span
+    pub fn value(&self) -> Bandwidth {
+        bandwidth_from_bps(self.bps).unwrap_or(Bandwidth::MAX)
+    }
+}
+
+/// Writes the value of the span in its unit, rounded to its number of
+/// decimals, e.g. `1.50MiB/s`
+impl fmt::Display for WrittenSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits = self.bits.max(1);
+        let amount = self.bps * self.seconds;
+        let (mut int, mut rest) = (amount / bits, amount % bits);
+        // Long division, as the remainder times a power of ten may overflow
+        let mut fraction: u128 = 0;
+        for _ in 0..self.decimals {
+            rest *= 10;
+            fraction = fraction * 10 + rest / bits;
+            rest %= bits;
+        }
+        if rest * 2 >= bits {
+            fraction += 1;
+            if fraction == 10u128.pow(self.decimals) {
+                int += 1;
+                fraction = 0;
+            }
+        }
+        write!(f, "{}", int)?;
+        if self.decimals > 0 {
+            write!(f, ".{:0width$}", fraction, width = self.decimals as usize)?;
+        }
+        if self.spaced {
+            f.write_str(" ")?;
+        }
+        f.write_str(&self.unit)
+    }
+}
+
+impl ParsedBandwidth {
+    /// Parses a bandwidth in any of the units of
+    /// [`ParseOptions::quadrant_units`], i.e. the units of
+    /// [`parse_bandwidth`](crate::parse_bandwidth) and the decimal and binary
+    /// prefixes of bits and Bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::parsed::ParsedBandwidth;
+    ///
+    /// let parsed = ParsedBandwidth::parse("1Gbps 500 Mbit/s").unwrap();
+    /// assert_eq!(parsed.value(), Bandwidth::from_mbps(1_500));
+    /// let units: Vec<_> = parsed.spans().iter().map(|span| span.unit()).collect();
+    /// assert_eq!(units, ["Gbps", "Mbit/s"]);
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        ParsedBandwidth::parse_with(s, &ParseOptions::new().quadrant_units(true))
+    }
+
+    /// Parses a bandwidth with the given options, see [`ParseOptions::parse`]
+    ///
+    /// The escapes decoded with [`ParseOptions::decode_escapes`] are not
+    /// kept, e.g. `1MB%2Fs` is written back as `1MB/s`.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        let decoded = Decoded::new(s, options);
+        let src = decoded.as_str();
+        let mut spans = Vec::new();
+        let mut parser = Parser::with_options(src, *options);
+        let parsed = parser.parse_with(
+            |parser, n, fraction, fraction_cnt, start, end| -> Result<(), Error> {
+                let before = parser.current;
+                parser.parse_unit(n, fraction, fraction_cnt, start, end)?;
+                // Recorded by every unit of `parse_unit`
+                let (bits, seconds) = parser.last_unit.unwrap_or((1, 1));
+                spans.push(WrittenSpan {
+                    unit: src[start..end].to_string(),
+                    decimals: fraction_cnt,
+                    spaced: src[..start].ends_with(char::is_whitespace),
+                    bits,
+                    seconds,
+                    bps: parser.current - before,
+                });
+                Ok(())
+            },
+        );
+        let value = parsed.map_err(|error| decoded.error(error))?;
+        Ok(ParsedBandwidth { value, spans })
+    }
+
+    /// The bandwidth
+    pub fn value(&self) -> Bandwidth {
+        self.value
+    }
+
+    /// The rate spans, in the order they were written
+    pub fn spans(&self) -> &[WrittenSpan] {
+        &self.spans
+    }
+
+    /// Changes the bandwidth, which is then written in the same units with
+    /// the same number of decimals
+    ///
+    /// Each span but the one of the smallest unit gets a whole number of its
+    /// unit, and the smallest one the rest, e.g. `2Gbps 250Mbps` for
+    /// `1Gbps 500Mbps` set to 2.25Gbps. The value is kept exactly, even when
+    /// the last span is written rounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::parsed::ParsedBandwidth;
+    ///
+    /// let mut parsed = ParsedBandwidth::parse("1Gbps 500Mbps").unwrap();
+    /// parsed.set(Bandwidth::from_mbps(2_250));
+    /// assert_eq!(parsed.to_string(), "2Gbps 250Mbps");
+    ///
+    /// let mut parsed = ParsedBandwidth::parse("0.5 GB/h").unwrap();
+    /// parsed.set(Bandwidth::from_bps(1_000_000));
+    /// assert_eq!(parsed.to_string(), "0.5 GB/h");
+    /// assert_eq!(parsed.value(), Bandwidth::from_bps(1_000_000));
+    /// ```
+    pub fn set(&mut self, val: Bandwidth) {
+        self.value = val;
+        // From the largest unit, the written order being kept between equal
+        // units
+        let mut order: Vec<usize> = (0..self.spans.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.spans[a], &self.spans[b]);
+            (b.bits * a.seconds).cmp(&(a.bits * b.seconds))
+        });
+        let mut rest = bandwidth_as_bps(val);
+        if let Some((&smallest, larger)) = order.split_last() {
+            for &i in larger {
+                let span = &mut self.spans[i];
+                let count = rest * span.seconds / span.bits.max(1);
+                span.bps = count * span.bits / span.seconds;
+                rest -= span.bps;
+            }
+            self.spans[smallest].bps = rest;
+        }
+    }
+}
+
+/// Writes the rate spans in their units, separated by a space
+impl fmt::Display for ParsedBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, span) in self.spans.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", span)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a bandwidth, see [`ParsedBandwidth::parse`]
+impl FromStr for ParsedBandwidth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ParsedBandwidth::parse(s)
+    }
+}
+
+impl From<ParsedBandwidth> for Bandwidth {
+    fn from(parsed: ParsedBandwidth) -> Self {
+        parsed.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(s: &str) -> String {
+        ParsedBandwidth::parse(s).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for s in [
+            "1.50MiB/s",
+            "1.5 MiB/s",
+            "10Mbps",
+            "1Gbps 500Mbps",
+            "1.000Gbit/s",
+            "0.125B/s",
+            "12.5 kB/s",
+            "2 Mibit/s",
+            "500GB/h",
+            "0.3kiB/s",
+            "0.000000000000000001Ebps",
+            "10 megabits per second",
+        ] {
+            assert_eq!(round_trip(s), s);
+        }
+        // The whitespace and the notation of the numbers are normalized
+        assert_eq!(round_trip("  1Gbps\t500Mbps "), "1Gbps 500Mbps");
+        assert_eq!(round_trip("1_500kbps"), "1500kbps");
+        assert_eq!(round_trip("1.5e3Mbps"), "1500Mbps");
+        assert_eq!(round_trip("1Gbps500Mbps"), "1Gbps 500Mbps");
+        // The fractions of a bit per second are dropped by the parser
+        assert_eq!(
+            round_trip("1.2345678901234567890bps"),
+            "1.000000000000000000bps"
+        );
+    }
+
+    #[test]
+    fn test_spans() {
+        let parsed = ParsedBandwidth::parse("2 GiB/s 1.25MB/s").unwrap();
+        assert_eq!(
+            parsed.value(),
+            Bandwidth::from_bps(16 * (1 << 30) + 10_000_000)
+        );
+        let spans = parsed.spans();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(
+            (spans[0].unit(), spans[0].decimals(), spans[0].is_spaced()),
+            ("GiB/s", 0, true)
+        );
+        assert_eq!(
+            (spans[1].unit(), spans[1].decimals(), spans[1].is_spaced()),
+            ("MB/s", 2, false)
+        );
+        assert_eq!(spans[1].value(), Bandwidth::from_mbps(10));
+        assert_eq!(spans[1].to_string(), "1.25MB/s");
+        assert_eq!(Bandwidth::from(parsed.clone()), parsed.value());
+    }
+
+    #[test]
+    fn test_set() {
+        let mut parsed = ParsedBandwidth::parse("1.50MiB/s").unwrap();
+        parsed.set(Bandwidth::from_bps(10_000_000));
+        assert_eq!(parsed.to_string(), "1.19MiB/s");
+        assert_eq!(parsed.value(), Bandwidth::from_bps(10_000_000));
+        parsed.set(Bandwidth::from_bps(0));
+        assert_eq!(parsed.to_string(), "0.00MiB/s");
+
+        // The smallest unit takes the rest, whatever the written order
+        let mut parsed = ParsedBandwidth::parse("500kbps 1Gbps").unwrap();
+        parsed.set(Bandwidth::from_bps(3_000_250_999));
+        assert_eq!(parsed.to_string(), "251kbps 3Gbps");
+        let mut parsed = ParsedBandwidth::parse("1Mbps 1Mbps").unwrap();
+        parsed.set(Bandwidth::from_kbps(2_500));
+        assert_eq!(parsed.to_string(), "2Mbps 1Mbps");
+
+        let mut parsed = ParsedBandwidth::parse("1GB/h 1MB/s").unwrap();
+        parsed.set(Bandwidth::from_mbps(20));
+        assert_eq!(parsed.to_string(), "2GB/h 2MB/s");
+        let mut parsed = ParsedBandwidth::parse("1TB/month").unwrap();
+        parsed.set(Bandwidth::MAX);
+        assert_eq!(parsed.value(), Bandwidth::MAX);
+        assert_eq!(parsed.to_string(), "5976745079881894723584TB/month");
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let options = ParseOptions::new()
+            .custom_units(&[("E1", 2_048_000)])
+            .decode_escapes(true)
+            .quadrant_units(true);
+        let mut parsed = ParsedBandwidth::parse_with("2E1 1MB%2Fs", &options).unwrap();
+        assert_eq!(parsed.to_string(), "2E1 1MB/s");
+        parsed.set(Bandwidth::from_bps(4_096_000));
+        assert_eq!(parsed.to_string(), "2E1 0MB/s");
+
+        assert_eq!(
+            ParsedBandwidth::parse_with("1MB%2Fx", &options),
+            Err(Error::UnknownUnit {
+                start: 1,
+                end: 7,
+                unit: "MB%2Fx".to_string(),
+                value: 1,
+            })
+        );
+        assert_eq!(ParsedBandwidth::parse(""), Err(Error::Empty));
+        assert!(matches!(
+            "10 Mbps/h".parse::<ParsedBandwidth>(),
+            Err(Error::UnknownUnit { .. })
+        ));
+    }
+}
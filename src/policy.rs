@@ -0,0 +1,461 @@
+//! Rate limit policies
+//!
+//! A [`Policy`] is a rate limit, an optional burst size and the scope the
+//! limit applies to, written like `limit 10Mbit/s burst 2MiB per-ip`. Many
+//! proxies and gateways accept such strings in their configuration, and this
+//! module parses them with the bandwidth parser of the crate rather than with
+//! a hand-written one.
+//!
+//! The clauses are separated by whitespace, may come in any order, and are:
+//!
+//! * `limit` followed by a bandwidth, parsed with [`parse_bandwidth`], which
+//!   is required
+//! * `burst` followed by an amount of data, e.g. `512kiB` or `1.5MB`, see
+//!   [`DataSize`]
+//! * one scope: `global` (the default), `per-ip`, `per-user` or
+//!   `per-connection`
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::{policy::{Policy, Scope}, size::DataSize};
+//!
+//! let policy: Policy = "limit 10Mbit/s burst 2MiB per-ip".parse().unwrap();
+//! assert_eq!(policy.limit, Bandwidth::from_mbps(10));
+//! assert_eq!(policy.burst, Some(DataSize::from_mib(2)));
+//! assert_eq!(policy.scope, Scope::PerIp);
+//! assert_eq!(policy.to_string(), "limit 10Mbps burst 2MiB per-ip");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+use bandwidth::Bandwidth;
+
+use crate::{
+    consts::{GIBI, KIBI, MEBI, TEBI},
+    format_bandwidth, parse_bandwidth,
+    size::DataSize,
+    Error,
+};
+
+/// What a rate limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Scope {
+    /// A single limit shared by all the traffic, written `global`
+    #[default]
+    Global,
+    /// A limit for each client address, written `per-ip`
+    PerIp,
+    /// A limit for each authenticated user, written `per-user`
+    PerUser,
+    /// A limit for each connection, written `per-connection`
+    PerConnection,
+}
+
+impl Scope {
+    const ALL: [Scope; 4] = [
+        Scope::Global,
+        Scope::PerIp,
+        Scope::PerUser,
+        Scope::PerConnection,
+    ];
+
+    /// The keyword of the scope in a policy, e.g. `"per-ip"`
+    pub const fn keyword(&self) -> &'static str {
+        match self {
+            Scope::Global => "global",
+            Scope::PerIp => "per-ip",
+            Scope::PerUser => "per-user",
+            Scope::PerConnection => "per-connection",
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.keyword())
+    }
+}
+
+/// A rate limit, with an optional burst size and a scope
+///
+/// With the `serde` feature, a policy is serialized as a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Policy {
+    /// The sustained rate
+    pub limit: Bandwidth,
+    /// The amount of data which may be sent at once above the rate, if any
+    pub burst: Option<DataSize>,
+    /// What the limit applies to
+    pub scope: Scope,
+}
+
+impl Policy {
+    /// Create a global policy without burst
+    pub const fn new(limit: Bandwidth) -> Self {
+        Policy {
+            limit,
+            burst: None,
+            scope: Scope::Global,
+        }
+    }
+}
+
+/// Error parsing a policy
+#[derive(Debug, PartialEq, Clone)]
+pub enum PolicyError {
+    /// The policy has no `limit` clause
+    MissingLimit,
+    /// The bandwidth after `limit` is invalid
+    InvalidLimit(Error),
+    /// The amount of data after `burst` is missing or invalid
+    InvalidBurst,
+    /// A clause is given twice, or two scopes are given
+    ///
+    /// The field is the keyword of the clause.
+    DuplicateClause(String),
+    /// A word is not a clause of a policy
+    UnknownClause(String),
+}
+
+#[cfg(feature = "std")]
+impl StdError for PolicyError {}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::MissingLimit => {
+                write!(f, "a policy must have a `limit` clause with a bandwidth")
+            }
+            PolicyError::InvalidLimit(error) => write!(f, "invalid limit: {}", error),
+            PolicyError::InvalidBurst => write!(
+                f,
+                "invalid burst, expected an amount of data like 512kiB or 2MB"
+            ),
+            PolicyError::DuplicateClause(keyword) => {
+                write!(f, "clause {:?} is given more than once", keyword)
+            }
+            PolicyError::UnknownClause(word) => write!(
+                f,
+                "unknown clause {:?}, expected limit, burst, global, per-ip, per-user or per-connection",
+                word
+            ),
+        }
+    }
+}
+
+/// Parses an amount of data like `512kiB`, `1.5MB` or `100 B`, truncated to
+/// the Byte
+fn parse_size(s: &str) -> Option<DataSize> {
+    let number_len = s
+        .bytes()
+        .take_while(|c| c.is_ascii_digit() || *c == b'.')
+        .count();
+    let (number, unit) = s.split_at(number_len);
+    let scale: u128 = match unit.trim_start() {
+        "B" => 1,
+        "kB" => 1_000,
+        "MB" => 1_000_000,
+        "GB" => 1_000_000_000,
+        "TB" => 1_000_000_000_000,
+        "kiB" | "KiB" => KIBI.into(),
+        "MiB" => MEBI.into(),
+        "GiB" => GIBI.into(),
+        "TiB" => TEBI.into(),
+        _ => return None,
+    };
+    let (int, fraction) = match number.split_once('.') {
+        Some((_, "")) => return None,
+        Some(parts) => parts,
+        None => (number, ""),
+    };
+    if int.is_empty() || fraction.len() > 18 {
+        return None;
+    }
+    let int: u128 = int.parse().ok()?;
+    let fraction_value: u128 = match fraction {
+        "" => 0,
+        fraction => fraction.parse().ok()?,
+    };
+    let fraction_bytes = fraction_value.checked_mul(scale)? / 10u128.pow(fraction.len() as u32);
+    let bytes = int.checked_mul(scale)?.checked_add(fraction_bytes)?;
+    u64::try_from(bytes).ok().map(DataSize::from_bytes)
+}
+
+/// Writes an amount of data exactly, in the largest unit in which it is at
+/// least 1: a whole number of the binary unit if there is one, e.g. `2MiB`,
+/// else the decimal unit with decimals, e.g. `1.5GB`
+fn fmt_size(f: &mut fmt::Formatter<'_>, size: DataSize) -> fmt::Result {
+    let bytes = size.bytes();
+    let units = [
+        (1_000_000_000_000, TEBI, "TB", "TiB"),
+        (1_000_000_000, GIBI, "GB", "GiB"),
+        (1_000_000, MEBI, "MB", "MiB"),
+        (1_000, KIBI, "kB", "kiB"),
+    ];
+    let (scale, binary_scale, unit, binary_unit) = units
+        .into_iter()
+        .find(|(scale, ..)| bytes >= *scale)
+        .unwrap_or((1, 1, "B", "B"));
+    if bytes % binary_scale == 0 {
+        return write!(f, "{}{}", bytes / binary_scale, binary_unit);
+    }
+    let (int, mut fraction) = (bytes / scale, bytes % scale);
+    if fraction == 0 {
+        return write!(f, "{}{}", int, unit);
+    }
+    let mut width = scale.ilog10() as usize;
+    while fraction % 10 == 0 {
+        fraction /= 10;
+        width -= 1;
+    }
+    write!(f, "{}.{:0width$}{}", int, fraction, unit, width = width)
+}
+
+/// Whether `word` starts a clause
+fn is_keyword(word: &str) -> bool {
+    word == "limit" || word == "burst" || Scope::ALL.iter().any(|scope| scope.keyword() == word)
+}
+
+impl FromStr for Policy {
+    type Err = PolicyError;
+
+    fn from_str(s: &str) -> Result<Self, PolicyError> {
+        let mut words = s.split_whitespace().peekable();
+        let mut limit = None;
+        let mut policy = Policy::new(Bandwidth::ZERO);
+        let mut scoped = false;
+        while let Some(word) = words.next() {
+            if word == "limit" {
+                if limit.is_some() {
+                    return Err(PolicyError::DuplicateClause(word.to_string()));
+                }
+                // The bandwidth may span several words, e.g. `10 Mbit/s`
+                let mut bandwidth = Vec::new();
+                while let Some(word) = words.next_if(|word| !is_keyword(word)) {
+                    bandwidth.push(word);
+                }
+                limit =
+                    Some(parse_bandwidth(&bandwidth.join(" ")).map_err(PolicyError::InvalidLimit)?);
+            } else if word == "burst" {
+                if policy.burst.is_some() {
+                    return Err(PolicyError::DuplicateClause(word.to_string()));
+                }
+                // The unit may be separated from the number
+                let mut size = words.next().ok_or(PolicyError::InvalidBurst)?.to_string();
+                if let Some(unit) = words.next_if(|word| !is_keyword(word)) {
+                    size.push_str(unit);
+                }
+                policy.burst = Some(parse_size(&size).ok_or(PolicyError::InvalidBurst)?);
+            } else if let Some(scope) = Scope::ALL.into_iter().find(|scope| scope.keyword() == word)
+            {
+                if scoped {
+                    return Err(PolicyError::DuplicateClause(word.to_string()));
+                }
+                policy.scope = scope;
+                scoped = true;
+            } else {
+                return Err(PolicyError::UnknownClause(word.to_string()));
+            }
+        }
+        policy.limit = limit.ok_or(PolicyError::MissingLimit)?;
+        Ok(policy)
+    }
+}
+
+impl TryFrom<String> for Policy {
+    type Error = PolicyError;
+
+    fn try_from(s: String) -> Result<Self, PolicyError> {
+        s.parse()
+    }
+}
+
+/// Writes the clauses in their canonical order, the limit like
+/// [`format_bandwidth`], the burst exactly in the largest unit in which it is
+/// at least 1, and the scope unless it is global
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "limit {}", format_bandwidth(self.limit))?;
+        if let Some(burst) = self.burst {
+            f.write_str(" burst ")?;
+            fmt_size(f, burst)?;
+        }
+        if self.scope != Scope::Global {
+            write!(f, " {}", self.scope)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Policy> for String {
+    fn from(val: Policy) -> String {
+        val.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let policy: Policy = "  limit 1Mbps 2000kbps  burst 1.5 MB per-connection"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            policy,
+            Policy {
+                limit: Bandwidth::from_mbps(3),
+                burst: Some(DataSize::from_bytes(1_500_000)),
+                scope: Scope::PerConnection,
+            }
+        );
+        assert_eq!(policy.to_string(), "limit 3Mbps burst 1.5MB per-connection");
+        assert_eq!(policy.to_string().parse(), Ok(policy));
+
+        let policy: Policy = "limit 10 Mbit/s global burst 64KiB".parse().unwrap();
+        assert_eq!(policy.to_string(), "limit 10Mbps burst 64kiB");
+        assert_eq!(
+            "limit 1bps".parse(),
+            Ok(Policy::new(Bandwidth::from_bps(1)))
+        );
+
+        // The clauses may come in any order
+        let policy: Policy = "burst 2MiB per-ip limit 1 Mbps".parse().unwrap();
+        assert_eq!(policy.limit, Bandwidth::from_mbps(1));
+        assert_eq!(policy.burst, Some(DataSize::from_mib(2)));
+        assert_eq!(policy.scope, Scope::PerIp);
+        assert_eq!(policy.to_string(), "limit 1Mbps burst 2MiB per-ip");
+
+        for (size, bytes) in [
+            ("0B", 0),
+            ("1.0009kB", 1_000),
+            ("0.5kiB", 512),
+            ("1TiB", 1 << 40),
+            ("16777215TiB", 16_777_215 << 40),
+        ] {
+            let policy = format!("limit 1Mbps burst {}", size).parse::<Policy>();
+            assert_eq!(
+                policy.map(|policy| policy.burst),
+                Ok(Some(DataSize::from_bytes(bytes))),
+                "{}",
+                size
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_burst() {
+        for (bytes, expected) in [
+            (0, "0B"),
+            (999, "999B"),
+            (1_000, "1kB"),
+            (1_024, "1kiB"),
+            (1_536, "1.536kB"),
+            (1_500_000_000, "1.5GB"),
+            (1_500_000_001, "1.500000001GB"),
+            (2 << 20, "2MiB"),
+            (1_000 << 10, "1.024MB"),
+            (1_000_000_000_000, "1TB"),
+            (u64::MAX, "18446744.073709551615TB"),
+        ] {
+            let mut policy = Policy::new(Bandwidth::from_mbps(1));
+            policy.burst = Some(DataSize::from_bytes(bytes));
+            let policy_str = policy.to_string();
+            assert_eq!(policy_str, format!("limit 1Mbps burst {}", expected));
+            assert_eq!(policy_str.parse(), Ok(policy));
+        }
+        assert_eq!(
+            "limit 1Mbps burst 340282366920938463463374607.9TB".parse::<Policy>(),
+            Err(PolicyError::InvalidBurst)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!("".parse::<Policy>(), Err(PolicyError::MissingLimit));
+        assert_eq!(
+            "burst 1MiB per-ip".parse::<Policy>(),
+            Err(PolicyError::MissingLimit)
+        );
+        assert_eq!(
+            "limit 1Mbps burst 1MiB limit 2Mbps".parse::<Policy>(),
+            Err(PolicyError::DuplicateClause("limit".to_string()))
+        );
+        assert_eq!(
+            "limit burst 1MiB".parse::<Policy>(),
+            Err(PolicyError::InvalidLimit(Error::Empty))
+        );
+        assert_eq!(
+            "limit 10Mbyte/s".parse::<Policy>().unwrap_err().to_string(),
+            "invalid limit: unknown bandwidth unit \"Mbyte/s\", \
+            supported units: bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps"
+        );
+        for burst in [
+            "",
+            "MiB",
+            "1",
+            "1.MiB",
+            "1..5MiB",
+            "1XB",
+            "16777216.1TiB",
+            "1 per-ip",
+        ] {
+            assert_eq!(
+                format!("limit 1Mbps burst {}", burst).parse::<Policy>(),
+                Err(PolicyError::InvalidBurst),
+                "{}",
+                burst
+            );
+        }
+        assert_eq!(
+            "limit 1Mbps burst 1MiB burst 2MiB".parse::<Policy>(),
+            Err(PolicyError::DuplicateClause("burst".to_string()))
+        );
+        assert_eq!(
+            "limit 1Mbps per-ip global".parse::<Policy>(),
+            Err(PolicyError::DuplicateClause("global".to_string()))
+        );
+        assert_eq!(
+            "limit 1Mbps burst 1 MiB per-host"
+                .parse::<Policy>()
+                .unwrap_err()
+                .to_string(),
+            "unknown clause \"per-host\", expected limit, burst, global, per-ip, per-user or per-connection"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        #[derive(Debug, ::serde::Serialize, ::serde::Deserialize)]
+        struct Foo {
+            policy: Policy,
+        }
+
+        let json = r#"{"policy": "limit 10Mbit/s burst 2MiB per-user"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.policy.scope, Scope::PerUser);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"policy":"limit 10Mbps burst 2MiB per-user"}"#);
+
+        let json = r#"{"policy": "burst 2MiB"}"#;
+        assert_eq!(
+            serde_json::from_str::<Foo>(json).unwrap_err().to_string(),
+            "a policy must have a `limit` clause with a bandwidth at line 1 column 24"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Protobuf mapping for bandwidth, to be used with [prost](https://docs.rs/prost)
+//!
+//! The recommended protobuf representation of a bandwidth is an unsigned
+//! number of bits per second:
+//!
+//! ```proto
+//! message Bandwidth {
+//!   uint64 bps = 1;
+//! }
+//! ```
+//!
+//! [`ProtoBandwidth`] is the prost message matching this definition. Fields that
+//! may be absent should use `google.protobuf.UInt64Value`, which prost maps to
+//! `Option<u64>`: see [`to_uint64_value`] and [`from_uint64_value`].
+//!
+//! Note that a [`Bandwidth`] can hold values larger than `u64::MAX` bps, those
+//! can not be represented and are reported as [`Error::NumberOverflow`].
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::prost::ProtoBandwidth;
+//!
+//! let proto: ProtoBandwidth = "10Mbps".parse().unwrap();
+//! assert_eq!(proto.bps, 10_000_000);
+//! assert_eq!(Bandwidth::from(proto), Bandwidth::from_mbps(10));
+//! assert_eq!(proto.to_string(), "10Mbps");
+//! ```
+
+use core::{fmt, str::FromStr};
+
+use bandwidth::Bandwidth;
+
+use crate::{format_bandwidth, parse_bandwidth, Error, FormattedBandwidth};
+
+/// The recommended protobuf message for a bandwidth
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ProtoBandwidth {
+    /// The bandwidth in bits per second
+    #[prost(uint64, tag = "1")]
+    pub bps: u64,
+}
+
+/// Converts a bandwidth to its protobuf representation in bits per second
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{prost::to_bps, Error};
+///
+/// assert_eq!(to_bps(Bandwidth::from_mbps(10)), Ok(10_000_000));
+/// assert_eq!(to_bps(Bandwidth::new(u64::MAX, 0)), Err(Error::NumberOverflow));
+/// ```
+pub fn to_bps(val: Bandwidth) -> Result<u64, Error> {
+    val.as_gbps()
+        .checked_mul(1_000_000_000)
+        .and_then(|bps| bps.checked_add(val.subgbps_bps() as u64))
+        .ok_or(Error::NumberOverflow)
+}
+
+/// Converts the protobuf representation in bits per second to a bandwidth
+pub fn from_bps(bps: u64) -> Bandwidth {
+    Bandwidth::from_bps(bps)
+}
+
+/// Converts an optional bandwidth to the prost mapping of `google.protobuf.UInt64Value`
+pub fn to_uint64_value(val: Option<Bandwidth>) -> Result<Option<u64>, Error> {
+    val.map(to_bps).transpose()
+}
+
+/// Converts the prost mapping of `google.protobuf.UInt64Value` to an optional bandwidth
+pub fn from_uint64_value(val: Option<u64>) -> Option<Bandwidth> {
+    val.map(from_bps)
+}
+
+/// Parses a human-readable bandwidth directly into its protobuf representation
+///
+/// See [`parse_bandwidth`] for the supported format.
+pub fn parse_proto(s: &str) -> Result<u64, Error> {
+    parse_bandwidth(s).and_then(to_bps)
+}
+
+/// Formats the protobuf representation of a bandwidth into a human-readable string
+///
+/// See [`format_bandwidth`] for the details of the format.
+pub fn format_proto(bps: u64) -> FormattedBandwidth {
+    format_bandwidth(from_bps(bps))
+}
+
+impl TryFrom<Bandwidth> for ProtoBandwidth {
+    type Error = Error;
+
+    fn try_from(val: Bandwidth) -> Result<Self, Error> {
+        to_bps(val).map(|bps| ProtoBandwidth { bps })
+    }
+}
+
+impl From<ProtoBandwidth> for Bandwidth {
+    fn from(val: ProtoBandwidth) -> Self {
+        from_bps(val.bps)
+    }
+}
+
+impl FromStr for ProtoBandwidth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        parse_proto(s).map(|bps| ProtoBandwidth { bps })
+    }
+}
+
+impl fmt::Display for ProtoBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&format_proto(self.bps), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::prost::Message;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(to_bps(Bandwidth::new(0, 0)), Ok(0));
+        assert_eq!(to_bps(Bandwidth::new(18, 446_744_073)), Ok(18_446_744_073));
+        assert_eq!(
+            to_bps(Bandwidth::new(18_446_744_073, 709_551_615)),
+            Ok(u64::MAX)
+        );
+        assert_eq!(
+            to_bps(Bandwidth::new(18_446_744_073, 709_551_616)),
+            Err(Error::NumberOverflow)
+        );
+        assert_eq!(from_bps(1_500), Bandwidth::new(0, 1_500));
+        assert_eq!(
+            to_uint64_value(Some(Bandwidth::from_kbps(3))),
+            Ok(Some(3_000))
+        );
+        assert_eq!(to_uint64_value(None), Ok(None));
+        assert_eq!(from_uint64_value(Some(7)), Some(Bandwidth::from_bps(7)));
+        assert_eq!(from_uint64_value(None), None);
+    }
+
+    #[test]
+    fn test_bridges() {
+        assert_eq!(parse_proto("2Gbps 5kbps"), Ok(2_000_005_000));
+        assert_eq!(parse_proto("20000000000Gbps"), Err(Error::NumberOverflow));
+        assert_eq!(format_proto(32_000_000).to_string(), "32Mbps");
+    }
+
+    #[test]
+    fn test_message() {
+        let proto = ProtoBandwidth::try_from(Bandwidth::from_mbps(150)).unwrap();
+        let encoded = proto.encode_to_vec();
+        let decoded = ProtoBandwidth::decode(encoded.as_slice()).unwrap();
+        assert_eq!(decoded, proto);
+        assert_eq!(Bandwidth::from(decoded), Bandwidth::from_mbps(150));
+    }
+}
@@ -0,0 +1,262 @@
+//! Ranges of bandwidths, e.g. `0.5–2Mbps`
+//!
+//! Both ends of a [`BandwidthRange`] are written in the unit of the largest
+//! one, which reads better than a unit per end like `500kbps–2Mbps`. Both
+//! forms are parsed by [`parse_bandwidth_range`], as well as a hyphen
+//! between the ends like `10Mbps-100Mbps`.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::range::BandwidthRange;
+//!
+//! let range = BandwidthRange::new(Bandwidth::from_kbps(500), Bandwidth::from_mbps(2));
+//! assert_eq!(range.to_string(), "0.5–2Mbps");
+//! assert_eq!("0.5–2Mbps".parse(), Ok(range));
+//! ```
+
+use core::{fmt, str::FromStr};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+use bandwidth::Bandwidth;
+
+use crate::{offset_error, parse_bandwidth, Error, FormatOptions};
+
+/// A range of bandwidths, from `min` to `max` inclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BandwidthRange {
+    /// The lower end of the range
+    pub min: Bandwidth,
+    /// The upper end of the range
+    pub max: Bandwidth,
+}
+
+impl BandwidthRange {
+    /// Create the range from `min` to `max`
+    pub const fn new(min: Bandwidth, max: Bandwidth) -> Self {
+        BandwidthRange { min, max }
+    }
+
+    /// Whether `val` is within the range
+    pub fn contains(&self, val: Bandwidth) -> bool {
+        self.min <= val && val <= self.max
+    }
+}
+
+/// Error parsing a range of bandwidths
+#[derive(Debug, PartialEq, Clone)]
+pub enum RangeError {
+    /// There is no `-` or `–` between the two ends
+    SeparatorExpected,
+    /// An end is not a valid bandwidth
+    ///
+    /// The offsets of the error are in the whole string.
+    InvalidBandwidth(Error),
+    /// The lower end is greater than the upper one
+    Reversed,
+}
+
+#[cfg(feature = "std")]
+impl StdError for RangeError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            RangeError::InvalidBandwidth(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::SeparatorExpected => {
+                write!(f, "expected a range like 10Mbps-100Mbps or 10–100Mbps")
+            }
+            RangeError::InvalidBandwidth(error) => write!(f, "invalid end of range: {}", error),
+            RangeError::Reversed => write!(f, "the lower end is greater than the upper one"),
+        }
+    }
+}
+
+/// Offset and length of the separator of the two ends, an en dash or a
+/// hyphen which is not the sign of an exponent like `1e-3`
+fn separator(s: &str) -> Option<(usize, usize)> {
+    if let Some(at) = s.find('–') {
+        return Some((at, '–'.len_utf8()));
+    }
+    s.char_indices()
+        .filter(|(_, c)| *c == '-')
+        .map(|(at, _)| at)
+        .find(|at| {
+            let mut before = s[..*at].chars().rev();
+            !matches!(
+                (before.next(), before.next()),
+                (Some('e' | 'E'), Some('0'..='9' | '.'))
+            )
+        })
+        .map(|at| (at, 1))
+}
+
+/// Parses a range with `parse` reading each end, see [`parse_bandwidth_range`]
+pub(crate) fn parse_range_with<F>(s: &str, parse: F) -> Result<BandwidthRange, RangeError>
+where
+    F: Fn(&str) -> Result<Bandwidth, Error>,
+{
+    let (at, len) = separator(s).ok_or(RangeError::SeparatorExpected)?;
+    let (min, max) = (&s[..at], &s[at + len..]);
+    let invalid = |start| move |error| RangeError::InvalidBandwidth(offset_error(error, start));
+    // The lower end may share the unit of the upper one, e.g. `10–100Mbps`
+    let min_val = if min.trim_end().ends_with(|c: char| c.is_ascii_digit()) {
+        let max = max.trim_end();
+        let unit = &max[max
+            .rfind(|c: char| c.is_ascii_digit())
+            .map_or(0, |at| at + 1)..];
+        parse(&[min.trim_end(), unit].concat())
+    } else {
+        parse(min)
+    };
+    let min_val = min_val.map_err(invalid(0))?;
+    let max_val = parse(max).map_err(invalid(at + len))?;
+    if min_val > max_val {
+        return Err(RangeError::Reversed);
+    }
+    Ok(BandwidthRange::new(min_val, max_val))
+}
+
+/// Parses a range of bandwidths like `10Mbps-100Mbps` or `10–100Mbps`
+///
+/// The ends are separated by a hyphen or an en dash, and parsed with
+/// [`parse_bandwidth`]. When the lower end is a number alone, it is in the
+/// unit of the upper one, as written by the [`Display`](fmt::Display) of
+/// [`BandwidthRange`]. The lower end must not be greater than the upper one.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::range::{parse_bandwidth_range, BandwidthRange, RangeError};
+///
+/// let range = BandwidthRange::new(Bandwidth::from_mbps(10), Bandwidth::from_mbps(100));
+/// assert_eq!(parse_bandwidth_range("10Mbps-100Mbps"), Ok(range));
+/// assert_eq!(parse_bandwidth_range("10 - 100 Mbps"), Ok(range));
+/// assert_eq!(parse_bandwidth_range("10–100Mbps"), Ok(range));
+/// assert_eq!(parse_bandwidth_range("100Mbps-10Mbps"), Err(RangeError::Reversed));
+/// assert_eq!(parse_bandwidth_range("10Mbps"), Err(RangeError::SeparatorExpected));
+/// ```
+pub fn parse_bandwidth_range(s: &str) -> Result<BandwidthRange, RangeError> {
+    parse_range_with(s, parse_bandwidth)
+}
+
+/// Parses a range like [`parse_bandwidth_range`]
+impl FromStr for BandwidthRange {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_bandwidth_range(s)
+    }
+}
+
+/// Writes both ends in a shared unit with the default [`FormatOptions`], see
+/// [`FormatOptions::format_range`]
+impl fmt::Display for BandwidthRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&FormatOptions::new().format_range(*self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PrefixBase, Quantity};
+
+    #[test]
+    fn test_display() {
+        let range =
+            |min, max| BandwidthRange::new(Bandwidth::from_bps(min), Bandwidth::from_bps(max));
+        assert_eq!(range(0, 0).to_string(), "0–0bps");
+        assert_eq!(range(10_000_000, 100_000_000).to_string(), "10–100Mbps");
+        assert_eq!(range(999, 1_000).to_string(), "0.999–1kbps");
+        assert_eq!(range(2_000, 1_500).to_string(), "2–1.5kbps");
+        assert_eq!(range(1, 1_000_000_000).to_string(), "0.000000001–1Gbps");
+
+        let options = FormatOptions::new().precision(1);
+        assert_eq!(
+            options.format_range(range(1_250_000, 2_000_000)),
+            "1.2–2.0Mbps"
+        );
+        assert_eq!(
+            options
+                .trim_trailing_zeros(true)
+                .format_range(range(1, 1_000_000_000)),
+            "0–1Gbps"
+        );
+        let options = FormatOptions::new()
+            .prefix_base(PrefixBase::Binary)
+            .quantity(Quantity::Bytes)
+            .iso80000(true);
+        assert_eq!(
+            options
+                .precision(2)
+                .format_range(range(4_194_304, 16_777_216)),
+            "0.50–2.00 MiB/s"
+        );
+        assert!(range(5, 10).contains(Bandwidth::from_bps(10)));
+        assert!(!range(5, 10).contains(Bandwidth::from_bps(11)));
+    }
+
+    #[test]
+    fn test_parse() {
+        let range = |min, max| {
+            Ok(BandwidthRange::new(
+                Bandwidth::from_bps(min),
+                Bandwidth::from_bps(max),
+            ))
+        };
+        assert_eq!(parse_bandwidth_range("0bps-0bps"), range(0, 0));
+        assert_eq!(
+            parse_bandwidth_range("500kbps–2Mbps"),
+            range(500_000, 2_000_000)
+        );
+        assert_eq!(
+            parse_bandwidth_range(" 0.5 – 2 Mbps "),
+            range(500_000, 2_000_000)
+        );
+        assert_eq!(parse_bandwidth_range("1e-3-2e-3Mbps"), range(1_000, 2_000));
+        assert_eq!(parse_bandwidth_range("1E-3kbps-1.5e3bps"), range(1, 1_500));
+        for (min, max) in [(0, 0), (1, 999), (999, 1_000), (1, 1_000_000_000), (7, 7)] {
+            let val = range(min, max).unwrap();
+            assert_eq!(val.to_string().parse(), Ok(val), "{}", val);
+        }
+
+        assert_eq!(
+            parse_bandwidth_range(""),
+            Err(RangeError::SeparatorExpected)
+        );
+        assert_eq!(
+            parse_bandwidth_range("-1Mbps"),
+            Err(RangeError::InvalidBandwidth(Error::Empty))
+        );
+        assert_eq!(
+            parse_bandwidth_range("1Mbps-"),
+            Err(RangeError::InvalidBandwidth(Error::Empty))
+        );
+        assert_eq!(
+            parse_bandwidth_range("1Mbps - 2Mbpx"),
+            Err(RangeError::InvalidBandwidth(Error::UnknownUnit {
+                start: 9,
+                end: 13,
+                unit: "Mbpx".to_string(),
+                value: 2,
+            }))
+        );
+        assert_eq!(
+            parse_bandwidth_range("1.2.3–5Mbps")
+                .unwrap_err()
+                .to_string(),
+            "invalid end of range: invalid character at 3"
+        );
+        assert_eq!(parse_bandwidth_range("2–1kbps"), Err(RangeError::Reversed));
+    }
+}
@@ -0,0 +1,212 @@
+//! What a bandwidth setting means in every family of units
+//!
+//! A rate configured as `1Mbps` is about `122KiB/s` in a file manager and
+//! `976.6Kibit/s` on some network gear. [`convert_report`] renders a
+//! bandwidth in the four families written by [`FormatOptions`], decimal and
+//! binary prefixes of bits and Bytes, with the error of each rendering, e.g.
+//! for documentation generators or support tools.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::report::convert_report;
+//!
+//! let report = convert_report(Bandwidth::from_mbps(1));
+//! assert_eq!(report.bps, 1_000_000);
+//! assert_eq!(
+//!     report.to_string(),
+//!     "exact value: 1000000bps\n\
+//!      SI bits    1Mbps            exact\n\
+//!      SI bytes   125kB/s          exact\n\
+//!      IEC bytes  122.070312KiB/s  exact\n\
+//!      IEC bits   976.562Kibps     -1bps",
+//! );
+//! ```
+
+use alloc::string::String;
+use core::fmt;
+
+use bandwidth::Bandwidth;
+
+use crate::{
+    bandwidth_as_bps, signed::SignedBandwidth, FormatOptions, ParseOptions, PrefixBase, Quantity,
+};
+
+/// A bandwidth rendered in every family of units, see [`convert_report`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionReport {
+    /// The exact value in bits per second
+    pub bps: u128,
+    /// The renderings in SI bits, SI Bytes, IEC Bytes and IEC bits, in this
+    /// order
+    pub renderings: [Rendering; 4],
+}
+
+/// A bandwidth written in one family of units
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendering {
+    /// The prefixes of the unit
+    pub prefix_base: PrefixBase,
+    /// What the unit counts
+    pub quantity: Quantity,
+    /// The bandwidth written with the default precision of the family, e.g.
+    /// `122.070312KiB/s`
+    pub text: String,
+    /// The value of [`text`](Self::text) minus the exact value, to the
+    /// nearest bit per second
+    pub error: SignedBandwidth,
+}
+
+impl Rendering {
+    /// The name of the family of units, e.g. `IEC bytes`
+    pub fn system(&self) -> &'static str {
+        match (self.prefix_base, self.quantity) {
+            (PrefixBase::Decimal, Quantity::Bits) => "SI bits",
+            (PrefixBase::Decimal, Quantity::Bytes) => "SI bytes",
+            (PrefixBase::Binary, Quantity::Bytes) => "IEC bytes",
+            (PrefixBase::Binary, Quantity::Bits) => "IEC bits",
+        }
+    }
+
+    /// Whether [`text`](Self::text) reads as the exact value
+    pub fn is_exact(&self) -> bool {
+        self.error.magnitude() == Bandwidth::new(0, 0)
+    }
+}
+
+/// Writes the exact value, then a table of the renderings with their error
+impl fmt::Display for ConversionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exact value: {}bps", self.bps)?;
+        let width = self
+            .renderings
+            .iter()
+            .map(|rendering| rendering.text.len())
+            .max()
+            .unwrap_or(0);
+        for rendering in &self.renderings {
+            write!(
+                f,
+                "\n{:<9}  {:<width$}  ",
+                rendering.system(),
+                rendering.text,
+                width = width
+            )?;
+            if rendering.is_exact() {
+                f.write_str("exact")?;
+            } else {
+                write!(f, "{}", rendering.error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders a bandwidth in SI bits, SI Bytes, IEC Bytes and IEC bits
+///
+/// Each rendering is written by [`FormatOptions`] with the
+/// [`PrefixBase`] and [`Quantity`] of its family, and read back with
+/// [`ParseOptions::quadrant_units`] to compute its error. The decimal
+/// prefixes are exact, while the binary ones are rounded to 3 decimals per
+/// prefix, and 3 more for Bytes.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{report::convert_report, PrefixBase, Quantity};
+///
+/// let report = convert_report(Bandwidth::from_bps(8_388_608));
+/// let iec_bytes = &report.renderings[2];
+/// assert_eq!((iec_bytes.prefix_base, iec_bytes.quantity), (PrefixBase::Binary, Quantity::Bytes));
+/// assert_eq!(iec_bytes.text, "1MiB/s");
+/// assert!(report.renderings.iter().all(|rendering| rendering.is_exact()));
+/// ```
+pub fn convert_report(bw: Bandwidth) -> ConversionReport {
+    let render = |prefix_base, quantity| {
+        let text = FormatOptions::new()
+            .prefix_base(prefix_base)
+            .quantity(quantity)
+            .format(bw);
+        // Rounding up may go past the largest bandwidth
+        let read = ParseOptions::new()
+            .quadrant_units(true)
+            .saturating(true)
+            .parse(&text)
+            .unwrap_or(bw);
+        Rendering {
+            prefix_base,
+            quantity,
+            text,
+            error: SignedBandwidth::delta(bw, read),
+        }
+    };
+    ConversionReport {
+        bps: bandwidth_as_bps(bw),
+        renderings: [
+            render(PrefixBase::Decimal, Quantity::Bits),
+            render(PrefixBase::Decimal, Quantity::Bytes),
+            render(PrefixBase::Binary, Quantity::Bytes),
+            render(PrefixBase::Binary, Quantity::Bits),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(report: &ConversionReport) -> [&str; 4] {
+        let text = |i: usize| report.renderings[i].text.as_str();
+        [text(0), text(1), text(2), text(3)]
+    }
+
+    #[test]
+    fn test_convert_report() {
+        let report = convert_report(Bandwidth::from_bps(0));
+        assert_eq!(report.bps, 0);
+        assert_eq!(texts(&report), ["0bps", "0B/s", "0B/s", "0bps"]);
+        assert!(report.renderings.iter().all(Rendering::is_exact));
+
+        let report = convert_report(Bandwidth::from_bps(1));
+        assert_eq!(texts(&report), ["1bps", "0.125B/s", "0.125B/s", "1bps"]);
+        assert!(report.renderings.iter().all(Rendering::is_exact));
+
+        let report = convert_report(Bandwidth::from_kbps(1_536));
+        assert_eq!(
+            texts(&report),
+            ["1.536Mbps", "192kB/s", "187.5KiB/s", "1.464844Mibps"]
+        );
+        assert!(report.renderings.iter().all(Rendering::is_exact));
+
+        let report = convert_report(Bandwidth::from_mbps(1));
+        assert_eq!(
+            texts(&report),
+            ["1Mbps", "125kB/s", "122.070312KiB/s", "976.562Kibps"]
+        );
+        let errors: Vec<_> = report
+            .renderings
+            .iter()
+            .map(|r| r.error.to_string())
+            .collect();
+        assert_eq!(errors, ["0bps", "0bps", "0bps", "-1bps"]);
+
+        let report = convert_report(Bandwidth::MAX);
+        assert_eq!(report.bps, bandwidth_as_bps(Bandwidth::MAX));
+        assert!(report.renderings[0].is_exact());
+        // Rounded down to the Byte per second rather than above the maximum
+        assert_eq!(
+            report.renderings[1].text,
+            "2305843009.213693951999999999EB/s"
+        );
+        assert_eq!(report.renderings[1].error.to_string(), "-7bps");
+    }
+
+    #[test]
+    fn test_system() {
+        let report = convert_report(Bandwidth::from_mbps(1));
+        let systems: Vec<_> = report.renderings.iter().map(Rendering::system).collect();
+        assert_eq!(systems, ["SI bits", "SI bytes", "IEC bytes", "IEC bits"]);
+    }
+}
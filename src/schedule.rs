@@ -0,0 +1,423 @@
+//! Bandwidth schedules
+//!
+//! A [`Schedule`] scripts the changes of a rate limit over time, e.g. for a
+//! test harness or a traffic shaper ramping a link up. It is written as steps
+//! separated by commas, each step being an offset, a colon and the rate from
+//! this offset on, like `0s:1Mbit/s, 60s:10Mbit/s, 300s:unlimited`.
+//!
+//! Offsets are integers followed by `ms`, `s`, `min` (or `m`) or `h`, or sums
+//! of them from the largest unit to the smallest like `1h30m`. Rates are
+//! parsed with [`parse_bandwidth`], or are `unlimited`.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::schedule::Schedule;
+//!
+//! let schedule: Schedule = "0s:1Mbit/s, 60s:10Mbit/s, 300s:unlimited".parse().unwrap();
+//! assert_eq!(schedule.rate_at(Duration::from_secs(30)), Some(Bandwidth::from_mbps(1)));
+//! assert_eq!(schedule.rate_at(Duration::from_secs(60)), Some(Bandwidth::from_mbps(10)));
+//! assert_eq!(schedule.rate_at(Duration::from_secs(3_600)), None);
+//! assert_eq!(schedule.to_string(), "0s:1Mbps, 1min:10Mbps, 5min:unlimited");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr, time::Duration};
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+use bandwidth::Bandwidth;
+
+use crate::{format_bandwidth, parse_bandwidth, Error};
+
+/// Rate changes over time, starting at offset zero
+///
+/// Each step is an offset and the rate from this offset on, `None` meaning
+/// unlimited. With the `serde` feature, a schedule is serialized as a string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(::serde::Serialize, ::serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Schedule {
+    steps: Vec<(Duration, Option<Bandwidth>)>,
+}
+
+/// Error building or parsing a schedule
+#[derive(Debug, PartialEq, Clone)]
+pub enum ScheduleError {
+    /// A step is not an offset, a colon and a rate
+    ///
+    /// The field is the position of the step in the schedule.
+    InvalidStep(usize),
+    /// The offset of a step is not an integer followed by `ms`, `s`, `min` or
+    /// `h`, nor a sum of them from the largest unit to the smallest, or is too
+    /// large
+    ///
+    /// From [`Schedule::new`], the offset is not a whole number of
+    /// milliseconds or is more than `u64::MAX` milliseconds, which could not
+    /// be written back.
+    ///
+    /// The field is the position of the step in the schedule.
+    InvalidOffset(usize),
+    /// The rate of a step is neither a valid bandwidth nor `unlimited`
+    InvalidRate {
+        /// Position of the step in the schedule
+        index: usize,
+        /// Error parsing the rate
+        error: Error,
+    },
+    /// An offset is not greater than the offset of the previous step
+    ///
+    /// The field is the position of the step in the schedule.
+    Unsorted(usize),
+    /// The first step is not at offset zero, or there is no step
+    MissingStart,
+}
+
+#[cfg(feature = "std")]
+impl StdError for ScheduleError {}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::InvalidStep(index) => {
+                write!(f, "step {} is not `offset:rate`", index)
+            }
+            ScheduleError::InvalidOffset(index) => write!(
+                f,
+                "invalid offset of step {}, expected for example 500ms, 60s, 5min, 1h or 1h30m",
+                index
+            ),
+            ScheduleError::InvalidRate { index, error } => {
+                write!(f, "invalid rate of step {}: {}", index, error)
+            }
+            ScheduleError::Unsorted(index) => write!(
+                f,
+                "offset of step {} is not greater than the previous one",
+                index
+            ),
+            ScheduleError::MissingStart => write!(f, "the first step must be at offset 0s"),
+        }
+    }
+}
+
+impl Schedule {
+    /// Create a schedule from its steps, sorted by strictly increasing
+    /// offsets, the first one being zero
+    ///
+    /// The offsets are whole numbers of milliseconds, of at most `u64::MAX`
+    /// milliseconds, like the parsed ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::schedule::{Schedule, ScheduleError};
+    ///
+    /// let ramp = Schedule::new(vec![
+    ///     (Duration::ZERO, Some(Bandwidth::from_mbps(1))),
+    ///     (Duration::from_secs(10), None),
+    /// ]);
+    /// assert!(ramp.is_ok());
+    /// assert_eq!(
+    ///     Schedule::new(vec![(Duration::from_secs(10), None)]),
+    ///     Err(ScheduleError::MissingStart)
+    /// );
+    /// assert_eq!(
+    ///     Schedule::new(vec![(Duration::ZERO, None), (Duration::from_micros(500), None)]),
+    ///     Err(ScheduleError::InvalidOffset(1))
+    /// );
+    /// ```
+    pub fn new(steps: Vec<(Duration, Option<Bandwidth>)>) -> Result<Self, ScheduleError> {
+        if let Some(index) = steps.iter().position(|(at, _)| {
+            at.subsec_nanos() % 1_000_000 != 0 || at.as_millis() > u128::from(u64::MAX)
+        }) {
+            return Err(ScheduleError::InvalidOffset(index));
+        }
+        if steps.first().map(|(at, _)| *at) != Some(Duration::ZERO) {
+            return Err(ScheduleError::MissingStart);
+        }
+        if let Some(index) = steps.windows(2).position(|pair| pair[0].0 >= pair[1].0) {
+            return Err(ScheduleError::Unsorted(index + 1));
+        }
+        Ok(Schedule { steps })
+    }
+
+    /// The steps of the schedule, as offsets and rates
+    pub fn steps(&self) -> &[(Duration, Option<Bandwidth>)] {
+        &self.steps
+    }
+
+    /// The rate at `offset` from the start of the schedule, `None` meaning
+    /// unlimited
+    pub fn rate_at(&self, offset: Duration) -> Option<Bandwidth> {
+        let index = self.steps.partition_point(|(at, _)| *at <= offset);
+        // The first step is at offset zero
+        self.steps[index - 1].1
+    }
+}
+
+/// Parses an offset like `500ms`, `60s`, `5min`, `5m` or `1h`, or a sum of
+/// them from the largest unit to the smallest like `1h30m`
+fn parse_offset(s: &str) -> Option<Duration> {
+    if s == "0" {
+        return Some(Duration::ZERO);
+    }
+    let mut millis: u64 = 0;
+    // The unit of the previous part in milliseconds, each unit being smaller
+    let mut previous = None;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let (n, unit) = rest.split_at(digits);
+        let n: u64 = n.parse().ok()?;
+        let unit = unit.trim_start();
+        let letters = unit.bytes().take_while(u8::is_ascii_alphabetic).count();
+        let (unit, tail) = unit.split_at(letters);
+        let scale: u64 = match unit {
+            "ms" => 1,
+            "s" => 1_000,
+            "min" | "m" => 60_000,
+            "h" => 3_600_000,
+            _ => return None,
+        };
+        if previous.is_some_and(|previous| scale >= previous) {
+            return None;
+        }
+        previous = Some(scale);
+        millis = millis.checked_add(n.checked_mul(scale)?)?;
+        rest = tail.trim_start();
+    }
+    previous.map(|_| Duration::from_millis(millis))
+}
+
+/// Writes an offset in the largest unit dividing it, a whole number of
+/// milliseconds
+fn fmt_offset(f: &mut fmt::Formatter, offset: Duration) -> fmt::Result {
+    let millis = offset.as_millis();
+    if millis % 1_000 != 0 {
+        return write!(f, "{}ms", millis);
+    }
+    let secs = offset.as_secs();
+    if secs != 0 && secs % 3_600 == 0 {
+        write!(f, "{}h", secs / 3_600)
+    } else if secs != 0 && secs % 60 == 0 {
+        write!(f, "{}min", secs / 60)
+    } else {
+        write!(f, "{}s", secs)
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, ScheduleError> {
+        let mut steps = Vec::new();
+        for (index, step) in s.split(',').enumerate() {
+            let (offset, rate) = step
+                .split_once(':')
+                .ok_or(ScheduleError::InvalidStep(index))?;
+            let offset = parse_offset(offset.trim()).ok_or(ScheduleError::InvalidOffset(index))?;
+            let rate = match rate.trim() {
+                "unlimited" => None,
+                rate => Some(
+                    parse_bandwidth(rate)
+                        .map_err(|error| ScheduleError::InvalidRate { index, error })?,
+                ),
+            };
+            steps.push((offset, rate));
+        }
+        Schedule::new(steps)
+    }
+}
+
+impl TryFrom<String> for Schedule {
+    type Error = ScheduleError;
+
+    fn try_from(s: String) -> Result<Self, ScheduleError> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (offset, rate)) in self.steps.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            fmt_offset(f, *offset)?;
+            match rate {
+                Some(rate) => write!(f, ":{}", format_bandwidth(*rate))?,
+                None => f.write_str(":unlimited")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Schedule> for String {
+    fn from(val: Schedule) -> String {
+        val.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let schedule: Schedule = " 0 : 1kbps,500ms:2kbps, 2s:unlimited,3min:4kbps,1h:5kbps"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            schedule.steps(),
+            [
+                (Duration::ZERO, Some(Bandwidth::from_kbps(1))),
+                (Duration::from_millis(500), Some(Bandwidth::from_kbps(2))),
+                (Duration::from_secs(2), None),
+                (Duration::from_secs(180), Some(Bandwidth::from_kbps(4))),
+                (Duration::from_secs(3_600), Some(Bandwidth::from_kbps(5))),
+            ]
+        );
+        assert_eq!(
+            schedule.to_string(),
+            "0s:1kbps, 500ms:2kbps, 2s:unlimited, 3min:4kbps, 1h:5kbps"
+        );
+        assert_eq!(schedule.to_string().parse(), Ok(schedule));
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        for (offset, millis) in [
+            ("0", 0),
+            ("0ms", 0),
+            ("1m", 60_000),
+            ("1min", 60_000),
+            ("1h30m", 5_400_000),
+            ("1h 30min", 5_400_000),
+            ("2m30s", 150_000),
+            ("1h1m1s1ms", 3_661_001),
+            ("90min", 5_400_000),
+        ] {
+            assert_eq!(
+                parse_offset(offset),
+                Some(Duration::from_millis(millis)),
+                "{}",
+                offset
+            );
+        }
+        // The units must decrease
+        for offset in ["30m1h", "1m1m", "1s1min", "1h30"] {
+            assert_eq!(parse_offset(offset), None, "{}", offset);
+        }
+        let schedule: Schedule = "0:1kbps, 1m:2kbps, 1h30m:3kbps".parse().unwrap();
+        assert_eq!(schedule.to_string(), "0s:1kbps, 1min:2kbps, 90min:3kbps");
+        assert_eq!(schedule.to_string().parse(), Ok(schedule));
+    }
+
+    #[test]
+    fn test_rate_at() {
+        let schedule: Schedule = "0s:1kbps, 10s:unlimited, 20s:3kbps".parse().unwrap();
+        let rate_at = |millis| schedule.rate_at(Duration::from_millis(millis));
+        assert_eq!(rate_at(0), Some(Bandwidth::from_kbps(1)));
+        assert_eq!(rate_at(9_999), Some(Bandwidth::from_kbps(1)));
+        assert_eq!(rate_at(10_000), None);
+        assert_eq!(rate_at(20_000), Some(Bandwidth::from_kbps(3)));
+        assert_eq!(rate_at(u64::MAX), Some(Bandwidth::from_kbps(3)));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!("".parse::<Schedule>(), Err(ScheduleError::InvalidStep(0)));
+        assert_eq!(
+            "0s:1kbps, 10s".parse::<Schedule>(),
+            Err(ScheduleError::InvalidStep(1))
+        );
+        for offset in ["", "10", "1.5s", "10d", "-1s", "s", "1h1h"] {
+            assert_eq!(
+                format!("0s:1kbps, {}:2kbps", offset).parse::<Schedule>(),
+                Err(ScheduleError::InvalidOffset(1)),
+                "{}",
+                offset
+            );
+        }
+        assert_eq!(
+            "0s:1kbps, 99999999999999999999h:2kbps".parse::<Schedule>(),
+            Err(ScheduleError::InvalidOffset(1))
+        );
+        assert_eq!(
+            "0s:fast".parse::<Schedule>().unwrap_err().to_string(),
+            "invalid rate of step 0: expected number at 0"
+        );
+        assert_eq!(
+            "0s:1kbps, 1min:2kbps, 60s:3kbps".parse::<Schedule>(),
+            Err(ScheduleError::Unsorted(2))
+        );
+        assert_eq!(
+            "1s:1kbps".parse::<Schedule>(),
+            Err(ScheduleError::MissingStart)
+        );
+        assert_eq!(Schedule::new(Vec::new()), Err(ScheduleError::MissingStart));
+        // Offsets which could not be written back
+        for offset in [
+            Duration::from_micros(500),
+            Duration::from_millis(u64::MAX) + Duration::from_millis(1),
+        ] {
+            assert_eq!(
+                Schedule::new(vec![(Duration::ZERO, None), (offset, None)]),
+                Err(ScheduleError::InvalidOffset(1))
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        #[derive(Debug, ::serde::Serialize, ::serde::Deserialize)]
+        struct Foo {
+            schedule: Schedule,
+        }
+
+        let json = r#"{"schedule": "0s:1Mbps, 60s:unlimited"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(
+            foo.schedule.rate_at(Duration::from_secs(1)),
+            Some(Bandwidth::from_mbps(1))
+        );
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"schedule":"0s:1Mbps, 1min:unlimited"}"#);
+
+        // The offsets are written exactly
+        let schedule = Schedule::new(vec![
+            (Duration::ZERO, None),
+            (Duration::from_millis(1_500), Some(Bandwidth::from_kbps(1))),
+            (Duration::from_millis(u64::MAX), None),
+        ])
+        .unwrap();
+        let foo = Foo { schedule };
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(
+            reverse,
+            r#"{"schedule":"0s:unlimited, 1500ms:1kbps, 18446744073709551615ms:unlimited"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Foo>(&reverse).unwrap().schedule,
+            foo.schedule
+        );
+
+        let json = r#"{"schedule": "1s:1Mbps"}"#;
+        assert_eq!(
+            serde_json::from_str::<Foo>(json).unwrap_err().to_string(),
+            "the first step must be at offset 0s at line 1 column 24"
+        );
+    }
+}
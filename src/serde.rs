@@ -24,11 +24,29 @@
 //!     bandwidth: Vec<Serde<Bandwidth>>,
 //! }
 //! ```
+//!
+//! See the [`structured`] module for an object form with separate `value` and
+//! `unit` fields, the [`canonical`] module for a stable output in configuration
+//! files, the [`any`] module to accept every encoding on input, and the
+//! [`lenient`] module to ignore quotes, whitespace and byte order marks around
+//! the input. The [`aligned`] module keeps columns of hand-edited files
+//! aligned, and the [`profile`] module writes with a [`Profile`](crate::Profile),
+//! e.g. for logs or for people.
 
+use alloc::string::ToString;
 use bandwidth::Bandwidth;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
 use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
-use std::fmt;
-use std::ops::{Deref, DerefMut};
+
+use crate::Error;
+
+pub mod aligned;
+pub mod any;
+pub mod canonical;
+pub mod lenient;
+pub mod profile;
+pub mod structured;
 
 /// Deserializes a `Bandwidth` in human-readable format.
 ///
@@ -95,30 +113,87 @@ impl<T> From<T> for Serde<T> {
     }
 }
 
+/// Builds the error of a string which is not a valid bandwidth
+///
+/// The message tells where the error is in the string and, for unknown units,
+/// which ones are supported.
+pub(crate) fn invalid_bandwidth<E>(v: &str, error: Error) -> E
+where
+    E: de::Error,
+{
+    let span = match error {
+        Error::UnknownUnit { start, end, .. } | Error::UnknownSystemUnit { start, end, .. } => {
+            Some((start, end))
+        }
+        // The alternatives of a pattern cannot depend on a feature
+        #[cfg(feature = "binary-system")]
+        Error::UnknownBinaryUnit { start, end, .. } => Some((start, end)),
+        _ => None,
+    };
+    match span {
+        Some((start, end)) => E::custom(format_args!(
+            "invalid bandwidth {:?} at bytes {}..{}: {}",
+            v, start, end, error
+        )),
+        None => E::custom(format_args!("invalid bandwidth {:?}: {}", v, error)),
+    }
+}
+
+/// The [`Visitor`](de::Visitor) used to deserialize a `Bandwidth` from a string
+///
+/// It parses the string with [`parse_bandwidth`](crate::parse_bandwidth) and can be
+/// reused by manual `Deserialize` implementations, e.g. for enums. Errors
+/// tell where the string is invalid and which units are supported.
+///
+/// # Example
+///
+/// ```
+/// use serde::{de::Visitor, Deserialize, Deserializer};
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::serde::BandwidthVisitor;
+///
+/// enum Limit {
+///     Unlimited,
+///     Rate(Bandwidth),
+/// }
+///
+/// impl<'de> Deserialize<'de> for Limit {
+///     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Limit, D::Error> {
+///         let s = String::deserialize(d)?;
+///         if s == "unlimited" {
+///             return Ok(Limit::Unlimited);
+///         }
+///         BandwidthVisitor.visit_str(&s).map(Limit::Rate)
+///     }
+/// }
+///
+/// let limit: Limit = serde_json::from_str(r#""10Mbps""#).unwrap();
+/// assert!(matches!(limit, Limit::Rate(_)));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BandwidthVisitor;
+
+impl de::Visitor<'_> for BandwidthVisitor {
+    type Value = Bandwidth;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a bandwidth")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
+    where
+        E: de::Error,
+    {
+        crate::parse_bandwidth(v).map_err(|e| invalid_bandwidth(v, e))
+    }
+}
+
 impl<'de> Deserialize<'de> for Serde<Bandwidth> {
     fn deserialize<D>(d: D) -> Result<Serde<Bandwidth>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct V;
-
-        impl de::Visitor<'_> for V {
-            type Value = Bandwidth;
-
-            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-                fmt.write_str("a bandwidth")
-            }
-
-            fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
-            where
-                E: de::Error,
-            {
-                crate::parse_bandwidth(v)
-                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
-            }
-        }
-
-        d.deserialize_str(V).map(Serde)
+        d.deserialize_str(BandwidthVisitor).map(Serde)
     }
 }
 
@@ -220,4 +295,59 @@ mod tests {
         let foo = serde_json::from_str::<Foo>(json).unwrap();
         assert_eq!(foo.bandwidth, None);
     }
+
+    #[test]
+    fn errors() {
+        #[derive(Debug, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            #[allow(dead_code)]
+            bandwidth: Bandwidth,
+        }
+
+        let error = |json| serde_json::from_str::<Foo>(json).unwrap_err().to_string();
+        assert_eq!(
+            error(r#"{"bandwidth": "10 Mbps 5 Mbpx"}"#),
+            "invalid bandwidth \"10 Mbps 5 Mbpx\" at bytes 10..14: unknown bandwidth unit \
+            \"Mbpx\", did you mean \"Mbps\"? at line 1 column 30"
+        );
+        assert_eq!(
+            error(r#"{"bandwidth": "10Mbps!"}"#),
+            "invalid bandwidth \"10Mbps!\": invalid character at 6 at line 1 column 23"
+        );
+        assert_eq!(
+            error(r#"{"bandwidth": ""}"#),
+            "invalid bandwidth \"\": expected a bandwidth, got an empty string at line 1 column 16"
+        );
+        assert_eq!(
+            error(r#"{"bandwidth": "  "}"#),
+            "invalid bandwidth \"  \": expected a bandwidth, got only whitespace (2 bytes) \
+            at line 1 column 18"
+        );
+    }
+
+    #[test]
+    fn human_bandwidth() {
+        use crate::HumanBandwidth;
+
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Foo {
+            bandwidth: HumanBandwidth,
+        }
+
+        let json = r#"{"bandwidth": "1Mbps 500kbps"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(*foo.bandwidth, Bandwidth::from_kbps(1_500));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        #[cfg(not(feature = "display-integer"))]
+        assert_eq!(reverse, r#"{"bandwidth":"1.5Mbps"}"#);
+        #[cfg(feature = "display-integer")]
+        assert_eq!(reverse, r#"{"bandwidth":"1Mbps 500kbps"}"#);
+
+        let json = r#"{"bandwidth": "1Mbpx"}"#;
+        assert_eq!(
+            serde_json::from_str::<Foo>(json).unwrap_err().to_string(),
+            "unknown bandwidth unit \"Mbpx\", did you mean \"Mbps\"? at line 1 column 22"
+        );
+    }
 }
@@ -0,0 +1,253 @@
+//! Serde support for bandwidth in aligned columns.
+//!
+//! Hand-edited configuration files, e.g. TOML or YAML, often list many rates
+//! one below the other. This module writes the
+//! [canonical form](crate::canonical) with its number right-aligned in a
+//! fixed-width field, e.g. `"  9.375 Tbit/s"`, so that the columns stay
+//! aligned after a program rewrites the file. The number is never truncated:
+//! a longer number widens its field.
+//!
+//! Any string accepted by [`parse_bandwidth`](crate::parse_bandwidth) is read,
+//! including the padded ones.
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Limits {
+//!     #[serde(with = "human_bandwidth::serde::aligned")]
+//!     upload: Bandwidth,
+//!     #[serde(with = "human_bandwidth::serde::aligned")]
+//!     download: Bandwidth,
+//! }
+//!
+//! let limits = Limits {
+//!     upload: Bandwidth::from_mbps(10),
+//!     download: Bandwidth::from_kbps(122_500),
+//! };
+//! let json = serde_json::to_string_pretty(&limits).unwrap();
+//! assert!(json.contains(r#""upload": "     10 Mbit/s""#));
+//! assert!(json.contains(r#""download": "  122.5 Mbit/s""#));
+//! ```
+//!
+//! Or use the `Aligned` wrapper type, which also sets the width of the field:
+//!
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use human_bandwidth::serde::aligned::Aligned;
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     bandwidth: Vec<Aligned<Bandwidth, 4>>,
+//! }
+//!
+//! let foo = Foo { bandwidth: vec![Bandwidth::from_mbps(1).into()] };
+//! assert_eq!(serde_json::to_string(&foo).unwrap(), r#"{"bandwidth":["   1 Mbit/s"]}"#);
+//! ```
+
+use alloc::{format, string::String};
+use bandwidth::Bandwidth;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::BandwidthVisitor;
+use crate::canonical::CanonicalProfile;
+
+/// The width of the numeric field used by the [`serialize`] function
+pub const DEFAULT_WIDTH: usize = 7;
+
+/// Deserializes a `Bandwidth` in human-readable format.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `deserialize_with` annotations.
+pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+where
+    Aligned<T>: Deserialize<'a>,
+    D: Deserializer<'a>,
+{
+    Aligned::deserialize(d).map(Aligned::into_inner)
+}
+
+/// Serializes a `Bandwidth` in canonical form, with its number right-aligned
+/// in a field of [`DEFAULT_WIDTH`] characters.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `serialize_with` annotations.
+pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    for<'a> Aligned<&'a T>: Serialize,
+    S: Serializer,
+{
+    Aligned::<_, DEFAULT_WIDTH>::from(d).serialize(s)
+}
+
+/// A wrapper type which implements `Serialize` and `Deserialize` for
+/// types involving `Bandwidth`, writing the number in a field of `WIDTH`
+/// characters.
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Aligned<T, const WIDTH: usize = DEFAULT_WIDTH>(T);
+
+impl<T, const WIDTH: usize> fmt::Debug for Aligned<T, WIDTH>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt(formatter)
+    }
+}
+
+impl<T, const WIDTH: usize> Deref for Aligned<T, WIDTH> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, const WIDTH: usize> DerefMut for Aligned<T, WIDTH> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T, const WIDTH: usize> Aligned<T, WIDTH> {
+    /// Consumes the `Aligned`, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const WIDTH: usize> From<T> for Aligned<T, WIDTH> {
+    fn from(val: T) -> Aligned<T, WIDTH> {
+        Aligned(val)
+    }
+}
+
+/// Writes the canonical form of `val` with its number right-aligned in a field
+/// of `width` characters
+fn format_aligned(val: Bandwidth, width: usize) -> String {
+    let canonical = CanonicalProfile.format(val);
+    // The canonical form always has a single space, before the unit
+    let (number, unit) = canonical.split_once(' ').unwrap_or((&canonical, ""));
+    format!("{:>width$} {}", number, unit, width = width)
+}
+
+impl<'de, const WIDTH: usize> Deserialize<'de> for Aligned<Bandwidth, WIDTH> {
+    fn deserialize<D>(d: D) -> Result<Aligned<Bandwidth, WIDTH>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_str(BandwidthVisitor).map(Aligned)
+    }
+}
+
+impl<'de, const WIDTH: usize> Deserialize<'de> for Aligned<Option<Bandwidth>, WIDTH> {
+    fn deserialize<D>(d: D) -> Result<Aligned<Option<Bandwidth>, WIDTH>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<Aligned<Bandwidth, WIDTH>>::deserialize(d)? {
+            Some(Aligned(dur)) => Ok(Aligned(Some(dur))),
+            None => Ok(Aligned(None)),
+        }
+    }
+}
+
+impl<const WIDTH: usize> ser::Serialize for Aligned<&Bandwidth, WIDTH> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        format_aligned(*self.0, WIDTH).serialize(serializer)
+    }
+}
+
+impl<const WIDTH: usize> ser::Serialize for Aligned<Bandwidth, WIDTH> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Aligned::<_, WIDTH>(&self.0).serialize(serializer)
+    }
+}
+
+impl<const WIDTH: usize> ser::Serialize for Aligned<&Option<Bandwidth>, WIDTH> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self.0 {
+            Some(dur) => serializer.serialize_some(&Aligned::<_, WIDTH>(dur)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<const WIDTH: usize> ser::Serialize for Aligned<Option<Bandwidth>, WIDTH> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Aligned::<_, WIDTH>(&self.0).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bandwidth: Bandwidth,
+        }
+
+        for (json, expected) in [
+            (r#"{"bandwidth": "9Tbps 375Gbps"}"#, "  9.375 Tbit/s"),
+            (r#"{"bandwidth": "  9.375 Tbit/s"}"#, "  9.375 Tbit/s"),
+            (r#"{"bandwidth": "0bps"}"#, "      0 bit/s"),
+            (r#"{"bandwidth": "1.000001Mbps"}"#, "1.000001 Mbit/s"),
+        ] {
+            let foo = serde_json::from_str::<Foo>(json).unwrap();
+            let reverse = serde_json::to_string(&foo).unwrap();
+            assert_eq!(reverse, format!(r#"{{"bandwidth":"{}"}}"#, expected));
+            let again = serde_json::from_str::<Foo>(&reverse).unwrap();
+            assert_eq!(again.bandwidth, foo.bandwidth);
+        }
+    }
+
+    #[test]
+    fn with_option() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super", default)]
+            bandwidth: Option<Bandwidth>,
+        }
+
+        let json = r#"{"bandwidth": "15Mbps"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Some(Bandwidth::from_mbps(15)));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":"     15 Mbit/s"}"#);
+
+        let json = r#"{"bandwidth": null}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":null}"#);
+    }
+
+    #[test]
+    fn width() {
+        let aligned: Aligned<Bandwidth, 0> = Bandwidth::from_kbps(1).into();
+        assert_eq!(serde_json::to_string(&aligned).unwrap(), r#""1 kbit/s""#);
+        let aligned: Aligned<Option<Bandwidth>, 3> = Some(Bandwidth::from_kbps(1)).into();
+        assert_eq!(serde_json::to_string(&aligned).unwrap(), r#""  1 kbit/s""#);
+    }
+}
@@ -0,0 +1,427 @@
+//! Serde support accepting every bandwidth encoding.
+//!
+//! On input the bandwidth may be any of:
+//!
+//! * a string in human-readable format, e.g. `"1.5Mbps"`
+//! * a number of bits per second, e.g. `1500000`
+//! * a [`structured`](super::structured) object, e.g. `{"value": 1.5, "unit": "Mbps"}`
+//!
+//! On output a single canonical form is used, which is selected by the module
+//! given to `serde_derive`'s `with` annotation:
+//!
+//! * `human_bandwidth::serde::any` writes the string form
+//! * `human_bandwidth::serde::any::number` writes the number of bits per second,
+//!   or the string form above `u64::MAX` bits per second
+//! * `human_bandwidth::serde::any::structured` writes the structured object form
+//!
+//! This eases migrations between API versions, as older clients keep working
+//! while newer ones already receive the new encoding.
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     #[serde(with = "human_bandwidth::serde::any::structured")]
+//!     bandwidth: Bandwidth,
+//! }
+//!
+//! for json in [
+//!     r#"{"bandwidth": "1.5Mbps"}"#,
+//!     r#"{"bandwidth": 1500000}"#,
+//!     r#"{"bandwidth": {"value": 1.5, "unit": "Mbps"}}"#,
+//! ] {
+//!     let foo = serde_json::from_str::<Foo>(json).unwrap();
+//!     assert_eq!(foo.bandwidth, Bandwidth::from_kbps(1_500));
+//!     let reverse = serde_json::to_string(&foo).unwrap();
+//!     assert_eq!(reverse, r#"{"bandwidth":{"value":1.5,"unit":"Mbps"}}"#);
+//! }
+//! ```
+
+use alloc::format;
+use bandwidth::Bandwidth;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::structured::Structured;
+use super::Serde;
+
+/// Deserializes a `Bandwidth` from any of the supported encodings.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `deserialize_with` annotations.
+pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+where
+    Any<T>: Deserialize<'a>,
+    D: Deserializer<'a>,
+{
+    Any::deserialize(d).map(Any::into_inner)
+}
+
+/// Serializes a `Bandwidth` in human-readable format.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `serialize_with` annotations.
+pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    for<'a> Any<&'a T>: Serialize,
+    S: Serializer,
+{
+    Any::from(d).serialize(s)
+}
+
+/// Accepts any encoding, writes the number of bits per second
+pub mod number {
+    use super::{Any, Number};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes a `Bandwidth` from any of the supported encodings.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `deserialize_with` annotations.
+    pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+    where
+        Any<T>: Deserialize<'a>,
+        D: Deserializer<'a>,
+    {
+        super::deserialize(d)
+    }
+
+    /// Serializes a `Bandwidth` as a number of bits per second.
+    ///
+    /// A bandwidth above `u64::MAX` bits per second is written in the string
+    /// form, since most JSON parsers would read such a number as an inexact
+    /// float.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `serialize_with` annotations.
+    pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+    where
+        for<'a> Number<&'a T>: Serialize,
+        S: Serializer,
+    {
+        Number(d).serialize(s)
+    }
+}
+
+/// Accepts any encoding, writes the structured object form
+pub mod structured {
+    use super::Any;
+    use crate::serde::structured::Structured;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes a `Bandwidth` from any of the supported encodings.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `deserialize_with` annotations.
+    pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+    where
+        Any<T>: Deserialize<'a>,
+        D: Deserializer<'a>,
+    {
+        super::deserialize(d)
+    }
+
+    /// Serializes a `Bandwidth` into a structured object.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `serialize_with` annotations.
+    pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+    where
+        for<'a> Structured<&'a T>: Serialize,
+        S: Serializer,
+    {
+        Structured::from(d).serialize(s)
+    }
+}
+
+/// A wrapper type which implements `Deserialize` from any encoding and
+/// `Serialize` into the string form for types involving `Bandwidth`.
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Any<T>(T);
+
+impl<T> fmt::Debug for Any<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt(formatter)
+    }
+}
+
+impl<T> Deref for Any<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Any<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> Any<T> {
+    /// Consumes the `Any`, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Any<T> {
+    fn from(val: T) -> Any<T> {
+        Any(val)
+    }
+}
+
+/// A wrapper type serializing types involving `Bandwidth` as a number of bits per second,
+/// or in the string form above `u64::MAX` bits per second
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Number<T>(T);
+
+impl<'de> Deserialize<'de> for Any<Bandwidth> {
+    fn deserialize<D>(d: D) -> Result<Any<Bandwidth>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct V;
+
+        impl<'de> de::Visitor<'de> for V {
+            type Value = Bandwidth;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.write_str("a bandwidth as a string, a number of bps or an object")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
+            where
+                E: de::Error,
+            {
+                crate::parse_bandwidth(v).map_err(|e| super::invalid_bandwidth(v, e))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Bandwidth, E>
+            where
+                E: de::Error,
+            {
+                Ok(Bandwidth::from_bps(v))
+            }
+
+            fn visit_u128<E>(self, v: u128) -> Result<Bandwidth, E>
+            where
+                E: de::Error,
+            {
+                crate::bandwidth_from_bps(v)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Other("u128"), &self))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Bandwidth, E>
+            where
+                E: de::Error,
+            {
+                u64::try_from(v)
+                    .map(Bandwidth::from_bps)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Bandwidth, E>
+            where
+                E: de::Error,
+            {
+                if !v.is_finite() || v < 0.0 {
+                    return Err(E::invalid_value(de::Unexpected::Float(v), &self));
+                }
+                // The fractional part less than 1bps is ignored, as in the string form
+                crate::parse_bandwidth(&format!("{}bps", v))
+                    .map_err(|_| E::invalid_value(de::Unexpected::Float(v), &self))
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Bandwidth, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                Structured::<Bandwidth>::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(Structured::into_inner)
+            }
+        }
+
+        d.deserialize_any(V).map(Any)
+    }
+}
+
+impl<'de> Deserialize<'de> for Any<Option<Bandwidth>> {
+    fn deserialize<D>(d: D) -> Result<Any<Option<Bandwidth>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<Any<Bandwidth>>::deserialize(d)? {
+            Some(Any(dur)) => Ok(Any(Some(dur))),
+            None => Ok(Any(None)),
+        }
+    }
+}
+
+impl ser::Serialize for Any<&Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Serde::from(self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Any<Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Any(&self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Any<&Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self.0 {
+            Some(dur) => serializer.serialize_some(&Any(dur)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl ser::Serialize for Any<Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Any(&self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Number<&Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match u64::try_from(crate::bandwidth_as_bps(*self.0)) {
+            Ok(bps) => serializer.serialize_u64(bps),
+            // Read back exactly, unlike a float
+            Err(_) => Serde::from(self.0).serialize(serializer),
+        }
+    }
+}
+
+impl ser::Serialize for Number<&Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self.0 {
+            Some(dur) => serializer.serialize_some(&Number(dur)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bandwidth: Bandwidth,
+        }
+
+        for json in [
+            r#"{"bandwidth": "1kbps"}"#,
+            r#"{"bandwidth": 1000}"#,
+            r#"{"bandwidth": 1000.4}"#,
+            r#"{"bandwidth": {"value": 1, "unit": "kbps"}}"#,
+        ] {
+            let foo = serde_json::from_str::<Foo>(json).unwrap();
+            assert_eq!(foo.bandwidth, Bandwidth::from_kbps(1));
+            let reverse = serde_json::to_string(&foo).unwrap();
+            assert_eq!(reverse, r#"{"bandwidth":"1kbps"}"#);
+        }
+        assert!(serde_json::from_str::<Foo>(r#"{"bandwidth": -1}"#).is_err());
+        assert!(serde_json::from_str::<Foo>(r#"{"bandwidth": true}"#).is_err());
+    }
+
+    #[test]
+    fn with_number() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super::number")]
+            bandwidth: Bandwidth,
+        }
+
+        let json = r#"{"bandwidth": "9Tbps 420Gbps"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Bandwidth::new(9420, 0));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":9420000000000}"#);
+
+        let largest_number = Bandwidth::new(18_446_744_073, 709_551_615);
+        for bandwidth in [largest_number, Bandwidth::MAX] {
+            let json = serde_json::to_string(&Foo { bandwidth }).unwrap();
+            let foo = serde_json::from_str::<Foo>(&json).unwrap();
+            assert_eq!(foo.bandwidth, bandwidth, "{}", json);
+        }
+        let json = serde_json::to_string(&Foo {
+            bandwidth: Bandwidth::MAX,
+        })
+        .unwrap();
+        assert!(json.starts_with(r#"{"bandwidth":""#), "{}", json);
+    }
+
+    #[test]
+    fn with_max() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bandwidth: Bandwidth,
+        }
+
+        let json = serde_json::to_string(&Foo {
+            bandwidth: Bandwidth::MAX,
+        })
+        .unwrap();
+        let foo = serde_json::from_str::<Foo>(&json).unwrap();
+        assert_eq!(foo.bandwidth, Bandwidth::MAX);
+    }
+
+    #[test]
+    fn with_option() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super::structured", default)]
+            bandwidth: Option<Bandwidth>,
+        }
+
+        let json = r#"{"bandwidth": "15Mbps"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Some(Bandwidth::from_mbps(15)));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":{"value":15,"unit":"Mbps"}}"#);
+
+        let json = r#"{"bandwidth": null}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":null}"#);
+
+        let json = r#"{}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+    }
+}
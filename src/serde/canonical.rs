@@ -0,0 +1,219 @@
+//! Serde support for bandwidth in canonical form.
+//!
+//! Any string accepted by [`parse_bandwidth`](crate::parse_bandwidth) is read,
+//! while bandwidths are always written in the stable form of
+//! [`crate::canonical::CanonicalProfile`], e.g.
+//! `"1.5 Mbit/s"`, so that generated configuration files diff reproducibly.
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     #[serde(with = "human_bandwidth::serde::canonical")]
+//!     bandwidth: Bandwidth,
+//! }
+//!
+//! let json = r#"{"bandwidth": "1500kbps"}"#;
+//! let foo = serde_json::from_str::<Foo>(json).unwrap();
+//! assert_eq!(foo.bandwidth, Bandwidth::from_kbps(1_500));
+//! let reverse = serde_json::to_string(&foo).unwrap();
+//! assert_eq!(reverse, r#"{"bandwidth":"1.5 Mbit/s"}"#);
+//! ```
+//!
+//! Or use the `Canonical` wrapper type:
+//!
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use human_bandwidth::serde::canonical::Canonical;
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     bandwidth: Vec<Canonical<Bandwidth>>,
+//! }
+//! ```
+
+use bandwidth::Bandwidth;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::BandwidthVisitor;
+use crate::canonical::CanonicalProfile;
+
+/// Deserializes a `Bandwidth` in human-readable format.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `deserialize_with` annotations.
+pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+where
+    Canonical<T>: Deserialize<'a>,
+    D: Deserializer<'a>,
+{
+    Canonical::deserialize(d).map(Canonical::into_inner)
+}
+
+/// Serializes a `Bandwidth` in canonical form.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `serialize_with` annotations.
+pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    for<'a> Canonical<&'a T>: Serialize,
+    S: Serializer,
+{
+    Canonical::from(d).serialize(s)
+}
+
+/// A wrapper type which implements `Serialize` and `Deserialize` for
+/// types involving `Bandwidth`, writing the canonical form.
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Canonical<T>(T);
+
+impl<T> fmt::Debug for Canonical<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt(formatter)
+    }
+}
+
+impl<T> Deref for Canonical<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Canonical<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> Canonical<T> {
+    /// Consumes the `Canonical`, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Canonical<T> {
+    fn from(val: T) -> Canonical<T> {
+        Canonical(val)
+    }
+}
+
+impl<'de> Deserialize<'de> for Canonical<Bandwidth> {
+    fn deserialize<D>(d: D) -> Result<Canonical<Bandwidth>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_str(BandwidthVisitor).map(Canonical)
+    }
+}
+
+impl<'de> Deserialize<'de> for Canonical<Option<Bandwidth>> {
+    fn deserialize<D>(d: D) -> Result<Canonical<Option<Bandwidth>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<Canonical<Bandwidth>>::deserialize(d)? {
+            Some(Canonical(dur)) => Ok(Canonical(Some(dur))),
+            None => Ok(Canonical(None)),
+        }
+    }
+}
+
+impl ser::Serialize for Canonical<&Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        CanonicalProfile.format(*self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Canonical<Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Canonical(&self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Canonical<&Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self.0 {
+            Some(dur) => serializer.serialize_some(&Canonical(dur)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl ser::Serialize for Canonical<Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Canonical(&self.0).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bandwidth: Bandwidth,
+        }
+
+        for json in [
+            r#"{"bandwidth": "9Tbps 420Gbps"}"#,
+            r#"{"bandwidth": "9.42 Tbit/s"}"#,
+        ] {
+            let foo = serde_json::from_str::<Foo>(json).unwrap();
+            assert_eq!(foo.bandwidth, Bandwidth::new(9420, 0));
+            let reverse = serde_json::to_string(&foo).unwrap();
+            assert_eq!(reverse, r#"{"bandwidth":"9.42 Tbit/s"}"#);
+        }
+    }
+
+    #[test]
+    fn with_option() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super", default)]
+            bandwidth: Option<Bandwidth>,
+        }
+
+        let json = r#"{"bandwidth": "15Mbps"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Some(Bandwidth::from_mbps(15)));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":"15 Mbit/s"}"#);
+
+        let json = r#"{"bandwidth": null}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":null}"#);
+
+        let json = r#"{}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+    }
+}
@@ -0,0 +1,274 @@
+//! Serde support tolerating cruft around humanized bandwidths.
+//!
+//! Values piped through YAML to JSON converters or saved by Windows editors
+//! often carry a UTF-8 byte order mark, extra quotes or whitespace, e.g.
+//! `"\u{feff}'10Mbps' "`. On input, this module strips them before parsing
+//! the string like [`parse_bandwidth`](crate::parse_bandwidth). On output the
+//! plain human-readable format is written, as with [`serde`](super).
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     #[serde(with = "human_bandwidth::serde::lenient")]
+//!     bandwidth: Bandwidth,
+//! }
+//!
+//! let json = r#"{"bandwidth": "\ufeff \"10Mbps\"\r\n"}"#;
+//! let foo = serde_json::from_str::<Foo>(json).unwrap();
+//! assert_eq!(foo.bandwidth, Bandwidth::from_mbps(10));
+//! let reverse = serde_json::to_string(&foo).unwrap();
+//! assert_eq!(reverse, r#"{"bandwidth":"10Mbps"}"#);
+//! ```
+//!
+//! Or use the `Lenient` wrapper type:
+//!
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use human_bandwidth::serde::lenient::Lenient;
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     bandwidth: Vec<Lenient<Bandwidth>>,
+//! }
+//! ```
+
+use bandwidth::Bandwidth;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Serde;
+
+/// Deserializes a `Bandwidth` in human-readable format, ignoring the
+/// surrounding cruft.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `deserialize_with` annotations.
+pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+where
+    Lenient<T>: Deserialize<'a>,
+    D: Deserializer<'a>,
+{
+    Lenient::deserialize(d).map(Lenient::into_inner)
+}
+
+/// Serializes a `Bandwidth` in human-readable format.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `serialize_with` annotations.
+pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    for<'a> Lenient<&'a T>: Serialize,
+    S: Serializer,
+{
+    Lenient::from(d).serialize(s)
+}
+
+/// Removes the byte order marks, whitespace and matching single or double
+/// quotes surrounding a value, however they are nested
+fn strip_cruft(mut s: &str) -> &str {
+    loop {
+        let stripped = s.trim_matches(|c: char| c == '\u{feff}' || c.is_whitespace());
+        let stripped = ['"', '\'']
+            .into_iter()
+            .find_map(|quote| {
+                stripped
+                    .strip_prefix(quote)
+                    .and_then(|inner| inner.strip_suffix(quote))
+            })
+            .unwrap_or(stripped);
+        if stripped.len() == s.len() {
+            return s;
+        }
+        s = stripped;
+    }
+}
+
+/// A wrapper type which implements `Serialize` and `Deserialize` for
+/// types involving `Bandwidth`, ignoring the cruft around the input.
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Lenient<T>(T);
+
+impl<T> fmt::Debug for Lenient<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt(formatter)
+    }
+}
+
+impl<T> Deref for Lenient<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Lenient<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> Lenient<T> {
+    /// Consumes the `Lenient`, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Lenient<T> {
+    fn from(val: T) -> Lenient<T> {
+        Lenient(val)
+    }
+}
+
+impl<'de> Deserialize<'de> for Lenient<Bandwidth> {
+    fn deserialize<D>(d: D) -> Result<Lenient<Bandwidth>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct V;
+
+        impl de::Visitor<'_> for V {
+            type Value = Bandwidth;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.write_str("a bandwidth")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
+            where
+                E: de::Error,
+            {
+                let v = strip_cruft(v);
+                crate::parse_bandwidth(v).map_err(|e| super::invalid_bandwidth(v, e))
+            }
+        }
+
+        d.deserialize_str(V).map(Lenient)
+    }
+}
+
+impl<'de> Deserialize<'de> for Lenient<Option<Bandwidth>> {
+    fn deserialize<D>(d: D) -> Result<Lenient<Option<Bandwidth>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<Lenient<Bandwidth>>::deserialize(d)? {
+            Some(Lenient(dur)) => Ok(Lenient(Some(dur))),
+            None => Ok(Lenient(None)),
+        }
+    }
+}
+
+impl ser::Serialize for Lenient<&Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Serde::from(self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Lenient<Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Lenient(&self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Lenient<&Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self.0 {
+            Some(dur) => serializer.serialize_some(&Lenient(dur)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl ser::Serialize for Lenient<Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Lenient(&self.0).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_cruft() {
+        assert_eq!(strip_cruft("10Mbps"), "10Mbps");
+        assert_eq!(strip_cruft("\u{feff}10Mbps"), "10Mbps");
+        assert_eq!(strip_cruft(" \"10Mbps\"\r\n"), "10Mbps");
+        assert_eq!(strip_cruft("'\u{feff}\" 1 kbps \"'"), "1 kbps");
+        assert_eq!(strip_cruft("\"10Mbps'"), "\"10Mbps'");
+        assert_eq!(strip_cruft("\"\""), "");
+    }
+
+    #[test]
+    fn with() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bandwidth: Bandwidth,
+        }
+
+        for json in [
+            r#"{"bandwidth": "1kbps"}"#,
+            r#"{"bandwidth": "\ufeff1kbps"}"#,
+            r#"{"bandwidth": "'1kbps'"}"#,
+            r#"{"bandwidth": " \"1kbps\"\t"}"#,
+        ] {
+            let foo = serde_json::from_str::<Foo>(json).unwrap();
+            assert_eq!(foo.bandwidth, Bandwidth::from_kbps(1));
+            let reverse = serde_json::to_string(&foo).unwrap();
+            assert_eq!(reverse, r#"{"bandwidth":"1kbps"}"#);
+        }
+
+        for json in [r#"{"bandwidth": "''"}"#, r#"{"bandwidth": "'1kbps"}"#] {
+            assert!(serde_json::from_str::<Foo>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn with_option() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super", default)]
+            bandwidth: Option<Bandwidth>,
+        }
+
+        let json = r#"{"bandwidth": "\"15Mbps\""}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Some(Bandwidth::from_mbps(15)));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":"15Mbps"}"#);
+
+        let json = r#"{"bandwidth": null}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":null}"#);
+
+        let json = r#"{}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+    }
+}
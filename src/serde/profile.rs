@@ -0,0 +1,245 @@
+//! Serde support for bandwidth written with a [`Profile`].
+//!
+//! Any string accepted by [`parse_bandwidth`](crate::parse_bandwidth) is read,
+//! while bandwidths are written with the profile selected by the module given
+//! to `serde_derive`'s `with` annotation:
+//!
+//! * `human_bandwidth::serde::profile::machine` writes [`Profile::Machine`],
+//!   e.g. `"1.536Mbps"`, whatever the crate features
+//! * `human_bandwidth::serde::profile::human` writes [`Profile::Human`], e.g.
+//!   `"1.5 Mbit/s"`
+//! * `human_bandwidth::serde::profile::v1` writes [`Profile::V1`], e.g.
+//!   `"1.536Mbps"`, which never changes across releases
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Status {
+//!     #[serde(with = "human_bandwidth::serde::profile::machine")]
+//!     logged: Bandwidth,
+//!     #[serde(with = "human_bandwidth::serde::profile::human")]
+//!     displayed: Bandwidth,
+//! }
+//!
+//! let rate = Bandwidth::from_kbps(1_536);
+//! let status = Status { logged: rate, displayed: rate };
+//! assert_eq!(
+//!     serde_json::to_string(&status).unwrap(),
+//!     r#"{"logged":"1.536Mbps","displayed":"1.5 Mbit/s"}"#
+//! );
+//! ```
+
+use bandwidth::Bandwidth;
+use serde::ser;
+
+use crate::Profile;
+
+/// Writes bandwidths with [`Profile::Machine`]
+pub mod machine {
+    use super::Profiled;
+    use crate::{serde::Serde, Profile};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes a `Bandwidth` in human-readable format.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `deserialize_with` annotations.
+    pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+    where
+        Serde<T>: Deserialize<'a>,
+        D: Deserializer<'a>,
+    {
+        crate::serde::deserialize(d)
+    }
+
+    /// Serializes a `Bandwidth` with the machine profile.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `serialize_with` annotations.
+    pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+    where
+        for<'a> Profiled<&'a T>: Serialize,
+        S: Serializer,
+    {
+        Profiled::new(Profile::Machine, d).serialize(s)
+    }
+}
+
+/// Writes bandwidths with [`Profile::Human`]
+pub mod human {
+    use super::Profiled;
+    use crate::{serde::Serde, Profile};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes a `Bandwidth` in human-readable format.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `deserialize_with` annotations.
+    pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+    where
+        Serde<T>: Deserialize<'a>,
+        D: Deserializer<'a>,
+    {
+        crate::serde::deserialize(d)
+    }
+
+    /// Serializes a `Bandwidth` with the human profile.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `serialize_with` annotations.
+    pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+    where
+        for<'a> Profiled<&'a T>: Serialize,
+        S: Serializer,
+    {
+        Profiled::new(Profile::Human, d).serialize(s)
+    }
+}
+
+/// Writes bandwidths with [`Profile::V1`]
+pub mod v1 {
+    use super::Profiled;
+    use crate::{serde::Serde, Profile};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes a `Bandwidth` in human-readable format.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `deserialize_with` annotations.
+    pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+    where
+        Serde<T>: Deserialize<'a>,
+        D: Deserializer<'a>,
+    {
+        crate::serde::deserialize(d)
+    }
+
+    /// Serializes a `Bandwidth` with the frozen version 1 profile.
+    ///
+    /// This function can be used with `serde_derive`'s `with` and
+    /// `serialize_with` annotations.
+    pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+    where
+        for<'a> Profiled<&'a T>: Serialize,
+        S: Serializer,
+    {
+        Profiled::new(Profile::V1, d).serialize(s)
+    }
+}
+
+/// A wrapper type which implements `Serialize` for types involving
+/// `Bandwidth`, writing them with a [`Profile`].
+#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Profiled<T> {
+    profile: Profile,
+    value: T,
+}
+
+impl<T> Profiled<T> {
+    /// Wraps a value to be written with `profile`.
+    pub fn new(profile: Profile, value: T) -> Self {
+        Profiled { profile, value }
+    }
+
+    /// The profile the value is written with.
+    pub fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// Consumes the `Profiled`, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl ser::Serialize for Profiled<&Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.profile.format(*self.value).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Profiled<Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Profiled::new(self.profile, &self.value).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Profiled<&Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self.value {
+            Some(val) => serializer.serialize_some(&Profiled::new(self.profile, val)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl ser::Serialize for Profiled<Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Profiled::new(self.profile, &self.value).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn with() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "machine")]
+            machine: Bandwidth,
+            #[serde(with = "human")]
+            human: Bandwidth,
+            #[serde(with = "v1")]
+            v1: Bandwidth,
+        }
+
+        let json = r#"{"machine": "9.42 Tbit/s", "human": "9Tbps 420Gbps 1bps", "v1": "1536kbps"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.machine, Bandwidth::new(9420, 0));
+        assert_eq!(foo.human, Bandwidth::new(9420, 1));
+        assert_eq!(foo.v1, Bandwidth::from_kbps(1_536));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(
+            reverse,
+            r#"{"machine":"9.42Tbps","human":"9.4 Tbit/s","v1":"1.536Mbps"}"#
+        );
+    }
+
+    #[test]
+    fn with_option() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "human", default)]
+            bandwidth: Option<Bandwidth>,
+        }
+
+        let json = r#"{"bandwidth": "1536kbps"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Some(Bandwidth::from_kbps(1_536)));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":"1.5 Mbit/s"}"#);
+
+        let foo = serde_json::from_str::<Foo>("{}").unwrap();
+        assert_eq!(foo.bandwidth, None);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":null}"#);
+    }
+}
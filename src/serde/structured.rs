@@ -0,0 +1,556 @@
+//! Serde support for bandwidth as a structured object.
+//!
+//! Instead of a single string like `"1.5Mbps"`, the bandwidth is represented
+//! by an object with a numeric `value` and a `unit` field, which spares API
+//! consumers from parsing the string on their side.
+//!
+//! The `unit` accepts exactly one of the SI units of bits per second read by
+//! [`parse_bandwidth`](crate::parse_bandwidth), like `Mbps` or `Mbit/s`,
+//! without any digit or whitespace, while serialization uses the
+//! largest unit in which the `value` is an integer or a float read back
+//! exactly, e.g. `1.5` with `Mbps`. A bandwidth which no JSON number can hold
+//! exactly has its `value` written as a string, e.g. `"18446744073.709551615"`
+//! with `Ebps`, which is accepted as well.
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     #[serde(with = "human_bandwidth::serde::structured")]
+//!     bandwidth: Bandwidth,
+//! }
+//!
+//! let json = r#"{"bandwidth": {"value": 1.5, "unit": "Mbps"}}"#;
+//! let foo = serde_json::from_str::<Foo>(json).unwrap();
+//! assert_eq!(foo.bandwidth, Bandwidth::from_kbps(1_500));
+//! ```
+//!
+//! Or use the `Structured` wrapper type:
+//!
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use human_bandwidth::serde::structured::Structured;
+//! use bandwidth::Bandwidth;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     bandwidth: Vec<Structured<Bandwidth>>,
+//! }
+//! ```
+
+use crate::Unit;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use bandwidth::Bandwidth;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+const FIELDS: &[&str] = &["value", "unit"];
+
+/// Deserializes a `Bandwidth` from a structured object.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `deserialize_with` annotations.
+pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+where
+    Structured<T>: Deserialize<'a>,
+    D: Deserializer<'a>,
+{
+    Structured::deserialize(d).map(Structured::into_inner)
+}
+
+/// Serializes a `Bandwidth` into a structured object.
+///
+/// This function can be used with `serde_derive`'s `with` and
+/// `serialize_with` annotations.
+pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    for<'a> Structured<&'a T>: Serialize,
+    S: Serializer,
+{
+    Structured::from(d).serialize(s)
+}
+
+/// A wrapper type which implements `Serialize` and `Deserialize` for
+/// types involving `Bandwidth`, using the structured object form.
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Structured<T>(T);
+
+impl<T> fmt::Debug for Structured<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.0.fmt(formatter)
+    }
+}
+
+impl<T> Deref for Structured<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Structured<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> Structured<T> {
+    /// Consumes the `Structured`, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Structured<T> {
+    fn from(val: T) -> Structured<T> {
+        Structured(val)
+    }
+}
+
+/// The numeric part of the structured form, kept verbatim so that it can be
+/// handed to the parser without losing precision.
+struct Value(String);
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(d: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct V;
+
+        impl de::Visitor<'_> for V {
+            type Value = Value;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.write_str("a non-negative number, or a decimal number as a string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Value, E>
+            where
+                E: de::Error,
+            {
+                let mut parts = v.splitn(2, '.');
+                let decimal =
+                    parts.all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+                if !decimal {
+                    return Err(E::invalid_value(de::Unexpected::Str(v), &self));
+                }
+                Ok(Value(v.to_string()))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Value(v.to_string()))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Value, E>
+            where
+                E: de::Error,
+            {
+                if v < 0 {
+                    return Err(E::invalid_value(de::Unexpected::Signed(v), &self));
+                }
+                Ok(Value(v.to_string()))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Value, E>
+            where
+                E: de::Error,
+            {
+                if !v.is_finite() || v < 0.0 {
+                    return Err(E::invalid_value(de::Unexpected::Float(v), &self));
+                }
+                Ok(Value(v.to_string()))
+            }
+        }
+
+        d.deserialize_any(V)
+    }
+}
+
+/// Combines the two fields of the structured form into a `Bandwidth`
+///
+/// The `unit` must be exactly one of the SI units of bits per second, whose
+/// multiplier scales the `value` without parsing them together.
+fn combine<E>(value: Value, unit: String) -> Result<Bandwidth, E>
+where
+    E: de::Error,
+{
+    let (int, digits) = value.0.split_once('.').unwrap_or((&value.0, ""));
+    let exponent = match crate::si_unit(&unit) {
+        Some(exponent) => exponent,
+        None => {
+            return Err(E::custom(crate::Error::UnknownUnit {
+                start: 0,
+                end: unit.len(),
+                value: int.parse().unwrap_or(u64::MAX),
+                unit,
+            }))
+        }
+    };
+    // The digits of the fraction less than 1bps are ignored, like the parser
+    let fraction: String = digits
+        .chars()
+        .chain(core::iter::repeat('0'))
+        .take(exponent as usize)
+        .collect();
+    let bps = int
+        .parse::<u128>()
+        .ok()
+        .and_then(|int| int.checked_mul(10u128.pow(exponent)))
+        .and_then(|bps| bps.checked_add(fraction.parse().unwrap_or(0)))
+        .ok_or(crate::Error::NumberOverflow)
+        .and_then(crate::bandwidth_from_bps);
+    bps.map_err(E::custom)
+}
+
+/// The numeric part of the structured form, as it is serialized
+enum Number {
+    Integer(u64),
+    Float(f64),
+    /// A value which no number holds exactly, in decimal form
+    Decimal(String),
+}
+
+/// Splits a bandwidth into the numeric value and unit used by the structured form
+///
+/// The value is written in the largest unit in which it is an integer or a
+/// float read back exactly, or else as a decimal string in the largest unit
+/// in which it is at least 1.
+fn split(val: Bandwidth) -> (Number, Unit) {
+    let bps = crate::bandwidth_as_bps(val);
+    let largest = Unit::of(bps);
+    for unit in Unit::ALL[..=largest as usize].iter().copied().rev() {
+        let value = exact(bps, unit);
+        if let Ok(value) = value.parse::<u64>() {
+            return (Number::Integer(value), unit);
+        }
+        match value.parse::<f64>() {
+            Ok(float) if float.to_string() == value => return (Number::Float(float), unit),
+            _ => {}
+        }
+    }
+    (Number::Decimal(exact(bps, largest)), largest)
+}
+
+/// Writes `bps` in `unit` exactly, without trailing zeros
+fn exact(bps: u128, unit: Unit) -> String {
+    let value = crate::fmt_ratio(bps, u128::from(unit.bps()), unit.exponent() as usize);
+    match value.split_once('.') {
+        Some((int, digits)) => match digits.trim_end_matches('0') {
+            "" => int.to_string(),
+            digits => format!("{}.{}", int, digits),
+        },
+        None => value,
+    }
+}
+
+impl<'de> Deserialize<'de> for Structured<Bandwidth> {
+    fn deserialize<D>(d: D) -> Result<Structured<Bandwidth>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        enum Field {
+            Value,
+            Unit,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(d: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct V;
+
+                impl de::Visitor<'_> for V {
+                    type Value = Field;
+
+                    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                        fmt.write_str("`value` or `unit`")
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<Field, E>
+                    where
+                        E: de::Error,
+                    {
+                        match v {
+                            "value" => Ok(Field::Value),
+                            "unit" => Ok(Field::Unit),
+                            _ => Err(E::unknown_field(v, FIELDS)),
+                        }
+                    }
+                }
+
+                d.deserialize_identifier(V)
+            }
+        }
+
+        struct V;
+
+        impl<'de> de::Visitor<'de> for V {
+            type Value = Bandwidth;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.write_str("a bandwidth object with `value` and `unit` fields")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Bandwidth, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let value = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let unit = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                combine(value, unit)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Bandwidth, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut value = None;
+                let mut unit = None;
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Value => {
+                            if value.is_some() {
+                                return Err(de::Error::duplicate_field("value"));
+                            }
+                            value = Some(map.next_value()?);
+                        }
+                        Field::Unit => {
+                            if unit.is_some() {
+                                return Err(de::Error::duplicate_field("unit"));
+                            }
+                            unit = Some(map.next_value()?);
+                        }
+                    }
+                }
+                let value = value.ok_or_else(|| de::Error::missing_field("value"))?;
+                let unit = unit.ok_or_else(|| de::Error::missing_field("unit"))?;
+                combine(value, unit)
+            }
+        }
+
+        d.deserialize_struct("Bandwidth", FIELDS, V).map(Structured)
+    }
+}
+
+impl<'de> Deserialize<'de> for Structured<Option<Bandwidth>> {
+    fn deserialize<D>(d: D) -> Result<Structured<Option<Bandwidth>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<Structured<Bandwidth>>::deserialize(d)? {
+            Some(Structured(dur)) => Ok(Structured(Some(dur))),
+            None => Ok(Structured(None)),
+        }
+    }
+}
+
+impl ser::Serialize for Structured<&Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeStruct;
+
+        let (value, unit) = split(*self.0);
+        let mut state = serializer.serialize_struct("Bandwidth", 2)?;
+        match value {
+            Number::Integer(value) => state.serialize_field("value", &value)?,
+            Number::Float(value) => state.serialize_field("value", &value)?,
+            Number::Decimal(value) => state.serialize_field("value", &value)?,
+        }
+        state.serialize_field("unit", unit.name())?;
+        state.end()
+    }
+}
+
+impl ser::Serialize for Structured<Bandwidth> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Structured(&self.0).serialize(serializer)
+    }
+}
+
+impl ser::Serialize for Structured<&Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self.0 {
+            Some(dur) => serializer.serialize_some(&Structured(dur)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl ser::Serialize for Structured<Option<Bandwidth>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Structured(&self.0).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bandwidth: Bandwidth,
+        }
+
+        let json = r#"{"bandwidth": {"value": 1.5, "unit": "Mbit/s"}}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Bandwidth::from_kbps(1_500));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":{"value":1.5,"unit":"Mbps"}}"#);
+
+        let json = r#"{"bandwidth": {"unit": "Gbps", "value": 9420}}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Bandwidth::new(9420, 0));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":{"value":9.42,"unit":"Tbps"}}"#);
+
+        let json = r#"{"bandwidth": {"value": 0, "unit": "kbps"}}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Bandwidth::new(0, 0));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":{"value":0,"unit":"bps"}}"#);
+    }
+
+    #[test]
+    fn with_option() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super", default)]
+            bandwidth: Option<Bandwidth>,
+        }
+
+        let json = r#"{"bandwidth": {"value": 15, "unit": "Mbps"}}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Some(Bandwidth::from_mbps(15)));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":{"value":15,"unit":"Mbps"}}"#);
+
+        let json = r#"{"bandwidth": null}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":null}"#);
+
+        let json = r#"{}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, None);
+    }
+
+    #[test]
+    fn invalid() {
+        #[derive(Debug, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            #[allow(dead_code)]
+            bandwidth: Bandwidth,
+        }
+
+        assert!(serde_json::from_str::<Foo>(r#"{"bandwidth": {"value": 1}}"#).is_err());
+        assert!(
+            serde_json::from_str::<Foo>(r#"{"bandwidth": {"value": -1, "unit": "bps"}}"#).is_err()
+        );
+        assert!(
+            serde_json::from_str::<Foo>(r#"{"bandwidth": {"value": 1, "unit": "MiB/s"}}"#).is_err()
+        );
+        assert!(serde_json::from_str::<Foo>(r#"{"bandwidth": "1Mbps"}"#).is_err());
+        assert!(
+            serde_json::from_str::<Foo>(r#"{"bandwidth": {"value": "1e3", "unit": "bps"}}"#)
+                .is_err()
+        );
+
+        // The unit is not parsed together with the value
+        for unit in ["0Mbps", "Mbps 5Gbps", "e3Mbps", " Mbps", "Mbps ", "10meg"] {
+            let json = format!(r#"{{"bandwidth": {{"value": 1, "unit": {:?}}}}}"#, unit);
+            let err = serde_json::from_str::<Foo>(&json).unwrap_err();
+            assert!(
+                err.to_string().starts_with("unknown bandwidth unit"),
+                "{}",
+                err
+            );
+        }
+        let err = serde_json::from_str::<Foo>(
+            r#"{"bandwidth": {"value": "18446744073709551616", "unit": "Ebps"}}"#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().starts_with("number is too large"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        #[derive(Serialize, Deserialize)]
+        struct Foo {
+            #[serde(with = "super")]
+            bandwidth: Bandwidth,
+        }
+
+        // No number holds it exactly, so the value is written as a string
+        let json = serde_json::to_string(&Foo {
+            bandwidth: Bandwidth::MAX,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"bandwidth":{"value":"18446744073.709551615999999999","unit":"Ebps"}}"#
+        );
+        let foo = serde_json::from_str::<Foo>(&json).unwrap();
+        assert_eq!(foo.bandwidth, Bandwidth::MAX);
+
+        // 18 significant digits are too many for a float, but not for an integer
+        let val = Bandwidth::new(123_456_789, 123_456_789);
+        let json = serde_json::to_string(&Foo { bandwidth: val }).unwrap();
+        assert_eq!(
+            json,
+            r#"{"bandwidth":{"value":123456789123456789,"unit":"bps"}}"#
+        );
+        let foo = serde_json::from_str::<Foo>(&json).unwrap();
+        assert_eq!(foo.bandwidth, val);
+
+        for val in [
+            Bandwidth::new(u64::MAX, 0),
+            Bandwidth::new(0, 1),
+            Bandwidth::new(1_000_000_000, 1),
+            Bandwidth::new(12_345_678_901, 500_000_000),
+        ] {
+            let json = serde_json::to_string(&Foo { bandwidth: val }).unwrap();
+            let foo = serde_json::from_str::<Foo>(&json).unwrap();
+            assert_eq!(foo.bandwidth, val, "{}", json);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Signed bandwidths, e.g. `-1.5Mbps`
+//!
+//! A [`SignedBandwidth`] represents the change between two measurements, e.g.
+//! in monitoring diffs. It is written with an explicit sign, and parsed with
+//! any of the parsers of the crate.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::signed::SignedBandwidth;
+//!
+//! let delta = SignedBandwidth::delta(Bandwidth::from_mbps(10), Bandwidth::from_mbps(8));
+//! assert_eq!(delta.to_string(), "-2Mbps");
+//! assert_eq!("-2Mbps".parse(), Ok(delta));
+//! assert_eq!("-1.5Mbps".parse(), Ok(SignedBandwidth::new(true, Bandwidth::from_kbps(1_500))));
+//! assert_eq!(delta.checked_add_to(Bandwidth::from_mbps(5)), Some(Bandwidth::from_mbps(3)));
+//! ```
+
+use core::{cmp::Ordering, fmt, str::FromStr};
+
+use bandwidth::Bandwidth;
+
+use crate::{
+    bandwidth_as_bps, bandwidth_from_bps, format_bandwidth, offset_error, parse_bandwidth, Error,
+};
+
+/// A bandwidth with a sign, e.g. a difference between two bandwidths
+///
+/// Zero is neither positive nor negative: it is written `0bps`, and `-0bps`
+/// is read as zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SignedBandwidth {
+    negative: bool,
+    magnitude: Bandwidth,
+}
+
+impl SignedBandwidth {
+    /// Create a bandwidth of the given magnitude, negative if `negative` is
+    /// set and the magnitude is not zero
+    pub fn new(negative: bool, magnitude: Bandwidth) -> Self {
+        SignedBandwidth {
+            negative: negative && magnitude != Bandwidth::new(0, 0),
+            magnitude,
+        }
+    }
+
+    /// The change from `old` to `new`, negative if the bandwidth decreased
+    pub fn delta(old: Bandwidth, new: Bandwidth) -> Self {
+        let (old, new) = (bandwidth_as_bps(old), bandwidth_as_bps(new));
+        // The difference of two bandwidths always fits in a bandwidth
+        let magnitude = bandwidth_from_bps(old.abs_diff(new)).unwrap_or(Bandwidth::MAX);
+        SignedBandwidth::new(new < old, magnitude)
+    }
+
+    /// Parses a sign followed by a bandwidth read with `parse`, e.g.
+    /// [`parse_binary_bandwidth`](crate::binary_system::parse_binary_bandwidth)
+    /// or [`ParseOptions::parse`](crate::ParseOptions::parse)
+    ///
+    /// The sign is optional, and may be preceded by whitespace. The offsets of
+    /// the errors are in `s`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{signed::SignedBandwidth, Error, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().iso80000(true);
+    /// let delta = SignedBandwidth::parse_with("-250 kbit/s", |s| options.parse(s));
+    /// assert_eq!(delta, Ok(SignedBandwidth::new(true, Bandwidth::from_kbps(250))));
+    /// let delta = SignedBandwidth::parse_with("+250kbit/s", |s| options.parse(s));
+    /// assert_eq!(delta, Err(Error::SpaceExpected(4)));
+    /// ```
+    pub fn parse_with<F>(s: &str, parse: F) -> Result<Self, Error>
+    where
+        F: FnOnce(&str) -> Result<Bandwidth, Error>,
+    {
+        let trimmed = s.trim_start();
+        let start = s.len() - trimmed.len();
+        let negative = match trimmed.chars().next() {
+            Some('-') => true,
+            Some('+') => false,
+            _ => return parse(s).map(|magnitude| SignedBandwidth::new(false, magnitude)),
+        };
+        let rest = &trimmed[1..];
+        // A sign alone is not a bandwidth
+        if rest.trim().is_empty() {
+            return Err(Error::NumberExpected(start + 1));
+        }
+        let magnitude = parse(rest).map_err(|error| offset_error(error, start + 1))?;
+        Ok(SignedBandwidth::new(negative, magnitude))
+    }
+
+    /// Whether the bandwidth is below zero
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The bandwidth without its sign
+    pub fn magnitude(&self) -> Bandwidth {
+        self.magnitude
+    }
+
+    /// Applies the change to `base`, returning `None` if the result is
+    /// negative or exceeds [`Bandwidth::MAX`]
+    pub fn checked_add_to(&self, base: Bandwidth) -> Option<Bandwidth> {
+        let (base, magnitude) = (bandwidth_as_bps(base), bandwidth_as_bps(self.magnitude));
+        let bps = if self.negative {
+            base.checked_sub(magnitude)?
+        } else {
+            base + magnitude
+        };
+        bandwidth_from_bps(bps).ok()
+    }
+}
+
+impl From<Bandwidth> for SignedBandwidth {
+    fn from(magnitude: Bandwidth) -> Self {
+        SignedBandwidth::new(false, magnitude)
+    }
+}
+
+impl Ord for SignedBandwidth {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (negative, _) => other.negative.cmp(&negative),
+        }
+    }
+}
+
+impl PartialOrd for SignedBandwidth {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Writes the sign, `+` or `-`, followed by the magnitude like
+/// [`format_bandwidth`], or `0bps` for zero
+impl fmt::Display for SignedBandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.magnitude == Bandwidth::new(0, 0) {
+            return f.write_str("0bps");
+        }
+        let sign = if self.negative { '-' } else { '+' };
+        write!(f, "{}{}", sign, format_bandwidth(self.magnitude))
+    }
+}
+
+/// Parses a sign followed by a bandwidth, see [`parse_bandwidth`]
+impl FromStr for SignedBandwidth {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SignedBandwidth::parse_with(s, parse_bandwidth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let delta =
+            |old, new| SignedBandwidth::delta(Bandwidth::from_bps(old), Bandwidth::from_bps(new));
+        assert_eq!(delta(0, 0).to_string(), "0bps");
+        assert_eq!(delta(5, 5).to_string(), "0bps");
+        assert_eq!(delta(1_000, 3_000).to_string(), "+2kbps");
+        assert_eq!(delta(3_000, 1_000).to_string(), "-2kbps");
+        assert_eq!(
+            SignedBandwidth::delta(Bandwidth::new(0, 0), Bandwidth::MAX).magnitude(),
+            Bandwidth::MAX
+        );
+        assert!(!SignedBandwidth::new(true, Bandwidth::new(0, 0)).is_negative());
+    }
+
+    #[test]
+    fn test_parse() {
+        let signed = |negative, bps| Ok(SignedBandwidth::new(negative, Bandwidth::from_bps(bps)));
+        assert_eq!("-1.5kbps".parse(), signed(true, 1_500));
+        assert_eq!(" +2bps".parse(), signed(false, 2));
+        assert_eq!("3bps 1bps".parse(), signed(false, 4));
+        assert_eq!("-0bps".parse(), signed(false, 0));
+        assert_eq!("- 2bps".parse(), signed(true, 2));
+        for value in [-7_000i64, -1, 0, 1, 12_345_678] {
+            let val = SignedBandwidth::new(value < 0, Bandwidth::from_bps(value.unsigned_abs()));
+            assert_eq!(val.to_string().parse(), Ok(val));
+        }
+
+        assert_eq!(
+            "-".parse::<SignedBandwidth>(),
+            Err(Error::NumberExpected(1))
+        );
+        assert_eq!(
+            "  + ".parse::<SignedBandwidth>(),
+            Err(Error::NumberExpected(3))
+        );
+        assert_eq!("".parse::<SignedBandwidth>(), Err(Error::Empty));
+        assert_eq!(
+            "--1bps".parse::<SignedBandwidth>(),
+            Err(Error::NumberExpected(1))
+        );
+        assert_eq!(
+            " -1x".parse::<SignedBandwidth>().unwrap_err(),
+            Error::UnknownUnit {
+                start: 3,
+                end: 4,
+                unit: "x".to_string(),
+                value: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_order() {
+        let signed =
+            |value: i64| SignedBandwidth::new(value < 0, Bandwidth::from_bps(value.unsigned_abs()));
+        let mut values: Vec<_> = [3, -1, 0, -5, 2].iter().map(|v| signed(*v)).collect();
+        values.sort();
+        assert_eq!(values, [-5, -1, 0, 2, 3].map(signed));
+        assert_eq!(signed(-3).checked_add_to(Bandwidth::from_bps(2)), None);
+        assert_eq!(signed(3).checked_add_to(Bandwidth::MAX), None);
+        assert_eq!(
+            signed(3).checked_add_to(Bandwidth::from_bps(2)),
+            Some(Bandwidth::from_bps(5))
+        );
+        assert_eq!(SignedBandwidth::from(Bandwidth::from_bps(2)), signed(2));
+    }
+}
@@ -0,0 +1,103 @@
+//! Amounts of data
+//!
+//! [`DataSize`] is a number of Bytes, for the computations combining a
+//! bandwidth and an amount of data, e.g. [`calc::chunk_size_for`]. It is
+//! written with binary prefixes, like `1.5MiB`.
+//!
+//! [`calc::chunk_size_for`]: crate::calc::chunk_size_for
+//!
+//! # Example
+//!
+//! ```
+//! use human_bandwidth::size::DataSize;
+//!
+//! assert_eq!(DataSize::from_kib(1_536).to_string(), "1.5MiB");
+//! assert_eq!(DataSize::from_bytes(100).to_string(), "100B");
+//! ```
+
+use core::fmt;
+
+use crate::consts::{GIBI, KIBI, MEBI, TEBI};
+
+/// A number of Bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DataSize(u64);
+
+impl DataSize {
+    /// Create a size of `bytes` Bytes
+    pub const fn from_bytes(bytes: u64) -> Self {
+        DataSize(bytes)
+    }
+
+    /// Create a size of `kib` kibibytes, saturating at `u64::MAX` Bytes
+    pub const fn from_kib(kib: u64) -> Self {
+        DataSize(kib.saturating_mul(KIBI))
+    }
+
+    /// Create a size of `mib` mebibytes, saturating at `u64::MAX` Bytes
+    pub const fn from_mib(mib: u64) -> Self {
+        DataSize(mib.saturating_mul(MEBI))
+    }
+
+    /// Create a size of `gib` gibibytes, saturating at `u64::MAX` Bytes
+    pub const fn from_gib(gib: u64) -> Self {
+        DataSize(gib.saturating_mul(GIBI))
+    }
+
+    /// The number of Bytes
+    pub const fn bytes(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for DataSize {
+    fn from(bytes: u64) -> Self {
+        DataSize(bytes)
+    }
+}
+
+impl From<DataSize> for u64 {
+    fn from(size: DataSize) -> u64 {
+        size.0
+    }
+}
+
+/// Writes the size in the largest binary unit in which it is at least 1,
+/// rounded to one decimal
+impl fmt::Display for DataSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (scale, unit) = [(TEBI, "TiB"), (GIBI, "GiB"), (MEBI, "MiB"), (KIBI, "kiB")]
+            .into_iter()
+            .find(|(scale, _)| self.0 >= *scale)
+            .unwrap_or((1, "B"));
+        if self.0 % scale == 0 {
+            write!(f, "{}{}", self.0 / scale, unit)
+        } else {
+            let tenths = (u128::from(self.0) * 10 + u128::from(scale) / 2) / u128::from(scale);
+            match tenths % 10 {
+                0 => write!(f, "{}{}", tenths / 10, unit),
+                tenth => write!(f, "{}.{}{}", tenths / 10, tenth, unit),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let display = |bytes| DataSize::from_bytes(bytes).to_string();
+        assert_eq!(display(0), "0B");
+        assert_eq!(display(1_023), "1023B");
+        assert_eq!(display(1_024), "1kiB");
+        assert_eq!(display(1_100), "1.1kiB");
+        // Rounded to one decimal, without a trailing zero
+        assert_eq!(display(1_025), "1kiB");
+        assert_eq!(display(3 * MEBI / 4 * 1_024), "768MiB");
+        assert_eq!(display(5 * TEBI), "5TiB");
+        assert_eq!(display(u64::MAX), "16777216TiB");
+        assert_eq!(DataSize::from_gib(u64::MAX), DataSize::from_bytes(u64::MAX));
+    }
+}
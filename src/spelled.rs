@@ -0,0 +1,223 @@
+//! Units spelled out in words, like `megabits per second` or `mebibytes/s`,
+//! which the parsers read as their symbols
+
+use core::{fmt, ops::Deref};
+
+/// The prefixes spelled out, and their symbols
+const PREFIXES: [(&str, &str); 12] = [
+    ("kilo", "k"),
+    ("mega", "M"),
+    ("giga", "G"),
+    ("tera", "T"),
+    ("peta", "P"),
+    ("exa", "E"),
+    ("kibi", "Ki"),
+    ("mebi", "Mi"),
+    ("gibi", "Gi"),
+    ("tebi", "Ti"),
+    ("pebi", "Pi"),
+    ("exbi", "Ei"),
+];
+
+/// The intervals spelled out or abbreviated, and the suffixes of their units
+const INTERVALS: [(&str, &str); 10] = [
+    ("s", "/s"),
+    ("sec", "/s"),
+    ("second", "/s"),
+    ("min", "/min"),
+    ("minute", "/min"),
+    ("h", "/h"),
+    ("hour", "/h"),
+    ("d", "/d"),
+    ("day", "/d"),
+    ("month", "/month"),
+];
+
+/// A unit symbol built from its parts without allocating, e.g. `Mbit/s` from
+/// `M`, `bit` and `/s`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct UnitBuf {
+    bytes: [u8; UnitBuf::CAPACITY],
+    len: usize,
+}
+
+impl UnitBuf {
+    /// Longer than any symbol of the crate, e.g. `EiByte/month`
+    const CAPACITY: usize = 16;
+
+    /// The concatenation of `parts`, if it fits
+    pub(crate) fn concat(parts: &[&str]) -> Option<Self> {
+        let mut buf = UnitBuf {
+            bytes: [0; UnitBuf::CAPACITY],
+            len: 0,
+        };
+        for part in parts {
+            let end = buf.len + part.len();
+            buf.bytes
+                .get_mut(buf.len..end)?
+                .copy_from_slice(part.as_bytes());
+            buf.len = end;
+        }
+        Some(buf)
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // Only whole strings are copied, so this never fails
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Deref for UnitBuf {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for UnitBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// The symbol of a unit spelled out, e.g. `Mbit/s` for `megabits per second`
+/// or `MiByte/s` for `Mebibytes/s`, in any case and in the singular or the
+/// plural
+///
+/// The Bytes are written `Byte` rather than `B`, which may be read as bits
+/// (see [`ParseOptions::bit_byte_policy`](crate::ParseOptions::bit_byte_policy)),
+/// except in the ISO/IEC 80000-13 mode. A unit with a slash is only spelled
+/// out with a prefix or in the plural, `byte/s` being a misspelled symbol.
+pub(crate) fn spelled_unit(unit: &str, iso80000: bool) -> Option<UnitBuf> {
+    let (quantity, interval) = match unit.split_once('/') {
+        Some((quantity, interval)) => {
+            if split_prefix(quantity).0.is_empty() && !quantity.ends_with(['s', 'S']) {
+                return None;
+            }
+            (quantity, interval)
+        }
+        None => {
+            let mut words = unit.split_whitespace();
+            match (words.next(), words.next(), words.next(), words.next()) {
+                (Some(quantity), Some(per), Some(interval), None)
+                    if per.eq_ignore_ascii_case("per") =>
+                {
+                    (quantity, interval)
+                }
+                _ => return None,
+            }
+        }
+    };
+    let (prefix, quantity) = split_prefix(quantity);
+    let quantity = match quantity_name(quantity)? {
+        "bit" => "bit",
+        _ if iso80000 => "B",
+        _ => "Byte",
+    };
+    let (_, suffix) = INTERVALS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(interval))?;
+    UnitBuf::concat(&[prefix, quantity, suffix])
+}
+
+/// The length of the `per second` and of the whitespace around `per`
+/// starting `rest`, when they follow a quantity spelled out like `megabits`,
+/// so that `10 megabits per second` is read as a single rate span
+pub(crate) fn per_interval_len(quantity: &str, rest: &str) -> Option<usize> {
+    quantity_name(split_prefix(quantity).1)?;
+    let per = rest.trim_start();
+    let after_per = per
+        .get(..3)
+        .filter(|word| per.len() < rest.len() && word.eq_ignore_ascii_case("per"))
+        .map(|word| &per[word.len()..])?;
+    let interval = after_per.trim_start();
+    let len = interval
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(interval.len());
+    let known = INTERVALS
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(&interval[..len]));
+    if !known || interval.len() == after_per.len() {
+        return None;
+    }
+    Some(rest.len() - interval.len() + len)
+}
+
+/// Splits the symbol of the prefix spelled out at the start of `word`
+fn split_prefix(word: &str) -> (&'static str, &str) {
+    PREFIXES
+        .iter()
+        .find_map(|(name, symbol)| {
+            let prefix = word.get(..name.len())?;
+            prefix
+                .eq_ignore_ascii_case(name)
+                .then(|| (*symbol, &word[name.len()..]))
+        })
+        .unwrap_or(("", word))
+}
+
+/// `bit` or `byte` for these quantities in the singular or the plural
+fn quantity_name(quantity: &str) -> Option<&'static str> {
+    let singular = quantity.strip_suffix(['s', 'S']).unwrap_or(quantity);
+    ["bit", "byte"]
+        .iter()
+        .copied()
+        .find(|name| singular.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spelled_unit() {
+        let unit = |unit| spelled_unit(unit, false);
+        assert_eq!(unit("megabits per second").as_deref(), Some("Mbit/s"));
+        assert_eq!(unit("Megabit  PER\tsec").as_deref(), Some("Mbit/s"));
+        assert_eq!(unit("mebibytes/s").as_deref(), Some("MiByte/s"));
+        assert_eq!(unit("KILOBYTES/second").as_deref(), Some("kByte/s"));
+        assert_eq!(unit("bits/s").as_deref(), Some("bit/s"));
+        assert_eq!(unit("gibibytes per hour").as_deref(), Some("GiByte/h"));
+        assert_eq!(unit("terabytes per month").as_deref(), Some("TByte/month"));
+        assert_eq!(unit("exbibit/day").as_deref(), Some("Eibit/d"));
+        assert_eq!(spelled_unit("megabytes/s", true).as_deref(), Some("MB/s"));
+        assert_eq!(unit("exbibytes per month").as_deref(), Some("EiByte/month"));
+        for unit in [
+            "Mbps",
+            "Mbit/s",
+            "megabits",
+            "megabits per",
+            "byte/s",
+            "BIT/S",
+            "megabits per second per day",
+            "megabits each second",
+            "megabitss/s",
+            "megas/s",
+            "kilobits/fortnight",
+            "",
+        ] {
+            assert_eq!(spelled_unit(unit, false), None, "{}", unit);
+        }
+    }
+
+    #[test]
+    fn test_unit_buf() {
+        let buf = UnitBuf::concat(&["Ki", "Byte", "/month"]).unwrap();
+        assert_eq!(buf.as_str(), "KiByte/month");
+        assert_eq!(UnitBuf::concat(&[]).as_deref(), Some(""));
+        assert_eq!(UnitBuf::concat(&["kilobytes", "/month", "ly"]), None);
+    }
+
+    #[test]
+    fn test_per_interval_len() {
+        assert_eq!(per_interval_len("megabits", " per second"), Some(11));
+        assert_eq!(per_interval_len("Bytes", "\tPer  Hour, then"), Some(10));
+        assert_eq!(per_interval_len("megabits", " per second2"), Some(11));
+        assert_eq!(per_interval_len("megabits", " per port"), None);
+        assert_eq!(per_interval_len("megabits", " persecond"), None);
+        assert_eq!(per_interval_len("megabits", " per"), None);
+        assert_eq!(per_interval_len("Mbps", " per second"), None);
+        assert_eq!(per_interval_len("megabits", " é"), None);
+    }
+}
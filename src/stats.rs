@@ -0,0 +1,470 @@
+//! Statistics of measured bandwidths
+//!
+//! Benchmarks report a throughput as a mean and a standard deviation, e.g.
+//! `94.3 ± 2.1 Mbit/s`. [`format_with_variation`] writes both numbers in one
+//! unit, with the precision given by the deviation, and
+//! [`parse_with_variation`] reads them back.
+//!
+//! [`compare`] tells whether two such results differ by more than their noise,
+//! e.g. for a performance gate in continuous integration.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::stats::{format_with_variation, parse_with_variation};
+//!
+//! let mean = Bandwidth::from_kbps(94_312);
+//! let stddev = Bandwidth::from_kbps(2_087);
+//! let report = format_with_variation(mean, stddev);
+//! assert_eq!(report, "94.3 ± 2.1 Mbit/s");
+//! assert_eq!(
+//!     parse_with_variation(&report),
+//!     Ok((Bandwidth::from_kbps(94_300), Bandwidth::from_kbps(2_100)))
+//! );
+//! ```
+//!
+//! Comparing the throughput of two builds:
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::stats::{compare, Summary};
+//!
+//! let before = Summary::new(Bandwidth::from_mbps(100), Bandwidth::from_mbps(4), 10);
+//! let after = Summary::new(Bandwidth::from_mbps(112), Bandwidth::from_mbps(5), 10);
+//! let comparison = compare(before, after);
+//! assert!(comparison.significant);
+//! assert_eq!(comparison.to_string(), "B is 12% faster (outside noise)");
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{cmp::Ordering, fmt};
+
+use bandwidth::Bandwidth;
+
+use crate::{
+    bandwidth_as_bps, bandwidth_from_bps, fmt_scaled, iso_unit_name, offset_error, parse_bandwidth,
+    rounded_bps, si_exponent_for, Error,
+};
+
+/// Formats a mean and its standard deviation like `94.3 ± 2.1 Mbit/s`
+///
+/// Both numbers share the unit of the larger one. The deviation is rounded to
+/// two significant digits and the mean to the same decimal place, but never
+/// below 1bps. Without deviation, the mean is written exactly.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::stats::format_with_variation;
+///
+/// assert_eq!(
+///     format_with_variation(Bandwidth::from_gbps(9), Bandwidth::from_mbps(120)),
+///     "9.00 ± 0.12 Gbit/s"
+/// );
+/// assert_eq!(
+///     format_with_variation(Bandwidth::from_kbps(1_500), Bandwidth::new(0, 0)),
+///     "1.5 ± 0.0 Mbit/s"
+/// );
+/// ```
+pub fn format_with_variation(mean: Bandwidth, stddev: Bandwidth) -> String {
+    let (mean, stddev) = (bandwidth_as_bps(mean), bandwidth_as_bps(stddev));
+    let largest = mean.max(stddev);
+    let mut exponent = si_exponent_for(largest);
+    let mut precision = variation_precision(mean, stddev, exponent);
+    // Rounding may carry the larger value to the next unit, e.g. 999.999kbit/s
+    // written 1.000 Mbit/s rather than 1000 kbit/s
+    let rounded = rounded_bps(largest, 10u128.pow(exponent), precision);
+    if let Some(next) = rounded.map(si_exponent_for).filter(|next| *next > exponent) {
+        exponent = next;
+        precision = variation_precision(mean, stddev, exponent);
+    }
+    format!(
+        "{} ± {} {}",
+        fmt_scaled(mean, exponent, precision),
+        fmt_scaled(stddev, exponent, precision),
+        iso_unit_name(exponent)
+    )
+}
+
+/// Decimals written in the unit of the exponent of ten `exponent`: as many as
+/// the mean needs without deviation, or two significant digits of the
+/// deviation
+fn variation_precision(mean: u128, stddev: u128, exponent: u32) -> usize {
+    if stddev == 0 {
+        let mut precision = exponent;
+        while precision > 0 && mean % 10u128.pow(exponent - precision + 1) == 0 {
+            precision -= 1;
+        }
+        precision as usize
+    } else {
+        let digits = stddev.to_string().len() as u32;
+        (exponent + 2).saturating_sub(digits).min(exponent) as usize
+    }
+}
+
+/// Parses a mean and its standard deviation like `94.3 ± 2.1 Mbit/s`
+///
+/// The deviation follows `±` or `+-`. Each number can have its own unit, like
+/// `1Gbps ± 20Mbps`, otherwise the mean has the unit of the deviation. Without
+/// deviation, e.g. `1Gbps`, the deviation is zero. See [`parse_bandwidth`]
+/// for the supported units.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::stats::parse_with_variation;
+///
+/// assert_eq!(
+///     parse_with_variation("1Gbps +- 20Mbps"),
+///     Ok((Bandwidth::from_gbps(1), Bandwidth::from_mbps(20)))
+/// );
+/// assert_eq!(
+///     parse_with_variation("120 ± 5 kbps"),
+///     Ok((Bandwidth::from_kbps(120), Bandwidth::from_kbps(5)))
+/// );
+/// assert_eq!(
+///     parse_with_variation("1Gbps"),
+///     Ok((Bandwidth::from_gbps(1), Bandwidth::new(0, 0)))
+/// );
+/// ```
+pub fn parse_with_variation(s: &str) -> Result<(Bandwidth, Bandwidth), Error> {
+    let (mean, stddev) = match ["±", "+-"]
+        .into_iter()
+        .find_map(|separator| s.split_once(separator).map(|parts| (separator, parts)))
+    {
+        Some((separator, (mean, stddev))) => {
+            let start = mean.len() + separator.len();
+            (mean, Some((start, stddev)))
+        }
+        None => (s, None),
+    };
+    let stddev = match stddev {
+        Some((start, stddev)) => {
+            let bw = parse_bandwidth(stddev).map_err(|error| offset_error(error, start))?;
+            let unit = stddev.trim_end();
+            let unit = &unit[unit
+                .rfind(|c: char| c.is_ascii_digit())
+                .map_or(0, |i| i + 1)..];
+            Some((bw, unit.trim_start()))
+        }
+        None => None,
+    };
+    let mean = match stddev {
+        Some((_, unit)) if mean.trim_end().ends_with(|c: char| c.is_ascii_digit()) => {
+            parse_bandwidth(&format!("{} {}", mean.trim_end(), unit))?
+        }
+        _ => parse_bandwidth(mean)?,
+    };
+    Ok((mean, stddev.map_or(Bandwidth::new(0, 0), |(bw, _)| bw)))
+}
+
+/// The mean and the standard deviation of measured bandwidths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Summary {
+    /// The mean of the samples
+    pub mean: Bandwidth,
+    /// The sample standard deviation
+    pub stddev: Bandwidth,
+    /// The number of samples
+    pub samples: usize,
+}
+
+impl Summary {
+    /// Create the summary of `samples` measurements
+    pub fn new(mean: Bandwidth, stddev: Bandwidth, samples: usize) -> Self {
+        Summary {
+            mean,
+            stddev,
+            samples,
+        }
+    }
+
+    /// Summarizes measured bandwidths, or returns `None` without any
+    ///
+    /// The mean and the standard deviation, with Bessel's correction, are
+    /// rounded to the nearest bps. A single sample has no deviation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::stats::Summary;
+    ///
+    /// let samples = [2, 4, 4, 4, 5, 5, 7, 9].map(Bandwidth::from_mbps);
+    /// let summary = Summary::from_samples(&samples).unwrap();
+    /// assert_eq!(summary.mean, Bandwidth::from_mbps(5));
+    /// assert_eq!(summary.stddev, Bandwidth::from_bps(2_138_090));
+    /// assert_eq!(summary.to_string(), "5.0 ± 2.1 Mbit/s");
+    /// ```
+    pub fn from_samples(samples: &[Bandwidth]) -> Option<Self> {
+        let n = samples.len() as u128;
+        let sum: u128 = samples.iter().copied().map(bandwidth_as_bps).sum();
+        let mean = (sum + n / 2).checked_div(n)?;
+        let variance = match samples.len() {
+            1 => 0.0,
+            len => {
+                let squares: f64 = samples
+                    .iter()
+                    .map(|bw| (bandwidth_as_bps(*bw) as f64 - mean as f64).powi(2))
+                    .sum();
+                squares / (len - 1) as f64
+            }
+        };
+        Some(Summary {
+            // The mean of bandwidths is a bandwidth
+            mean: bandwidth_from_bps(mean).unwrap_or(Bandwidth::MAX),
+            stddev: bandwidth_from_bps(variance.sqrt().round() as u128).unwrap_or(Bandwidth::MAX),
+            samples: samples.len(),
+        })
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&format_with_variation(self.mean, self.stddev))
+    }
+}
+
+/// The outcome of [`compare`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Comparison {
+    /// The baseline
+    pub a: Summary,
+    /// The candidate
+    pub b: Summary,
+    /// Whether the difference of the means is outside the noise, i.e. outside
+    /// the 95% confidence interval of the difference
+    pub significant: bool,
+}
+
+impl Comparison {
+    /// Whether the mean of `b` is greater, smaller or equal to the mean of `a`
+    pub fn direction(&self) -> Ordering {
+        self.b.mean.cmp(&self.a.mean)
+    }
+
+    /// The change of the mean of `b` relative to the mean of `a`, e.g. `0.12`
+    /// for 12% faster
+    ///
+    /// Returns `None` when the mean of `a` is zero.
+    pub fn relative(&self) -> Option<f64> {
+        let a = bandwidth_as_bps(self.a.mean);
+        if a == 0 {
+            return None;
+        }
+        let b = bandwidth_as_bps(self.b.mean);
+        Some((b as f64 - a as f64) / a as f64)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verdict = match self.direction() {
+            Ordering::Equal => "as fast as A",
+            Ordering::Greater => "faster",
+            Ordering::Less => "slower",
+        };
+        f.write_str("B is ")?;
+        if let (Some(relative), Ordering::Greater | Ordering::Less) =
+            (self.relative(), self.direction())
+        {
+            let percent = format!("{:.1}", relative.abs() * 100.0);
+            write!(f, "{}% ", percent.trim_end_matches(".0"))?;
+        }
+        let noise = if self.significant {
+            "outside noise"
+        } else {
+            "within noise"
+        };
+        write!(f, "{} ({})", verdict, noise)
+    }
+}
+
+/// Compares a candidate `b` to a baseline `a`
+///
+/// The difference of the means is significant when it is larger than 1.96
+/// times its standard error, i.e. outside the 95% confidence interval of
+/// Welch's t-test with a normal approximation. A summary without samples is
+/// never significantly different, and two summaries without deviation are as
+/// soon as their means differ.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::stats::{compare, Summary};
+///
+/// let a = Summary::new(Bandwidth::from_mbps(100), Bandwidth::from_mbps(10), 5);
+/// let b = Summary::new(Bandwidth::from_mbps(97), Bandwidth::from_mbps(10), 5);
+/// let comparison = compare(a, b);
+/// assert!(!comparison.significant);
+/// assert_eq!(comparison.to_string(), "B is 3% slower (within noise)");
+/// ```
+pub fn compare(a: Summary, b: Summary) -> Comparison {
+    let significant = if a.samples == 0 || b.samples == 0 {
+        false
+    } else {
+        let variance = |summary: Summary| {
+            (bandwidth_as_bps(summary.stddev) as f64).powi(2) / summary.samples as f64
+        };
+        let error = (variance(a) + variance(b)).sqrt();
+        let difference = bandwidth_as_bps(a.mean).abs_diff(bandwidth_as_bps(b.mean)) as f64;
+        difference > 1.96 * error
+    };
+    Comparison { a, b, significant }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_with_variation() {
+        let format = |mean, stddev| {
+            format_with_variation(Bandwidth::from_bps(mean), Bandwidth::from_bps(stddev))
+        };
+        assert_eq!(format(94_312_000, 2_087_000), "94.3 ± 2.1 Mbit/s");
+        assert_eq!(format(94_312_000, 12_000_000), "94 ± 12 Mbit/s");
+        // The deviation is at least 1bps
+        assert_eq!(format(94_312_000, 5), "94.312000 ± 0.000005 Mbit/s");
+        // The deviation can choose the unit
+        assert_eq!(format(900, 1_500_000), "0.0 ± 1.5 Mbit/s");
+        assert_eq!(format(999, 2_000), "1.0 ± 2.0 kbit/s");
+        // The unit is chosen after rounding
+        assert_eq!(format(999_999, 10_000), "1.000 ± 0.010 Mbit/s");
+        assert_eq!(format(999_400, 10_000), "999 ± 10 kbit/s");
+        assert_eq!(format(0, 0), "0 ± 0 bit/s");
+        assert_eq!(format(12_000, 0), "12 ± 0 kbit/s");
+        assert_eq!(format(12_340, 0), "12.34 ± 0.00 kbit/s");
+        assert_eq!(
+            format(1_200_000_000_000, 3_000_000_000),
+            "1.2000 ± 0.0030 Tbit/s"
+        );
+        // The mean is rounded down rather than above the largest bandwidth
+        let report = format_with_variation(Bandwidth::MAX, Bandwidth::from_bps(560_000));
+        assert_eq!(
+            report,
+            "18446744073.70955161599999 ± 0.00000000000056 Ebit/s"
+        );
+        assert_eq!(
+            parse_with_variation(&report),
+            Ok((
+                Bandwidth::new(u64::MAX, 999_990_000),
+                Bandwidth::from_bps(560_000)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_with_variation() {
+        let kbps = Bandwidth::from_kbps;
+        assert_eq!(
+            parse_with_variation("94.3 ± 2.1 Mbit/s"),
+            Ok((kbps(94_300), kbps(2_100)))
+        );
+        assert_eq!(
+            parse_with_variation("94.3±2.1Mbps"),
+            Ok((kbps(94_300), kbps(2_100)))
+        );
+        assert_eq!(
+            parse_with_variation("1Mbps+-2kbps"),
+            Ok((kbps(1_000), kbps(2)))
+        );
+        assert_eq!(parse_with_variation(" 12kbps "), Ok((kbps(12), kbps(0))));
+        assert_eq!(
+            parse_with_variation("1Gbps 5Mbps ± 1Mbps 500kbps"),
+            Ok((kbps(1_005_000), kbps(1_500)))
+        );
+        for (mean, stddev) in [(94_312_000, 2_087_000), (5, 1), (1, 1_000_000_000)] {
+            let mean = Bandwidth::from_bps(mean);
+            let stddev = Bandwidth::from_bps(stddev);
+            let report = format_with_variation(mean, stddev);
+            let (parsed_mean, parsed_stddev) = parse_with_variation(&report).unwrap();
+            assert_eq!(format_with_variation(parsed_mean, parsed_stddev), report);
+        }
+    }
+
+    #[test]
+    fn test_from_samples() {
+        assert_eq!(Summary::from_samples(&[]), None);
+        let one = Summary::from_samples(&[Bandwidth::from_kbps(3)]).unwrap();
+        assert_eq!(
+            one,
+            Summary::new(Bandwidth::from_kbps(3), Bandwidth::new(0, 0), 1)
+        );
+        let bps = |bps: &[u64]| {
+            let samples: Vec<_> = bps.iter().copied().map(Bandwidth::from_bps).collect();
+            Summary::from_samples(&samples).unwrap()
+        };
+        // The mean is rounded to the nearest
+        assert_eq!(bps(&[1, 2]).mean, Bandwidth::from_bps(2));
+        assert_eq!(bps(&[1, 2]).stddev, Bandwidth::from_bps(1));
+        assert_eq!(bps(&[1, 1, 2]).mean, Bandwidth::from_bps(1));
+        let max = Summary::from_samples(&[Bandwidth::MAX, Bandwidth::MAX]).unwrap();
+        assert_eq!(max.mean, Bandwidth::MAX);
+        assert_eq!(max.stddev, Bandwidth::new(0, 0));
+    }
+
+    #[test]
+    fn test_compare() {
+        let summary = |mbps, stddev, samples| {
+            Summary::new(
+                Bandwidth::from_mbps(mbps),
+                Bandwidth::from_mbps(stddev),
+                samples,
+            )
+        };
+        let base = summary(100, 10, 4);
+        // The standard error of the difference is 10Mbps / 2 * sqrt(2)
+        assert!(!compare(base, summary(113, 10, 4)).significant);
+        assert!(compare(base, summary(114, 10, 4)).significant);
+        assert!(compare(base, summary(86, 10, 4)).significant);
+        assert_eq!(
+            compare(base, summary(86, 10, 4)).to_string(),
+            "B is 14% slower (outside noise)"
+        );
+        assert_eq!(
+            compare(base, summary(100, 1, 100)).to_string(),
+            "B is as fast as A (within noise)"
+        );
+        assert_eq!(
+            compare(summary(300, 0, 1), summary(301, 0, 1)).to_string(),
+            "B is 0.3% faster (outside noise)"
+        );
+        assert!(!compare(summary(1, 0, 0), summary(100, 0, 1)).significant);
+
+        let zero = compare(summary(0, 0, 3), summary(5, 1, 3));
+        assert_eq!(zero.relative(), None);
+        assert_eq!(zero.direction(), Ordering::Greater);
+        assert_eq!(zero.to_string(), "B is faster (outside noise)");
+    }
+
+    #[test]
+    fn test_parse_with_variation_errors() {
+        assert_eq!(parse_with_variation(""), Err(Error::Empty));
+        assert_eq!(parse_with_variation("± 1Mbps"), Err(Error::Empty));
+        assert_eq!(
+            parse_with_variation("1Mbps ± x"),
+            Err(Error::NumberExpected(8))
+        );
+        assert_eq!(
+            parse_with_variation("1 ± 2 Mbpx").unwrap_err(),
+            Error::UnknownUnit {
+                start: 7,
+                end: 11,
+                unit: "Mbpx".to_string(),
+                value: 2,
+            }
+        );
+        assert_eq!(
+            parse_with_variation("1 ± 2").unwrap_err().to_string(),
+            "bandwidth unit needed, for example 2Mbps or 2bps"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Parsing bandwidths received in chunks
+//!
+//! A [`StreamParser`] is fed the pieces of a bandwidth as they arrive, e.g.
+//! from network reads or log lines, and only buffers the rate span being
+//! received: the complete spans are parsed and added up as soon as the next
+//! one starts. The result and the errors, whose offsets are in the whole
+//! stream, are the same as parsing the concatenated string at once.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::stream::StreamParser;
+//!
+//! let mut parser = StreamParser::new();
+//! for chunk in ["1Gb", "ps 25", "0Mbps 1", "2kbps"] {
+//!     parser.feed(chunk).unwrap();
+//! }
+//! assert_eq!(parser.finish(), Ok(Bandwidth::from_kbps(1_250_012)));
+//! ```
+
+use alloc::string::String;
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_from_bps, escapes::Decoded, offset_error, Error, ParseOptions, Parser};
+
+/// A push-based parser, see the [module documentation](self)
+///
+/// Once a chunk is invalid, the error is returned by every later call.
+#[derive(Debug, Clone)]
+pub struct StreamParser {
+    options: ParseOptions,
+    #[cfg(feature = "binary-system")]
+    binary: bool,
+    /// The text of the spans not parsed yet
+    pending: String,
+    /// Offset of `pending` in the stream
+    offset: usize,
+    /// Length of `pending` already searched for the start of a span
+    scanned: usize,
+    /// The bits per second of the parsed spans
+    total: u128,
+    /// The value of the unit of the last parsed span and the length of its
+    /// interval, see [`ParseOptions::strict_spans`]
+    last_unit: Option<(u128, u128)>,
+    /// Whether a value overflowed, see [`ParseOptions::saturating`]
+    saturated: bool,
+    error: Option<Error>,
+}
+
+impl Default for StreamParser {
+    fn default() -> Self {
+        StreamParser::new()
+    }
+}
+
+impl StreamParser {
+    /// Create a parser of the format of [`parse_bandwidth`](crate::parse_bandwidth)
+    pub fn new() -> Self {
+        StreamParser::with_options(ParseOptions::new())
+    }
+
+    /// Create a parser of the format of [`ParseOptions::parse`]
+    pub fn with_options(options: ParseOptions) -> Self {
+        StreamParser {
+            options,
+            #[cfg(feature = "binary-system")]
+            binary: false,
+            pending: String::new(),
+            offset: 0,
+            scanned: 0,
+            total: 0,
+            last_unit: None,
+            saturated: false,
+            error: None,
+        }
+    }
+
+    /// Create a parser of the format of
+    /// [`parse_binary_bandwidth`](crate::binary_system::parse_binary_bandwidth),
+    /// with the case of the prefixes and the unit aliases of `options`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{stream::StreamParser, ParseOptions};
+    ///
+    /// let mut parser = StreamParser::binary(ParseOptions::new());
+    /// parser.feed("1MiB/s 512").unwrap();
+    /// parser.feed("kiB/s").unwrap();
+    /// assert_eq!(parser.finish(), Ok(Bandwidth::from_bps(12 << 20)));
+    /// ```
+    #[cfg(feature = "binary-system")]
+    pub fn binary(options: ParseOptions) -> Self {
+        StreamParser {
+            binary: true,
+            ..StreamParser::with_options(options)
+        }
+    }
+
+    /// Parses the next chunk of the stream
+    ///
+    /// The spans completed by the chunk are parsed, and their errors reported
+    /// right away. A span is complete once the next one starts, so that the
+    /// last span is only parsed by [`finish`](Self::finish).
+    pub fn feed(&mut self, chunk: &str) -> Result<(), Error> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        self.pending.push_str(chunk);
+        if let Some(end) = self.complete_spans() {
+            let result = self.parse_pending(end);
+            self.pending.drain(..end);
+            self.offset += end;
+            result?;
+        }
+        self.scanned = self.pending.len();
+        Ok(())
+    }
+
+    /// Parses the last span and returns the sum of all of them
+    pub fn finish(mut self) -> Result<Bandwidth, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.parse_pending(self.pending.len())?;
+        match bandwidth_from_bps(self.total) {
+            Ok(val) if !self.saturated => Ok(val),
+            _ if self.options.is_saturating() => Ok(Bandwidth::MAX),
+            _ => Err(Error::NumberOverflow),
+        }
+    }
+
+    /// The end of the complete spans in `pending`, i.e. the start of the last
+    /// span following a unit, if any
+    ///
+    /// A digit after whitespace starts a span when the whitespace follows a
+    /// unit, which ends with a letter or is a custom one. Custom units and
+    /// aliases spanning whitespace, a single span, and the spans whose
+    /// whitespace must be single spaces, are only parsed at the end.
+    fn complete_spans(&self) -> Option<usize> {
+        let custom = self
+            .options
+            .get_custom_units()
+            .iter()
+            .map(|(alias, _)| *alias)
+            .chain(
+                self.options
+                    .get_unit_aliases()
+                    .iter()
+                    .map(|(alias, _)| *alias),
+            );
+        if !self.options.is_multiple_spans()
+            || self.options.is_single_spaces()
+            || custom
+                .clone()
+                .any(|alias| alias.contains(char::is_whitespace))
+        {
+            return None;
+        }
+        let ends_unit = |s: &str| {
+            s.ends_with(char::is_alphabetic)
+                || custom
+                    .clone()
+                    .any(|alias| !alias.is_empty() && s.ends_with(alias))
+        };
+        let start = self.scanned;
+        self.pending[start..]
+            .char_indices()
+            .rev()
+            .map(|(i, c)| (start + i, c))
+            .find(|(i, c)| {
+                let before = &self.pending[..*i];
+                c.is_ascii_digit()
+                    && before.ends_with(char::is_whitespace)
+                    && ends_unit(before.trim_end())
+            })
+            .map(|(i, _)| i)
+    }
+
+    /// Parses `pending[..end]`, adding its spans to the total
+    fn parse_pending(&mut self, end: usize) -> Result<(), Error> {
+        // An escape never spans the whitespace before a span
+        let decoded = Decoded::new(&self.pending[..end], &self.options);
+        let mut parser = Parser::with_options(decoded.as_str(), self.options);
+        parser.last_unit = self.last_unit;
+        #[cfg(feature = "binary-system")]
+        let parsed = if self.binary {
+            parser.parse_spans(Parser::parse_binary_unit)
+        } else {
+            parser.parse_spans(Parser::parse_unit)
+        };
+        #[cfg(not(feature = "binary-system"))]
+        let parsed = parser.parse_spans(Parser::parse_unit);
+        // The total is only converted at the end, as the whole string would be
+        let saturating = self.options.is_saturating();
+        let result = parsed
+            .map_err(|error| offset_error(decoded.error(error), self.offset))
+            .and_then(|_| match self.total.checked_add(parser.current) {
+                Some(total) => Ok((total, parser.saturated)),
+                None if saturating => Ok((self.total, true)),
+                None => Err(Error::NumberOverflow),
+            });
+        match result {
+            Ok((total, saturated)) => {
+                self.total = total;
+                self.saturated |= saturated;
+                self.last_unit = parser.last_unit;
+                Ok(())
+            }
+            Err(error) => {
+                self.error = Some(error.clone());
+                Err(error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_bandwidth;
+
+    /// Feeds `s` in chunks of `size` bytes, or of whole characters
+    fn feed(mut parser: StreamParser, s: &str, size: usize) -> Result<Bandwidth, Error> {
+        let mut rest = s;
+        while !rest.is_empty() {
+            let mut end = size.min(rest.len());
+            while !rest.is_char_boundary(end) {
+                end += 1;
+            }
+            parser.feed(&rest[..end])?;
+            rest = &rest[end..];
+        }
+        parser.finish()
+    }
+
+    #[test]
+    fn test_chunks() {
+        for s in [
+            "",
+            "   ",
+            "1bps",
+            "1Ebps 2Pbps 3Tbps 4Gbps 5Mbps 6kbps 7bps",
+            "1 000 000bps 2Mbps",
+            "10Mbps  1.5e3kbps\t7bps",
+            "10Mbps 20Mbps 1",
+            "10Mbps 20Mbpx 5kbps",
+            "10Mbps 20Mbps 5kbps éx",
+            "18446744073Gbps 709551616bps",
+            "10000000000000000000Ebps 1bps",
+            "10000000000000000000Ebps 1x",
+        ] {
+            for size in [1, 2, 3, 7, 100] {
+                assert_eq!(
+                    feed(StreamParser::new(), s, size),
+                    parse_bandwidth(s),
+                    "{:?} by {}",
+                    s,
+                    size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_options() {
+        const TELECOM: &[(&str, u64)] = &[("E1", 2_048_000), ("T 1", 1_544_000)];
+        for options in [
+            ParseOptions::new().iso80000(true),
+            ParseOptions::strict(),
+            ParseOptions::new().multiple_spans(false),
+            ParseOptions::new().custom_units(TELECOM),
+            ParseOptions::new().custom_units(&TELECOM[..1]),
+            ParseOptions::new().saturating(true),
+            ParseOptions::new().decode_escapes(true),
+        ] {
+            for s in [
+                "1 Mbit/s 2 kbit/s",
+                "1Mbps 2kbps",
+                "1kbps 2Mbps",
+                "1kbps 2kbps",
+                "2E1 3E1",
+                "1T 1 1E1",
+                "18446744073709551616bps 1x",
+                "18446744073709551615Gbps 1000000000bps",
+                "99999999999999999999Ebps 9999999999999999999Ebps 1bps",
+                "1Mbit%2Fs 2kbit&#47;s%203bps &nbsp;4bpx",
+            ] {
+                for size in [1, 4] {
+                    assert_eq!(
+                        feed(StreamParser::with_options(options), s, size),
+                        options.parse(s),
+                        "{:?} by {} with {:?}",
+                        s,
+                        size,
+                        options
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sticky_error() {
+        let mut parser = StreamParser::new();
+        assert_eq!(parser.feed("1Mbps 2x"), Ok(()));
+        assert_eq!(
+            parser.feed(" 3"),
+            Err(Error::UnknownUnit {
+                start: 7,
+                end: 8,
+                unit: "x".to_string(),
+                value: 2,
+            })
+        );
+        assert!(parser.feed("kbps").is_err());
+        assert!(parser.finish().is_err());
+    }
+}
@@ -0,0 +1,504 @@
+//! Pluggable systems of units
+//!
+//! A [`UnitSystem`] describes the units of a family of bandwidths: which unit
+//! symbols are read, how much they are worth and in which unit a value is
+//! written. The decimal system of [`parse_bandwidth`](crate::parse_bandwidth)
+//! is available as [`Decimal`] and, with the `binary-system` feature, the
+//! binary prefix system as [`Binary`]. Any other system can be defined and
+//! used with [`parse`], [`format()`] and the [`serde`] helpers.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::system::{format, parse, UnitSystem};
+//!
+//! /// Decimal prefixes of Bytes per second
+//! struct DecimalBytes;
+//!
+//! impl UnitSystem for DecimalBytes {
+//!     fn parse_unit(&self, unit: &str) -> Option<u128> {
+//!         self.units()
+//!             .iter()
+//!             .find(|(symbol, _)| *symbol == unit)
+//!             .map(|(_, bps)| *bps)
+//!     }
+//!
+//!     fn units(&self) -> &[(&'static str, u128)] {
+//!         &[("B/s", 8), ("kB/s", 8_000), ("MB/s", 8_000_000), ("GB/s", 8_000_000_000)]
+//!     }
+//! }
+//!
+//! assert_eq!(parse("1MB/s 500kB/s", &DecimalBytes), Ok(Bandwidth::from_mbps(12)));
+//! assert_eq!(format(Bandwidth::from_mbps(12), &DecimalBytes), "1.5MB/s");
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, saturating_value, si_unit, Error, Parser, Unit};
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+/// A family of units, e.g. decimal prefixes of bits or binary prefixes of
+/// Bytes per second
+///
+/// Like the built-in ones, the unit symbols must consist of ASCII letters and
+/// `/`, as a digit starts the next rate span.
+pub trait UnitSystem {
+    /// Value in bits per second of one `unit`, or `None` when the unit is
+    /// not part of the system
+    fn parse_unit(&self, unit: &str) -> Option<u128>;
+
+    /// The units used to format bandwidths, from the smallest to the largest,
+    /// as their symbol and their value in bits per second
+    ///
+    /// There must be at least one unit.
+    fn units(&self) -> &[(&'static str, u128)];
+
+    /// The error reported for the `unit` of `value`, at bytes `start..end` of
+    /// the parsed string, which is not part of the system
+    ///
+    /// By default, it is an [`Error::UnknownSystemUnit`] listing the symbols
+    /// of [`units`](UnitSystem::units).
+    fn unknown_unit(&self, start: usize, end: usize, unit: &str, value: u64) -> Error {
+        let symbols: Vec<_> = self.units().iter().map(|(symbol, _)| *symbol).collect();
+        Error::UnknownSystemUnit {
+            start,
+            end,
+            unit: unit.to_string(),
+            value,
+            supported: symbols.join(", "),
+        }
+    }
+
+    /// Value in bits per second of the rate span `n.fraction`, the fraction
+    /// having `fraction_cnt` digits, in a unit worth `multiplier` bps
+    ///
+    /// Returns `None` on overflow. By default, the fraction less than 1bps is
+    /// ignored.
+    fn span_bps(
+        &self,
+        n: u128,
+        fraction: u64,
+        fraction_cnt: u32,
+        multiplier: u128,
+    ) -> Option<u128> {
+        let int = n.checked_mul(multiplier)?;
+        let fraction = u128::from(fraction).checked_mul(multiplier)? / 10u128.pow(fraction_cnt);
+        int.checked_add(fraction)
+    }
+
+    /// Index in [`units`](UnitSystem::units) of the unit in which `bps` is
+    /// written
+    ///
+    /// By default, it is the largest unit in which the value is at least 1,
+    /// or the smallest unit.
+    fn unit_for(&self, bps: u128) -> usize {
+        self.units()
+            .iter()
+            .rposition(|(_, multiplier)| bps >= *multiplier)
+            .unwrap_or(0)
+    }
+
+    /// Maximum number of decimals written in the unit at `index` of
+    /// [`units`](UnitSystem::units)
+    ///
+    /// By default, it is the number of digits of the ratio between the unit
+    /// and the smallest one, minus one: values of the decimal system are exact,
+    /// e.g. up to 12 decimals for `Tbps`, and values of the binary system are
+    /// rounded to 3 decimals for `kiB/s`.
+    fn decimals(&self, index: usize) -> usize {
+        let units = self.units();
+        let mut ratio = units[index].1 / units[0].1.max(1);
+        let mut decimals = 0;
+        while ratio >= 10 {
+            ratio /= 10;
+            decimals += 1;
+        }
+        decimals
+    }
+
+    /// Parses a bandwidth written in the units of the system, see [`parse`]
+    ///
+    /// By default, the string is a sequence of rate spans, each being
+    /// converted with [`span_bps`](UnitSystem::span_bps). The built-in
+    /// systems are read by the parsers of the crate instead, e.g. with the
+    /// amounts per interval.
+    fn parse(&self, s: &str) -> Result<Bandwidth, Error> {
+        parse_spans(s, self)
+    }
+
+    /// Formats a bandwidth in the units of the system, see [`format()`]
+    ///
+    /// By default, the value is written in the unit selected by
+    /// [`unit_for`](UnitSystem::unit_for) with at most
+    /// [`decimals`](UnitSystem::decimals) decimals. The built-in systems are
+    /// written by the formatters of the crate instead.
+    fn format(&self, val: Bandwidth) -> String {
+        format_units(val, self)
+    }
+}
+
+impl<S: UnitSystem + ?Sized> UnitSystem for &S {
+    fn parse_unit(&self, unit: &str) -> Option<u128> {
+        (**self).parse_unit(unit)
+    }
+
+    fn units(&self) -> &[(&'static str, u128)] {
+        (**self).units()
+    }
+
+    fn unknown_unit(&self, start: usize, end: usize, unit: &str, value: u64) -> Error {
+        (**self).unknown_unit(start, end, unit, value)
+    }
+
+    fn span_bps(
+        &self,
+        n: u128,
+        fraction: u64,
+        fraction_cnt: u32,
+        multiplier: u128,
+    ) -> Option<u128> {
+        (**self).span_bps(n, fraction, fraction_cnt, multiplier)
+    }
+
+    fn unit_for(&self, bps: u128) -> usize {
+        (**self).unit_for(bps)
+    }
+
+    fn decimals(&self, index: usize) -> usize {
+        (**self).decimals(index)
+    }
+
+    fn parse(&self, s: &str) -> Result<Bandwidth, Error> {
+        (**self).parse(s)
+    }
+
+    fn format(&self, val: Bandwidth) -> String {
+        (**self).format(val)
+    }
+}
+
+/// The units of [`Decimal`], those of [`Unit::ALL`]
+const DECIMAL_UNITS: [(&str, u128); 7] = {
+    let mut units = [("", 0); 7];
+    let mut i = 0;
+    while i < units.len() {
+        let unit = Unit::ALL[i];
+        units[i] = (unit.name(), unit.bps() as u128);
+        i += 1;
+    }
+    units
+};
+
+/// The decimal system of [`parse_bandwidth`](crate::parse_bandwidth), e.g.
+/// `1.5Mbps`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Decimal;
+
+impl UnitSystem for Decimal {
+    fn parse_unit(&self, unit: &str) -> Option<u128> {
+        si_unit(unit).map(|exponent| 10u128.pow(exponent))
+    }
+
+    fn units(&self) -> &[(&'static str, u128)] {
+        &DECIMAL_UNITS
+    }
+
+    fn unknown_unit(&self, start: usize, end: usize, unit: &str, value: u64) -> Error {
+        Error::UnknownUnit {
+            start,
+            end,
+            unit: unit.to_string(),
+            value,
+        }
+    }
+
+    fn parse(&self, s: &str) -> Result<Bandwidth, Error> {
+        crate::parse_bandwidth(s)
+    }
+
+    fn format(&self, val: Bandwidth) -> String {
+        crate::DecimalBandwidth(val).to_string()
+    }
+}
+
+/// The units of [`Binary`], those of
+/// [`BinaryUnit::ALL`](crate::binary_system::BinaryUnit::ALL)
+#[cfg(feature = "binary-system")]
+const BINARY_UNITS: [(&str, u128); 7] = {
+    use crate::binary_system::BinaryUnit;
+
+    let mut units = [("", 0); 7];
+    let mut i = 0;
+    while i < units.len() {
+        let unit = BinaryUnit::ALL[i];
+        units[i] = (unit.name(), 8 * unit.bytes_per_second() as u128);
+        i += 1;
+    }
+    units
+};
+
+/// The binary prefix system of
+/// [`parse_binary_bandwidth`](crate::binary_system::parse_binary_bandwidth),
+/// e.g. `1.5MiB/s`
+#[cfg(feature = "binary-system")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Binary;
+
+#[cfg(feature = "binary-system")]
+impl UnitSystem for Binary {
+    fn parse_unit(&self, unit: &str) -> Option<u128> {
+        crate::binary_system::binary_unit(unit).map(|power| 8 << (10 * power))
+    }
+
+    fn units(&self) -> &[(&'static str, u128)] {
+        &BINARY_UNITS
+    }
+
+    fn unknown_unit(&self, start: usize, end: usize, unit: &str, value: u64) -> Error {
+        Error::UnknownBinaryUnit {
+            start,
+            end,
+            unit: unit.to_string(),
+            value,
+        }
+    }
+
+    /// The fraction is rounded to the nearest Byte, with ties away from zero
+    fn span_bps(
+        &self,
+        n: u128,
+        fraction: u64,
+        fraction_cnt: u32,
+        multiplier: u128,
+    ) -> Option<u128> {
+        let bytes = multiplier / 8;
+        let scale = 10u128.pow(fraction_cnt);
+        let fraction = (u128::from(fraction).checked_mul(bytes)? + scale / 2) / scale;
+        n.checked_mul(bytes)?.checked_add(fraction)?.checked_mul(8)
+    }
+
+    fn parse(&self, s: &str) -> Result<Bandwidth, Error> {
+        crate::binary_system::parse_binary_bandwidth(s)
+    }
+
+    fn format(&self, val: Bandwidth) -> String {
+        BinaryDecimal(crate::binary_system::format_binary_bandwidth(val)).to_string()
+    }
+}
+
+/// Displays a bandwidth in the binary prefix system in decimal form, whether
+/// `display-integer` is enabled or not
+#[cfg(feature = "binary-system")]
+struct BinaryDecimal(crate::binary_system::FormattedBinaryBandwidth);
+
+#[cfg(feature = "binary-system")]
+impl core::fmt::Display for BinaryDecimal {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.0.fmt_decimal(f)
+    }
+}
+
+/// Parses a bandwidth written in the units of `system`, with
+/// [`UnitSystem::parse`]
+///
+/// The string is a sequence of rate spans like for
+/// [`parse_bandwidth`](crate::parse_bandwidth), each being converted with
+/// [`UnitSystem::span_bps`] unless the system reads it on its own, as
+/// [`Decimal`] does with [`parse_bandwidth`](crate::parse_bandwidth).
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{system::{parse, Decimal}, Error};
+///
+/// assert_eq!(parse("1Gbps 500Mbps", &Decimal), Ok(Bandwidth::new(1, 500_000_000)));
+/// assert!(matches!(parse("1MiB/s", &Decimal), Err(Error::UnknownUnit { .. })));
+/// ```
+pub fn parse<S: UnitSystem + ?Sized>(s: &str, system: &S) -> Result<Bandwidth, Error> {
+    system.parse(s)
+}
+
+/// Parses the rate spans of `s` in the units of `system`, see
+/// [`UnitSystem::parse`]
+fn parse_spans<S: UnitSystem + ?Sized>(s: &str, system: &S) -> Result<Bandwidth, Error> {
+    Parser::new(s).parse_with(
+        |parser, n, fraction, fraction_cnt, start, end| -> Result<(), Error> {
+            let unit = &parser.src[start..end];
+            let multiplier = system
+                .parse_unit(unit)
+                .ok_or_else(|| system.unknown_unit(start, end, unit, saturating_value(n)))?;
+            let bps = system
+                .span_bps(n, fraction, fraction_cnt, multiplier)
+                .ok_or(Error::NumberOverflow)?;
+            Ok(parser.add_bps(bps)?)
+        },
+    )
+}
+
+/// Formats a bandwidth in the units of `system`, with [`UnitSystem::format`]
+///
+/// The value is written in the unit selected by [`UnitSystem::unit_for`],
+/// rounded to the nearest with ties to even to at most
+/// [`UnitSystem::decimals`] decimals, without trailing zeros, unless the
+/// system writes it on its own, as [`Decimal`] does in the decimal form of
+/// [`format_bandwidth`](crate::format_bandwidth).
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::system::{format, Decimal};
+///
+/// assert_eq!(format(Bandwidth::new(1, 500_000_000), &Decimal), "1.5Gbps");
+/// assert_eq!(format(Bandwidth::new(0, 0), &Decimal), "0bps");
+/// ```
+pub fn format<S: UnitSystem + ?Sized>(val: Bandwidth, system: &S) -> String {
+    system.format(val)
+}
+
+/// Writes `val` in the units of `system`, see [`UnitSystem::format`]
+fn format_units<S: UnitSystem + ?Sized>(val: Bandwidth, system: &S) -> String {
+    let bps = bandwidth_as_bps(val);
+    let index = system.unit_for(bps);
+    let (symbol, multiplier) = system.units()[index];
+    // Keep the fraction computation within an u128, 10^38 being the largest
+    // power of ten in it
+    let mut decimals = system.decimals(index).min(38) as u32;
+    while multiplier.checked_mul(10u128.pow(decimals)).is_none() {
+        decimals -= 1;
+    }
+    let scale = 10u128.pow(decimals);
+    let (mut int, remainder) = (bps / multiplier, bps % multiplier);
+    let (mut fraction, loss) = (
+        remainder * scale / multiplier,
+        remainder * scale % multiplier,
+    );
+    if loss * 2 > multiplier || (loss * 2 == multiplier && fraction % 2 == 1) {
+        fraction += 1;
+        if fraction == scale {
+            int += 1;
+            fraction = 0;
+        }
+    }
+    if fraction == 0 {
+        return format!("{}{}", int, symbol);
+    }
+    let digits = format!("{:0width$}", fraction, width = decimals as usize);
+    format!("{}.{}{}", int, digits.trim_end_matches('0'), symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal() {
+        for (unit, (symbol, bps)) in Unit::ALL.iter().zip(Decimal.units()) {
+            assert_eq!(*symbol, unit.name());
+            assert_eq!(Decimal.parse_unit(symbol), Some(*bps));
+        }
+        // Read by the parser of the crate, with the amounts per interval
+        assert_eq!(parse("36Gbit/h", &Decimal), Ok(Bandwidth::from_mbps(10)));
+        assert_eq!(
+            parse("10 megabits per second", &Decimal),
+            Ok(Bandwidth::from_mbps(10))
+        );
+        assert!(matches!(
+            parse("1MiB/s", &Decimal),
+            Err(Error::UnknownUnit { .. })
+        ));
+        assert_eq!(
+            format(Bandwidth::new(8_700, 32_000_000), &Decimal),
+            "8.700032Tbps"
+        );
+        let max = format(Bandwidth::MAX, &Decimal);
+        assert_eq!(parse(&max, &Decimal), Ok(Bandwidth::MAX));
+    }
+
+    #[cfg(feature = "binary-system")]
+    #[test]
+    fn test_binary() {
+        use crate::binary_system::BinaryUnit;
+
+        for (unit, (symbol, bps)) in BinaryUnit::ALL.iter().zip(Binary.units()) {
+            assert_eq!(*symbol, unit.name());
+            assert_eq!(Binary.parse_unit(symbol), Some(*bps));
+        }
+        assert_eq!(
+            parse("300MiB/min", &Binary),
+            Ok(Bandwidth::from_bps(41_943_040))
+        );
+        assert!(matches!(
+            parse("1Mbps", &Binary),
+            Err(Error::UnknownBinaryUnit { .. })
+        ));
+        // Written in decimal form, whether `display-integer` is enabled or not
+        assert_eq!(format(Bandwidth::from_bps(12_582_912), &Binary), "1.5MiB/s");
+        assert_eq!(format(Bandwidth::from_bps(12_288), &Binary), "1.5kiB/s");
+    }
+
+    #[test]
+    fn test_custom() {
+        struct Odd;
+        impl UnitSystem for Odd {
+            fn parse_unit(&self, unit: &str) -> Option<u128> {
+                match unit {
+                    "o" => Some(3),
+                    "oo" => Some(u128::MAX / 7),
+                    _ => None,
+                }
+            }
+
+            fn units(&self) -> &[(&'static str, u128)] {
+                &[("o", 3), ("oo", u128::MAX / 7)]
+            }
+        }
+
+        assert_eq!(parse("2o 0.5o", &Odd), Ok(Bandwidth::from_bps(7)));
+        assert_eq!(parse("1oo", &Odd), Err(Error::NumberOverflow));
+        assert_eq!(
+            parse("1o 2x", &Odd).unwrap_err().to_string(),
+            "unknown bandwidth unit \"x\", supported units: o, oo"
+        );
+        let err = parse("1ooo", &Odd).unwrap_err();
+        assert_eq!(err.suggestion(), Some("oo"));
+        assert_eq!(
+            err.to_string(),
+            "unknown bandwidth unit \"ooo\", did you mean \"oo\"?"
+        );
+        // Without decimals in the smallest unit
+        assert_eq!(format(Bandwidth::from_bps(7), &Odd), "2o");
+        assert_eq!(format(Bandwidth::from_bps(2), &Odd), "1o");
+
+        struct Precise;
+        impl UnitSystem for Precise {
+            fn parse_unit(&self, unit: &str) -> Option<u128> {
+                (unit == "p").then_some(3)
+            }
+
+            fn units(&self) -> &[(&'static str, u128)] {
+                &[("p", 3)]
+            }
+
+            fn decimals(&self, _index: usize) -> usize {
+                usize::MAX
+            }
+        }
+
+        // As many decimals as fit in an u128 once scaled by the unit
+        assert_eq!(
+            format(Bandwidth::from_bps(7), &Precise),
+            format!("2.{}p", "3".repeat(38))
+        );
+        assert_eq!(format(Bandwidth::from_bps(6), &Precise), "2p");
+    }
+}
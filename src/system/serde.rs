@@ -0,0 +1,126 @@
+//! Serde support for bandwidths in any [`UnitSystem`].
+//!
+//! The system is given as a type parameter, which must implement `Default`.
+//!
+//! # Example
+//! ```
+//! use serde::{Serialize, Deserialize};
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::system::{Decimal, UnitSystem};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo {
+//!     #[serde(
+//!         serialize_with = "human_bandwidth::system::serde::serialize::<Decimal, _>",
+//!         deserialize_with = "human_bandwidth::system::serde::deserialize::<Decimal, _>"
+//!     )]
+//!     bandwidth: Bandwidth,
+//! }
+//!
+//! let json = r#"{"bandwidth": "1.5 Mbps"}"#;
+//! let foo = serde_json::from_str::<Foo>(json).unwrap();
+//! assert_eq!(foo.bandwidth, Bandwidth::from_kbps(1_500));
+//! let reverse = serde_json::to_string(&foo).unwrap();
+//! assert_eq!(reverse, r#"{"bandwidth":"1.5Mbps"}"#);
+//! ```
+
+use bandwidth::Bandwidth;
+use core::fmt;
+use serde::{de, Deserializer, Serialize, Serializer};
+
+use super::UnitSystem;
+
+/// Deserializes a `Bandwidth` written in the units of `S`.
+///
+/// This function can be used with `serde_derive`'s `deserialize_with`
+/// annotation.
+pub fn deserialize<'de, S, D>(d: D) -> Result<Bandwidth, D::Error>
+where
+    S: UnitSystem + Default,
+    D: Deserializer<'de>,
+{
+    d.deserialize_str(SystemVisitor(S::default()))
+}
+
+/// Serializes a `Bandwidth` in the units of `S`.
+///
+/// This function can be used with `serde_derive`'s `serialize_with`
+/// annotation.
+pub fn serialize<S, Ser>(d: &Bandwidth, s: Ser) -> Result<Ser::Ok, Ser::Error>
+where
+    S: UnitSystem + Default,
+    Ser: Serializer,
+{
+    super::format(*d, &S::default()).serialize(s)
+}
+
+/// The [`Visitor`](de::Visitor) used to deserialize a `Bandwidth` from a
+/// string written in the units of a system
+///
+/// It can be reused by manual `Deserialize` implementations, e.g. when the
+/// system is only known at runtime.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemVisitor<S>(pub S);
+
+impl<S: UnitSystem> de::Visitor<'_> for SystemVisitor<S> {
+    type Value = Bandwidth;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a bandwidth")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Bandwidth, E>
+    where
+        E: de::Error,
+    {
+        super::parse(v, &self.0).map_err(|e| crate::serde::invalid_bandwidth(v, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Default)]
+    struct Bits;
+
+    impl UnitSystem for Bits {
+        fn parse_unit(&self, unit: &str) -> Option<u128> {
+            match unit {
+                "bit/s" => Some(1),
+                "kibit/s" => Some(1 << 10),
+                _ => None,
+            }
+        }
+
+        fn units(&self) -> &[(&'static str, u128)] {
+            &[("bit/s", 1), ("kibit/s", 1 << 10)]
+        }
+    }
+
+    #[test]
+    fn with() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Foo {
+            #[serde(
+                serialize_with = "serialize::<Bits, _>",
+                deserialize_with = "deserialize::<Bits, _>"
+            )]
+            bandwidth: Bandwidth,
+        }
+
+        let json = r#"{"bandwidth": "1.5kibit/s"}"#;
+        let foo = serde_json::from_str::<Foo>(json).unwrap();
+        assert_eq!(foo.bandwidth, Bandwidth::from_bps(1_536));
+        let reverse = serde_json::to_string(&foo).unwrap();
+        assert_eq!(reverse, r#"{"bandwidth":"1.5kibit/s"}"#);
+
+        let json = r#"{"bandwidth": "1kbps"}"#;
+        assert_eq!(
+            serde_json::from_str::<Foo>(json).unwrap_err().to_string(),
+            "invalid bandwidth \"1kbps\" at bytes 1..5: unknown bandwidth unit \"kbps\", \
+            supported units: bit/s, kibit/s at line 1 column 21"
+        );
+    }
+}
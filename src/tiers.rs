@@ -0,0 +1,240 @@
+//! Classification of bandwidths into rate tiers
+//!
+//! Monitoring agents often report a quality of experience rather than a raw
+//! rate, e.g. `slow` below 10Mbps. A tier table is a slice of thresholds
+//! sorted in increasing order, each tier starting at its threshold, and can be
+//! written in configuration files like `<10Mbit/s: slow, <100Mbit/s: ok, _: fast`.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::tiers::{classify, parse_tiers};
+//!
+//! let tiers = parse_tiers("<10Mbit/s: slow, <100Mbit/s: ok, _: fast").unwrap();
+//! assert_eq!(classify(Bandwidth::from_mbps(5), &tiers), "slow");
+//! assert_eq!(classify(Bandwidth::from_mbps(10), &tiers), "ok");
+//! assert_eq!(classify(Bandwidth::from_gbps(1), &tiers), "fast");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+use bandwidth::Bandwidth;
+
+use crate::{parse_bandwidth, Error};
+
+/// Returns the tier of a bandwidth, i.e. the last tier whose threshold is at
+/// most `bw`
+///
+/// The tiers must be sorted by increasing threshold, as they are looked up
+/// with a binary search. A bandwidth below every threshold is in the first
+/// tier.
+///
+/// # Panics
+///
+/// Panics if `tiers` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::tiers::classify;
+///
+/// let tiers = [
+///     (Bandwidth::new(0, 0), 'C'),
+///     (Bandwidth::from_mbps(25), 'B'),
+///     (Bandwidth::from_mbps(100), 'A'),
+/// ];
+/// assert_eq!(*classify(Bandwidth::from_mbps(24), &tiers), 'C');
+/// assert_eq!(*classify(Bandwidth::from_mbps(25), &tiers), 'B');
+/// assert_eq!(*classify(Bandwidth::from_gbps(10), &tiers), 'A');
+/// ```
+pub fn classify<T>(bw: Bandwidth, tiers: &[(Bandwidth, T)]) -> &T {
+    assert!(!tiers.is_empty(), "no tier to classify a bandwidth into");
+    let index = tiers.partition_point(|(threshold, _)| *threshold <= bw);
+    &tiers[index.saturating_sub(1)].1
+}
+
+/// Error parsing a tier table
+#[derive(Debug, PartialEq, Clone)]
+pub enum TierError {
+    /// The bound of a tier is not a valid bandwidth
+    ///
+    /// The index is the position of the tier in the table.
+    InvalidBound {
+        /// Position of the tier in the table
+        index: usize,
+        /// Error parsing the bound
+        error: Error,
+    },
+    /// A tier is neither `<bandwidth: label` nor `_: label`, or is after the
+    /// `_` tier
+    ///
+    /// The field is the position of the tier in the table.
+    InvalidTier(usize),
+    /// A bound is not greater than the bound of the previous tier
+    ///
+    /// The field is the position of the tier in the table.
+    Unsorted(usize),
+    /// The table does not end with a `_` tier for the bandwidths above the
+    /// last bound
+    MissingDefault,
+}
+
+#[cfg(feature = "std")]
+impl StdError for TierError {}
+
+impl fmt::Display for TierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TierError::InvalidBound { index, error } => {
+                write!(f, "invalid bound of tier {}: {}", index, error)
+            }
+            TierError::InvalidTier(index) => write!(
+                f,
+                "tier {} is neither `<bandwidth: label` nor `_: label`",
+                index
+            ),
+            TierError::Unsorted(index) => write!(
+                f,
+                "bound of tier {} is not greater than the previous one",
+                index
+            ),
+            TierError::MissingDefault => write!(f, "the last tier must be `_: label`"),
+        }
+    }
+}
+
+/// Parses a tier table like `<10Mbit/s: slow, <100Mbit/s: ok, _: fast`
+///
+/// Tiers are separated by commas. Each tier but the last is an exclusive upper
+/// bound and a label, and the last tier, `_`, covers the remaining bandwidths.
+/// The result is sorted by threshold and can be used with [`classify`].
+///
+/// # Examples
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::tiers::{parse_tiers, TierError};
+///
+/// assert_eq!(
+///     parse_tiers("<1Gbps: copper, _: fiber"),
+///     Ok(vec![
+///         (Bandwidth::new(0, 0), "copper".to_string()),
+///         (Bandwidth::from_gbps(1), "fiber".to_string()),
+///     ])
+/// );
+/// assert_eq!(parse_tiers("<1Gbps: copper"), Err(TierError::MissingDefault));
+/// assert_eq!(parse_tiers("<1Gbps: a, <1Mbps: b, _: c"), Err(TierError::Unsorted(1)));
+/// ```
+pub fn parse_tiers(s: &str) -> Result<Vec<(Bandwidth, String)>, TierError> {
+    let mut tiers = Vec::new();
+    let mut threshold = Bandwidth::new(0, 0);
+    let mut has_default = false;
+    for (index, tier) in s.split(',').enumerate() {
+        let (bound, label) = tier.split_once(':').ok_or(TierError::InvalidTier(index))?;
+        let (bound, label) = (bound.trim(), label.trim());
+        if has_default || label.is_empty() {
+            return Err(TierError::InvalidTier(index));
+        }
+        tiers.push((threshold, label.to_string()));
+        if bound == "_" {
+            has_default = true;
+            continue;
+        }
+        let bound = bound
+            .strip_prefix('<')
+            .ok_or(TierError::InvalidTier(index))?;
+        let bound =
+            parse_bandwidth(bound).map_err(|error| TierError::InvalidBound { index, error })?;
+        if bound <= threshold {
+            return Err(TierError::Unsorted(index));
+        }
+        threshold = bound;
+    }
+    if !has_default {
+        return Err(TierError::MissingDefault);
+    }
+    Ok(tiers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let tiers = [
+            (Bandwidth::from_mbps(1), 1),
+            (Bandwidth::from_mbps(10), 2),
+            (Bandwidth::from_mbps(10), 3),
+            (Bandwidth::from_mbps(100), 4),
+        ];
+        let classify = |mbps| *classify(Bandwidth::from_mbps(mbps), &tiers);
+        assert_eq!(classify(0), 1);
+        assert_eq!(classify(1), 1);
+        assert_eq!(classify(9), 1);
+        assert_eq!(classify(10), 3);
+        assert_eq!(classify(99), 3);
+        assert_eq!(classify(100), 4);
+        assert_eq!(classify(u64::MAX), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_classify_empty() {
+        classify::<()>(Bandwidth::new(0, 0), &[]);
+    }
+
+    #[test]
+    fn test_parse_tiers() {
+        let tiers = parse_tiers(" <10Mbit/s : slow,<100Mbit/s: ok ,_: very fast ").unwrap();
+        assert_eq!(
+            tiers,
+            [
+                (Bandwidth::new(0, 0), "slow".to_string()),
+                (Bandwidth::from_mbps(10), "ok".to_string()),
+                (Bandwidth::from_mbps(100), "very fast".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_tiers("_: any"),
+            Ok(vec![(Bandwidth::new(0, 0), "any".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_parse_tiers_errors() {
+        assert_eq!(parse_tiers(""), Err(TierError::InvalidTier(0)));
+        assert_eq!(
+            parse_tiers("10Mbps: a, _: b"),
+            Err(TierError::InvalidTier(0))
+        );
+        assert_eq!(
+            parse_tiers("<10Mbps a, _: b"),
+            Err(TierError::InvalidTier(0))
+        );
+        assert_eq!(
+            parse_tiers("<10Mbps: , _: b"),
+            Err(TierError::InvalidTier(0))
+        );
+        assert_eq!(parse_tiers("_: a, _: b"), Err(TierError::InvalidTier(1)));
+        assert_eq!(parse_tiers("_: a,"), Err(TierError::InvalidTier(1)));
+        assert_eq!(parse_tiers("<0bps: a, _: b"), Err(TierError::Unsorted(0)));
+        assert_eq!(
+            parse_tiers("<1Mbps: a, <1Mbps: b, _: c"),
+            Err(TierError::Unsorted(1))
+        );
+        assert_eq!(parse_tiers("<1Mbps: a"), Err(TierError::MissingDefault));
+        assert_eq!(
+            parse_tiers("<1Mbpx: a, _: b").unwrap_err().to_string(),
+            "invalid bound of tier 0: unknown bandwidth unit \"Mbpx\", did you mean \"Mbps\"?"
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! [utoipa](https://docs.rs/utoipa) schema support for the serde wrapper types
+//!
+//! The wrapper types of the [`serde`](crate::serde) modules implement
+//! [`ToSchema`] so that OpenAPI documents show the actual bandwidth format,
+//! with the accepted pattern and a few examples.
+//!
+//! When a plain [`bandwidth::Bandwidth`] field is (de)serialized with
+//! `#[serde(with = "...")]`, the matching schema function can be used with
+//! utoipa's `schema_with` attribute.
+//!
+//! # Example
+//!
+//! ```
+//! use utoipa::PartialSchema;
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::serde::Serde;
+//!
+//! let schema = serde_json::to_value(Serde::<Bandwidth>::schema()).unwrap();
+//! assert_eq!(schema["type"], "string");
+//! assert_eq!(schema["examples"][0], "10Mbps");
+//! ```
+
+use alloc::borrow::Cow;
+
+use bandwidth::Bandwidth;
+use utoipa::openapi::schema::{ObjectBuilder, OneOfBuilder, Schema, Type};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+
+/// Pattern of the usual strings accepted by
+/// [`parse_bandwidth`](crate::parse_bandwidth), and of every string written
+/// by [`format_bandwidth`](crate::format_bandwidth)
+///
+/// It matches the rate spans of decimal numbers, whose digits may be
+/// separated by `_`, and SI units of bits per second. The other syntaxes
+/// accepted by the parser, like the units spelled out or per interval and
+/// the scientific notation, are not matched.
+pub const BANDWIDTH_PATTERN: &str =
+    r"^\s*([0-9][0-9_]*(\.[0-9_]*)?\s*[kKmMgGtTpPeE]?(bps|bit/s|b/s)\s*)+$";
+
+/// Pattern of the usual strings accepted by
+/// [`parse_binary_bandwidth`](crate::binary_system::parse_binary_bandwidth),
+/// like [`BANDWIDTH_PATTERN`] with the binary units of Bytes
+#[cfg(feature = "binary-system")]
+pub const BINARY_BANDWIDTH_PATTERN: &str = r"^\s*([0-9][0-9_]*(\.[0-9_]*)?\s*([kKmMgGtTpPeE]i)?(Bps|Byte/s|B/s|ops|o/s|(B|Byte|o)/(min|h|d|day|month))\s*)+$";
+
+/// Schema of a bandwidth serialized with [`serde`](crate::serde)
+pub fn bandwidth_schema() -> Schema {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .description(Some("A bandwidth in human-readable format"))
+        .pattern(Some(BANDWIDTH_PATTERN))
+        .examples(["10Mbps", "1.5Gbps", "9Tbps 420Gbps", "1.2Pbps", "18.4Ebps"])
+        .into()
+}
+
+/// Schema of a bandwidth serialized with
+/// [`binary_system::serde`](crate::binary_system::serde)
+#[cfg(feature = "binary-system")]
+pub fn binary_bandwidth_schema() -> Schema {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .description(Some(
+            "A bandwidth in human-readable format using the binary prefix system",
+        ))
+        .pattern(Some(BINARY_BANDWIDTH_PATTERN))
+        .examples([
+            "4MiB/s",
+            "1.5GiB/s",
+            "9TiB/s 420GiB/s",
+            "2PiB/s",
+            "1.5EiB/s",
+            "500GiB/h",
+        ])
+        .into()
+}
+
+/// Pattern of the canonical form of [`CanonicalProfile`](crate::canonical::CanonicalProfile)
+///
+/// The canonical form is never written in `Pbit/s` or `Ebit/s`, the values of
+/// these units being written in `Tbit/s`, e.g. `1000 Tbit/s`.
+pub const CANONICAL_BANDWIDTH_PATTERN: &str = r"^(0|[1-9][0-9]*)(\.[0-9]*[1-9])? [kMGT]?bit/s$";
+
+/// Schema of a bandwidth serialized with
+/// [`serde::canonical`](crate::serde::canonical)
+pub fn canonical_bandwidth_schema() -> Schema {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .description(Some("A bandwidth in canonical form"))
+        .pattern(Some(CANONICAL_BANDWIDTH_PATTERN))
+        .examples(["10 Mbit/s", "1.5 Gbit/s", "9.42 Tbit/s"])
+        .into()
+}
+
+/// Pattern of the unit of [`serde::structured`](crate::serde::structured)
+const STRUCTURED_UNIT_PATTERN: &str = r"^[kKmMgGtTpPeE]?(bps|bit/s|b/s)$";
+
+/// Pattern of the value of [`serde::structured`](crate::serde::structured)
+/// written as a string, when no number holds it exactly
+const STRUCTURED_VALUE_PATTERN: &str = r"^[0-9]+(\.[0-9]+)?$";
+
+/// Schema of a bandwidth serialized with
+/// [`serde::structured`](crate::serde::structured)
+pub fn structured_bandwidth_schema() -> Schema {
+    ObjectBuilder::new()
+        .schema_type(Type::Object)
+        .description(Some("A bandwidth as a numeric value and its unit"))
+        .property(
+            "value",
+            OneOfBuilder::new()
+                .item(
+                    ObjectBuilder::new()
+                        .schema_type(Type::Number)
+                        .minimum(Some(0)),
+                )
+                .item(
+                    ObjectBuilder::new()
+                        .schema_type(Type::String)
+                        .description(Some("A decimal value no number holds exactly"))
+                        .pattern(Some(STRUCTURED_VALUE_PATTERN)),
+                ),
+        )
+        .required("value")
+        .property(
+            "unit",
+            ObjectBuilder::new()
+                .schema_type(Type::String)
+                .pattern(Some(STRUCTURED_UNIT_PATTERN))
+                .examples(["bps", "kbps", "Mbps", "Gbps", "Tbps", "Pbps", "Ebps"]),
+        )
+        .required("unit")
+        .examples([serde_json::json!({"value": 1.5, "unit": "Mbps"})])
+        .into()
+}
+
+/// Schema of a bandwidth deserialized with [`serde::any`](crate::serde::any)
+pub fn any_bandwidth_schema() -> Schema {
+    OneOfBuilder::new()
+        .item(bandwidth_schema())
+        .item(
+            ObjectBuilder::new()
+                .schema_type(Type::Integer)
+                .description(Some("A bandwidth in bits per second"))
+                .minimum(Some(0))
+                .examples([1_500_000]),
+        )
+        .item(structured_bandwidth_schema())
+        .into()
+}
+
+impl PartialSchema for crate::serde::Serde<Bandwidth> {
+    fn schema() -> RefOr<Schema> {
+        bandwidth_schema().into()
+    }
+}
+
+impl ToSchema for crate::serde::Serde<Bandwidth> {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("Bandwidth")
+    }
+}
+
+impl PartialSchema for crate::HumanBandwidth {
+    fn schema() -> RefOr<Schema> {
+        bandwidth_schema().into()
+    }
+}
+
+impl ToSchema for crate::HumanBandwidth {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("Bandwidth")
+    }
+}
+
+// The cruft tolerated on input is not advertised
+impl PartialSchema for crate::serde::lenient::Lenient<Bandwidth> {
+    fn schema() -> RefOr<Schema> {
+        bandwidth_schema().into()
+    }
+}
+
+impl ToSchema for crate::serde::lenient::Lenient<Bandwidth> {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("Bandwidth")
+    }
+}
+
+impl PartialSchema for crate::serde::canonical::Canonical<Bandwidth> {
+    fn schema() -> RefOr<Schema> {
+        canonical_bandwidth_schema().into()
+    }
+}
+
+impl ToSchema for crate::serde::canonical::Canonical<Bandwidth> {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("CanonicalBandwidth")
+    }
+}
+
+impl PartialSchema for crate::serde::structured::Structured<Bandwidth> {
+    fn schema() -> RefOr<Schema> {
+        structured_bandwidth_schema().into()
+    }
+}
+
+impl ToSchema for crate::serde::structured::Structured<Bandwidth> {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("StructuredBandwidth")
+    }
+}
+
+impl PartialSchema for crate::serde::any::Any<Bandwidth> {
+    fn schema() -> RefOr<Schema> {
+        any_bandwidth_schema().into()
+    }
+}
+
+impl ToSchema for crate::serde::any::Any<Bandwidth> {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("AnyBandwidth")
+    }
+}
+
+#[cfg(feature = "binary-system")]
+impl PartialSchema for crate::binary_system::serde::Serde<Bandwidth> {
+    fn schema() -> RefOr<Schema> {
+        binary_bandwidth_schema().into()
+    }
+}
+
+#[cfg(feature = "binary-system")]
+impl ToSchema for crate::binary_system::serde::Serde<Bandwidth> {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("BinaryBandwidth")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(crate::serde::Serde::<Bandwidth>::name(), "Bandwidth");
+        assert_eq!(crate::HumanBandwidth::name(), "Bandwidth");
+        assert_eq!(
+            crate::serde::lenient::Lenient::<Bandwidth>::name(),
+            "Bandwidth"
+        );
+        assert_eq!(
+            crate::serde::structured::Structured::<Bandwidth>::name(),
+            "StructuredBandwidth"
+        );
+        assert_eq!(crate::serde::any::Any::<Bandwidth>::name(), "AnyBandwidth");
+        assert_eq!(
+            crate::serde::canonical::Canonical::<Bandwidth>::name(),
+            "CanonicalBandwidth"
+        );
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = serde_json::to_value(crate::serde::Serde::<Bandwidth>::schema()).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["pattern"], BANDWIDTH_PATTERN);
+
+        let schema =
+            serde_json::to_value(crate::serde::structured::Structured::<Bandwidth>::schema())
+                .unwrap();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], serde_json::json!(["value", "unit"]));
+
+        let schema = serde_json::to_value(crate::serde::any::Any::<Bandwidth>::schema()).unwrap();
+        assert_eq!(schema["oneOf"].as_array().map(Vec::len), Some(3));
+    }
+
+    /// Whether the whole of `s` matches `pattern`
+    fn matches(pattern: &str, s: &str) -> bool {
+        regex::Regex::new(pattern).unwrap().is_match(s)
+    }
+
+    #[test]
+    fn test_schema_units() {
+        let peta = Bandwidth::new(1_000_000, 0);
+        let exa = Bandwidth::new(1_000_000_000, 0);
+        for val in [Bandwidth::from_gbps(1), peta, exa, Bandwidth::MAX] {
+            let formatted = crate::format_bandwidth(val).to_string();
+            assert!(matches(BANDWIDTH_PATTERN, &formatted), "{}", formatted);
+            let structured =
+                serde_json::to_value(crate::serde::structured::Structured::from(val)).unwrap();
+            let unit = structured["unit"].as_str().unwrap();
+            assert!(matches(STRUCTURED_UNIT_PATTERN, unit), "{}", unit);
+            let canonical = crate::canonical::CanonicalProfile.format(val);
+            assert!(
+                matches(CANONICAL_BANDWIDTH_PATTERN, &canonical),
+                "{}",
+                canonical
+            );
+        }
+        for unit in crate::Unit::ALL {
+            assert!(matches(BANDWIDTH_PATTERN, &format!("1{}", unit.name())));
+            assert!(matches(STRUCTURED_UNIT_PATTERN, unit.name()));
+        }
+        // Every string of the pattern is parsed
+        for s in [
+            "1_000Mbps",
+            " 2Gbps 340Mbps ",
+            "1.5 Mbit/s",
+            "3.kb/s",
+            "7Ebps 1Pbps",
+        ] {
+            assert!(matches(BANDWIDTH_PATTERN, s), "{}", s);
+            assert!(crate::parse_bandwidth(s).is_ok(), "{}", s);
+        }
+        for s in ["", "Mbps", "1", "1MB/s", "1.5.5Mbps", "1Mbps,"] {
+            assert!(!matches(BANDWIDTH_PATTERN, s), "{}", s);
+        }
+        // So is every unit of the structured form, and not the others
+        for unit in ["KBps", "MiB/s", "1Mbps", " Mbps", "megabits"] {
+            assert!(!matches(STRUCTURED_UNIT_PATTERN, unit), "{}", unit);
+        }
+
+        let schema = serde_json::to_value(bandwidth_schema()).unwrap();
+        for example in schema["examples"].as_array().unwrap() {
+            let example = example.as_str().unwrap();
+            assert!(crate::parse_bandwidth(example).is_ok(), "{}", example);
+            assert!(matches(BANDWIDTH_PATTERN, example), "{}", example);
+        }
+        let schema = serde_json::to_value(canonical_bandwidth_schema()).unwrap();
+        for example in schema["examples"].as_array().unwrap() {
+            let example = example.as_str().unwrap();
+            assert!(matches(CANONICAL_BANDWIDTH_PATTERN, example), "{}", example);
+        }
+        let schema = serde_json::to_value(structured_bandwidth_schema()).unwrap();
+        let examples = schema["properties"]["unit"]["examples"].as_array().unwrap();
+        assert_eq!(examples.len(), crate::Unit::ALL.len());
+    }
+
+    #[test]
+    fn test_structured_value() {
+        let schema = serde_json::to_value(structured_bandwidth_schema()).unwrap();
+        let value = &schema["properties"]["value"]["oneOf"];
+        assert_eq!(value[0]["type"], "number");
+        assert_eq!(value[1]["type"], "string");
+        let pattern = value[1]["pattern"].as_str().unwrap();
+        assert_eq!(pattern, STRUCTURED_VALUE_PATTERN);
+
+        // The values written as strings match the pattern
+        let structured =
+            serde_json::to_value(crate::serde::structured::Structured::from(Bandwidth::MAX))
+                .unwrap();
+        let value = structured["value"].as_str().unwrap();
+        assert!(matches(pattern, value), "{}", value);
+        for value in ["1.5e3", "-1", "1.", ".5", ""] {
+            assert!(!matches(pattern, value), "{}", value);
+        }
+    }
+
+    #[cfg(feature = "binary-system")]
+    #[test]
+    fn test_binary_schema_units() {
+        use crate::binary_system::{binary_unit_name, format_binary_bandwidth, BinaryUnit};
+        use crate::Interval;
+
+        let pebi = Bandwidth::from_bps(8 << 50);
+        let exbi = Bandwidth::new(9_223_372_036, 854_775_808);
+        for val in [Bandwidth::from_mbps(1), pebi, exbi, Bandwidth::MAX] {
+            let formatted = format_binary_bandwidth(val).to_string();
+            assert!(
+                matches(BINARY_BANDWIDTH_PATTERN, &formatted),
+                "{}",
+                formatted
+            );
+        }
+        for unit in BinaryUnit::ALL {
+            for interval in [
+                Interval::Second,
+                Interval::Minute,
+                Interval::Hour,
+                Interval::Day,
+                Interval::Month,
+            ] {
+                let s = format!("1{}", binary_unit_name(unit as u32, interval));
+                assert!(matches(BINARY_BANDWIDTH_PATTERN, &s), "{}", s);
+                assert!(
+                    crate::binary_system::parse_binary_bandwidth(&s).is_ok(),
+                    "{}",
+                    s
+                );
+            }
+        }
+        for s in ["1GiB/day", "2Mio/h", "3KiByte/min", "4B/s 5ops"] {
+            assert!(matches(BINARY_BANDWIDTH_PATTERN, s), "{}", s);
+            assert!(
+                crate::binary_system::parse_binary_bandwidth(s).is_ok(),
+                "{}",
+                s
+            );
+        }
+
+        let schema = serde_json::to_value(binary_bandwidth_schema()).unwrap();
+        assert_eq!(schema["pattern"], BINARY_BANDWIDTH_PATTERN);
+        for example in schema["examples"].as_array().unwrap() {
+            let example = example.as_str().unwrap();
+            assert!(
+                crate::binary_system::parse_binary_bandwidth(example).is_ok(),
+                "{}",
+                example
+            );
+            assert!(matches(BINARY_BANDWIDTH_PATTERN, example), "{}", example);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Bandwidths as nodes of JSON and TOML documents
+//!
+//! Programs building configuration documents node by node, rather than
+//! serializing a struct, write a bandwidth with [`to_json_value`] for a
+//! `serde_json` document with the `serde_json` feature, and with
+//! [`to_toml_value`] for a `toml` document with the `toml` feature. The
+//! [`Encoding`] chooses between a string formatted with a [`Profile`] or
+//! [`FormatOptions`], and a number of bits per second.
+//!
+//! Both nodes are read back by `human_bandwidth::serde::any`.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::{value::{to_json_value, Encoding}, Profile};
+//!
+//! let rate = Bandwidth::from_kbps(1_536);
+//! let mut config = serde_json::Map::new();
+//! config.insert("logged".into(), to_json_value(rate, &Encoding::from(Profile::Machine)));
+//! config.insert("displayed".into(), to_json_value(rate, &Encoding::from(Profile::Human)));
+//! config.insert("bps".into(), to_json_value(rate, &Encoding::Bps));
+//! assert_eq!(
+//!     serde_json::Value::from(config).to_string(),
+//!     r#"{"bps":1536000,"displayed":"1.5 Mbit/s","logged":"1.536Mbps"}"#
+//! );
+//! # }
+//! ```
+
+use bandwidth::Bandwidth;
+
+use crate::{bandwidth_as_bps, FormatOptions, Profile};
+
+/// How a bandwidth is written as a node of a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// A string formatted with these options, e.g. `"1.536Mbps"`
+    String(FormatOptions),
+    /// A number of bits per second, e.g. `1536000`
+    ///
+    /// A bandwidth too large for the integers of the document, above
+    /// `u64::MAX` bps in JSON and `i64::MAX` bps in TOML, is written as a
+    /// string with [`Profile::Machine`] instead, so that it is not rounded.
+    Bps,
+}
+
+/// A node of any document
+#[derive(Debug, PartialEq)]
+enum Node {
+    String(String),
+    Bps(u64),
+}
+
+impl Encoding {
+    /// The node of `val`, whose number of bits per second is at most `max`
+    fn encode(&self, val: Bandwidth, max: u64) -> Node {
+        match self {
+            Encoding::String(options) => Node::String(options.format(val)),
+            Encoding::Bps => match u64::try_from(bandwidth_as_bps(val)) {
+                Ok(bps) if bps <= max => Node::Bps(bps),
+                _ => Node::String(Profile::Machine.format(val)),
+            },
+        }
+    }
+}
+
+/// Strings written with [`Profile::Machine`]
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding::from(Profile::Machine)
+    }
+}
+
+impl From<Profile> for Encoding {
+    fn from(profile: Profile) -> Self {
+        Encoding::String(profile.format_options())
+    }
+}
+
+impl From<FormatOptions> for Encoding {
+    fn from(options: FormatOptions) -> Self {
+        Encoding::String(options)
+    }
+}
+
+/// Writes a bandwidth as a `serde_json` node, a string or a number depending
+/// on `encoding`
+///
+/// # Example
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{value::{to_json_value, Encoding}, FormatOptions};
+/// use serde_json::json;
+///
+/// let rate = Bandwidth::from_mbps(10);
+/// let options = FormatOptions::new().iso80000(true);
+/// assert_eq!(to_json_value(rate, &options.into()), json!("10 Mbit/s"));
+/// assert_eq!(to_json_value(rate, &Encoding::Bps), json!(10_000_000));
+/// ```
+#[cfg(feature = "serde_json")]
+pub fn to_json_value(val: Bandwidth, encoding: &Encoding) -> serde_json::Value {
+    match encoding.encode(val, u64::MAX) {
+        Node::String(s) => serde_json::Value::String(s),
+        Node::Bps(bps) => serde_json::Value::from(bps),
+    }
+}
+
+/// Writes a bandwidth as a `toml` node, a string or an integer depending on
+/// `encoding`
+///
+/// # Example
+///
+/// ```
+/// use bandwidth::Bandwidth;
+/// use human_bandwidth::{value::{to_toml_value, Encoding}, Profile};
+///
+/// let rate = Bandwidth::from_kbps(1_536);
+/// let node = to_toml_value(rate, &Profile::Machine.into());
+/// assert_eq!(node, toml::Value::String("1.536Mbps".to_string()));
+/// assert_eq!(to_toml_value(rate, &Encoding::Bps), toml::Value::Integer(1_536_000));
+/// ```
+#[cfg(feature = "toml")]
+pub fn to_toml_value(val: Bandwidth, encoding: &Encoding) -> toml::Value {
+    match encoding.encode(val, i64::MAX as u64) {
+        Node::String(s) => toml::Value::String(s),
+        Node::Bps(bps) => toml::Value::Integer(bps as i64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let rate = Bandwidth::from_mbps(2);
+        let string = |s: &str| Node::String(s.to_string());
+        assert_eq!(Encoding::default().encode(rate, 0), string("2Mbps"));
+        assert_eq!(Encoding::Bps.encode(rate, u64::MAX), Node::Bps(2_000_000));
+        assert_eq!(Encoding::Bps.encode(rate, 2_000_000), Node::Bps(2_000_000));
+        assert_eq!(Encoding::Bps.encode(rate, 1_999_999), string("2Mbps"));
+        let options = FormatOptions::from_profile(Profile::Human);
+        assert_eq!(Encoding::from(options).encode(rate, 0), string("2 Mbit/s"));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_json() {
+        use serde_json::json;
+
+        let rate = Bandwidth::from_bps(u64::MAX);
+        assert_eq!(to_json_value(rate, &Encoding::Bps), json!(u64::MAX));
+        let rate = Bandwidth::new(20_000_000_000, 0);
+        assert_eq!(to_json_value(rate, &Encoding::Bps), json!("20Ebps"));
+        assert_eq!(
+            to_json_value(Bandwidth::from_kbps(1_536), &Profile::Human.into()),
+            json!("1.5 Mbit/s")
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml() {
+        let rate = Bandwidth::from_bps(i64::MAX as u64);
+        assert_eq!(
+            to_toml_value(rate, &Encoding::Bps),
+            toml::Value::Integer(i64::MAX)
+        );
+        let rate = Bandwidth::from_bps(i64::MAX as u64 + 1);
+        assert_eq!(
+            to_toml_value(rate, &Encoding::Bps),
+            toml::Value::String(Profile::Machine.format(rate))
+        );
+
+        let mut table = toml::Table::new();
+        table.insert(
+            "limit".to_string(),
+            to_toml_value(Bandwidth::from_mbps(10), &Encoding::default()),
+        );
+        assert_eq!(table["limit"].as_str(), Some("10Mbps"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Rates reloaded from configuration and pushed to their consumers
+//!
+//! A [`BandwidthWatch`] parses the rate strings read from a reloaded
+//! configuration, rejects the ones outside of its bounds, and notifies the
+//! throttles and token buckets subscribed to it through a
+//! [`tokio::sync::watch`] channel.
+//!
+//! # Example
+//!
+//! ```
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::{range::BandwidthRange, watch::BandwidthWatch};
+//!
+//! let watch = BandwidthWatch::new(Bandwidth::from_mbps(10))
+//!     .bounds(BandwidthRange::new(Bandwidth::from_kbps(64), Bandwidth::from_gbps(1)));
+//! let mut limit = watch.subscribe();
+//!
+//! assert_eq!(watch.update("25 Mbps"), Ok(Bandwidth::from_mbps(25)));
+//! assert!(limit.has_changed().unwrap());
+//! assert_eq!(*limit.borrow_and_update(), Bandwidth::from_mbps(25));
+//!
+//! // An invalid configuration keeps the previous rate
+//! assert!(watch.update("10Gbps").is_err());
+//! assert!(!limit.has_changed().unwrap());
+//! ```
+
+use std::{error::Error as StdError, fmt};
+
+use bandwidth::Bandwidth;
+use tokio::sync::watch;
+
+use crate::{range::BandwidthRange, Error, ParseOptions};
+
+/// Error updating a [`BandwidthWatch`]
+#[derive(Debug, PartialEq, Clone)]
+pub enum WatchError {
+    /// The configuration string is not a valid bandwidth
+    Parse(Error),
+    /// The bandwidth is outside of the bounds of the watch
+    OutOfBounds {
+        /// The rejected bandwidth
+        value: Bandwidth,
+        /// The bounds of the watch
+        bounds: BandwidthRange,
+    },
+}
+
+impl StdError for WatchError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            WatchError::Parse(error) => Some(error),
+            WatchError::OutOfBounds { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for WatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchError::Parse(error) => write!(f, "invalid bandwidth: {}", error),
+            WatchError::OutOfBounds { value, bounds } => write!(
+                f,
+                "bandwidth {} is outside of {}",
+                crate::format_bandwidth(*value),
+                bounds
+            ),
+        }
+    }
+}
+
+impl From<Error> for WatchError {
+    fn from(error: Error) -> Self {
+        WatchError::Parse(error)
+    }
+}
+
+/// The sending side of a bandwidth configuration, see the
+/// [module documentation](self)
+///
+/// The receivers are only notified when the bandwidth changes, so that
+/// reloading an unchanged configuration does not wake them up.
+#[derive(Debug)]
+pub struct BandwidthWatch {
+    sender: watch::Sender<Bandwidth>,
+    options: ParseOptions,
+    bounds: BandwidthRange,
+}
+
+impl BandwidthWatch {
+    /// Create a watch holding `initial`, which is not checked against the
+    /// bounds
+    pub fn new(initial: Bandwidth) -> Self {
+        BandwidthWatch {
+            sender: watch::Sender::new(initial),
+            options: ParseOptions::new(),
+            bounds: BandwidthRange::new(Bandwidth::new(0, 0), Bandwidth::MAX),
+        }
+    }
+
+    /// Parse the configuration strings with `options` rather than with the
+    /// default ones
+    pub fn options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Reject the bandwidths outside of `bounds`, inclusive
+    pub fn bounds(mut self, bounds: BandwidthRange) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// The bounds of the accepted bandwidths
+    pub fn get_bounds(&self) -> BandwidthRange {
+        self.bounds
+    }
+
+    /// A new receiver of the bandwidth, which sees the current one as
+    /// already seen
+    pub fn subscribe(&self) -> watch::Receiver<Bandwidth> {
+        self.sender.subscribe()
+    }
+
+    /// The current bandwidth
+    pub fn current(&self) -> Bandwidth {
+        *self.sender.borrow()
+    }
+
+    /// Parses a configuration string and publishes the bandwidth
+    ///
+    /// On error, the current bandwidth is kept and the receivers are not
+    /// notified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bandwidth::Bandwidth;
+    /// use human_bandwidth::{watch::{BandwidthWatch, WatchError}, Error, ParseOptions};
+    ///
+    /// let watch = BandwidthWatch::new(Bandwidth::from_mbps(10))
+    ///     .options(ParseOptions::new().iso80000(true));
+    /// assert_eq!(watch.update("5 Mbit/s"), Ok(Bandwidth::from_mbps(5)));
+    /// assert_eq!(watch.update("5Mbit/s"), Err(WatchError::Parse(Error::SpaceExpected(1))));
+    /// assert_eq!(watch.current(), Bandwidth::from_mbps(5));
+    /// ```
+    pub fn update(&self, config: &str) -> Result<Bandwidth, WatchError> {
+        let val = self.options.parse(config)?;
+        self.set(val)?;
+        Ok(val)
+    }
+
+    /// Publishes a bandwidth if it is within the bounds
+    pub fn set(&self, val: Bandwidth) -> Result<(), WatchError> {
+        if !self.bounds.contains(val) {
+            return Err(WatchError::OutOfBounds {
+                value: val,
+                bounds: self.bounds,
+            });
+        }
+        self.sender.send_if_modified(|current| {
+            let modified = *current != val;
+            *current = val;
+            modified
+        });
+        Ok(())
+    }
+}
+
+impl Default for BandwidthWatch {
+    fn default() -> Self {
+        BandwidthWatch::new(Bandwidth::new(0, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update() {
+        let bounds = BandwidthRange::new(Bandwidth::from_mbps(1), Bandwidth::from_mbps(10));
+        let watch = BandwidthWatch::new(Bandwidth::from_mbps(2)).bounds(bounds);
+        let mut receiver = watch.subscribe();
+        assert!(!receiver.has_changed().unwrap());
+
+        assert_eq!(watch.update("2000kbps"), Ok(Bandwidth::from_mbps(2)));
+        assert!(!receiver.has_changed().unwrap());
+        assert_eq!(watch.update("10Mbps"), Ok(Bandwidth::from_mbps(10)));
+        assert!(receiver.has_changed().unwrap());
+        assert_eq!(*receiver.borrow_and_update(), Bandwidth::from_mbps(10));
+
+        let error = watch.update("11Mbps").unwrap_err();
+        assert_eq!(
+            error,
+            WatchError::OutOfBounds {
+                value: Bandwidth::from_mbps(11),
+                bounds,
+            }
+        );
+        assert_eq!(error.to_string(), "bandwidth 11Mbps is outside of 1–10Mbps");
+        assert_eq!(
+            watch.update("fast"),
+            Err(WatchError::Parse(Error::NumberExpected(0)))
+        );
+        assert!(watch.set(Bandwidth::new(0, 0)).is_err());
+        assert!(!receiver.has_changed().unwrap());
+        assert_eq!(watch.current(), Bandwidth::from_mbps(10));
+
+        drop(watch);
+        assert!(receiver.has_changed().is_err());
+        assert_eq!(*receiver.borrow(), Bandwidth::from_mbps(10));
+    }
+}
@@ -0,0 +1,244 @@
+//! Helpers for bandwidth values received in query strings
+//!
+//! [`BandwidthParam`] deserializes a bandwidth from an already percent-decoded
+//! query value, e.g. `?limit=10Mbit%2Fs`, and reports the parsing error in a
+//! message fit to be sent back to the client. It can be used as a field of the
+//! parameters of axum's or actix-web's `Query` extractors.
+//!
+//! [`BandwidthQuery`] is an axum extractor deserializing the whole query string
+//! and answering `400 Bad Request` with that message on failure.
+//!
+//! # Example
+//!
+//! ```
+//! use serde::Deserialize;
+//! use bandwidth::Bandwidth;
+//! use human_bandwidth::web::{BandwidthParam, BandwidthQuery};
+//!
+//! #[derive(Deserialize)]
+//! struct Params {
+//!     limit: BandwidthParam,
+//! }
+//!
+//! let uri = "/download?limit=10Mbit%2Fs".parse().unwrap();
+//! let BandwidthQuery(params) = BandwidthQuery::<Params>::try_from_uri(&uri).unwrap();
+//! assert_eq!(*params.limit, Bandwidth::from_mbps(10));
+//!
+//! let uri = "/download?limit=10Mbyte%2Fs".parse().unwrap();
+//! let rejection = BandwidthQuery::<Params>::try_from_uri(&uri).err().unwrap();
+//! assert_eq!(
+//!     rejection.to_string(),
+//!     "invalid query string: invalid bandwidth \"10Mbyte/s\": \
+//!     unknown bandwidth unit \"Mbyte/s\", supported units: bps, kbps, Mbps, Gbps, Tbps, Pbps, Ebps",
+//! );
+//! ```
+
+use core::ops::{Deref, DerefMut};
+use core::{fmt, str::FromStr};
+
+use axum_core::extract::FromRequestParts;
+use axum_core::response::{IntoResponse, Response};
+use bandwidth::Bandwidth;
+use http::request::Parts;
+use http::{StatusCode, Uri};
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{format_bandwidth, parse_bandwidth, Error};
+
+/// A bandwidth received as a query parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BandwidthParam(pub Bandwidth);
+
+impl BandwidthParam {
+    /// Consumes the `BandwidthParam`, returning the inner value.
+    pub fn into_inner(self) -> Bandwidth {
+        self.0
+    }
+}
+
+impl Deref for BandwidthParam {
+    type Target = Bandwidth;
+
+    fn deref(&self) -> &Bandwidth {
+        &self.0
+    }
+}
+
+impl DerefMut for BandwidthParam {
+    fn deref_mut(&mut self) -> &mut Bandwidth {
+        &mut self.0
+    }
+}
+
+impl From<Bandwidth> for BandwidthParam {
+    fn from(val: Bandwidth) -> Self {
+        BandwidthParam(val)
+    }
+}
+
+impl FromStr for BandwidthParam {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        parse_bandwidth(s).map(BandwidthParam)
+    }
+}
+
+impl fmt::Display for BandwidthParam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&format_bandwidth(self.0), f)
+    }
+}
+
+impl<'de> Deserialize<'de> for BandwidthParam {
+    fn deserialize<D>(d: D) -> Result<BandwidthParam, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct V;
+
+        impl de::Visitor<'_> for V {
+            type Value = BandwidthParam;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.write_str("a bandwidth")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<BandwidthParam, E>
+            where
+                E: de::Error,
+            {
+                v.parse()
+                    .map_err(|e| E::custom(format_args!("invalid bandwidth {:?}: {}", v, e)))
+            }
+        }
+
+        d.deserialize_str(V)
+    }
+}
+
+impl Serialize for BandwidthParam {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Rejection used by [`BandwidthQuery`] when the query string can not be deserialized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BandwidthQueryRejection(String);
+
+impl fmt::Display for BandwidthQueryRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid query string: {}", self.0)
+    }
+}
+
+impl std::error::Error for BandwidthQueryRejection {}
+
+impl IntoResponse for BandwidthQueryRejection {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, self.to_string()).into_response()
+    }
+}
+
+/// An axum extractor deserializing the query string into `T`
+///
+/// Unlike axum's own `Query`, the error message sent back to the client states
+/// the offending value and the reason it was rejected.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BandwidthQuery<T>(pub T);
+
+impl<T: DeserializeOwned> BandwidthQuery<T> {
+    /// Deserializes the query string of an URI
+    ///
+    /// This is the logic of the extractor, usable with other web frameworks.
+    pub fn try_from_uri(uri: &Uri) -> Result<Self, BandwidthQueryRejection> {
+        serde_urlencoded::from_str(uri.query().unwrap_or_default())
+            .map(BandwidthQuery)
+            .map_err(|e| BandwidthQueryRejection(e.to_string()))
+    }
+}
+
+impl<T, S> FromRequestParts<S> for BandwidthQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = BandwidthQueryRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::try_from_uri(&parts.uri)
+    }
+}
+
+impl<T> Deref for BandwidthQuery<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for BandwidthQuery<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Params {
+        limit: BandwidthParam,
+        burst: Option<BandwidthParam>,
+    }
+
+    fn query(uri: &str) -> Result<Params, BandwidthQueryRejection> {
+        BandwidthQuery::<Params>::try_from_uri(&uri.parse().unwrap()).map(|q| q.0)
+    }
+
+    #[test]
+    fn test_query() {
+        let params = query("/?limit=10Mbit%2Fs").unwrap();
+        assert_eq!(*params.limit, Bandwidth::from_mbps(10));
+        assert_eq!(params.burst, None);
+
+        let params = query("/?burst=1.5Gbps&limit=2+Mbps").unwrap();
+        assert_eq!(*params.limit, Bandwidth::from_mbps(2));
+        assert_eq!(
+            params.burst,
+            Some(BandwidthParam(Bandwidth::from_kbps(1_500_000)))
+        );
+    }
+
+    #[test]
+    fn test_rejection() {
+        assert_eq!(
+            query("/").unwrap_err().to_string(),
+            "invalid query string: missing field `limit`"
+        );
+        assert_eq!(
+            query("/?limit=1Mbps&burst=12").unwrap_err().to_string(),
+            "invalid query string: invalid bandwidth \"12\": \
+            bandwidth unit needed, for example 12Mbps or 12bps"
+        );
+        let response = query("/?limit=fast").unwrap_err().into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_param() {
+        let param: BandwidthParam = "32Mbps".parse().unwrap();
+        assert_eq!(param.to_string(), "32Mbps");
+        assert_eq!(
+            serde_json::to_string(&param).unwrap(),
+            r#""32Mbps""#.to_string()
+        );
+    }
+}